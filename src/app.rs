@@ -1,24 +1,48 @@
 use std::fs;
 use std::io::Stdout;
 use std::path::{Component, Path, PathBuf};
-use std::time::Duration;
+use std::process::Command;
+use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, Result};
 use arboard::Clipboard;
 use crossterm::event::{
-    self, Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind,
+    self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind,
 };
 use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
 
+use crate::diff::DiffDocument;
 use crate::editor::Editor;
+use crate::editor_settings::EditorSettings;
 use crate::file_tree::FileTree;
+use crate::macros::{self, EditorCommand, MacroRecorder, MacroStore, Motion, Playback};
 use crate::manager::cargo_manager;
-use crate::manager::rust_formatter;
+use crate::manager::cargo_runner::{self, CargoRun, Diagnostic, DiagnosticLevel, RunnerEvent};
+use crate::manager::dependency_report;
+use crate::manager::dependency_tree::DependencyTree;
+use crate::manager::format_settings::FormatSettings;
+use crate::manager::formatter::{self, FormatterRegistry};
+use crate::manager::jobs::{Job, JobEvent, JobOutcome};
+use crate::manager::registry_index;
+use crate::manager::rust_formatter::{self, FormatRange};
 use crate::manager::workspace_manager;
-use crate::signature::write_signature_blob_for_file;
+use crate::navigation::{BookmarkStore, JumpList, Location};
+use crate::signature::keys::{ActiveKey, KeyStore};
+use crate::signature::verify::SignatureCache;
+use crate::signature::{project_root, write_signature_blob_for_file};
+use crate::syntax::comment::CommentStyle;
+use crate::syntax::outline::{SymbolKind, rust_outline};
+use crate::syntax::symbol_index::{self, SymbolIndex, WorkspaceSymbol};
 use crate::tab_manager::TabManager;
+use crate::terminal_pane::TerminalPanes;
+use crate::transform::{self, Pos, Transform};
 use crate::ui;
+use crate::vcs::git::GitRepository;
+use crate::vcs::git_ops;
+use crate::vim::{ExCommand, VimOutcome, VimState};
+
+const GIT_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
 
 pub fn run(
     terminal: &mut Terminal<CrosstermBackend<Stdout>>,
@@ -29,7 +53,16 @@ pub fn run(
     let mut focus = ui::FocusPane::Editor;
     let mut save_dialog: Option<ui::SaveDialog> = None;
     let mut search_dialog: Option<ui::SearchDialog> = None;
+    let mut symbol_dialog: Option<ui::WorkspaceSymbolDialog> = None;
+    let mut goto_dialog: Option<ui::GoToLineDialog> = None;
+    let mut bookmark_name_dialog: Option<ui::BookmarkNameDialog> = None;
+    let mut bookmark_picker: Option<ui::BookmarkPicker> = None;
+    let mut macro_dialog: Option<ui::MacroDialog> = None;
+    let mut transform_dialog: Option<ui::TransformDialog> = None;
     let mut manager_dialog: Option<ui::ManagerDialog> = None;
+    let mut git_dialog: Option<ui::GitDialog> = None;
+    let mut diff_view: Option<ui::DiffViewDialog> = None;
+    let mut unsaved_prompt: Option<ui::UnsavedPromptDialog> = None;
     let mut help_dialog_open = false;
     let mut start_menu = if startup_files.is_empty() {
         Some(ui::StartMenuDialog::default())
@@ -40,11 +73,39 @@ pub fn run(
     let mut explorer_context_menu: Option<ui::ExplorerContextMenuDialog> = None;
     let mut explorer_input_dialog: Option<ui::ExplorerInputDialog> = None;
     let mut show_file_tree = true;
+    let mut show_outline = false;
+    let mut outline = ui::OutlinePanel::default();
+    let mut symbol_index = SymbolIndex::spawn(file_tree.root());
+    let mut jump_list = JumpList::default();
+    let mut last_location: Option<Location> = None;
+    let mut bookmarks = BookmarkStore::load();
+    let mut bookmark_anchor: Option<(PathBuf, u64)> = None;
     let mut clipboard = String::new();
     let mut system_clipboard = Clipboard::new().ok();
     let mut dragging_editor_selection = false;
     let mut theme = ui::ThemeMode::Dark;
     let mut ui_state = ui::UiState::default();
+    let mut cargo_run: Option<CargoRun> = None;
+    let mut cargo_log = String::new();
+    let mut jobs: Vec<(Job, JobFollowUp)> = Vec::new();
+    let mut format_settings = FormatSettings::load();
+    let mut formatters = FormatterRegistry::new(&format_settings);
+    let mut editor_settings = EditorSettings::load();
+    let mut vim = VimState::default();
+    let mut macro_recorder = MacroRecorder::default();
+    let mut macro_store = MacroStore::load();
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    // (path tab aktif, path kanoniknya) agar loop tidak menyentuh filesystem tiap frame.
+    let mut canonical_active: Option<(PathBuf, PathBuf)> = None;
+    let mut terminal_panes = TerminalPanes::default();
+    let mut git_repo = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| GitRepository::discover(&cwd));
+    let mut last_git_refresh = Instant::now();
+    let mut signatures = SignatureCache::new(file_tree.root());
+    let mut key_store = KeyStore::load(file_tree.root());
+    let mut key_dialog: Option<ui::KeyDialog> = None;
+    let mut last_signature_refresh: Option<Instant> = None;
     let mut status_message =
         String::from("Ctrl+S simpan | Ctrl+F search | Ctrl+Z/Y undo redo | Ctrl+C/V/X editor");
 
@@ -80,32 +141,319 @@ pub fn run(
     }
 
     loop {
+        if let Some(run) = cargo_run.as_mut() {
+            for event in run.poll() {
+                match event {
+                    RunnerEvent::Output(line) => {
+                        if !cargo_log.is_empty() {
+                            cargo_log.push('\n');
+                        }
+                        cargo_log.push_str(&line);
+                        if let Some(dialog) = manager_dialog.as_mut()
+                            && dialog.output_follow
+                        {
+                            dialog.append_output_line(&line);
+                        }
+                    }
+                    RunnerEvent::Diagnostic(diagnostic) => diagnostics.push(diagnostic),
+                    RunnerEvent::Finished { success, cancelled } => {
+                        status_message = if cancelled {
+                            format!("cargo {} dibatalkan", run.command().subcommand())
+                        } else {
+                            cargo_run_summary(run.command(), success, &diagnostics)
+                        };
+                        if let Some(dialog) = manager_dialog.as_mut() {
+                            if dialog.output_follow {
+                                dialog.append_output_line(&status_message);
+                            }
+                            if dialog
+                                .job
+                                .as_ref()
+                                .is_some_and(|status| status.id == run.status().id)
+                            {
+                                dialog.job = None;
+                            }
+                        }
+                    }
+                }
+            }
+            if run.is_finished() {
+                cargo_run = None;
+            }
+        }
+
+        let mut finished_jobs = Vec::new();
+        for (job, follow_up) in jobs.iter_mut() {
+            for event in job.poll() {
+                match event {
+                    JobEvent::Output(line) => {
+                        if let Some(dialog) = manager_dialog.as_mut()
+                            && dialog
+                                .job
+                                .as_ref()
+                                .is_some_and(|status| status.id == job.id())
+                        {
+                            dialog.append_output_line(&line);
+                        }
+                    }
+                    JobEvent::Stdout(_) => {}
+                    JobEvent::Finished(outcome) => {
+                        finished_jobs.push((
+                            job.id(),
+                            job.label().to_string(),
+                            follow_up.clone(),
+                            outcome,
+                        ));
+                    }
+                }
+            }
+        }
+        jobs.retain(|(job, _)| !job.is_finished());
+        for (id, label, follow_up, outcome) in finished_jobs {
+            if let Some(dialog) = manager_dialog.as_mut()
+                && dialog.job.as_ref().is_some_and(|status| status.id == id)
+            {
+                dialog.job = None;
+            }
+            let save_after = match follow_up {
+                JobFollowUp::FormatBuffer(request) => {
+                    // Hasil format dan simpan berlaku untuk tab asal walau pengguna sudah pindah.
+                    let active = tabs.active_index();
+                    tabs.set_active(request.tab);
+                    let save_after = finish_format_job(
+                        &label,
+                        &request,
+                        &outcome,
+                        &mut tabs,
+                        &mut diagnostics,
+                        &mut status_message,
+                    );
+                    tabs.set_active(active);
+                    save_after.map(|filename| (request.tab, filename))
+                }
+                JobFollowUp::PipeSelection(request) => {
+                    status_message = finish_pipe_job(&label, &request, &outcome, &mut tabs);
+                    None
+                }
+                follow_up => {
+                    finish_job(
+                        &label,
+                        follow_up,
+                        outcome,
+                        manager_dialog.as_mut(),
+                        &mut file_tree,
+                        &mut status_message,
+                    );
+                    None
+                }
+            };
+            if let Some(dialog) = manager_dialog.as_mut()
+                && dialog.mode == ui::ManagerMode::Output
+            {
+                dialog.append_output_line(&status_message);
+            }
+            if let Some((tab, filename)) = save_after {
+                let format_status = std::mem::take(&mut status_message);
+                let active = tabs.active_index();
+                tabs.set_active(tab);
+                save_to_filename(
+                    &mut tabs,
+                    &mut file_tree,
+                    &mut symbol_index,
+                    &mut save_dialog,
+                    &mut key_store,
+                    &mut status_message,
+                    filename,
+                )?;
+                tabs.set_active(active);
+                status_message = format!("{} | {}", status_message, format_status);
+            }
+        }
+        if let Some(dialog) = manager_dialog.as_mut()
+            && let Some(status) = dialog.job.as_mut()
+        {
+            if let Some((job, _)) = jobs.iter().find(|(job, _)| job.id() == status.id) {
+                *status = job.status().clone();
+            } else if let Some(run) = cargo_run.as_ref()
+                && run.status().id == status.id
+            {
+                *status = run.status().clone();
+            }
+        }
+        if symbol_index.poll()
+            && let Some(dialog) = symbol_dialog.as_mut()
+        {
+            dialog.refresh(&symbol_index);
+        }
+
+        let job_statuses = cargo_run
+            .iter()
+            .map(|run| run.status().clone())
+            .chain(jobs.iter().map(|(job, _)| job.status().clone()))
+            .collect::<Vec<_>>();
+
+        let active_diagnostics = tabs
+            .active_path()
+            .map(|path| {
+                if canonical_active
+                    .as_ref()
+                    .is_some_and(|(raw, _)| raw != path)
+                {
+                    canonical_active = None;
+                }
+                let canonical = canonical_active.get_or_insert_with(|| {
+                    (path.to_path_buf(), cargo_runner::canonical_path(path))
+                });
+                diagnostics
+                    .iter()
+                    .filter(|diagnostic| diagnostic.is_for_path(&canonical.1))
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        if let Some(repo) = git_repo.as_mut() {
+            repo.poll();
+            if last_git_refresh.elapsed() >= GIT_REFRESH_INTERVAL {
+                repo.refresh_in_background();
+                last_git_refresh = Instant::now();
+            }
+        }
+        if last_signature_refresh.is_none_or(|at| at.elapsed() >= GIT_REFRESH_INTERVAL) {
+            signatures.refresh(
+                file_tree
+                    .entries()
+                    .iter()
+                    .filter(|entry| !entry.is_dir())
+                    .map(|entry| entry.path()),
+            );
+            last_signature_refresh = Some(Instant::now());
+        }
+        let change_markers = match (git_repo.as_mut(), tabs.active_path()) {
+            (Some(repo), Some(path)) => {
+                let editor = tabs.active_editor();
+                repo.line_markers(path, editor.revision(), editor.lines())
+            }
+            _ => Vec::new(),
+        };
+
+        let location = current_location(&tabs);
+        if let (Some(previous), Some(current)) = (last_location.as_ref(), location.as_ref())
+            && current.is_far_from(previous)
+        {
+            jump_list.record(previous.clone());
+        }
+        last_location = location;
+
+        // Posisi bookmark disinkronkan setiap kali buffer bersih (baru dibuka atau disimpan).
+        if let Some(path) = tabs.active_path()
+            && !tabs.active_tab_is_dirty()
+        {
+            let anchor = (path.to_path_buf(), tabs.active_editor().revision());
+            if bookmark_anchor.as_ref() != Some(&anchor) {
+                if bookmarks.reanchor(path, tabs.active_editor().lines()) {
+                    let _ = bookmarks.save();
+                }
+                bookmark_anchor = Some(anchor);
+            }
+        }
+        let vim_label = editor_settings.vim_mode.then(|| vim.status_label());
+        let bookmark_rows = tabs
+            .active_path()
+            .map(|path| {
+                let lines = tabs.active_editor().lines();
+                bookmarks
+                    .for_file(path)
+                    .iter()
+                    .map(|bookmark| bookmark.resolve(lines))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        if show_file_tree && show_outline {
+            let editor = tabs.active_editor();
+            outline.refresh(
+                tabs.active_index(),
+                editor.revision(),
+                tabs.active_path(),
+                editor.lines(),
+            );
+            if focus != ui::FocusPane::Outline {
+                outline.follow_cursor(editor.cursor_y());
+            }
+        }
+
         terminal.draw(|frame| {
-            let viewport_height = ui::editor_viewport_height(frame.area().height);
+            let viewport_height =
+                ui::editor_viewport_height(frame.area().height, terminal_panes.is_visible());
             tabs.active_editor_mut()
                 .ensure_cursor_visible(viewport_height.max(1));
             file_tree.ensure_visible(viewport_height.max(1));
+            outline.ensure_visible(viewport_height.saturating_sub(1));
             ui_state = ui::render(
                 frame,
                 tabs.active_editor(),
-                save_dialog.as_ref(),
-                search_dialog.as_ref(),
-                manager_dialog.as_ref(),
-                help_dialog_open,
-                start_menu.as_ref(),
-                editor_context_menu.as_ref(),
-                explorer_context_menu.as_ref(),
-                explorer_input_dialog.as_ref(),
-                &status_message,
-                &tabs,
-                &file_tree,
-                focus,
-                show_file_tree,
-                theme,
+                ui::Dialogs {
+                    save: save_dialog.as_ref(),
+                    search: search_dialog.as_ref(),
+                    symbol: symbol_dialog.as_ref(),
+                    goto: goto_dialog.as_ref(),
+                    bookmark_name: bookmark_name_dialog.as_ref(),
+                    bookmark_picker: bookmark_picker.as_ref(),
+                    macros: macro_dialog.as_ref(),
+                    transform: transform_dialog.as_ref(),
+                    manager: manager_dialog.as_ref(),
+                    git: git_dialog.as_ref(),
+                    key: key_dialog.as_ref(),
+                    diff_view: diff_view.as_ref(),
+                    unsaved_prompt: unsaved_prompt.as_ref(),
+                    help_open: help_dialog_open,
+                    start_menu: start_menu.as_ref(),
+                    context_menu: editor_context_menu.as_ref(),
+                    explorer_context_menu: explorer_context_menu.as_ref(),
+                    explorer_input: explorer_input_dialog.as_ref(),
+                },
+                ui::EditorView {
+                    status_message: &status_message,
+                    tabs: &tabs,
+                    file_tree: &file_tree,
+                    terminal_panes: &terminal_panes,
+                    outline: show_outline.then_some(&outline),
+                    focus,
+                    show_file_tree,
+                    decorations: ui::EditorDecorations {
+                        diagnostics: &active_diagnostics,
+                        change_markers: &change_markers,
+                        git: git_repo.as_ref(),
+                        signatures: Some(&signatures),
+                        jobs: &job_statuses,
+                        bookmarks: &bookmark_rows,
+                        vim_mode: vim_label.as_deref(),
+                        macro_recording: macro_recorder.is_recording(),
+                        settings: &editor_settings,
+                    },
+                    theme,
+                },
             );
         })?;
 
-        if !event::poll(Duration::from_millis(200))? {
+        let terminal_rect = ui_state.terminal_content_rect;
+        if terminal_panes.is_visible()
+            && terminal_rect.width > 0
+            && terminal_rect.height > 0
+            && let Err(err) = terminal_panes.resize_all(terminal_rect.height, terminal_rect.width)
+        {
+            status_message = format!("Resize terminal gagal ({})", err);
+        }
+
+        let poll_timeout = if terminal_panes.is_visible() {
+            Duration::from_millis(40)
+        } else if !jobs.is_empty() || cargo_run.is_some() {
+            Duration::from_millis(100)
+        } else {
+            Duration::from_millis(200)
+        };
+        if !event::poll(poll_timeout)? {
             continue;
         }
 
@@ -115,6 +463,75 @@ pub fn run(
                 continue;
             }
 
+            if let Some(view) = diff_view.as_mut() {
+                match key.code {
+                    KeyCode::Esc | KeyCode::Char('q') => {
+                        diff_view = None;
+                        status_message = String::from("Diff ditutup");
+                    }
+                    KeyCode::Up => view.scroll_up(1),
+                    KeyCode::Down => view.scroll_down(1),
+                    KeyCode::PageUp => view.scroll_up(20),
+                    KeyCode::PageDown => view.scroll_down(20),
+                    KeyCode::Left => view.scroll_x = view.scroll_x.saturating_sub(4),
+                    KeyCode::Right => view.scroll_x = view.scroll_x.saturating_add(4),
+                    KeyCode::Char('n' | ']') => {
+                        status_message = if view.next_hunk() {
+                            format!("Hunk {}/{}", view.current_hunk(), view.document.hunk_rows.len())
+                        } else {
+                            String::from("Tidak ada hunk berikutnya")
+                        };
+                    }
+                    KeyCode::Char('p' | '[') => {
+                        status_message = if view.prev_hunk() {
+                            format!("Hunk {}/{}", view.current_hunk(), view.document.hunk_rows.len())
+                        } else {
+                            String::from("Tidak ada hunk sebelumnya")
+                        };
+                    }
+                    KeyCode::Char('v') | KeyCode::Tab => view.inline = !view.inline,
+                    _ => {}
+                }
+                continue;
+            }
+
+            if let Some(prompt) = unsaved_prompt.as_mut() {
+                match key.code {
+                    KeyCode::Esc => {
+                        unsaved_prompt = None;
+                        status_message = String::from("Dibatalkan");
+                    }
+                    KeyCode::Tab | KeyCode::Right => prompt.next(),
+                    KeyCode::BackTab | KeyCode::Left => prompt.prev(),
+                    KeyCode::Enter => {
+                        match prompt.selected {
+                            ui::UnsavedPromptChoice::Save => {
+                                save_dialog = Some(ui::SaveDialog {
+                                    filename: tabs.active_suggested_filename(),
+                                    ..Default::default()
+                                });
+                                status_message = String::from("Dialog simpan terbuka");
+                            }
+                            ui::UnsavedPromptChoice::DiffDisk => match open_disk_diff(&tabs) {
+                                Ok(view) => {
+                                    diff_view = Some(view);
+                                    status_message = String::from("Diff buffer dengan disk");
+                                }
+                                Err(err) => {
+                                    status_message = format!("Diff gagal ({})", err);
+                                }
+                            },
+                            ui::UnsavedPromptChoice::Cancel => {
+                                status_message = String::from("Dibatalkan");
+                            }
+                        }
+                        unsaved_prompt = None;
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
             if let Some(menu) = start_menu.as_mut() {
                 match key.code {
                     KeyCode::Esc => {
@@ -132,6 +549,7 @@ pub fn run(
                             }
                             ui::StartMenuAction::FocusFileTree => {
                                 if show_file_tree {
+                                    show_outline = false;
                                     focus = ui::FocusPane::FileTree;
                                     status_message = String::from("Fokus ke file tree");
                                 } else {
@@ -139,9 +557,18 @@ pub fn run(
                                 }
                             }
                             ui::StartMenuAction::OpenManager => {
-                                manager_dialog = Some(ui::ManagerDialog::default());
+                                manager_dialog = Some(open_manager_dialog(
+                                    cargo_run.as_ref(),
+                                    &cargo_log,
+                                    &jobs,
+                                    &format_settings,
+                                ));
                                 status_message = String::from("Rust/Cargo manager dibuka");
                             }
+                            ui::StartMenuAction::OpenKeyManager => {
+                                key_dialog = Some(ui::KeyDialog::new(&key_store));
+                                status_message = String::from("Dialog kunci signing dibuka");
+                            }
                             ui::StartMenuAction::ContinueEditor => {
                                 focus = ui::FocusPane::Editor;
                                 status_message = String::from("Lanjut ke editor");
@@ -170,6 +597,19 @@ pub fn run(
             }
 
             if let Some(dialog) = manager_dialog.as_mut() {
+                if key.code == KeyCode::Esc
+                    && let Some(status) = dialog.job.as_ref()
+                {
+                    if let Some((job, _)) = jobs.iter_mut().find(|(job, _)| job.id() == status.id) {
+                        job.cancel();
+                    } else if let Some(run) = cargo_run.as_mut()
+                        && run.status().id == status.id
+                    {
+                        run.cancel();
+                    }
+                    status_message = format!("Membatalkan {}...", status.label);
+                    continue;
+                }
                 match dialog.mode {
                     ui::ManagerMode::Menu => match key.code {
                         KeyCode::Esc => {
@@ -179,24 +619,121 @@ pub fn run(
                         KeyCode::Up => dialog.move_up(),
                         KeyCode::Down => dialog.move_down(),
                         KeyCode::Enter => match dialog.current_action() {
-                            ui::ManagerAction::FormatRust => {
-                                let result = format_active_rust_file(&mut tabs);
-                                match result {
-                                    Ok((message, changed)) => {
-                                        if changed {
-                                            tabs.mark_active_dirty();
+                            ui::ManagerAction::Format => {
+                                let request = FormatRequest::new(
+                                    &tabs,
+                                    &formatters,
+                                    selection_format_range(tabs.active_editor()),
+                                    None,
+                                );
+                                start_manager_job(
+                                    dialog,
+                                    &mut jobs,
+                                    JobFollowUp::FormatBuffer(request.clone()),
+                                    &mut status_message,
+                                    || request.spawn(&tabs, &formatters),
+                                );
+                            }
+                            ui::ManagerAction::FormatOnSave => {
+                                format_settings.format_on_save = !format_settings.format_on_save;
+                                dialog.format_on_save = format_settings.format_on_save;
+                                status_message = match format_settings.save() {
+                                    Ok(()) if format_settings.format_on_save => {
+                                        String::from("Format saat simpan aktif")
+                                    }
+                                    Ok(()) => String::from("Format saat simpan nonaktif"),
+                                    Err(err) => format!("Gagal menyimpan pengaturan ({})", err),
+                                };
+                            }
+                            ui::ManagerAction::ListFormatters => {
+                                // Dibaca ulang supaya perubahan formatter.json langsung terpakai.
+                                format_settings = FormatSettings::load();
+                                formatters = FormatterRegistry::new(&format_settings);
+                                dialog.format_on_save = format_settings.format_on_save;
+                                dialog.set_output(formatters.describe().join("\n"));
+                                status_message = String::from("Daftar formatter dimuat ulang");
+                            }
+                            ui::ManagerAction::CargoRun(command) => {
+                                if let Some(running) = dialog.job.as_ref() {
+                                    status_message = format!(
+                                        "{} masih berjalan (Esc untuk batal)",
+                                        running.label
+                                    );
+                                } else if let Some(run) = cargo_run.as_ref() {
+                                    dialog.start_streaming_output(cargo_log.clone());
+                                    dialog.job = Some(run.status().clone());
+                                    status_message = format!(
+                                        "cargo {} masih berjalan (Esc untuk batal)",
+                                        run.command().subcommand()
+                                    );
+                                } else {
+                                    match cargo_runner::spawn_cargo_command(
+                                        command,
+                                        &dialog.target_args(),
+                                    ) {
+                                        Ok(run) => {
+                                            diagnostics.clear();
+                                            cargo_log =
+                                                format!("$ cargo {}", command.subcommand());
+                                            dialog.start_streaming_output(cargo_log.clone());
+                                            dialog.job = Some(run.status().clone());
+                                            cargo_run = Some(run);
+                                            status_message = format!(
+                                                "cargo {} berjalan...",
+                                                command.subcommand()
+                                            );
                                         }
-                                        dialog.output = message.clone();
-                                        dialog.mode = ui::ManagerMode::Output;
-                                        status_message = message;
+                                        Err(err) => {
+                                            dialog.set_output(err.to_string());
+                                            status_message = format!("Cargo gagal: {}", err);
+                                        }
+                                    }
+                                }
+                            }
+                            ui::ManagerAction::SelectPackage => {
+                                refresh_manager_workspace(dialog);
+                                if dialog.workspace.is_some() {
+                                    dialog.open_packages();
+                                    status_message =
+                                        String::from("Pilih package target lalu Enter");
+                                } else {
+                                    status_message =
+                                        String::from("Workspace cargo tidak ditemukan");
+                                }
+                            }
+                            ui::ManagerAction::DependencyReport => {
+                                refresh_dependency_report(dialog, &mut status_message);
+                            }
+                            ui::ManagerAction::DependencyTree => {
+                                refresh_manager_workspace(dialog);
+                                let tree = dialog
+                                    .workspace
+                                    .as_ref()
+                                    .map(DependencyTree::load)
+                                    .unwrap_or_else(|| {
+                                        Err(anyhow!("Workspace cargo tidak ditemukan"))
+                                    });
+                                match tree {
+                                    Ok(tree) => {
+                                        status_message = format!(
+                                            "{} entri pohon dependency",
+                                            tree.entries().len()
+                                        );
+                                        dialog.dependency_tree = Some(tree);
+                                        dialog.mode = ui::ManagerMode::Tree;
                                     }
                                     Err(err) => {
-                                        dialog.output = err.to_string();
-                                        dialog.mode = ui::ManagerMode::Output;
-                                        status_message = String::from("Format Rust gagal");
+                                        dialog.set_output(err.to_string());
+                                        status_message =
+                                            format!("Pohon dependency gagal: {}", err);
                                     }
                                 }
                             }
+                            ui::ManagerAction::ShowProblems => {
+                                dialog.set_problems(diagnostics.clone());
+                                status_message =
+                                    format!("{} problem ditemukan", diagnostics.len());
+                            }
                             ui::ManagerAction::CargoSearch
                             | ui::ManagerAction::CargoAdd
                             | ui::ManagerAction::CargoRemove
@@ -225,30 +762,31 @@ pub fn run(
                             let input = dialog.input.trim().to_string();
                             match action {
                                 ui::ManagerAction::CargoSearch => {
-                                    match cargo_manager::search_crate_packages(&input) {
-                                        Ok(results) => {
-                                            let installed = cargo_manager::installed_packages()
-                                                .unwrap_or_default();
-                                            dialog.set_search_results(
-                                                results
-                                                    .into_iter()
-                                                    .map(|pkg| ui::CargoSearchItem {
-                                                        name: pkg.name,
-                                                        version: pkg.version,
-                                                        description: pkg.description,
-                                                        installed: false,
-                                                    })
-                                                    .collect::<Vec<_>>(),
-                                                installed,
-                                            );
-                                            status_message = String::from(
-                                                "Pilih paket lalu Enter untuk install/hapus",
-                                            );
-                                        }
-                                        Err(err) => {
-                                            dialog.set_output(err.to_string());
-                                            status_message = String::from("Search crate gagal");
-                                        }
+                                    start_manager_job(
+                                        dialog,
+                                        &mut jobs,
+                                        JobFollowUp::SearchResults,
+                                        &mut status_message,
+                                        || cargo_manager::search_crate(&input),
+                                    );
+                                }
+                                ui::ManagerAction::CargoRemove => {
+                                    let target_args = dialog.target_args();
+                                    start_manager_job(
+                                        dialog,
+                                        &mut jobs,
+                                        JobFollowUp::CrateRemoved(input.clone()),
+                                        &mut status_message,
+                                        || cargo_manager::remove_crate(&input, &target_args),
+                                    );
+                                }
+                                ui::ManagerAction::CargoAdd => {
+                                    if input.is_empty() {
+                                        status_message =
+                                            String::from("Nama crate tidak boleh kosong");
+                                    } else {
+                                        open_add_dependency_form(dialog, &input, None);
+                                        status_message = format!("Tambah dependency {}", input);
                                     }
                                 }
                                 _ => match run_manager_input_action(action, &input) {
@@ -257,12 +795,11 @@ pub fn run(
                                         status_message = message;
                                         if matches!(
                                             action,
-                                            ui::ManagerAction::CargoAdd
-                                                | ui::ManagerAction::CargoRemove
-                                                | ui::ManagerAction::WorkspaceAddMember
+                                            ui::ManagerAction::WorkspaceAddMember
                                                 | ui::ManagerAction::WorkspaceRemoveMember
                                         ) {
                                             let _ = file_tree.refresh();
+                                            refresh_manager_workspace(dialog);
                                         }
                                     }
                                     Err(err) => {
@@ -272,10 +809,8 @@ pub fn run(
                                 },
                             }
                         }
-                        KeyCode::Char(ch) => {
-                            if !key.modifiers.contains(KeyModifiers::CONTROL) {
-                                dialog.input.push(ch);
-                            }
+                        KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            dialog.input.push(ch);
                         }
                         _ => {}
                     },
@@ -296,41 +831,151 @@ pub fn run(
                             dialog.search_scroll_x = dialog.search_scroll_x.saturating_add(2);
                         }
                         KeyCode::Enter => {
-                            if let Some(name) = dialog.selected_search_name() {
-                                let installed = dialog.installed_packages.contains(&name);
-                                let result = if installed {
-                                    cargo_manager::remove_crate(&name)
+                            if let Some(item) = dialog.selected_search_item().cloned() {
+                                if !item.installed {
+                                    open_add_dependency_form(
+                                        dialog,
+                                        &item.name,
+                                        Some(&item.version),
+                                    );
+                                    status_message = format!("Tambah dependency {}", item.name);
                                 } else {
-                                    cargo_manager::add_crate(&name)
-                                };
-                                match result {
-                                    Ok(message) => {
-                                        dialog.mark_installed(&name, !installed);
-                                        dialog.output = message;
-                                        dialog.output_scroll_y = 0;
-                                        dialog.output_scroll_x = 0;
-                                        status_message = if installed {
-                                            format!("Paket dihapus: {}", name)
-                                        } else {
-                                            format!("Paket di-install: {}", name)
-                                        };
-                                    }
-                                    Err(err) => {
-                                        dialog.output = err.to_string();
-                                        dialog.output_scroll_y = 0;
-                                        dialog.output_scroll_x = 0;
-                                        status_message = format!("Aksi paket gagal: {}", err);
-                                    }
+                                    let target_args = dialog.target_args();
+                                    start_manager_job(
+                                        dialog,
+                                        &mut jobs,
+                                        JobFollowUp::CrateRemoved(item.name.clone()),
+                                        &mut status_message,
+                                        || cargo_manager::remove_crate(&item.name, &target_args),
+                                    );
+                                }
+                            }
+                        }
+                        _ => {}
+                    },
+                    ui::ManagerMode::AddDependency => {
+                        handle_add_dependency_key(dialog, key, &mut status_message);
+                    }
+                    ui::ManagerMode::Tree => {
+                        let Some(tree) = dialog.dependency_tree.as_mut() else {
+                            dialog.mode = ui::ManagerMode::Menu;
+                            continue;
+                        };
+                        match key.code {
+                            KeyCode::Esc => {
+                                if tree.inverse_target().is_some() {
+                                    tree.show_dependencies();
+                                } else {
+                                    dialog.mode = ui::ManagerMode::Menu;
                                 }
                             }
+                            KeyCode::Up => tree.move_up(),
+                            KeyCode::Down => tree.move_down(),
+                            KeyCode::Enter | KeyCode::Right | KeyCode::Char(' ') => {
+                                tree.toggle_selected();
+                            }
+                            KeyCode::Left => tree.collapse_selected(),
+                            KeyCode::Char('i') => {
+                                tree.show_dependents_of_selected();
+                                if let Some(package) = tree.inverse_target() {
+                                    status_message = format!(
+                                        "Yang bergantung pada {} {}",
+                                        package.name, package.version
+                                    );
+                                }
+                            }
+                            _ => {}
+                        }
+                        tree.ensure_visible(11);
+                    }
+                    ui::ManagerMode::Report => match key.code {
+                        KeyCode::Esc => {
+                            dialog.mode = ui::ManagerMode::Menu;
+                        }
+                        KeyCode::Up => dialog.move_report_up(),
+                        KeyCode::Down => dialog.move_report_down(12),
+                        KeyCode::Char('r') => {
+                            refresh_dependency_report(dialog, &mut status_message);
+                        }
+                        KeyCode::Enter | KeyCode::Char('u') | KeyCode::Char('x') => {
+                            if let Some(entry) = dialog.selected_report().cloned() {
+                                let target_args = dialog
+                                    .workspace
+                                    .as_ref()
+                                    .map(|workspace| workspace.cargo_args(Some(&entry.package)))
+                                    .unwrap_or_default();
+                                let removed = key.code == KeyCode::Char('x');
+                                let latest = entry.latest.clone().filter(|_| entry.outdated());
+                                if !removed && latest.is_none() {
+                                    status_message = format!(
+                                        "{} sudah versi terbaru yang diketahui",
+                                        entry.dependency.name
+                                    );
+                                    continue;
+                                }
+                                start_manager_job(
+                                    dialog,
+                                    &mut jobs,
+                                    JobFollowUp::ReportDependency {
+                                        name: entry.dependency.name.clone(),
+                                        removed,
+                                    },
+                                    &mut status_message,
+                                    || match latest.as_deref().filter(|_| !removed) {
+                                        Some(latest) => cargo_manager::upgrade_dependency(
+                                            &entry.dependency,
+                                            latest,
+                                            &target_args,
+                                        ),
+                                        None => cargo_manager::remove_dependency(
+                                            &entry.dependency,
+                                            &target_args,
+                                        ),
+                                    },
+                                );
+                            }
+                        }
+                        _ => {}
+                    },
+                    ui::ManagerMode::AddDependencyPreview => match key.code {
+                        KeyCode::Esc => {
+                            dialog.mode = ui::ManagerMode::AddDependency;
+                        }
+                        KeyCode::Up => {
+                            if let Some(form) = dialog.add_form.as_mut() {
+                                form.preview_scroll = form.preview_scroll.saturating_sub(1);
+                            }
+                        }
+                        KeyCode::Down => {
+                            if let Some(form) = dialog.add_form.as_mut() {
+                                form.preview_scroll = form.preview_scroll.saturating_add(1);
+                            }
+                        }
+                        KeyCode::Enter => {
+                            if let Some(spec) = dialog.add_form.as_ref().map(|form| form.spec()) {
+                                let target_args = dialog.target_args();
+                                start_manager_job(
+                                    dialog,
+                                    &mut jobs,
+                                    JobFollowUp::DependencyAdded(spec.name.clone()),
+                                    &mut status_message,
+                                    || cargo_manager::add_dependency(&spec, &target_args),
+                                );
+                            }
                         }
                         _ => {}
                     },
                     ui::ManagerMode::Output => match key.code {
                         KeyCode::Esc | KeyCode::Enter => {
+                            dialog.output_follow = false;
                             dialog.mode = ui::ManagerMode::Menu;
                         }
                         KeyCode::Up => {
+                            if dialog.output_follow {
+                                dialog.output_follow = false;
+                                dialog.output_scroll_y =
+                                    dialog.output.lines().count().saturating_sub(1);
+                            }
                             dialog.output_scroll_y = dialog.output_scroll_y.saturating_sub(1);
                         }
                         KeyCode::Down => {
@@ -344,6 +989,83 @@ pub fn run(
                         }
                         _ => {}
                     },
+                    ui::ManagerMode::Packages => match key.code {
+                        KeyCode::Esc => {
+                            dialog.mode = ui::ManagerMode::Menu;
+                        }
+                        KeyCode::Up => dialog.move_package_up(),
+                        KeyCode::Down => dialog.move_package_down(),
+                        KeyCode::PageUp => {
+                            dialog.package_scroll_y = dialog.package_scroll_y.saturating_sub(5);
+                        }
+                        KeyCode::PageDown => {
+                            dialog.package_scroll_y = dialog.package_scroll_y.saturating_add(5);
+                        }
+                        KeyCode::Enter => {
+                            if let Some(name) =
+                                dialog.highlighted_package().map(|package| package.name.clone())
+                            {
+                                status_message = format!("Package target: {}", name);
+                                dialog.target_package = Some(name);
+                                dialog.mode = ui::ManagerMode::Menu;
+                            }
+                        }
+                        KeyCode::Char('a') => {
+                            dialog.target_package = None;
+                            dialog.mode = ui::ManagerMode::Menu;
+                            status_message = String::from("Package target: semua member");
+                        }
+                        _ => {}
+                    },
+                    ui::ManagerMode::Problems => match key.code {
+                        KeyCode::Esc => {
+                            dialog.mode = ui::ManagerMode::Menu;
+                        }
+                        KeyCode::Up => dialog.move_problem_up(),
+                        KeyCode::Down => dialog.move_problem_down(12),
+                        KeyCode::Left => {
+                            dialog.output_scroll_x = dialog.output_scroll_x.saturating_sub(2);
+                        }
+                        KeyCode::Right => {
+                            dialog.output_scroll_x = dialog.output_scroll_x.saturating_add(2);
+                        }
+                        KeyCode::Enter => {
+                            if let Some(problem) = dialog.selected_problem().cloned() {
+                                match open_diagnostic_location(&mut tabs, &problem) {
+                                    Ok(()) => {
+                                        manager_dialog = None;
+                                        focus = ui::FocusPane::Editor;
+                                        status_message = problem.summary();
+                                    }
+                                    Err(err) => {
+                                        status_message =
+                                            format!("Gagal buka lokasi problem ({})", err);
+                                    }
+                                }
+                            }
+                        }
+                        _ => {}
+                    },
+                }
+                continue;
+            }
+
+            if let Some(dialog) = git_dialog.as_mut() {
+                if let Some(repo) = git_repo.as_mut() {
+                    if handle_git_dialog_key(dialog, key, repo, &mut status_message) {
+                        git_dialog = None;
+                    }
+                } else {
+                    git_dialog = None;
+                }
+                continue;
+            }
+
+            if let Some(dialog) = key_dialog.as_mut() {
+                if handle_key_dialog_key(dialog, key, &mut key_store, &mut status_message) {
+                    key_dialog = None;
+                    signatures.invalidate();
+                    last_signature_refresh = None;
                 }
                 continue;
             }
@@ -362,19 +1084,31 @@ pub fn run(
                             status_message = String::from("Simpan dibatalkan");
                         } else {
                             let filename = dialog.filename.trim().to_string();
-                            save_to_filename(
-                                &mut tabs,
-                                &mut file_tree,
-                                &mut save_dialog,
-                                &mut status_message,
-                                filename,
-                            )?;
+                            if format_settings.format_on_save
+                                && start_format_on_save(
+                                    &tabs,
+                                    &formatters,
+                                    &mut jobs,
+                                    &filename,
+                                    &mut status_message,
+                                )
+                            {
+                                save_dialog = None;
+                            } else {
+                                save_to_filename(
+                                    &mut tabs,
+                                    &mut file_tree,
+                                    &mut symbol_index,
+                                    &mut save_dialog,
+                                    &mut key_store,
+                                    &mut status_message,
+                                    filename,
+                                )?;
+                            }
                         }
                     }
-                    KeyCode::Char(ch) => {
-                        if !key.modifiers.contains(KeyModifiers::CONTROL) {
-                            dialog.push_char(ch);
-                        }
+                    KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        dialog.push_char(ch);
                     }
                     _ => {}
                 }
@@ -398,18 +1132,25 @@ pub fn run(
                             let mode = dialog.mode;
                             let base_dir = dialog.base_dir.clone();
                             let target_path = dialog.target_path.clone();
-                            match run_explorer_input_action(
+                            match submit_explorer_input(
                                 mode,
                                 &base_dir,
                                 target_path.as_deref(),
                                 &input,
                                 file_tree.root(),
                             ) {
-                                Ok(message) => {
+                                Ok(ExplorerInputOutcome::Message(message)) => {
                                     explorer_input_dialog = None;
                                     explorer_context_menu = None;
                                     status_message = message;
                                     file_tree.refresh()?;
+                                    symbol_index.rebuild();
+                                }
+                                Ok(ExplorerInputOutcome::Diff(view)) => {
+                                    explorer_input_dialog = None;
+                                    explorer_context_menu = None;
+                                    diff_view = Some(view);
+                                    status_message = String::from("Diff dibuka");
                                 }
                                 Err(err) => {
                                     status_message = format!("Aksi explorer gagal ({})", err);
@@ -417,10 +1158,8 @@ pub fn run(
                             }
                         }
                     }
-                    KeyCode::Char(ch) => {
-                        if !key.modifiers.contains(KeyModifiers::CONTROL) {
-                            dialog.push_char(ch);
-                        }
+                    KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        dialog.push_char(ch);
                     }
                     _ => {}
                 }
@@ -428,56 +1167,562 @@ pub fn run(
             }
 
             if editor_context_menu.is_some() || explorer_context_menu.is_some() {
-                match key.code {
-                    KeyCode::Esc => {
-                        editor_context_menu = None;
-                        explorer_context_menu = None;
-                        status_message = String::from("Menu klik kanan ditutup");
-                    }
-                    _ => {}
+                if key.code == KeyCode::Esc {
+                    editor_context_menu = None;
+                    explorer_context_menu = None;
+                    status_message = String::from("Menu klik kanan ditutup");
                 }
                 continue;
             }
 
-            if let Some(dialog) = search_dialog.as_mut() {
+            if let Some(dialog) = goto_dialog.as_mut() {
                 match key.code {
                     KeyCode::Esc => {
-                        search_dialog = None;
-                        status_message = String::from("Search ditutup");
+                        goto_dialog = None;
+                        status_message = String::from("Pergi ke baris dibatalkan");
                     }
                     KeyCode::Backspace => dialog.pop_char(),
-                    KeyCode::Enter => {
-                        let query = dialog.query.trim().to_string();
-                        if query.is_empty() {
-                            status_message = String::from("Keyword search tidak boleh kosong");
-                        } else if let Some((row, col)) =
-                            find_next_match(tabs.active_editor(), &query)
-                        {
-                            tabs.active_editor_mut().set_cursor(row, col);
+                    KeyCode::Enter => match dialog.target() {
+                        Some((line, column)) => {
+                            let editor = tabs.active_editor_mut();
+                            let row = (line - 1).min(editor.lines().len().saturating_sub(1));
+                            let col = column
+                                .map(|column| char_column_to_byte(&editor.lines()[row], column))
+                                .unwrap_or(0);
+                            editor.set_cursor(row, col);
+                            goto_dialog = None;
                             focus = ui::FocusPane::Editor;
-                            status_message = format!(
-                                "Ditemukan '{}' di baris {}, kolom {}",
-                                query,
-                                row + 1,
-                                col + 1
-                            );
-                        } else {
-                            status_message = format!("Keyword '{}' tidak ditemukan", query);
+                            status_message =
+                                format!("Pindah ke baris {}, kolom {}", row + 1, col + 1);
                         }
-                    }
-                    KeyCode::Char(ch) => {
-                        if !key.modifiers.contains(KeyModifiers::CONTROL) {
-                            dialog.push_char(ch);
+                        None => {
+                            status_message =
+                                String::from("Format: baris atau baris:kolom (mulai dari 1)");
                         }
+                    },
+                    KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        dialog.push_char(ch);
                     }
                     _ => {}
                 }
                 continue;
             }
 
-            match key.code {
-                KeyCode::Esc => {
+            if let Some(dialog) = bookmark_name_dialog.as_mut() {
+                match key.code {
+                    KeyCode::Esc => {
+                        bookmark_name_dialog = None;
+                        status_message = String::from("Bookmark dibatalkan");
+                    }
+                    KeyCode::Backspace => dialog.pop_char(),
+                    KeyCode::Enter => {
+                        let name = dialog.name.trim().to_string();
+                        if name.is_empty() {
+                            status_message = String::from("Nama bookmark tidak boleh kosong");
+                        } else {
+                            let line = dialog.line;
+                            bookmarks.add(
+                                &dialog.path,
+                                name.clone(),
+                                tabs.active_editor().lines(),
+                                line,
+                            );
+                            bookmark_name_dialog = None;
+                            status_message = match bookmarks.save() {
+                                Ok(()) => {
+                                    format!("Bookmark '{}' ditambahkan di baris {}", name, line + 1)
+                                }
+                                Err(err) => format!(
+                                    "Bookmark '{}' ditambahkan, gagal disimpan ({})",
+                                    name, err
+                                ),
+                            };
+                        }
+                    }
+                    KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        dialog.push_char(ch);
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            if let Some(picker) = bookmark_picker.as_mut() {
+                match key.code {
+                    KeyCode::Esc => {
+                        bookmark_picker = None;
+                        status_message = String::from("Daftar bookmark ditutup");
+                    }
+                    KeyCode::Up => picker.move_up(),
+                    KeyCode::Down => picker.move_down(),
+                    KeyCode::Backspace => picker.pop_char(),
+                    KeyCode::Delete => {
+                        if let Some((path, bookmark)) = picker.selected_entry().cloned() {
+                            bookmarks.remove(&path, &bookmark.name);
+                            picker.set_entries(bookmarks.all());
+                            status_message = match bookmarks.save() {
+                                Ok(()) => format!("Bookmark '{}' dihapus", bookmark.name),
+                                Err(err) => format!(
+                                    "Bookmark '{}' dihapus, gagal disimpan ({})",
+                                    bookmark.name, err
+                                ),
+                            };
+                        }
+                    }
+                    KeyCode::Enter => {
+                        if let Some((path, bookmark)) = picker.selected_entry().cloned() {
+                            bookmark_picker = None;
+                            focus = ui::FocusPane::Editor;
+                            status_message = match tabs.open_file(&path) {
+                                Ok(()) => {
+                                    let editor = tabs.active_editor_mut();
+                                    let row = bookmark.resolve(editor.lines());
+                                    editor.set_cursor(row, 0);
+                                    format!("Bookmark '{}' (baris {})", bookmark.name, row + 1)
+                                }
+                                Err(err) => format!("Gagal membuka {} ({})", path.display(), err),
+                            };
+                        }
+                    }
+                    KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        picker.push_char(ch);
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            if let Some(dialog) = macro_dialog.as_mut() {
+                if let Some(name) = dialog.naming.clone() {
+                    match key.code {
+                        KeyCode::Esc => dialog.naming = None,
+                        KeyCode::Backspace => dialog.pop_char(),
+                        KeyCode::Enter => {
+                            let name = name.trim().to_string();
+                            let commands = match dialog.selected_entry() {
+                                Some((None, _)) => macro_recorder.last().map(<[_]>::to_vec),
+                                Some((Some(saved), _)) => {
+                                    macro_store.get(saved).map(|item| item.commands.clone())
+                                }
+                                None => None,
+                            };
+                            if name.is_empty() {
+                                status_message = String::from("Nama macro tidak boleh kosong");
+                            } else if let Some(commands) = commands {
+                                macro_store.insert(name.clone(), commands);
+                                macro_dialog = None;
+                                status_message = match macro_store.save() {
+                                    Ok(()) => format!("Macro '{}' disimpan", name),
+                                    Err(err) => {
+                                        format!("Macro '{}' gagal disimpan ({})", name, err)
+                                    }
+                                };
+                            }
+                        }
+                        KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            dialog.push_char(ch);
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+                match key.code {
+                    KeyCode::Esc => {
+                        macro_dialog = None;
+                        status_message = String::from("Dialog macro ditutup");
+                    }
+                    KeyCode::Up => dialog.move_up(),
+                    KeyCode::Down => dialog.move_down(),
+                    KeyCode::Tab => dialog.toggle_per_line(),
+                    KeyCode::Backspace => dialog.pop_char(),
+                    KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        dialog.naming = Some(
+                            dialog
+                                .selected_entry()
+                                .and_then(|(name, _)| name.clone())
+                                .unwrap_or_default(),
+                        );
+                    }
+                    KeyCode::Delete => {
+                        if let Some((Some(name), _)) = dialog.selected_entry().cloned() {
+                            macro_store.remove(&name);
+                            dialog.remove_selected();
+                            status_message = match macro_store.save() {
+                                Ok(()) => format!("Macro '{}' dihapus", name),
+                                Err(err) => {
+                                    format!("Macro '{}' dihapus, gagal disimpan ({})", name, err)
+                                }
+                            };
+                        }
+                    }
+                    KeyCode::Enter => {
+                        let Some((name, _)) = dialog.selected_entry().cloned() else {
+                            continue;
+                        };
+                        let commands = match name.as_deref() {
+                            None => macro_recorder.last().map(<[_]>::to_vec),
+                            Some(name) => macro_store.get(name).map(|item| item.commands.clone()),
+                        };
+                        let playback = if dialog.per_line {
+                            Playback::EachLine
+                        } else {
+                            Playback::Times(dialog.times())
+                        };
+                        macro_dialog = None;
+                        if let Some(commands) = commands {
+                            let revision = tabs.active_editor().revision();
+                            macros::play(&commands, playback, tabs.active_editor_mut(), &mut vim);
+                            if tabs.active_editor().revision() != revision {
+                                tabs.mark_active_dirty();
+                            }
+                            let name = name.unwrap_or_else(|| String::from("terakhir"));
+                            status_message = match playback {
+                                Playback::Times(1) => format!("Macro '{}' diputar", name),
+                                Playback::Times(times) => {
+                                    format!("Macro '{}' diputar {} kali", name, times)
+                                }
+                                Playback::EachLine => {
+                                    format!("Macro '{}' diputar di setiap baris seleksi", name)
+                                }
+                            };
+                        }
+                    }
+                    KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        dialog.push_char(ch);
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            if let Some(dialog) = transform_dialog.as_mut() {
+                match key.code {
+                    KeyCode::Esc if dialog.input.is_some() => dialog.input = None,
+                    KeyCode::Esc => {
+                        transform_dialog = None;
+                        status_message = String::from("Dialog transformasi ditutup");
+                    }
+                    KeyCode::Up => dialog.move_up(),
+                    KeyCode::Down => dialog.move_down(),
+                    KeyCode::Backspace => dialog.pop_char(),
+                    KeyCode::Enter => {
+                        let (kind, arg) = match dialog.input.clone() {
+                            Some((_, arg)) if arg.trim().is_empty() => {
+                                status_message =
+                                    String::from("Argumen transformasi tidak boleh kosong");
+                                continue;
+                            }
+                            Some(input) => input,
+                            None => {
+                                let Some(kind) = dialog.selected_transform() else {
+                                    continue;
+                                };
+                                if kind.prompt().is_some() {
+                                    dialog.input = Some((kind, String::new()));
+                                    continue;
+                                }
+                                (kind, String::new())
+                            }
+                        };
+                        transform_dialog = None;
+                        status_message = run_transform(
+                            &mut tabs,
+                            &mut vim,
+                            &mut macro_recorder,
+                            &mut jobs,
+                            kind,
+                            arg,
+                            editor_settings.tab_width,
+                        );
+                    }
+                    KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        dialog.push_char(ch);
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            if let Some(dialog) = symbol_dialog.as_mut() {
+                match key.code {
+                    KeyCode::Esc => {
+                        symbol_dialog = None;
+                        status_message = String::from("Pencarian simbol ditutup");
+                    }
+                    KeyCode::Up => dialog.move_up(1),
+                    KeyCode::Down => dialog.move_down(1),
+                    KeyCode::PageUp => dialog.move_up(10),
+                    KeyCode::PageDown => dialog.move_down(10),
+                    KeyCode::Backspace => dialog.pop_char(&symbol_index),
+                    KeyCode::Enter => {
+                        if let Some(symbol) = dialog.selected_symbol().cloned() {
+                            symbol_dialog = None;
+                            focus = ui::FocusPane::Editor;
+                            status_message =
+                                match jump_to_workspace_symbol(&mut tabs, &symbol_index, &symbol) {
+                                    Ok(message) => message,
+                                    Err(err) => {
+                                        format!("Gagal membuka {} ({})", symbol.path.display(), err)
+                                    }
+                                };
+                        }
+                    }
+                    KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        dialog.push_char(ch, &symbol_index);
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            if let Some(dialog) = search_dialog.as_mut() {
+                match key.code {
+                    KeyCode::Esc => {
+                        search_dialog = None;
+                        status_message = String::from("Search ditutup");
+                    }
+                    KeyCode::Backspace => dialog.pop_char(),
+                    KeyCode::Enter => {
+                        let query = dialog.query.trim().to_string();
+                        if query.is_empty() {
+                            status_message = String::from("Keyword search tidak boleh kosong");
+                        } else if let Some((row, col)) =
+                            find_next_match(tabs.active_editor(), &query)
+                        {
+                            tabs.active_editor_mut().set_cursor(row, col);
+                            focus = ui::FocusPane::Editor;
+                            status_message = format!(
+                                "Ditemukan '{}' di baris {}, kolom {}",
+                                query,
+                                row + 1,
+                                col + 1
+                            );
+                        } else {
+                            status_message = format!("Keyword '{}' tidak ditemukan", query);
+                        }
+                    }
+                    KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        dialog.push_char(ch);
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            if focus == ui::FocusPane::Terminal {
+                let alt = key.modifiers.contains(KeyModifiers::ALT);
+                match key.code {
+                    KeyCode::F(12) => {
+                        focus = ui::FocusPane::Editor;
+                        status_message = String::from("Fokus ke editor");
+                    }
+                    KeyCode::Char('`') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        focus = ui::FocusPane::Editor;
+                        status_message = String::from("Fokus ke editor");
+                    }
+                    KeyCode::Char('n') if alt => {
+                        match open_terminal_session(
+                            &mut terminal_panes,
+                            file_tree.root(),
+                            terminal.size()?.width,
+                        ) {
+                            Ok(()) => status_message = String::from("Terminal baru dibuka"),
+                            Err(err) => status_message = format!("Gagal membuka terminal ({})", err),
+                        }
+                    }
+                    KeyCode::Char('w') if alt => {
+                        terminal_panes.close_active();
+                        if terminal_panes.is_visible() {
+                            status_message = String::from("Terminal ditutup");
+                        } else {
+                            focus = ui::FocusPane::Editor;
+                            status_message = String::from("Semua terminal ditutup");
+                        }
+                    }
+                    KeyCode::Char('h') if alt => {
+                        terminal_panes.set_visible(false);
+                        focus = ui::FocusPane::Editor;
+                        status_message = String::from("Panel terminal disembunyikan");
+                    }
+                    KeyCode::Char(ch)
+                        if key.modifiers.contains(KeyModifiers::CONTROL)
+                            && key.modifiers.contains(KeyModifiers::SHIFT)
+                            && ch.eq_ignore_ascii_case(&'v') =>
+                    {
+                        let paste_text = read_clipboard_text(&mut system_clipboard, &clipboard);
+                        if let Some(session) = terminal_panes.active_mut()
+                            && let Err(err) = session.send_text(&paste_text)
+                        {
+                            status_message = format!("Terminal: {}", err);
+                        }
+                    }
+                    KeyCode::Left if alt => terminal_panes.prev(),
+                    KeyCode::Right if alt => terminal_panes.next(),
+                    KeyCode::PageUp if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                        if let Some(session) = terminal_panes.active_mut() {
+                            session.scroll_up(terminal_rect.height.max(1) as usize / 2);
+                        }
+                    }
+                    KeyCode::PageDown if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                        if let Some(session) = terminal_panes.active_mut() {
+                            session.scroll_down(terminal_rect.height.max(1) as usize / 2);
+                        }
+                    }
+                    _ => {
+                        if let Some(session) = terminal_panes.active_mut()
+                            && let Err(err) = session.send_key(key)
+                        {
+                            status_message = format!("Terminal: {}", err);
+                        }
+                    }
+                }
+                continue;
+            }
+
+            if focus == ui::FocusPane::Outline
+                && !key.modifiers.contains(KeyModifiers::CONTROL)
+                && !matches!(key.code, KeyCode::Tab | KeyCode::BackTab | KeyCode::F(_))
+            {
+                match key.code {
+                    KeyCode::Esc if !outline.filter.is_empty() => {
+                        outline.filter.clear();
+                        status_message = String::from("Filter outline dihapus");
+                    }
+                    KeyCode::Esc => {
+                        focus = ui::FocusPane::Editor;
+                        status_message = String::from("Fokus ke editor");
+                    }
+                    KeyCode::Up => outline.move_up(),
+                    KeyCode::Down => outline.move_down(),
+                    KeyCode::Enter => {
+                        if let Some(symbol) = outline.selected_symbol() {
+                            tabs.active_editor_mut()
+                                .set_cursor(symbol.line, symbol.column);
+                            status_message = format!(
+                                "{} {} (baris {})",
+                                symbol.kind.label(),
+                                symbol.name,
+                                symbol.line + 1
+                            );
+                            focus = ui::FocusPane::Editor;
+                        }
+                    }
+                    KeyCode::Backspace => outline.pop_char(),
+                    KeyCode::Char(ch) => outline.push_char(ch),
+                    _ => {}
+                }
+                continue;
+            }
+
+            if editor_settings.vim_mode
+                && focus == ui::FocusPane::Editor
+                && let Some(ch) = vim.key_char(key)
+            {
+                let revision = tabs.active_editor().revision();
+                let outcome = vim.feed(ch, tabs.active_editor_mut());
+                if tabs.active_editor().revision() != revision {
+                    tabs.mark_active_dirty();
+                }
+                // Karakter Vim sudah lepas dari keymap; lihat `EditorCommand::Vim`.
+                macro_recorder.record(EditorCommand::Vim(ch.to_string()));
+                match outcome {
+                    VimOutcome::Done => continue,
+                    VimOutcome::Status(message) => {
+                        status_message = message;
+                        continue;
+                    }
+                    VimOutcome::Ex(ExCommand::Write(name)) => {
+                        let filename = name.or_else(|| {
+                            tabs.active_path()
+                                .map(|path| path.to_string_lossy().into_owned())
+                        });
+                        match filename {
+                            Some(filename) => {
+                                if !(format_settings.format_on_save
+                                    && start_format_on_save(
+                                        &tabs,
+                                        &formatters,
+                                        &mut jobs,
+                                        &filename,
+                                        &mut status_message,
+                                    ))
+                                {
+                                    save_to_filename(
+                                        &mut tabs,
+                                        &mut file_tree,
+                                        &mut symbol_index,
+                                        &mut save_dialog,
+                                        &mut key_store,
+                                        &mut status_message,
+                                        filename,
+                                    )?;
+                                }
+                            }
+                            None => {
+                                save_dialog = Some(ui::SaveDialog {
+                                    filename: tabs.active_suggested_filename(),
+                                    ..Default::default()
+                                });
+                                status_message =
+                                    String::from("Buffer belum punya nama, isi nama file");
+                            }
+                        }
+                        continue;
+                    }
+                    VimOutcome::Ex(command) => {
+                        // `:wq` menyimpan langsung tanpa format-on-save supaya tab bisa langsung ditutup.
+                        if command == ExCommand::WriteQuit {
+                            match tabs
+                                .active_path()
+                                .map(|path| path.to_string_lossy().into_owned())
+                            {
+                                Some(filename) => save_to_filename(
+                                    &mut tabs,
+                                    &mut file_tree,
+                                    &mut symbol_index,
+                                    &mut save_dialog,
+                                    &mut key_store,
+                                    &mut status_message,
+                                    filename,
+                                )?,
+                                None => {
+                                    status_message = String::from(
+                                        "Buffer belum punya nama, simpan dengan :w <nama>",
+                                    );
+                                    continue;
+                                }
+                            }
+                        }
+                        let (all, force) = match command {
+                            ExCommand::QuitAll { force } => (true, force),
+                            ExCommand::Quit { force } => (false, force),
+                            _ => (false, false),
+                        };
+                        if all && !force && tabs.has_unsaved_changes() {
+                            status_message = String::from(
+                                "Masih ada perubahan yang belum disimpan (:qa! untuk membuang)",
+                            );
+                        } else if !all && !force && tabs.active_tab_is_dirty() {
+                            status_message = String::from(
+                                "Tab memiliki perubahan yang belum disimpan (:q! untuk membuang)",
+                            );
+                        } else if all || tabs.tabs().len() == 1 {
+                            break;
+                        } else {
+                            tabs.close_active_tab();
+                            vim.reset(tabs.active_editor_mut());
+                            status_message =
+                                format!("Tab ditutup, aktif: {}", tabs.active_tab_title());
+                        }
+                        continue;
+                    }
+                }
+            }
+
+            match key.code {
+                KeyCode::Esc => {
                     if tabs.has_unsaved_changes() {
+                        open_unsaved_prompt(&mut tabs, &mut unsaved_prompt, true);
                         status_message = String::from("Masih ada perubahan yang belum disimpan! Simpan dulu dengan Ctrl+S");
                     } else {
                         break;
@@ -485,6 +1730,7 @@ pub fn run(
                 }
                 KeyCode::Char('q') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                     if tabs.has_unsaved_changes() {
+                        open_unsaved_prompt(&mut tabs, &mut unsaved_prompt, true);
                         status_message = String::from("Masih ada perubahan yang belum disimpan! Simpan dulu dengan Ctrl+S");
                     } else {
                         break;
@@ -494,6 +1740,24 @@ pub fn run(
                     help_dialog_open = true;
                     status_message = String::from("Bantuan dibuka");
                 }
+                KeyCode::F(12) => {
+                    focus_terminal(
+                        &mut terminal_panes,
+                        &mut focus,
+                        &mut status_message,
+                        file_tree.root(),
+                        terminal.size()?.width,
+                    );
+                }
+                KeyCode::Char('`') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    focus_terminal(
+                        &mut terminal_panes,
+                        &mut focus,
+                        &mut status_message,
+                        file_tree.root(),
+                        terminal.size()?.width,
+                    );
+                }
                 KeyCode::Char('m') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                     start_menu = Some(ui::StartMenuDialog::default());
                     status_message = String::from("Start menu dibuka");
@@ -511,20 +1775,82 @@ pub fn run(
                         && key.modifiers.contains(KeyModifiers::SHIFT)
                         && ch.eq_ignore_ascii_case(&'f') =>
                 {
-                    match format_active_rust_file(&mut tabs) {
-                        Ok((message, changed)) => {
-                            if changed {
-                                tabs.mark_active_dirty();
-                            }
-                            status_message = message;
+                    let range = selection_format_range(tabs.active_editor());
+                    match spawn_format_job(&tabs, &formatters, range, None) {
+                        Ok(entry) => {
+                            status_message = format!("{} berjalan...", entry.0.label());
+                            jobs.push(entry);
                         }
                         Err(err) => {
-                            status_message = format!("Format Rust gagal: {}", err);
+                            status_message = format!("Format gagal: {}", err);
                         }
                     }
                 }
+                KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    symbol_dialog = Some(ui::WorkspaceSymbolDialog::search(&symbol_index));
+                    status_message =
+                        String::from("Cari simbol workspace: ketik nama, Enter untuk buka");
+                }
+                KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    goto_dialog = Some(ui::GoToLineDialog::new(tabs.active_editor().lines().len()));
+                    status_message =
+                        String::from("Pergi ke baris: ketik baris[:kolom], Enter untuk lompat");
+                }
+                KeyCode::Char('m') if key.modifiers.contains(KeyModifiers::ALT) => {
+                    bookmark_picker =
+                        Some(ui::BookmarkPicker::new(bookmarks.all(), file_tree.root()));
+                    status_message = String::from("Daftar bookmark dibuka");
+                }
+                KeyCode::Char('b')
+                    if focus == ui::FocusPane::Editor
+                        && key.modifiers.contains(KeyModifiers::ALT) =>
+                {
+                    match tabs.active_path().map(Path::to_path_buf) {
+                        Some(path) => {
+                            let editor = tabs.active_editor();
+                            let row = editor.cursor_y();
+                            if let Some(name) = bookmarks
+                                .at_line(&path, editor.lines(), row)
+                                .map(|bookmark| bookmark.name.clone())
+                            {
+                                bookmarks.remove(&path, &name);
+                                status_message = match bookmarks.save() {
+                                    Ok(()) => format!("Bookmark '{}' dihapus", name),
+                                    Err(err) => format!(
+                                        "Bookmark '{}' dihapus, gagal disimpan ({})",
+                                        name, err
+                                    ),
+                                };
+                            } else {
+                                let title = tabs.active_tab_title();
+                                bookmark_name_dialog = Some(ui::BookmarkNameDialog {
+                                    name: format!("{}:{}", title, row + 1),
+                                    path,
+                                    line: row,
+                                });
+                                status_message =
+                                    String::from("Beri nama bookmark lalu tekan Enter");
+                            }
+                        }
+                        None => {
+                            status_message =
+                                String::from("Simpan file dulu sebelum menambah bookmark");
+                        }
+                    }
+                }
+                KeyCode::Char('d')
+                    if focus == ui::FocusPane::Editor
+                        && key.modifiers.contains(KeyModifiers::CONTROL) =>
+                {
+                    symbol_dialog = go_to_definition(&mut tabs, &symbol_index, &mut status_message);
+                }
                 KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                    manager_dialog = Some(ui::ManagerDialog::default());
+                    manager_dialog = Some(open_manager_dialog(
+                        cargo_run.as_ref(),
+                        &cargo_log,
+                        &jobs,
+                        &format_settings,
+                    ));
                     status_message = String::from("Rust/Cargo manager dibuka");
                 }
                 KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
@@ -534,6 +1860,7 @@ pub fn run(
                 }
                 KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                     if tabs.active_tab_is_dirty() {
+                        open_unsaved_prompt(&mut tabs, &mut unsaved_prompt, false);
                         status_message = String::from("Tab memiliki perubahan yang belum disimpan! Simpan dulu dengan Ctrl+S");
                     } else {
                         tabs.close_active_tab();
@@ -541,9 +1868,27 @@ pub fn run(
                         status_message = format!("Tab ditutup, aktif: {}", tabs.active_tab_title());
                     }
                 }
+                KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    match git_repo.as_mut() {
+                        Some(repo) => match open_git_dialog(repo, &tabs) {
+                            Ok(dialog) => {
+                                git_dialog = Some(dialog);
+                                status_message = String::from("Source control dibuka");
+                            }
+                            Err(err) => {
+                                status_message = format!("Git gagal: {}", err);
+                            }
+                        },
+                        None => {
+                            status_message = String::from("Direktori kerja bukan repository git");
+                        }
+                    }
+                }
                 KeyCode::Char('b') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                     show_file_tree = !show_file_tree;
-                    if !show_file_tree && focus == ui::FocusPane::FileTree {
+                    if !show_file_tree
+                        && matches!(focus, ui::FocusPane::FileTree | ui::FocusPane::Outline)
+                    {
                         focus = ui::FocusPane::Editor;
                     }
                     status_message = if show_file_tree {
@@ -552,10 +1897,109 @@ pub fn run(
                         String::from("File tree disembunyikan")
                     };
                 }
+                KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    show_outline = !show_outline;
+                    if show_outline {
+                        show_file_tree = true;
+                        focus = ui::FocusPane::Outline;
+                        status_message =
+                            String::from("Outline dibuka: ketik untuk filter, Enter untuk lompat");
+                    } else {
+                        if focus == ui::FocusPane::Outline {
+                            focus = ui::FocusPane::Editor;
+                        }
+                        status_message = String::from("Explorer ditampilkan");
+                    }
+                }
+                KeyCode::Char('q') if key.modifiers.contains(KeyModifiers::ALT) => {
+                    if macro_recorder.is_recording() {
+                        status_message = match macro_recorder.stop() {
+                            0 => String::from("Rekaman macro kosong, dibatalkan"),
+                            count => format!(
+                                "Macro terekam: {} perintah (Alt+P untuk memutar/menyimpan)",
+                                count
+                            ),
+                        };
+                    } else {
+                        macro_recorder.start();
+                        focus = ui::FocusPane::Editor;
+                        status_message = String::from("Merekam macro (Alt+Q untuk berhenti)");
+                    }
+                }
+                KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::ALT) => {
+                    let recent = macro_recorder.last().map(<[_]>::len);
+                    if macro_recorder.is_recording() {
+                        status_message = String::from("Hentikan rekaman dulu dengan Alt+Q");
+                    } else if recent.is_none() && macro_store.all().is_empty() {
+                        status_message = String::from("Belum ada macro (Alt+Q untuk merekam)");
+                    } else {
+                        let editor = tabs.active_editor();
+                        let selected_lines = if editor.has_selection() {
+                            let (start, end) = editor.movable_line_range();
+                            end - start + 1
+                        } else {
+                            0
+                        };
+                        macro_dialog = Some(ui::MacroDialog::new(
+                            recent,
+                            macro_store.all(),
+                            selected_lines,
+                        ));
+                        status_message = String::from("Pilih macro untuk diputar");
+                    }
+                }
+                KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::ALT) => {
+                    transform_dialog = Some(ui::TransformDialog::default());
+                    focus = ui::FocusPane::Editor;
+                    status_message = String::from("Pilih transformasi teks");
+                }
+                KeyCode::Char('.') if key.modifiers.contains(KeyModifiers::ALT) => {
+                    editor_settings.show_whitespace = !editor_settings.show_whitespace;
+                    status_message = match editor_settings.save() {
+                        Ok(()) if editor_settings.show_whitespace => {
+                            String::from("Whitespace ditampilkan")
+                        }
+                        Ok(()) => String::from("Whitespace disembunyikan"),
+                        Err(err) => format!("Pengaturan editor gagal disimpan ({})", err),
+                    };
+                }
+                KeyCode::Char('i') if key.modifiers.contains(KeyModifiers::ALT) => {
+                    editor_settings.indent_guides = !editor_settings.indent_guides;
+                    status_message = match editor_settings.save() {
+                        Ok(()) if editor_settings.indent_guides => {
+                            String::from("Indent guide aktif")
+                        }
+                        Ok(()) => String::from("Indent guide nonaktif"),
+                        Err(err) => format!("Pengaturan editor gagal disimpan ({})", err),
+                    };
+                }
+                KeyCode::Char('v') if key.modifiers.contains(KeyModifiers::ALT) => {
+                    editor_settings.vim_mode = !editor_settings.vim_mode;
+                    vim.reset(tabs.active_editor_mut());
+                    status_message = match editor_settings.save() {
+                        Ok(()) if editor_settings.vim_mode => {
+                            String::from("Mode Vim aktif (Alt+V untuk mematikan)")
+                        }
+                        Ok(()) => String::from("Mode Vim nonaktif"),
+                        Err(err) => format!("Pengaturan editor gagal disimpan ({})", err),
+                    };
+                }
                 KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                     theme = theme.toggle();
                     status_message = format!("Tema diubah: {}", theme.label());
                 }
+                KeyCode::Tab | KeyCode::BackTab
+                    if focus == ui::FocusPane::Editor
+                        && !key.modifiers.contains(KeyModifiers::CONTROL)
+                        && tabs.active_editor().has_selection() =>
+                {
+                    let command = if key.code == KeyCode::Tab {
+                        EditorCommand::Indent
+                    } else {
+                        EditorCommand::Outdent
+                    };
+                    run_editor_command(&mut tabs, &mut vim, &mut macro_recorder, command);
+                }
                 KeyCode::Tab if key.modifiers.contains(KeyModifiers::CONTROL) => {
                     tabs.next_tab();
                     status_message = format!("Tab aktif: {}", tabs.active_tab_title());
@@ -566,21 +2010,30 @@ pub fn run(
                 }
                 KeyCode::Tab => {
                     focus = match focus {
+                        ui::FocusPane::Editor if show_file_tree && show_outline => {
+                            ui::FocusPane::Outline
+                        }
                         ui::FocusPane::Editor if show_file_tree => ui::FocusPane::FileTree,
                         ui::FocusPane::Editor => ui::FocusPane::Tabs,
-                        ui::FocusPane::FileTree => ui::FocusPane::Tabs,
-                        ui::FocusPane::Tabs => ui::FocusPane::Editor,
+                        ui::FocusPane::FileTree | ui::FocusPane::Outline => ui::FocusPane::Tabs,
+                        ui::FocusPane::Tabs if terminal_panes.is_visible() => {
+                            ui::FocusPane::Terminal
+                        }
+                        ui::FocusPane::Tabs | ui::FocusPane::Terminal => ui::FocusPane::Editor,
                     };
                     status_message = match focus {
                         ui::FocusPane::Editor => String::from("Fokus ke editor"),
                         ui::FocusPane::FileTree => String::from("Fokus ke file tree"),
+                        ui::FocusPane::Outline => String::from("Fokus ke outline"),
                         ui::FocusPane::Tabs => String::from("Fokus ke tabs"),
+                        ui::FocusPane::Terminal => String::from("Fokus ke terminal (F12 kembali)"),
                     };
                 }
                 KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                    let mut dialog = ui::SaveDialog::default();
-                    dialog.filename = tabs.active_suggested_filename();
-                    save_dialog = Some(dialog);
+                    save_dialog = Some(ui::SaveDialog {
+                        filename: tabs.active_suggested_filename(),
+                        ..ui::SaveDialog::default()
+                    });
                     status_message = String::from("Dialog simpan terbuka");
                 }
                 KeyCode::Enter if focus == ui::FocusPane::FileTree => {
@@ -613,15 +2066,37 @@ pub fn run(
                     if focus == ui::FocusPane::Editor
                         && key.modifiers.contains(KeyModifiers::CONTROL) =>
                 {
-                    tabs.active_editor_mut().select_all();
+                    run_editor_command(
+                        &mut tabs,
+                        &mut vim,
+                        &mut macro_recorder,
+                        EditorCommand::SelectAll,
+                    );
                     status_message = String::from("Select all aktif");
                 }
+                KeyCode::Up | KeyCode::Down
+                    if focus == ui::FocusPane::Editor
+                        && key
+                            .modifiers
+                            .contains(KeyModifiers::ALT | KeyModifiers::SHIFT) =>
+                {
+                    let command = EditorCommand::DuplicateLines {
+                        below: key.code == KeyCode::Down,
+                    };
+                    run_editor_command(&mut tabs, &mut vim, &mut macro_recorder, command);
+                    status_message = String::from("Blok/baris digandakan");
+                }
                 KeyCode::Up
                     if focus == ui::FocusPane::Editor
                         && key.modifiers.contains(KeyModifiers::ALT) =>
                 {
-                    if tabs.active_editor_mut().move_selected_lines_up() {
-                        tabs.mark_active_dirty();
+                    let moved = run_editor_command(
+                        &mut tabs,
+                        &mut vim,
+                        &mut macro_recorder,
+                        EditorCommand::MoveLinesUp,
+                    );
+                    if moved {
                         status_message = String::from("Blok/baris dipindah ke atas");
                     }
                 }
@@ -629,8 +2104,13 @@ pub fn run(
                     if focus == ui::FocusPane::Editor
                         && key.modifiers.contains(KeyModifiers::ALT) =>
                 {
-                    if tabs.active_editor_mut().move_selected_lines_down() {
-                        tabs.mark_active_dirty();
+                    let moved = run_editor_command(
+                        &mut tabs,
+                        &mut vim,
+                        &mut macro_recorder,
+                        EditorCommand::MoveLinesDown,
+                    );
+                    if moved {
                         status_message = String::from("Blok/baris dipindah ke bawah");
                     }
                 }
@@ -695,8 +2175,8 @@ pub fn run(
                     if paste_text.is_empty() {
                         status_message = String::from("Clipboard kosong");
                     } else {
-                        tabs.active_editor_mut().insert_text(&paste_text);
-                        tabs.mark_active_dirty();
+                        let command = EditorCommand::Insert(paste_text);
+                        run_editor_command(&mut tabs, &mut vim, &mut macro_recorder, command);
                         status_message = String::from("Paste berhasil");
                     }
                 }
@@ -705,6 +2185,7 @@ pub fn run(
                         && key.modifiers.contains(KeyModifiers::CONTROL) =>
                 {
                     let cut_text = tabs.active_editor_mut().cut_current_line();
+                    macro_recorder.record(EditorCommand::CutLine);
                     let wrote_system =
                         write_clipboard_text(&mut system_clipboard, &mut clipboard, cut_text);
                     tabs.mark_active_dirty();
@@ -714,101 +2195,330 @@ pub fn run(
                         String::from("Teks dipotong (fallback clipboard internal)")
                     };
                 }
+                KeyCode::Char('j')
+                    if focus == ui::FocusPane::Editor
+                        && key.modifiers.contains(KeyModifiers::CONTROL) =>
+                {
+                    let joined = run_editor_command(
+                        &mut tabs,
+                        &mut vim,
+                        &mut macro_recorder,
+                        EditorCommand::JoinLines,
+                    );
+                    if !joined {
+                        status_message = String::from("Tidak ada baris berikutnya untuk digabung");
+                    }
+                }
+                // Terminal mengirim Ctrl+/ sebagai Ctrl+7 (0x1F).
+                KeyCode::Char('/' | '7')
+                    if focus == ui::FocusPane::Editor
+                        && key.modifiers.contains(KeyModifiers::CONTROL) =>
+                {
+                    match CommentStyle::for_path(tabs.active_path()).and_then(|style| style.line) {
+                        Some(token) => {
+                            let command = EditorCommand::ToggleLineComment(token.to_string());
+                            run_editor_command(&mut tabs, &mut vim, &mut macro_recorder, command);
+                        }
+                        None => {
+                            status_message =
+                                String::from("Komentar baris tidak didukung untuk file ini")
+                        }
+                    }
+                }
+                KeyCode::Char('/')
+                    if focus == ui::FocusPane::Editor
+                        && key.modifiers.contains(KeyModifiers::ALT) =>
+                {
+                    match CommentStyle::for_path(tabs.active_path()).and_then(|style| style.block) {
+                        Some((open, close)) => {
+                            let command = EditorCommand::ToggleBlockComment(
+                                open.to_string(),
+                                close.to_string(),
+                            );
+                            run_editor_command(&mut tabs, &mut vim, &mut macro_recorder, command);
+                        }
+                        None => {
+                            status_message =
+                                String::from("Komentar blok tidak didukung untuk file ini")
+                        }
+                    }
+                }
+                // Ctrl+Backspace umumnya sampai sebagai Ctrl+H.
+                KeyCode::Char('h')
+                    if focus == ui::FocusPane::Editor
+                        && key.modifiers.contains(KeyModifiers::CONTROL) =>
+                {
+                    run_editor_command(
+                        &mut tabs,
+                        &mut vim,
+                        &mut macro_recorder,
+                        EditorCommand::DeleteWordLeft,
+                    );
+                }
                 KeyCode::Char(ch) if focus == ui::FocusPane::Editor => {
-                    tabs.active_editor_mut().type_char_smart(ch);
-                    tabs.mark_active_dirty();
+                    let command = EditorCommand::Type(ch.to_string());
+                    run_editor_command(&mut tabs, &mut vim, &mut macro_recorder, command);
                 }
                 KeyCode::Enter if focus == ui::FocusPane::Editor => {
-                    tabs.active_editor_mut().insert_newline_smart();
-                    tabs.mark_active_dirty();
+                    run_editor_command(
+                        &mut tabs,
+                        &mut vim,
+                        &mut macro_recorder,
+                        EditorCommand::Newline,
+                    );
+                }
+                KeyCode::Backspace
+                    if focus == ui::FocusPane::Editor
+                        && key
+                            .modifiers
+                            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+                {
+                    run_editor_command(
+                        &mut tabs,
+                        &mut vim,
+                        &mut macro_recorder,
+                        EditorCommand::DeleteWordLeft,
+                    );
                 }
                 KeyCode::Backspace if focus == ui::FocusPane::Editor => {
-                    tabs.active_editor_mut().backspace();
-                    tabs.mark_active_dirty();
+                    run_editor_command(
+                        &mut tabs,
+                        &mut vim,
+                        &mut macro_recorder,
+                        EditorCommand::Backspace,
+                    );
+                }
+                KeyCode::Delete if focus == ui::FocusPane::Editor => {
+                    let command = if key.modifiers.contains(KeyModifiers::CONTROL) {
+                        EditorCommand::DeleteWordRight
+                    } else {
+                        EditorCommand::Delete
+                    };
+                    run_editor_command(&mut tabs, &mut vim, &mut macro_recorder, command);
+                }
+                KeyCode::Left | KeyCode::Right
+                    if focus == ui::FocusPane::Editor
+                        && key.modifiers.contains(KeyModifiers::CONTROL) =>
+                {
+                    let subword = key.modifiers.contains(KeyModifiers::ALT);
+                    let motion = match (key.code == KeyCode::Left, subword) {
+                        (true, false) => Motion::WordLeft,
+                        (true, true) => Motion::SubwordLeft,
+                        (false, false) => Motion::WordRight,
+                        (false, true) => Motion::SubwordRight,
+                    };
+                    move_cursor(
+                        &mut tabs,
+                        &mut vim,
+                        &mut macro_recorder,
+                        motion,
+                        key.modifiers,
+                    );
+                }
+                KeyCode::Up | KeyCode::Down
+                    if focus == ui::FocusPane::Editor
+                        && key.modifiers.contains(KeyModifiers::CONTROL) =>
+                {
+                    let motion = if key.code == KeyCode::Up {
+                        Motion::ParagraphUp
+                    } else {
+                        Motion::ParagraphDown
+                    };
+                    move_cursor(
+                        &mut tabs,
+                        &mut vim,
+                        &mut macro_recorder,
+                        motion,
+                        key.modifiers,
+                    );
+                }
+                KeyCode::Left | KeyCode::Right
+                    if focus == ui::FocusPane::Editor
+                        && key.modifiers.contains(KeyModifiers::ALT) =>
+                {
+                    let back = key.code == KeyCode::Left;
+                    let target = current_location(&tabs).and_then(|current| {
+                        if back {
+                            jump_list.back(current)
+                        } else {
+                            jump_list.forward(current)
+                        }
+                    });
+                    status_message = match target {
+                        Some(target) => match open_location(&mut tabs, &target) {
+                            Ok(()) => {
+                                last_location = current_location(&tabs);
+                                format!(
+                                    "{} {}:{}",
+                                    if back { "Kembali ke" } else { "Maju ke" },
+                                    target.path.display(),
+                                    target.line + 1
+                                )
+                            }
+                            Err(err) => {
+                                format!("Gagal membuka {} ({})", target.path.display(), err)
+                            }
+                        },
+                        None if back => String::from("Tidak ada lokasi sebelumnya di jump list"),
+                        None => String::from("Tidak ada lokasi berikutnya di jump list"),
+                    };
+                }
+                KeyCode::Left
+                | KeyCode::Right
+                | KeyCode::Up
+                | KeyCode::Down
+                | KeyCode::Home
+                | KeyCode::End
+                    if focus == ui::FocusPane::Editor =>
+                {
+                    let control = key.modifiers.contains(KeyModifiers::CONTROL);
+                    let motion = match key.code {
+                        KeyCode::Left => Motion::Left,
+                        KeyCode::Right => Motion::Right,
+                        KeyCode::Up => Motion::Up,
+                        KeyCode::Down => Motion::Down,
+                        KeyCode::Home if control => Motion::DocumentStart,
+                        KeyCode::Home => Motion::Home,
+                        _ if control => Motion::DocumentEnd,
+                        _ => Motion::End,
+                    };
+                    move_cursor(
+                        &mut tabs,
+                        &mut vim,
+                        &mut macro_recorder,
+                        motion,
+                        key.modifiers,
+                    );
+                }
+                KeyCode::PageUp | KeyCode::PageDown if focus == ui::FocusPane::Editor => {
+                    let page = ui::editor_viewport_height(
+                        terminal.size()?.height,
+                        terminal_panes.is_visible(),
+                    )
+                    .max(1);
+                    let motion = if key.code == KeyCode::PageUp {
+                        Motion::PageUp(page)
+                    } else {
+                        Motion::PageDown(page)
+                    };
+                    move_cursor(
+                        &mut tabs,
+                        &mut vim,
+                        &mut macro_recorder,
+                        motion,
+                        key.modifiers,
+                    );
                 }
-                KeyCode::Left if focus == ui::FocusPane::Editor => tabs
-                    .active_editor_mut()
-                    .move_left_selecting(key.modifiers.contains(KeyModifiers::SHIFT)),
-                KeyCode::Right if focus == ui::FocusPane::Editor => tabs
-                    .active_editor_mut()
-                    .move_right_selecting(key.modifiers.contains(KeyModifiers::SHIFT)),
-                KeyCode::Up if focus == ui::FocusPane::Editor => tabs
-                    .active_editor_mut()
-                    .move_up_selecting(key.modifiers.contains(KeyModifiers::SHIFT)),
-                KeyCode::Down if focus == ui::FocusPane::Editor => tabs
-                    .active_editor_mut()
-                    .move_down_selecting(key.modifiers.contains(KeyModifiers::SHIFT)),
                 _ => {}
             }
         } else if let Event::Mouse(mouse) = ev {
             if start_menu.is_some()
                 || help_dialog_open
                 || search_dialog.is_some()
+                || symbol_dialog.is_some()
+                || goto_dialog.is_some()
+                || bookmark_name_dialog.is_some()
+                || bookmark_picker.is_some()
+                || macro_dialog.is_some()
+                || transform_dialog.is_some()
                 || manager_dialog.is_some()
+                || git_dialog.is_some()
+                || key_dialog.is_some()
+                || unsaved_prompt.is_some()
             {
                 continue;
             }
 
+            if let Some(view) = diff_view.as_mut() {
+                match mouse.kind {
+                    MouseEventKind::ScrollUp => view.scroll_up(3),
+                    MouseEventKind::ScrollDown => view.scroll_down(3),
+                    _ => {}
+                }
+                continue;
+            }
+
             if let Some(dialog) = save_dialog.as_mut() {
-                if mouse.kind == MouseEventKind::Down(MouseButton::Left) {
-                    if let Some(hit) = ui_state.save_dialog_hit {
-                        let point = (mouse.column, mouse.row);
-                        if point_in_rect(point, hit.save_button_rect) {
-                            dialog.selected = ui::DialogButton::Save;
-                            let filename = dialog.filename.trim().to_string();
+                if mouse.kind == MouseEventKind::Down(MouseButton::Left)
+                    && let Some(hit) = ui_state.save_dialog_hit
+                {
+                    let point = (mouse.column, mouse.row);
+                    if point_in_rect(point, hit.save_button_rect) {
+                        dialog.selected = ui::DialogButton::Save;
+                        let filename = dialog.filename.trim().to_string();
+                        if format_settings.format_on_save
+                            && start_format_on_save(
+                                &tabs,
+                                &formatters,
+                                &mut jobs,
+                                &filename,
+                                &mut status_message,
+                            )
+                        {
+                            save_dialog = None;
+                        } else {
                             save_to_filename(
                                 &mut tabs,
                                 &mut file_tree,
+                                &mut symbol_index,
                                 &mut save_dialog,
+                                &mut key_store,
                                 &mut status_message,
                                 filename,
                             )?;
-                        } else if point_in_rect(point, hit.cancel_button_rect) {
-                            dialog.selected = ui::DialogButton::Cancel;
-                            save_dialog = None;
-                            status_message = String::from("Simpan dibatalkan");
-                        } else if point_in_rect(point, hit.input_rect) {
-                            dialog.selected = ui::DialogButton::Save;
                         }
+                    } else if point_in_rect(point, hit.cancel_button_rect) {
+                        dialog.selected = ui::DialogButton::Cancel;
+                        save_dialog = None;
+                        status_message = String::from("Simpan dibatalkan");
+                    } else if point_in_rect(point, hit.input_rect) {
+                        dialog.selected = ui::DialogButton::Save;
                     }
                 }
                 continue;
             }
 
             if let Some(dialog) = explorer_input_dialog.as_mut() {
-                if mouse.kind == MouseEventKind::Down(MouseButton::Left) {
-                    if let Some(hit) = ui_state.explorer_input_dialog_hit {
-                        let point = (mouse.column, mouse.row);
-                        if point_in_rect(point, hit.save_button_rect) {
-                            dialog.selected = ui::DialogButton::Save;
-                            let input = dialog.input.trim().to_string();
-                            let mode = dialog.mode;
-                            let base_dir = dialog.base_dir.clone();
-                            let target_path = dialog.target_path.clone();
-                            match run_explorer_input_action(
-                                mode,
-                                &base_dir,
-                                target_path.as_deref(),
-                                &input,
-                                file_tree.root(),
-                            ) {
-                                Ok(message) => {
-                                    explorer_input_dialog = None;
-                                    explorer_context_menu = None;
-                                    status_message = message;
-                                    file_tree.refresh()?;
-                                }
-                                Err(err) => {
-                                    status_message = format!("Aksi explorer gagal ({})", err);
-                                }
+                if mouse.kind == MouseEventKind::Down(MouseButton::Left)
+                    && let Some(hit) = ui_state.explorer_input_dialog_hit
+                {
+                    let point = (mouse.column, mouse.row);
+                    if point_in_rect(point, hit.save_button_rect) {
+                        dialog.selected = ui::DialogButton::Save;
+                        let input = dialog.input.trim().to_string();
+                        let mode = dialog.mode;
+                        let base_dir = dialog.base_dir.clone();
+                        let target_path = dialog.target_path.clone();
+                        match submit_explorer_input(
+                            mode,
+                            &base_dir,
+                            target_path.as_deref(),
+                            &input,
+                            file_tree.root(),
+                        ) {
+                            Ok(ExplorerInputOutcome::Message(message)) => {
+                                explorer_input_dialog = None;
+                                explorer_context_menu = None;
+                                status_message = message;
+                                file_tree.refresh()?;
+                                symbol_index.rebuild();
+                            }
+                            Ok(ExplorerInputOutcome::Diff(view)) => {
+                                explorer_input_dialog = None;
+                                explorer_context_menu = None;
+                                diff_view = Some(view);
+                                status_message = String::from("Diff dibuka");
+                            }
+                            Err(err) => {
+                                status_message = format!("Aksi explorer gagal ({})", err);
                             }
-                        } else if point_in_rect(point, hit.cancel_button_rect) {
-                            dialog.selected = ui::DialogButton::Cancel;
-                            explorer_input_dialog = None;
-                            status_message = String::from("Aksi explorer dibatalkan");
-                        } else if point_in_rect(point, hit.input_rect) {
-                            dialog.selected = ui::DialogButton::Save;
                         }
+                    } else if point_in_rect(point, hit.cancel_button_rect) {
+                        dialog.selected = ui::DialogButton::Cancel;
+                        explorer_input_dialog = None;
+                        status_message = String::from("Aksi explorer dibatalkan");
+                    } else if point_in_rect(point, hit.input_rect) {
+                        dialog.selected = ui::DialogButton::Save;
                     }
                 }
                 continue;
@@ -869,11 +2579,15 @@ pub fn run(
                     let clicked_delete_entry = hit
                         .delete_entry_rect
                         .is_some_and(|rect| point_in_rect(point, rect));
+                    let clicked_compare_with = hit
+                        .compare_with_rect
+                        .is_some_and(|rect| point_in_rect(point, rect));
 
                     if clicked_add_folder
                         || clicked_add_file
                         || clicked_rename_entry
                         || clicked_delete_entry
+                        || clicked_compare_with
                     {
                         if let Some(menu) = explorer_context_menu.as_ref() {
                             if clicked_add_folder {
@@ -918,6 +2632,7 @@ pub fn run(
                                         Ok(message) => {
                                             status_message = message;
                                             file_tree.refresh()?;
+                                            symbol_index.rebuild();
                                         }
                                         Err(err) => {
                                             status_message = format!("Hapus objek gagal ({})", err);
@@ -926,6 +2641,17 @@ pub fn run(
                                 } else {
                                     status_message = String::from("Target hapus tidak valid");
                                 }
+                            } else if clicked_compare_with {
+                                if let Some(target_path) = menu.target_path.as_ref() {
+                                    explorer_input_dialog = Some(ui::ExplorerInputDialog::new(
+                                        ui::ExplorerInputMode::CompareWith,
+                                        file_tree.root().to_path_buf(),
+                                        Some(target_path.clone()),
+                                    ));
+                                    status_message = String::from("Pilih file pembanding");
+                                } else {
+                                    status_message = String::from("Target compare tidak valid");
+                                }
                             }
                         }
                         explorer_context_menu = None;
@@ -953,7 +2679,30 @@ pub fn run(
                             status_message = format!("Tab aktif: {}", tabs.active_tab_title());
                         }
                     } else if point_in_rect(point, ui_state.tree_content_rect) {
-                        if show_file_tree {
+                        if show_file_tree && show_outline {
+                            focus = ui::FocusPane::Outline;
+                            // Baris pertama panel adalah filter.
+                            let row =
+                                mouse.row.saturating_sub(ui_state.tree_content_rect.y) as usize;
+                            let position = (outline.scroll + row).checked_sub(1);
+                            if let Some(position) = position
+                                && position < outline.visible().len()
+                            {
+                                outline.selected = position;
+                                if let Some(symbol) = outline.selected_symbol() {
+                                    tabs.active_editor_mut()
+                                        .set_cursor(symbol.line, symbol.column);
+                                    status_message = format!(
+                                        "{} {} (baris {})",
+                                        symbol.kind.label(),
+                                        symbol.name,
+                                        symbol.line + 1
+                                    );
+                                }
+                            } else {
+                                status_message = String::from("Fokus ke outline");
+                            }
+                        } else if show_file_tree {
                             focus = ui::FocusPane::FileTree;
                             let row =
                                 mouse.row.saturating_sub(ui_state.tree_content_rect.y) as usize;
@@ -965,6 +2714,9 @@ pub fn run(
                                 status_message = String::from("Area kosong explorer");
                             }
                         }
+                    } else if point_in_rect(point, ui_state.terminal_content_rect) {
+                        focus = ui::FocusPane::Terminal;
+                        status_message = String::from("Fokus ke terminal (F12 kembali)");
                     } else if point_in_rect(point, ui_state.editor_content_rect) {
                         focus = ui::FocusPane::Editor;
                         let x =
@@ -973,6 +2725,7 @@ pub fn run(
                         tabs.active_editor_mut().set_cursor_from_screen_selecting(
                             x,
                             y,
+                            editor_settings.tab_width,
                             mouse.modifiers.contains(KeyModifiers::SHIFT),
                         );
                         dragging_editor_selection = true;
@@ -988,8 +2741,12 @@ pub fn run(
                         let x =
                             mouse.column.saturating_sub(ui_state.editor_content_rect.x) as usize;
                         let y = mouse.row.saturating_sub(ui_state.editor_content_rect.y) as usize;
-                        tabs.active_editor_mut()
-                            .set_cursor_from_screen_selecting(x, y, true);
+                        tabs.active_editor_mut().set_cursor_from_screen_selecting(
+                            x,
+                            y,
+                            editor_settings.tab_width,
+                            true,
+                        );
                     }
                 }
                 MouseEventKind::Up(MouseButton::Left) => {
@@ -1006,7 +2763,10 @@ pub fn run(
                             y: mouse.row,
                         });
                         status_message = String::from("Menu editor dibuka");
-                    } else if show_file_tree && point_in_rect(point, ui_state.tree_content_rect) {
+                    } else if show_file_tree
+                        && !show_outline
+                        && point_in_rect(point, ui_state.tree_content_rect)
+                    {
                         let row = mouse.row.saturating_sub(ui_state.tree_content_rect.y) as usize;
                         let idx = file_tree.scroll() + row;
                         if idx < file_tree.entry_count() {
@@ -1031,8 +2791,16 @@ pub fn run(
                     if point_in_rect(point, ui_state.tab_content_rect) {
                         tabs.prev_tab();
                         status_message = format!("Tab aktif: {}", tabs.active_tab_title());
+                    } else if point_in_rect(point, ui_state.terminal_content_rect) {
+                        if let Some(session) = terminal_panes.active_mut() {
+                            session.scroll_up(3);
+                        }
                     } else if show_file_tree && point_in_rect(point, ui_state.tree_content_rect) {
-                        file_tree.move_up();
+                        if show_outline {
+                            outline.move_up();
+                        } else {
+                            file_tree.move_up();
+                        }
                     } else if point_in_rect(point, ui_state.editor_content_rect) {
                         tabs.active_editor_mut().move_up_selecting(false);
                     }
@@ -1042,8 +2810,16 @@ pub fn run(
                     if point_in_rect(point, ui_state.tab_content_rect) {
                         tabs.next_tab();
                         status_message = format!("Tab aktif: {}", tabs.active_tab_title());
+                    } else if point_in_rect(point, ui_state.terminal_content_rect) {
+                        if let Some(session) = terminal_panes.active_mut() {
+                            session.scroll_down(3);
+                        }
                     } else if show_file_tree && point_in_rect(point, ui_state.tree_content_rect) {
-                        file_tree.move_down();
+                        if show_outline {
+                            outline.move_down();
+                        } else {
+                            file_tree.move_down();
+                        }
                     } else if point_in_rect(point, ui_state.editor_content_rect) {
                         tabs.active_editor_mut().move_down_selecting(false);
                     }
@@ -1056,10 +2832,72 @@ pub fn run(
     Ok(())
 }
 
+// Edit dan gerak cursor dari keyboard lewat sini supaya bisa direkam macro.
+fn run_editor_command(
+    tabs: &mut TabManager,
+    vim: &mut VimState,
+    recorder: &mut MacroRecorder,
+    command: EditorCommand,
+) -> bool {
+    let revision = tabs.active_editor().revision();
+    let changed = command.apply(tabs.active_editor_mut(), vim);
+    if tabs.active_editor().revision() != revision {
+        tabs.mark_active_dirty();
+    }
+    recorder.record(command);
+    changed
+}
+
+// Pipe berjalan sebagai job di latar; transformasi lain langsung diterapkan dan ikut direkam.
+fn run_transform(
+    tabs: &mut TabManager,
+    vim: &mut VimState,
+    recorder: &mut MacroRecorder,
+    jobs: &mut Vec<(Job, JobFollowUp)>,
+    kind: Transform,
+    arg: String,
+    tab_width: usize,
+) -> String {
+    if kind == Transform::Pipe {
+        return match spawn_pipe_job(tabs, &arg) {
+            Ok((job, follow_up)) => {
+                let status = format!("{} berjalan...", job.label());
+                jobs.push((job, follow_up));
+                status
+            }
+            Err(err) => format!("Pipe gagal: {}", err),
+        };
+    }
+    let label = kind.label().trim_end_matches("...");
+    let command = EditorCommand::Transform {
+        kind,
+        arg,
+        tab_width,
+    };
+    if run_editor_command(tabs, vim, recorder, command) {
+        format!("{} diterapkan", label)
+    } else {
+        format!("{}: tidak ada perubahan", label)
+    }
+}
+
+fn move_cursor(
+    tabs: &mut TabManager,
+    vim: &mut VimState,
+    recorder: &mut MacroRecorder,
+    motion: Motion,
+    modifiers: KeyModifiers,
+) {
+    let select = modifiers.contains(KeyModifiers::SHIFT);
+    run_editor_command(tabs, vim, recorder, EditorCommand::Move { motion, select });
+}
+
 fn save_to_filename(
     tabs: &mut TabManager,
     file_tree: &mut FileTree,
+    symbol_index: &mut SymbolIndex,
     save_dialog: &mut Option<ui::SaveDialog>,
+    key_store: &mut KeyStore,
     status_message: &mut String,
     filename: String,
 ) -> Result<()> {
@@ -1072,31 +2910,37 @@ fn save_to_filename(
     if let Some(parent) = save_path
         .parent()
         .filter(|parent| !parent.as_os_str().is_empty())
+        && let Err(err) = fs::create_dir_all(parent)
     {
-        if let Err(err) = fs::create_dir_all(parent) {
-            *status_message = format!("Gagal membuat folder target {} ({})", parent.display(), err);
-            return Ok(());
-        }
+        *status_message = format!("Gagal membuat folder target {} ({})", parent.display(), err);
+        return Ok(());
     }
 
     match tabs.save_active_to(save_path.clone()) {
         Ok(()) => {
             *save_dialog = None;
-            let content = tabs.active_editor().text();
-            match write_signature_blob_for_file(&save_path, content.as_bytes()) {
-                Ok(marker_path) => {
-                    let marker_name = marker_path
-                        .file_name()
-                        .map(|name| name.to_string_lossy().to_string())
-                        .unwrap_or_else(|| marker_path.to_string_lossy().to_string());
-                    *status_message =
-                        format!("Berhasil simpan: {} | signature: {}", filename, marker_name);
-                }
-                Err(err) => {
-                    *status_message =
-                        format!("Berhasil simpan: {} | signature gagal ({})", filename, err);
-                }
-            }
+            symbol_index.update_file(&save_path, tabs.active_editor().lines());
+            let content = tabs.active_editor().text();
+            let root = project_root(&save_path).unwrap_or_else(|| file_tree.root().to_path_buf());
+            let signed = key_store
+                .active_signing_key()
+                .and_then(|active| match active {
+                    ActiveKey::Ready(signing_key) => write_signature_blob_for_file(
+                        &root,
+                        &save_path,
+                        content.as_bytes(),
+                        &signing_key,
+                    )
+                    .map(|key| format!(" | signature: {}", key)),
+                    ActiveKey::Disabled => Ok(String::from(" | signing nonaktif")),
+                    ActiveKey::Missing { hint } => {
+                        Ok(hint.map(|hint| format!(" | {}", hint)).unwrap_or_default())
+                    }
+                });
+            *status_message = match signed {
+                Ok(detail) => format!("Berhasil simpan: {}{}", filename, detail),
+                Err(err) => format!("Berhasil simpan: {} | signature gagal ({})", filename, err),
+            };
             file_tree.refresh()?;
         }
         Err(err) => {
@@ -1128,6 +2972,7 @@ fn build_explorer_context_menu(
             base_dir = entry.path().to_path_buf();
             target_is_dir = true;
         } else {
+            actions.push(ui::ExplorerMenuAction::CompareWith);
             base_dir = entry
                 .path()
                 .parent()
@@ -1146,6 +2991,81 @@ fn build_explorer_context_menu(
     }
 }
 
+enum ExplorerInputOutcome {
+    Message(String),
+    Diff(ui::DiffViewDialog),
+}
+
+fn submit_explorer_input(
+    mode: ui::ExplorerInputMode,
+    base_dir: &Path,
+    target_path: Option<&Path>,
+    input: &str,
+    root: &Path,
+) -> Result<ExplorerInputOutcome> {
+    if mode != ui::ExplorerInputMode::CompareWith {
+        return run_explorer_input_action(mode, base_dir, target_path, input, root)
+            .map(ExplorerInputOutcome::Message);
+    }
+
+    let source = target_path.ok_or_else(|| anyhow!("Target compare tidak valid"))?;
+    if input.is_empty() {
+        return Err(anyhow!("Path file pembanding tidak boleh kosong"));
+    }
+    let other = if Path::new(input).is_absolute() {
+        PathBuf::from(input)
+    } else {
+        base_dir.join(input)
+    };
+    if !other.is_file() {
+        return Err(anyhow!("File pembanding tidak ditemukan: {}", other.display()));
+    }
+    let left = fs::read_to_string(source)?;
+    let right = fs::read_to_string(&other)?;
+    let title = |path: &Path| {
+        path.strip_prefix(root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string()
+    };
+    Ok(ExplorerInputOutcome::Diff(ui::DiffViewDialog::new(
+        title(source),
+        title(&other),
+        DiffDocument::build(&left, &right),
+    )))
+}
+
+fn open_disk_diff(tabs: &TabManager) -> Result<ui::DiffViewDialog> {
+    let path = tabs
+        .active_path()
+        .ok_or_else(|| anyhow!("Tab belum punya file di disk"))?;
+    let disk = if path.exists() {
+        fs::read_to_string(path)?
+    } else {
+        String::new()
+    };
+    let buffer = tabs.active_editor().text();
+    Ok(ui::DiffViewDialog::new(
+        format!("{} (disk)", path.display()),
+        format!("{} (buffer)", path.display()),
+        DiffDocument::build(&disk, &buffer),
+    ))
+}
+
+fn open_unsaved_prompt(
+    tabs: &mut TabManager,
+    unsaved_prompt: &mut Option<ui::UnsavedPromptDialog>,
+    quitting: bool,
+) {
+    if quitting
+        && !tabs.active_tab_is_dirty()
+        && let Some(index) = tabs.tabs().iter().position(|tab| tab.dirty())
+    {
+        tabs.set_active(index);
+    }
+    *unsaved_prompt = Some(ui::UnsavedPromptDialog::new(quitting));
+}
+
 fn run_explorer_input_action(
     mode: ui::ExplorerInputMode,
     base_dir: &Path,
@@ -1154,6 +3074,7 @@ fn run_explorer_input_action(
     root: &Path,
 ) -> Result<String> {
     match mode {
+        ui::ExplorerInputMode::CompareWith => Err(anyhow!("Mode bandingkan tidak mengubah file")),
         ui::ExplorerInputMode::AddFolder => {
             let target = resolve_explorer_target(base_dir, input)?;
             if target.exists() {
@@ -1283,13 +3204,12 @@ fn find_next_match(editor: &Editor, query: &str) -> Option<(usize, usize)> {
     let lines = editor.lines();
     let start_row = editor.cursor_y();
     let start_col = editor.cursor_x().saturating_add(1);
-    for row in start_row..lines.len() {
-        let line = &lines[row];
+    for (row, line) in lines.iter().enumerate().skip(start_row) {
         let from = if row == start_row { start_col } else { 0 };
-        if from <= line.len() {
-            if let Some(pos) = line[from..].find(query) {
-                return Some((row, from + pos));
-            }
+        if from <= line.len()
+            && let Some(pos) = line[from..].find(query)
+        {
+            return Some((row, from + pos));
         }
     }
     for (row, line) in lines.iter().enumerate().take(start_row + 1) {
@@ -1330,21 +3250,1010 @@ fn read_clipboard_text(
         .unwrap_or_else(|| fallback_clipboard.to_string())
 }
 
-fn format_active_rust_file(tabs: &mut TabManager) -> Result<(String, bool)> {
-    let source = tabs.active_editor().text();
-    let formatted = rust_formatter::format_rust_source(&source)?;
-    if formatted == source {
-        return Ok((String::from("Rust formatter: tidak ada perubahan"), false));
+#[derive(Debug, Clone)]
+enum JobFollowUp {
+    SearchResults,
+    DependencyAdded(String),
+    CrateRemoved(String),
+    ReportDependency { name: String, removed: bool },
+    FormatBuffer(FormatRequest),
+    PipeSelection(PipeRequest),
+}
+
+/// Rentang buffer yang akan diganti output perintah pipe.
+#[derive(Debug, Clone)]
+struct PipeRequest {
+    tab: usize,
+    revision: u64,
+    start: Pos,
+    end: Pos,
+}
+
+#[derive(Debug, Clone)]
+struct FormatRequest {
+    tab: usize,
+    tab_path: Option<PathBuf>,
+    revision: u64,
+    range: FormatRange,
+    file: Option<PathBuf>,
+    save_as: Option<String>,
+}
+
+impl FormatRequest {
+    fn new(
+        tabs: &TabManager,
+        formatters: &FormatterRegistry,
+        range: FormatRange,
+        save_as: Option<String>,
+    ) -> Self {
+        let file = save_as
+            .as_deref()
+            .map(PathBuf::from)
+            .or_else(|| tabs.active_path().map(Path::to_path_buf));
+        Self {
+            tab: tabs.active_index(),
+            tab_path: tabs.active_path().map(Path::to_path_buf),
+            revision: tabs.active_editor().revision(),
+            range: formatters.effective_range(file.as_deref(), range),
+            file,
+            save_as,
+        }
+    }
+
+    fn spawn(&self, tabs: &TabManager, formatters: &FormatterRegistry) -> Result<Job> {
+        formatters.spawn(
+            tabs.active_editor().lines(),
+            &self.range,
+            self.file.as_deref(),
+        )
+    }
+}
+
+// Satu job per dialog supaya dua `cargo add/remove` tidak berebut manifest yang sama.
+fn start_manager_job(
+    dialog: &mut ui::ManagerDialog,
+    jobs: &mut Vec<(Job, JobFollowUp)>,
+    follow_up: JobFollowUp,
+    status: &mut String,
+    spawn: impl FnOnce() -> Result<Job>,
+) {
+    if let Some(running) = dialog.job.as_ref() {
+        *status = format!("{} masih berjalan (Esc untuk batal)", running.label);
+        return;
+    }
+    match spawn() {
+        Ok(job) => {
+            dialog.start_streaming_output(format!("$ {}", job.label()));
+            dialog.job = Some(job.status().clone());
+            *status = format!("{} berjalan...", job.label());
+            jobs.push((job, follow_up));
+        }
+        Err(err) => {
+            dialog.set_output(err.to_string());
+            *status = format!("Manager gagal: {}", err);
+        }
+    }
+}
+
+fn finish_job(
+    label: &str,
+    follow_up: JobFollowUp,
+    outcome: JobOutcome,
+    mut dialog: Option<&mut ui::ManagerDialog>,
+    file_tree: &mut FileTree,
+    status: &mut String,
+) {
+    *status = if !outcome.success || outcome.cancelled {
+        outcome.summary(label)
+    } else {
+        match &follow_up {
+            JobFollowUp::SearchResults => {
+                let results = cargo_manager::parse_search_output(&outcome.stdout);
+                let count = results.len();
+                if let Some(dialog) = dialog.as_deref_mut() {
+                    let installed = cargo_manager::installed_packages(&dialog.target_manifest())
+                        .unwrap_or_default();
+                    dialog.set_search_results(
+                        results
+                            .into_iter()
+                            .map(|pkg| ui::CargoSearchItem {
+                                name: pkg.name,
+                                version: pkg.version,
+                                description: pkg.description,
+                                installed: false,
+                            })
+                            .collect::<Vec<_>>(),
+                        installed,
+                    );
+                }
+                format!("{} crate ditemukan, Enter untuk install/hapus", count)
+            }
+            JobFollowUp::DependencyAdded(name) => {
+                if let Some(dialog) = dialog.as_deref_mut() {
+                    dialog.add_form = None;
+                    dialog.mark_installed(name, true);
+                    refresh_manager_workspace(dialog);
+                }
+                let _ = file_tree.refresh();
+                format!("Dependency ditambahkan: {}", name)
+            }
+            JobFollowUp::CrateRemoved(name) => {
+                if let Some(dialog) = dialog.as_deref_mut() {
+                    dialog.mark_installed(name, false);
+                    refresh_manager_workspace(dialog);
+                }
+                let _ = file_tree.refresh();
+                format!("Paket dihapus: {}", name)
+            }
+            JobFollowUp::ReportDependency { name, removed } => {
+                let mut message = if *removed {
+                    format!("Dependency dihapus: {}", name)
+                } else {
+                    format!("Dependency di-upgrade: {}", name)
+                };
+                if let Some(dialog) = dialog {
+                    refresh_manager_workspace(dialog);
+                    refresh_dependency_report(dialog, &mut message);
+                }
+                let _ = file_tree.refresh();
+                message
+            }
+            JobFollowUp::FormatBuffer(_) | JobFollowUp::PipeSelection(_) => String::new(),
+        }
+    };
+}
+
+// Error formatter masuk daftar problems (kode = nama formatter) dan menggantikan error
+// format sebelumnya untuk file yang sama.
+fn finish_format_job(
+    label: &str,
+    request: &FormatRequest,
+    outcome: &JobOutcome,
+    tabs: &mut TabManager,
+    diagnostics: &mut Vec<Diagnostic>,
+    status: &mut String,
+) -> Option<String> {
+    // Format-on-save tetap menyimpan walau formatter gagal; pemanggil sudah mengaktifkan tab asal,
+    // jadi indeks atau path yang berbeda berarti tab itu sudah ditutup.
+    let tab_closed =
+        tabs.active_index() != request.tab || tabs.active_path() != request.tab_path.as_deref();
+    let save_after = request.save_as.clone().filter(|_| !tab_closed);
+    if tab_closed {
+        *status = match request.save_as.as_deref() {
+            Some(file) => format!("{} tidak disimpan: tab sudah ditutup", file),
+            None => format!("Hasil {} dibuang: tab sudah ditutup", label),
+        };
+        return None;
+    }
+    let code = label.split_whitespace().next().unwrap_or(label);
+    if let Some(file) = request.file.as_deref() {
+        let file = cargo_runner::canonical_path(file);
+        diagnostics.retain(|diagnostic| {
+            diagnostic.code.as_deref() != Some(code) || !diagnostic.is_for_path(&file)
+        });
+    }
+    if outcome.cancelled || outcome.incomplete {
+        *status = outcome.summary(label);
+        return save_after;
+    }
+    if outcome.success {
+        *status = apply_formatted_source(tabs, request, label, &outcome.stdout);
+        return save_after;
+    }
+    let Some(problem) = formatter::parse_problem(&outcome.stderr) else {
+        *status = outcome.summary(label);
+        return save_after;
+    };
+    let line = request.range.buffer_line(problem.line);
+    *status = format!(
+        "{} gagal: baris {}:{} {}",
+        label, line, problem.column, problem.message
+    );
+    if let Some(file) = request.file.clone() {
+        diagnostics.push(Diagnostic {
+            level: DiagnosticLevel::Error,
+            message: problem.message,
+            code: Some(code.to_string()),
+            file: cargo_runner::canonical_path(&file),
+            line_start: line,
+            column_start: problem.column,
+            line_end: line,
+            column_end: problem.column + 1,
+        });
+    }
+    save_after
+}
+
+fn apply_formatted_source(
+    tabs: &mut TabManager,
+    request: &FormatRequest,
+    label: &str,
+    output: &str,
+) -> String {
+    // Buffer yang berubah selama formatter berjalan tidak ditimpa.
+    if tabs.active_index() != request.tab || tabs.active_editor().revision() != request.revision {
+        return String::from("Hasil format dibuang: buffer sudah berubah");
+    }
+    let formatted = match rust_formatter::splice_formatted(
+        tabs.active_editor().lines(),
+        &request.range,
+        output,
+    ) {
+        Ok(formatted) => formatted,
+        Err(err) => return format!("Format gagal: {}", err),
+    };
+    if formatted == tabs.active_editor().text() {
+        return format!("{}: tidak ada perubahan", label);
+    }
+    tabs.active_editor_mut()
+        .replace_text_preserving_cursor(&formatted);
+    tabs.mark_active_dirty();
+    match request.range {
+        FormatRange::Whole => format!("{} diterapkan", label),
+        FormatRange::Lines { start, end, .. } => {
+            format!("Seleksi baris {}-{} diformat", start + 1, end + 1)
+        }
+    }
+}
+
+// Baris seleksi (atau baris cursor) dikirim ke stdin `sh -c`, diakhiri newline seperti file.
+fn spawn_pipe_job(tabs: &TabManager, command: &str) -> Result<(Job, JobFollowUp)> {
+    let editor = tabs.active_editor();
+    let (start, end) =
+        transform::target_range(Transform::Pipe, editor).ok_or_else(|| anyhow!("buffer kosong"))?;
+    let mut input = transform::range_text(editor.lines(), start, end);
+    input.push('\n');
+    let mut process = Command::new("sh");
+    process.arg("-c").arg(command);
+    let job = Job::spawn(command.to_string(), process, Some(input))?;
+    let request = PipeRequest {
+        tab: tabs.active_index(),
+        revision: editor.revision(),
+        start,
+        end,
+    };
+    Ok((job, JobFollowUp::PipeSelection(request)))
+}
+
+fn finish_pipe_job(
+    label: &str,
+    request: &PipeRequest,
+    outcome: &JobOutcome,
+    tabs: &mut TabManager,
+) -> String {
+    if outcome.cancelled || !outcome.success {
+        return outcome.summary(label);
+    }
+    if tabs.active_index() != request.tab || tabs.active_editor().revision() != request.revision {
+        return String::from("Output pipe dibuang: buffer sudah berubah");
+    }
+    let output = outcome.stdout.strip_suffix('\n').unwrap_or(&outcome.stdout);
+    let editor = tabs.active_editor_mut();
+    if transform::range_text(editor.lines(), request.start, request.end) == output {
+        return format!("{}: tidak ada perubahan", label);
+    }
+    editor.replace_range(request.start, request.end, output);
+    tabs.mark_active_dirty();
+    format!("Seleksi diganti output {}", label)
+}
+
+// Seleksi diperluas ke baris penuh; seleksi yang berakhir di kolom 0 tidak ikut baris itu.
+fn selection_format_range(editor: &Editor) -> FormatRange {
+    match editor.selection_range() {
+        Some((start, end)) => {
+            let last = if end.0 == 0 && end.1 > start.1 {
+                end.1 - 1
+            } else {
+                end.1
+            };
+            FormatRange::lines(editor.lines(), start.1, last)
+        }
+        None => FormatRange::Whole,
+    }
+}
+
+fn spawn_format_job(
+    tabs: &TabManager,
+    formatters: &FormatterRegistry,
+    range: FormatRange,
+    save_as: Option<String>,
+) -> Result<(Job, JobFollowUp)> {
+    let request = FormatRequest::new(tabs, formatters, range, save_as);
+    let job = request.spawn(tabs, formatters)?;
+    Ok((job, JobFollowUp::FormatBuffer(request)))
+}
+
+// Simpan ditunda sampai formatter selesai supaya signature mencakup hasil format.
+fn start_format_on_save(
+    tabs: &TabManager,
+    formatters: &FormatterRegistry,
+    jobs: &mut Vec<(Job, JobFollowUp)>,
+    filename: &str,
+    status: &mut String,
+) -> bool {
+    if formatters
+        .formatter_for(Some(Path::new(filename)))
+        .is_none()
+    {
+        return false;
+    }
+    match spawn_format_job(
+        tabs,
+        formatters,
+        FormatRange::Whole,
+        Some(filename.to_string()),
+    ) {
+        Ok(entry) => {
+            *status = format!("{} sebelum simpan...", entry.0.label());
+            jobs.push(entry);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+fn focus_terminal(
+    terminal_panes: &mut TerminalPanes,
+    focus: &mut ui::FocusPane,
+    status_message: &mut String,
+    cwd: &Path,
+    frame_width: u16,
+) {
+    if terminal_panes.sessions().is_empty()
+        && let Err(err) = open_terminal_session(terminal_panes, cwd, frame_width)
+    {
+        *status_message = format!("Gagal membuka terminal ({})", err);
+        return;
+    }
+    terminal_panes.set_visible(true);
+    *focus = ui::FocusPane::Terminal;
+    *status_message = String::from("Fokus ke terminal (F12 kembali)");
+}
+
+fn open_terminal_session(
+    terminal_panes: &mut TerminalPanes,
+    cwd: &Path,
+    frame_width: u16,
+) -> Result<()> {
+    let rows = ui::TERMINAL_PANEL_HEIGHT.saturating_sub(2);
+    let cols = frame_width.saturating_sub(4);
+    terminal_panes.open_session(cwd, rows, cols)
+}
+
+fn open_git_dialog(repo: &mut GitRepository, tabs: &TabManager) -> Result<ui::GitDialog> {
+    let _ = repo.refresh();
+    let files = git_ops::changed_files(repo.root())?;
+    let blame = match tabs.active_path().and_then(|path| repo.relative_path(path)) {
+        Some(rel) => {
+            let editor = tabs.active_editor();
+            let line = editor.cursor_y() + 1;
+            match git_ops::blame_line(repo.root(), &rel, line, &editor.text()) {
+                Ok(blame) => format!("{}:{} {}", rel, line, blame.describe()),
+                Err(err) => format!("{}:{} tidak tersedia ({})", rel, line, err),
+            }
+        }
+        None => String::from("tab aktif tidak berada di repository"),
+    };
+    let branch = repo.branch().unwrap_or("-").to_string();
+    Ok(ui::GitDialog::new(branch, blame, files))
+}
+
+fn handle_git_dialog_key(
+    dialog: &mut ui::GitDialog,
+    key: KeyEvent,
+    repo: &mut GitRepository,
+    status_message: &mut String,
+) -> bool {
+    let root = repo.root().to_path_buf();
+    let result = match dialog.mode {
+        ui::GitDialogMode::Files => match key.code {
+            KeyCode::Esc => {
+                *status_message = String::from("Source control ditutup");
+                return true;
+            }
+            KeyCode::Up => {
+                dialog.move_file_up();
+                Ok(())
+            }
+            KeyCode::Down => {
+                dialog.move_file_down(20);
+                Ok(())
+            }
+            KeyCode::Enter => match dialog.selected_file().cloned() {
+                Some(file) => {
+                    dialog.hunk_selected = 0;
+                    git_ops::file_diff(&root, &file, !file.has_unstaged())
+                        .map(|diff| dialog.set_diff(diff))
+                }
+                None => Ok(()),
+            },
+            KeyCode::Char('s') => match dialog.selected_file().cloned() {
+                Some(file) => git_ops::stage_file(&root, &file.path).map(|()| {
+                    *status_message = format!("Staged: {}", file.path);
+                }),
+                None => Ok(()),
+            },
+            KeyCode::Char('u') => match dialog.selected_file().cloned() {
+                Some(file) => git_ops::unstage_file(&root, &file.path).map(|()| {
+                    *status_message = format!("Unstaged: {}", file.path);
+                }),
+                None => Ok(()),
+            },
+            KeyCode::Char('c') => {
+                dialog.mode = ui::GitDialogMode::Commit;
+                Ok(())
+            }
+            KeyCode::Char('r') => Ok(()),
+            _ => return false,
+        },
+        ui::GitDialogMode::Diff => match key.code {
+            KeyCode::Esc => {
+                dialog.mode = ui::GitDialogMode::Files;
+                Ok(())
+            }
+            KeyCode::Up => {
+                dialog.select_hunk(dialog.hunk_selected.saturating_sub(1));
+                return false;
+            }
+            KeyCode::Down => {
+                dialog.select_hunk(dialog.hunk_selected + 1);
+                return false;
+            }
+            KeyCode::PageUp => {
+                dialog.diff_scroll_y = dialog.diff_scroll_y.saturating_sub(10);
+                return false;
+            }
+            KeyCode::PageDown => {
+                dialog.diff_scroll_y = dialog.diff_scroll_y.saturating_add(10);
+                return false;
+            }
+            KeyCode::Tab => {
+                let staged = dialog.diff.as_ref().is_some_and(|diff| diff.staged);
+                match dialog.selected_file().cloned() {
+                    Some(file) => {
+                        dialog.hunk_selected = 0;
+                        git_ops::file_diff(&root, &file, !staged).map(|diff| dialog.set_diff(diff))
+                    }
+                    None => Ok(()),
+                }
+            }
+            KeyCode::Char(ch @ ('s' | 'u')) => match dialog.diff.as_ref() {
+                Some(diff) if diff.staged == (ch == 'u') => {
+                    let result = if ch == 's' {
+                        git_ops::stage_hunk(&root, diff, dialog.hunk_selected)
+                    } else {
+                        git_ops::unstage_hunk(&root, diff, dialog.hunk_selected)
+                    };
+                    result.map(|()| {
+                        *status_message = if ch == 's' {
+                            String::from("Hunk di-stage")
+                        } else {
+                            String::from("Hunk di-unstage")
+                        };
+                    })
+                }
+                Some(_) => {
+                    *status_message = String::from(
+                        "Gunakan s pada diff unstaged dan u pada diff staged (Tab untuk ganti)",
+                    );
+                    return false;
+                }
+                None => Ok(()),
+            },
+            _ => return false,
+        },
+        ui::GitDialogMode::Commit => match key.code {
+            KeyCode::Esc => {
+                dialog.mode = ui::GitDialogMode::Files;
+                return false;
+            }
+            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                match git_ops::commit(&root, &dialog.commit_message) {
+                    Ok(output) => {
+                        dialog.commit_message.clear();
+                        *status_message = output.lines().next().unwrap_or("Commit berhasil").to_string();
+                        dialog.set_output(output);
+                        Ok(())
+                    }
+                    Err(err) => Err(err),
+                }
+            }
+            KeyCode::Enter => {
+                dialog.commit_message.push('\n');
+                return false;
+            }
+            KeyCode::Backspace => {
+                dialog.commit_message.pop();
+                return false;
+            }
+            KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                dialog.commit_message.push(ch);
+                return false;
+            }
+            _ => return false,
+        },
+        ui::GitDialogMode::Output => match key.code {
+            KeyCode::Esc | KeyCode::Enter => {
+                dialog.mode = ui::GitDialogMode::Files;
+                Ok(())
+            }
+            _ => return false,
+        },
+    };
+
+    if let Err(err) = result {
+        *status_message = format!("Git gagal: {}", err);
+        dialog.set_output(err.to_string());
+        return false;
+    }
+
+    let _ = repo.refresh();
+    match git_ops::changed_files(&root) {
+        Ok(files) => dialog.set_files(files),
+        Err(err) => *status_message = format!("Git gagal: {}", err),
+    }
+    dialog.branch = repo.branch().unwrap_or("-").to_string();
+    if dialog.mode == ui::GitDialogMode::Diff
+        && let Some(file) = dialog.selected_file().cloned()
+    {
+        let staged = dialog.diff.as_ref().is_some_and(|diff| diff.staged);
+        match git_ops::file_diff(&root, &file, staged) {
+            Ok(diff) => dialog.set_diff(diff),
+            Err(err) => *status_message = format!("Git gagal: {}", err),
+        }
+    }
+    false
+}
+
+fn handle_key_dialog_key(
+    dialog: &mut ui::KeyDialog,
+    key: KeyEvent,
+    store: &mut KeyStore,
+    status_message: &mut String,
+) -> bool {
+    let result = if let Some(mode) = dialog.input_mode {
+        match key.code {
+            KeyCode::Esc => {
+                dialog.input_mode = None;
+                dialog.input.clear();
+                Ok(None)
+            }
+            KeyCode::Backspace => {
+                dialog.input.pop();
+                Ok(None)
+            }
+            KeyCode::Enter => {
+                let input = std::mem::take(&mut dialog.input);
+                dialog.input_mode = None;
+                submit_key_dialog_input(dialog, mode, input, store).map(Some)
+            }
+            KeyCode::Char(ch) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                dialog.input.push(ch);
+                Ok(None)
+            }
+            _ => Ok(None),
+        }
+    } else {
+        let selected = dialog.selected_key().map(|info| info.name.clone());
+        match (dialog.focus, key.code) {
+            (_, KeyCode::Esc) => {
+                *status_message = String::from("Dialog kunci signing ditutup");
+                return true;
+            }
+            (_, KeyCode::Tab) => {
+                dialog.focus = match dialog.focus {
+                    ui::KeyDialogFocus::Keys => ui::KeyDialogFocus::Trusted,
+                    ui::KeyDialogFocus::Trusted => ui::KeyDialogFocus::Keys,
+                };
+                Ok(None)
+            }
+            (_, KeyCode::Up) => {
+                dialog.move_up();
+                Ok(None)
+            }
+            (_, KeyCode::Down) => {
+                dialog.move_down();
+                Ok(None)
+            }
+            (_, KeyCode::Char('s')) => {
+                let enabled = !store.settings().enabled;
+                store.set_enabled(enabled).map(|()| {
+                    Some(if enabled {
+                        String::from("Signing diaktifkan")
+                    } else {
+                        String::from("Signing dinonaktifkan")
+                    })
+                })
+            }
+            (_, KeyCode::Char('l')) => {
+                let location = store.settings().location.toggle();
+                store
+                    .set_location(location)
+                    .map(|()| Some(format!("Lokasi kunci: {}", location.label())))
+            }
+            (ui::KeyDialogFocus::Keys, KeyCode::Enter) => match selected {
+                Some(name) => store
+                    .set_active(&name)
+                    .map(|()| Some(format!("Kunci aktif: {}", name))),
+                None => Ok(None),
+            },
+            (ui::KeyDialogFocus::Keys, KeyCode::Char('g')) => {
+                dialog.start_input(ui::KeyDialogInput::GenerateName, String::new());
+                Ok(None)
+            }
+            (ui::KeyDialogFocus::Keys, KeyCode::Char('i')) => {
+                dialog.start_input(ui::KeyDialogInput::Import, String::new());
+                Ok(None)
+            }
+            (ui::KeyDialogFocus::Keys, KeyCode::Char(ch @ ('e' | 'E' | 'u' | 'p'))) => {
+                if let Some(name) = selected {
+                    let (mode, initial) = match ch {
+                        'e' => (
+                            ui::KeyDialogInput::ExportPublic,
+                            format!("{}.pub.pem", name),
+                        ),
+                        'E' => (ui::KeyDialogInput::ExportPrivate, format!("{}.pem", name)),
+                        'u' => (ui::KeyDialogInput::Unlock, String::new()),
+                        _ => (ui::KeyDialogInput::Passphrase, String::new()),
+                    };
+                    dialog.start_input(mode, initial);
+                }
+                Ok(None)
+            }
+            (ui::KeyDialogFocus::Trusted, KeyCode::Char('a')) => {
+                dialog.start_input(ui::KeyDialogInput::Trust, String::new());
+                Ok(None)
+            }
+            (ui::KeyDialogFocus::Trusted, KeyCode::Char('d')) => {
+                match dialog.selected_trusted().cloned() {
+                    Some(entry) => store.untrust(&entry.key).map(|()| {
+                        Some(format!("Public key {} dihapus dari daftar", entry.fingerprint()))
+                    }),
+                    None => Ok(None),
+                }
+            }
+            _ => Ok(None),
+        }
+    };
+
+    match result {
+        Ok(Some(message)) => {
+            dialog.message = message.clone();
+            *status_message = message;
+        }
+        Ok(None) => {}
+        Err(err) => {
+            dialog.message = format!("Gagal: {}", err);
+            *status_message = format!("Kunci signing gagal: {}", err);
+        }
+    }
+    dialog.reload(store);
+    false
+}
+
+fn submit_key_dialog_input(
+    dialog: &mut ui::KeyDialog,
+    mode: ui::KeyDialogInput,
+    input: String,
+    store: &mut KeyStore,
+) -> Result<String> {
+    let name = dialog
+        .selected_key()
+        .map(|info| info.name.clone())
+        .unwrap_or_default();
+    let path = PathBuf::from(input.trim());
+    match mode {
+        ui::KeyDialogInput::GenerateName => {
+            dialog.pending_name = input.trim().to_string();
+            dialog.start_input(ui::KeyDialogInput::GeneratePassphrase, String::new());
+            Ok(format!("Membuat kunci {}", dialog.pending_name))
+        }
+        ui::KeyDialogInput::GeneratePassphrase => {
+            let name = std::mem::take(&mut dialog.pending_name);
+            store.generate(&name, Some(&input))?;
+            Ok(format!("Kunci dibuat: {}", name))
+        }
+        ui::KeyDialogInput::Import => store
+            .import(&path)
+            .map(|name| format!("Kunci diimpor: {}", name)),
+        ui::KeyDialogInput::ExportPublic => store
+            .export_public(&name, &path)
+            .map(|()| format!("Public key {} diekspor ke {}", name, path.display())),
+        ui::KeyDialogInput::ExportPrivate => store
+            .export_private(&name, &path)
+            .map(|()| format!("Private key {} diekspor ke {}", name, path.display())),
+        ui::KeyDialogInput::Unlock => store
+            .unlock(&name, &input)
+            .map(|()| format!("Kunci {} terbuka untuk sesi ini", name)),
+        ui::KeyDialogInput::Passphrase => store.set_passphrase(&name, Some(&input)).map(|()| {
+            if input.is_empty() {
+                format!("Proteksi passphrase {} dihapus", name)
+            } else {
+                format!("Passphrase {} diperbarui", name)
+            }
+        }),
+        ui::KeyDialogInput::Trust => store
+            .trust(&input)
+            .map(|entry| format!("Public key {} dipercaya", entry.fingerprint())),
+    }
+}
+
+fn open_manager_dialog(
+    cargo_run: Option<&CargoRun>,
+    cargo_log: &str,
+    jobs: &[(Job, JobFollowUp)],
+    format_settings: &FormatSettings,
+) -> ui::ManagerDialog {
+    let mut dialog = ui::ManagerDialog {
+        format_on_save: format_settings.format_on_save,
+        ..ui::ManagerDialog::default()
+    };
+    refresh_manager_workspace(&mut dialog);
+    // Job cargo yang masih jalan dari dialog sebelumnya dipasang lagi agar bisa dibatalkan.
+    if let Some(run) = cargo_run {
+        dialog.start_streaming_output(cargo_log.to_string());
+        dialog.job = Some(run.status().clone());
+    } else if let Some((job, _)) = jobs.iter().find(|(_, follow_up)| {
+        !matches!(
+            follow_up,
+            JobFollowUp::FormatBuffer(_) | JobFollowUp::PipeSelection(_)
+        )
+    }) {
+        dialog.start_streaming_output(format!("$ {}", job.label()));
+        dialog.job = Some(job.status().clone());
+    }
+    dialog
+}
+
+fn open_add_dependency_form(
+    dialog: &mut ui::ManagerDialog,
+    name: &str,
+    fallback_version: Option<&str>,
+) {
+    let versions = registry_index::cached_versions(name).unwrap_or_default();
+    dialog.open_add_form(ui::AddDependencyForm::new(name, versions, fallback_version));
+}
+
+fn handle_add_dependency_key(
+    dialog: &mut ui::ManagerDialog,
+    key: KeyEvent,
+    status: &mut String,
+) {
+    let Some(form) = dialog.add_form.as_mut() else {
+        dialog.mode = ui::ManagerMode::Menu;
+        return;
+    };
+    let on_features = form.field == ui::AddDependencyField::Features;
+    match key.code {
+        KeyCode::Esc => {
+            dialog.add_form = None;
+            dialog.mode = ui::ManagerMode::Menu;
+        }
+        KeyCode::Tab => form.next_field(),
+        KeyCode::BackTab => form.prev_field(),
+        KeyCode::Up if on_features && form.feature_selected > 0 => form.move_feature_up(),
+        KeyCode::Down if on_features => form.move_feature_down(),
+        KeyCode::Up => form.prev_field(),
+        KeyCode::Down => form.next_field(),
+        KeyCode::Left | KeyCode::Right => match form.field {
+            ui::AddDependencyField::Version => form.cycle_version(key.code == KeyCode::Right),
+            ui::AddDependencyField::Kind => form.cycle_kind(),
+            _ => form.toggle(),
+        },
+        KeyCode::Char(' ') if form.field != ui::AddDependencyField::Version => form.toggle(),
+        KeyCode::Backspace if form.field == ui::AddDependencyField::Version => {
+            form.pop_requirement_char();
+        }
+        KeyCode::Char(ch)
+            if form.field == ui::AddDependencyField::Version
+                && !key.modifiers.contains(KeyModifiers::CONTROL) =>
+        {
+            form.push_requirement_char(ch);
+        }
+        KeyCode::Enter => {
+            let spec = form.spec();
+            match cargo_manager::preview_dependency(&form.preview_spec(), &dialog.target_manifest())
+            {
+                Ok((before, after)) => {
+                    if let Some(form) = dialog.add_form.as_mut() {
+                        form.set_preview(&before, &after);
+                    }
+                    dialog.mode = ui::ManagerMode::AddDependencyPreview;
+                    *status = format!(
+                        "Periksa perubahan lalu Enter: cargo {}",
+                        cargo_manager::cargo_add_args(&spec).join(" ")
+                    );
+                }
+                Err(err) => {
+                    *status = format!("Preview manifest gagal: {}", err);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn refresh_dependency_report(dialog: &mut ui::ManagerDialog, status: &mut String) {
+    refresh_manager_workspace(dialog);
+    let Some(workspace) = dialog.workspace.as_ref() else {
+        *status = String::from("Workspace cargo tidak ditemukan");
+        return;
+    };
+    let package = dialog.target().map(|package| package.name.clone());
+    match dependency_report::build_report(workspace, package.as_deref()) {
+        Ok(report) => {
+            let flagged = report
+                .iter()
+                .filter(|entry| !entry.flags().is_empty())
+                .count();
+            *status = format!(
+                "{} dependency, {} perlu perhatian",
+                report.len(),
+                flagged
+            );
+            dialog.set_report(report);
+        }
+        Err(err) => {
+            dialog.set_output(err.to_string());
+            *status = format!("Laporan dependency gagal: {}", err);
+        }
+    }
+}
+
+fn refresh_manager_workspace(dialog: &mut ui::ManagerDialog) {
+    if let Ok(workspace) = workspace_manager::discover_workspace() {
+        dialog.set_workspace(workspace);
+    }
+}
+
+fn current_location(tabs: &TabManager) -> Option<Location> {
+    let path = tabs.active_path()?.to_path_buf();
+    let editor = tabs.active_editor();
+    Some(Location {
+        path,
+        line: editor.cursor_y(),
+        column: editor.cursor_x(),
+    })
+}
+
+fn open_location(tabs: &mut TabManager, location: &Location) -> Result<()> {
+    tabs.open_file(&location.path)?;
+    tabs.active_editor_mut()
+        .set_cursor(location.line, location.column);
+    Ok(())
+}
+
+fn char_column_to_byte(line: &str, column: usize) -> usize {
+    line.char_indices()
+        .nth(column.saturating_sub(1))
+        .map(|(idx, _)| idx)
+        .unwrap_or(line.len())
+}
+
+fn jump_to_workspace_symbol(
+    tabs: &mut TabManager,
+    index: &SymbolIndex,
+    symbol: &WorkspaceSymbol,
+) -> Result<String> {
+    tabs.open_file(&symbol.path)?;
+    tabs.active_editor_mut()
+        .set_cursor(symbol.line, symbol.column);
+    Ok(format!(
+        "{} {} ({}:{})",
+        symbol.kind.label(),
+        symbol.name,
+        index.relative_path(&symbol.path).display(),
+        symbol.line + 1
+    ))
+}
+
+// Definisi di buffer aktif dipindai langsung supaya perubahan yang belum disimpan ikut;
+// file lain diambil dari indeks. Lebih dari satu kandidat yang cocok → dialog pilihan.
+fn go_to_definition(
+    tabs: &mut TabManager,
+    index: &SymbolIndex,
+    status: &mut String,
+) -> Option<ui::WorkspaceSymbolDialog> {
+    let editor = tabs.active_editor();
+    let row = editor.cursor_y();
+    let Some((start, name, usage)) = editor
+        .lines()
+        .get(row)
+        .and_then(|line| symbol_index::identifier_at(line, editor.cursor_x()))
+    else {
+        *status = String::from("Tidak ada identifier di posisi cursor");
+        return None;
+    };
+    let name = name.to_string();
+    let local = rust_outline(editor.lines())
+        .into_iter()
+        .filter(|symbol| symbol.name == name && symbol.kind != SymbolKind::Impl)
+        .filter(|symbol| (symbol.line, symbol.column) != (row, start))
+        .collect::<Vec<_>>();
+    let remote = index.definitions(&name, usage, tabs.active_path());
+    let preferred = remote
+        .iter()
+        .filter(|symbol| usage.prefers(symbol.kind))
+        .count();
+    let local_target = local
+        .iter()
+        .find(|symbol| usage.prefers(symbol.kind))
+        .or(local.first().filter(|_| preferred == 0));
+    if let Some(symbol) = local_target {
+        tabs.active_editor_mut()
+            .set_cursor(symbol.line, symbol.column);
+        *status = format!(
+            "Definisi: {} {} (baris {})",
+            symbol.kind.label(),
+            symbol.name,
+            symbol.line + 1
+        );
+        return None;
+    }
+    match (preferred, remote.len()) {
+        (_, 0) => {
+            *status = if index.is_indexing() {
+                format!("Definisi '{}' belum ditemukan, indeks masih dibangun", name)
+            } else {
+                format!("Definisi '{}' tidak ditemukan", name)
+            };
+            None
+        }
+        (1, _) | (0, 1) => {
+            *status = match jump_to_workspace_symbol(tabs, index, &remote[0]) {
+                Ok(message) => format!("Definisi: {}", message),
+                Err(err) => format!("Gagal membuka {} ({})", remote[0].path.display(), err),
+            };
+            None
+        }
+        _ => {
+            *status = format!(
+                "{} definisi '{}' ditemukan, pilih salah satu",
+                remote.len(),
+                name
+            );
+            Some(ui::WorkspaceSymbolDialog::definitions(&name, remote, index))
+        }
     }
-    tabs.active_editor_mut().replace_text(&formatted);
-    Ok((String::from("Rust formatter berhasil diterapkan"), true))
+}
+
+fn open_diagnostic_location(tabs: &mut TabManager, diagnostic: &Diagnostic) -> Result<()> {
+    tabs.open_file(&diagnostic.file)?;
+    let row = diagnostic.line_start.saturating_sub(1);
+    let col = tabs
+        .active_editor()
+        .lines()
+        .get(row)
+        .map(|line| char_column_to_byte(line, diagnostic.column_start))
+        .unwrap_or(0);
+    tabs.active_editor_mut().set_cursor(row, col);
+    Ok(())
+}
+
+fn cargo_run_summary(
+    command: cargo_runner::CargoCommand,
+    success: bool,
+    diagnostics: &[Diagnostic],
+) -> String {
+    let errors = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.level == DiagnosticLevel::Error)
+        .count();
+    let warnings = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.level == DiagnosticLevel::Warning)
+        .count();
+    format!(
+        "cargo {} {} | {} error, {} warning",
+        command.subcommand(),
+        if success { "selesai" } else { "gagal" },
+        errors,
+        warnings
+    )
 }
 
 fn run_manager_input_action(action: ui::ManagerAction, input: &str) -> Result<String> {
     match action {
-        ui::ManagerAction::CargoSearch => cargo_manager::search_crate(input),
-        ui::ManagerAction::CargoAdd => cargo_manager::add_crate(input),
-        ui::ManagerAction::CargoRemove => cargo_manager::remove_crate(input),
         ui::ManagerAction::WorkspaceAddMember => workspace_manager::add_workspace_member(input),
         ui::ManagerAction::WorkspaceRemoveMember => {
             workspace_manager::remove_workspace_member(input)
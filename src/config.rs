@@ -0,0 +1,33 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+const APP_DIR: &str = "action-codex";
+const FALLBACK_DIR: &str = ".action-codex";
+
+/// Folder konfigurasi global milik pengguna (mis. `~/.config/action-codex`); `None` bila sistem
+/// tidak punya folder konfigurasi.
+pub fn user_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(APP_DIR))
+}
+
+/// Folder konfigurasi, atau `.action-codex` di folder kerja bila tidak ada folder global.
+pub fn dir() -> PathBuf {
+    user_dir().unwrap_or_else(|| PathBuf::from(FALLBACK_DIR))
+}
+
+/// Isi `file` di folder konfigurasi; `None` bila belum ada atau bukan JSON valid.
+pub fn load_json(file: &str) -> Option<Value> {
+    let text = fs::read_to_string(dir().join(file)).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+pub fn save_json(file: &str, value: &Value) -> Result<()> {
+    let dir = dir();
+    fs::create_dir_all(&dir).with_context(|| format!("gagal membuat {}", dir.display()))?;
+    let path = dir.join(file);
+    fs::write(&path, serde_json::to_string_pretty(value)?)
+        .with_context(|| format!("gagal menulis {}", path.display()))
+}
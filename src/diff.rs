@@ -0,0 +1,362 @@
+use similar::{Algorithm, DiffOp, capture_diff_slices};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hunk {
+    pub old_start: usize,
+    pub old_len: usize,
+    pub new_start: usize,
+    pub new_len: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeMarker {
+    Added,
+    Modified,
+    DeletedAbove,
+}
+
+pub fn diff_lines<S: AsRef<str>, T: AsRef<str>>(old: &[S], new: &[T]) -> Vec<Hunk> {
+    let old = old.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+    let new = new.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+    let ops = capture_diff_slices(Algorithm::Myers, &old, &new);
+
+    let mut hunks: Vec<Hunk> = Vec::new();
+    let mut current: Option<Hunk> = None;
+    for op in ops {
+        match op {
+            DiffOp::Equal { .. } => {
+                if let Some(hunk) = current.take() {
+                    hunks.push(hunk);
+                }
+            }
+            _ => {
+                let (old_range, new_range) = (op.old_range(), op.new_range());
+                let hunk = current.get_or_insert(Hunk {
+                    old_start: old_range.start,
+                    old_len: 0,
+                    new_start: new_range.start,
+                    new_len: 0,
+                });
+                hunk.old_len = old_range.end - hunk.old_start;
+                hunk.new_len = new_range.end - hunk.new_start;
+            }
+        }
+    }
+    if let Some(hunk) = current {
+        hunks.push(hunk);
+    }
+    hunks
+}
+
+/// Posisi baris `row` lama di teks baru; baris di dalam hunk diarahkan ke baris hunk yang sejajar.
+pub fn map_line(hunks: &[Hunk], row: usize) -> usize {
+    let mut shift = 0isize;
+    for hunk in hunks {
+        if row < hunk.old_start {
+            break;
+        }
+        if row < hunk.old_start + hunk.old_len {
+            return hunk.new_start + (row - hunk.old_start).min(hunk.new_len.saturating_sub(1));
+        }
+        shift = (hunk.new_start + hunk.new_len) as isize - (hunk.old_start + hunk.old_len) as isize;
+    }
+    row.saturating_add_signed(shift)
+}
+
+/// Posisi elemen ke-`index` dari `old` di `new`; elemen yang dihapus diarahkan ke titik
+/// penggantinya.
+pub fn map_index(old: &[char], new: &[char], index: usize) -> usize {
+    for op in capture_diff_slices(Algorithm::Myers, old, new) {
+        let (old_range, new_range) = (op.old_range(), op.new_range());
+        if old_range.contains(&index) {
+            return match op {
+                DiffOp::Equal { .. } => new_range.start + (index - old_range.start),
+                _ => new_range.start,
+            };
+        }
+    }
+    new.len()
+}
+
+pub fn change_markers(hunks: &[Hunk], new_line_count: usize) -> Vec<Option<ChangeMarker>> {
+    let mut markers = vec![None; new_line_count];
+    for hunk in hunks {
+        if hunk.new_len == 0 {
+            let row = hunk.new_start.min(new_line_count.saturating_sub(1));
+            if let Some(slot) = markers.get_mut(row)
+                && slot.is_none()
+            {
+                *slot = Some(ChangeMarker::DeletedAbove);
+            }
+            continue;
+        }
+        let marker = if hunk.old_len == 0 {
+            ChangeMarker::Added
+        } else {
+            ChangeMarker::Modified
+        };
+        for slot in markers.iter_mut().skip(hunk.new_start).take(hunk.new_len) {
+            *slot = Some(marker);
+        }
+    }
+    markers
+}
+
+pub type ByteRange = (usize, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffRowKind {
+    Equal,
+    Delete,
+    Insert,
+    Replace,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiffSide {
+    pub line_no: usize,
+    pub text: String,
+    pub changed: Vec<ByteRange>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiffRow {
+    pub kind: DiffRowKind,
+    pub old: Option<DiffSide>,
+    pub new: Option<DiffSide>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DiffDocument {
+    pub rows: Vec<DiffRow>,
+    pub hunk_rows: Vec<usize>,
+}
+
+impl DiffDocument {
+    pub fn build(old_text: &str, new_text: &str) -> Self {
+        let old = old_text.split('\n').collect::<Vec<_>>();
+        let new = new_text.split('\n').collect::<Vec<_>>();
+        let side = |lines: &[&str], idx: usize| DiffSide {
+            line_no: idx + 1,
+            text: lines[idx].to_string(),
+            changed: Vec::new(),
+        };
+
+        let mut document = Self::default();
+        let mut last_equal = true;
+        for op in capture_diff_slices(Algorithm::Myers, &old, &new) {
+            let (old_range, new_range) = (op.old_range(), op.new_range());
+            if let DiffOp::Equal { .. } = op {
+                for (o, n) in old_range.zip(new_range) {
+                    document.rows.push(DiffRow {
+                        kind: DiffRowKind::Equal,
+                        old: Some(side(&old, o)),
+                        new: Some(side(&new, n)),
+                    });
+                }
+                last_equal = true;
+                continue;
+            }
+
+            if last_equal {
+                document.hunk_rows.push(document.rows.len());
+            }
+            last_equal = false;
+            let paired = old_range.len().min(new_range.len());
+            for offset in 0..old_range.len().max(new_range.len()) {
+                let mut old_side =
+                    (offset < old_range.len()).then(|| side(&old, old_range.start + offset));
+                let mut new_side =
+                    (offset < new_range.len()).then(|| side(&new, new_range.start + offset));
+                let kind = if offset < paired {
+                    if let (Some(o), Some(n)) = (old_side.as_mut(), new_side.as_mut()) {
+                        let (old_changed, new_changed) = word_changes(&o.text, &n.text);
+                        o.changed = old_changed;
+                        n.changed = new_changed;
+                    }
+                    DiffRowKind::Replace
+                } else if old_side.is_some() {
+                    DiffRowKind::Delete
+                } else {
+                    DiffRowKind::Insert
+                };
+                document.rows.push(DiffRow {
+                    kind,
+                    old: old_side,
+                    new: new_side,
+                });
+            }
+        }
+        document
+    }
+
+    pub fn next_hunk(&self, row: usize) -> Option<usize> {
+        self.hunk_rows.iter().copied().find(|start| *start > row)
+    }
+
+    pub fn prev_hunk(&self, row: usize) -> Option<usize> {
+        self.hunk_rows
+            .iter()
+            .copied()
+            .rev()
+            .find(|start| *start < row)
+    }
+}
+
+pub fn word_changes(old: &str, new: &str) -> (Vec<ByteRange>, Vec<ByteRange>) {
+    let old_tokens = tokenize_words(old);
+    let new_tokens = tokenize_words(new);
+    let old_words = old_tokens
+        .iter()
+        .map(|(s, e)| &old[*s..*e])
+        .collect::<Vec<_>>();
+    let new_words = new_tokens
+        .iter()
+        .map(|(s, e)| &new[*s..*e])
+        .collect::<Vec<_>>();
+
+    let mut old_changed: Vec<ByteRange> = Vec::new();
+    let mut new_changed: Vec<ByteRange> = Vec::new();
+    let push =
+        |ranges: &mut Vec<ByteRange>, tokens: &[(usize, usize)], range: std::ops::Range<usize>| {
+            if range.is_empty() {
+                return;
+            }
+            let (start, end) = (tokens[range.start].0, tokens[range.end - 1].1);
+            match ranges.last_mut() {
+                Some(last) if last.1 == start => last.1 = end,
+                _ => ranges.push((start, end)),
+            }
+        };
+    for op in capture_diff_slices(Algorithm::Myers, &old_words, &new_words) {
+        if let DiffOp::Equal { .. } = op {
+            continue;
+        }
+        push(&mut old_changed, &old_tokens, op.old_range());
+        push(&mut new_changed, &new_tokens, op.new_range());
+    }
+    (old_changed, new_changed)
+}
+
+fn tokenize_words(text: &str) -> Vec<ByteRange> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, ch)) = chars.next() {
+        let class = char_class(ch);
+        let mut end = start + ch.len_utf8();
+        if class != 2 {
+            while let Some(&(idx, next)) = chars.peek() {
+                if char_class(next) != class {
+                    break;
+                }
+                end = idx + next.len_utf8();
+                chars.next();
+            }
+        }
+        tokens.push((start, end));
+    }
+    tokens
+}
+
+fn char_class(ch: char) -> u8 {
+    if ch.is_alphanumeric() || ch == '_' {
+        0
+    } else if ch.is_whitespace() {
+        1
+    } else {
+        2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(text: &str) -> Vec<char> {
+        text.chars().collect()
+    }
+
+    #[test]
+    fn document_pairs_replaced_lines_and_marks_hunks() {
+        let document = DiffDocument::build("a\nb\nc\nd", "a\nB\nc\nd\ne");
+        let kinds = document.rows.iter().map(|row| row.kind).collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            [
+                DiffRowKind::Equal,
+                DiffRowKind::Replace,
+                DiffRowKind::Equal,
+                DiffRowKind::Equal,
+                DiffRowKind::Insert,
+            ]
+        );
+        assert_eq!(document.hunk_rows, [1, 4]);
+
+        let replaced = &document.rows[1];
+        assert_eq!(replaced.old.as_ref().map(|side| side.line_no), Some(2));
+        assert_eq!(
+            replaced.new.as_ref().map(|side| side.text.as_str()),
+            Some("B")
+        );
+        let inserted = &document.rows[4];
+        assert!(inserted.old.is_none());
+        assert_eq!(inserted.new.as_ref().map(|side| side.line_no), Some(5));
+    }
+
+    #[test]
+    fn document_hunk_navigation() {
+        let document = DiffDocument::build("a\nb\nc\nd\ne", "x\nb\nc\nd\ny");
+        assert_eq!(document.hunk_rows, [0, 4]);
+        assert_eq!(document.next_hunk(0), Some(4));
+        assert_eq!(document.next_hunk(4), None);
+        assert_eq!(document.prev_hunk(4), Some(0));
+        assert_eq!(document.prev_hunk(0), None);
+    }
+
+    #[test]
+    fn document_of_identical_text_has_no_hunks() {
+        let document = DiffDocument::build("same\ntext", "same\ntext");
+        assert!(document.hunk_rows.is_empty());
+        assert!(
+            document
+                .rows
+                .iter()
+                .all(|row| row.kind == DiffRowKind::Equal)
+        );
+    }
+
+    #[test]
+    fn word_changes_highlight_only_changed_words() {
+        let (old, new) = word_changes("let value = 1;", "let total = 1;");
+        assert_eq!(old, [(4, 9)]);
+        assert_eq!(new, [(4, 9)]);
+    }
+
+    #[test]
+    fn word_changes_merge_adjacent_tokens() {
+        let old = "foo(bar)";
+        let new = "foo(baz, qux)";
+        let (old_changed, new_changed) = word_changes(old, new);
+        assert_eq!(old_changed, [(4, 7)]);
+        assert_eq!(&new[new_changed[0].0..new_changed[0].1], "baz, qux");
+    }
+
+    #[test]
+    fn word_changes_of_equal_lines_are_empty() {
+        assert_eq!(word_changes("same", "same"), (Vec::new(), Vec::new()));
+    }
+
+    #[test]
+    fn map_index_follows_insertions_and_deletions() {
+        let old = chars("hello world");
+        let new = chars("hello, big world");
+        // 'w' bergeser sejauh teks yang disisipkan.
+        assert_eq!(map_index(&old, &new, 6), 11);
+        assert_eq!(map_index(&old, &new, 0), 0);
+        // Indeks di luar teks lama diarahkan ke akhir teks baru.
+        assert_eq!(map_index(&old, &new, old.len()), new.len());
+
+        let shorter = chars("hello");
+        assert_eq!(map_index(&old, &shorter, 8), shorter.len());
+    }
+}
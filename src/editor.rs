@@ -1,4 +1,5 @@
-use crate::syntax::indent::next_indent_for_rust;
+use crate::diff::{diff_lines, map_index, map_line};
+use crate::syntax::indent::{INDENT, next_indent_for_rust, tab_advance};
 
 const HISTORY_LIMIT: usize = 200;
 
@@ -22,6 +23,11 @@ pub struct Editor {
     selection_anchor: Option<Cursor>,
     undo_stack: Vec<EditorSnapshot>,
     redo_stack: Vec<EditorSnapshot>,
+    revision: u64,
+    /// Kedalaman grup undo yang sedang terbuka; grup bersarang menyatu dengan grup terluar.
+    undo_group: usize,
+    /// Posisi `undo_stack` yang dicatat `mark_undo`, untuk `merge_undo_since_mark`.
+    undo_mark: Option<usize>,
 }
 
 impl Editor {
@@ -51,11 +57,12 @@ impl Editor {
             return;
         }
 
-        if let Some(next) = self.char_at_cursor() {
-            if is_closing_pair(ch) && next == ch {
-                self.move_right_raw();
-                return;
-            }
+        if let Some(next) = self.char_at_cursor()
+            && is_closing_pair(ch)
+            && next == ch
+        {
+            self.move_right_raw();
+            return;
         }
 
         self.begin_edit();
@@ -64,11 +71,11 @@ impl Editor {
             self.try_outdent_before_closing();
         }
 
-        if let Some(close) = pair_for_open(ch) {
-            if self.should_autopair(ch) {
-                self.insert_pair(ch, close);
-                return;
-            }
+        if let Some(close) = pair_for_open(ch)
+            && self.should_autopair(ch)
+        {
+            self.insert_pair(ch, close);
+            return;
         }
 
         self.insert_char_raw(ch);
@@ -151,6 +158,51 @@ impl Editor {
         self.lines[self.cursor_y].push_str(&current);
     }
 
+    pub fn delete_forward(&mut self) {
+        if self.has_selection() {
+            self.begin_edit();
+            self.delete_selection_if_any();
+            return;
+        }
+
+        let len = self.lines[self.cursor_y].len();
+        if self.cursor_x >= len && self.cursor_y + 1 >= self.lines.len() {
+            return;
+        }
+
+        self.begin_edit();
+        if self.cursor_x < len {
+            self.lines[self.cursor_y].remove(self.cursor_x);
+        } else {
+            let next = self.lines.remove(self.cursor_y + 1);
+            self.lines[self.cursor_y].push_str(&next);
+        }
+    }
+
+    /// Hapus sampai awal kata; di kolom 0 atau dengan seleksi sama seperti Backspace.
+    pub fn delete_word_left(&mut self) {
+        if self.has_selection() || self.cursor_x == 0 {
+            self.backspace();
+            return;
+        }
+
+        self.begin_edit();
+        let start = word_start(&self.lines[self.cursor_y], self.cursor_x, false);
+        self.lines[self.cursor_y].drain(start..self.cursor_x);
+        self.cursor_x = start;
+    }
+
+    pub fn delete_word_right(&mut self) {
+        if self.has_selection() || self.cursor_x >= self.lines[self.cursor_y].len() {
+            self.delete_forward();
+            return;
+        }
+
+        self.begin_edit();
+        let end = word_end(&self.lines[self.cursor_y], self.cursor_x, false);
+        self.lines[self.cursor_y].drain(self.cursor_x..end);
+    }
+
     pub fn move_left_selecting(&mut self, selecting: bool) {
         self.prepare_selection(selecting);
         self.move_left_raw();
@@ -171,6 +223,96 @@ impl Editor {
         self.move_down_raw();
     }
 
+    /// `subword` juga berhenti di batas camelCase dan `_`.
+    pub fn move_word_left_selecting(&mut self, subword: bool, selecting: bool) {
+        self.prepare_selection(selecting);
+        if self.cursor_x == 0 {
+            self.move_left_raw();
+        } else {
+            self.cursor_x = word_start(&self.lines[self.cursor_y], self.cursor_x, subword);
+        }
+    }
+
+    pub fn move_word_right_selecting(&mut self, subword: bool, selecting: bool) {
+        self.prepare_selection(selecting);
+        if self.cursor_x >= self.lines[self.cursor_y].len() {
+            self.move_right_raw();
+        } else {
+            self.cursor_x = word_end(&self.lines[self.cursor_y], self.cursor_x, subword);
+        }
+    }
+
+    /// Ke baris kosong sebelum paragraf (blok baris tidak kosong) di atas cursor.
+    pub fn move_paragraph_up_selecting(&mut self, selecting: bool) {
+        self.prepare_selection(selecting);
+        let is_blank = |row: usize| self.lines[row].trim().is_empty();
+        let mut row = self.cursor_y;
+        while row > 0 && is_blank(row) {
+            row -= 1;
+        }
+        while row > 0 && !is_blank(row) {
+            row -= 1;
+        }
+        self.cursor_y = row;
+        self.cursor_x = 0;
+    }
+
+    pub fn move_paragraph_down_selecting(&mut self, selecting: bool) {
+        self.prepare_selection(selecting);
+        let is_blank = |row: usize| self.lines[row].trim().is_empty();
+        let last = self.lines.len().saturating_sub(1);
+        let mut row = self.cursor_y;
+        while row < last && is_blank(row) {
+            row += 1;
+        }
+        while row < last && !is_blank(row) {
+            row += 1;
+        }
+        self.cursor_y = row;
+        self.cursor_x = if is_blank(row) {
+            0
+        } else {
+            self.lines[row].len()
+        };
+    }
+
+    /// Home pintar: ke karakter pertama yang bukan spasi, atau ke kolom 0 bila sudah di sana.
+    pub fn move_home_selecting(&mut self, selecting: bool) {
+        self.prepare_selection(selecting);
+        let indent = leading_ws(&self.lines[self.cursor_y]).len();
+        self.cursor_x = if self.cursor_x == indent { 0 } else { indent };
+    }
+
+    pub fn move_end_selecting(&mut self, selecting: bool) {
+        self.prepare_selection(selecting);
+        self.cursor_x = self.lines[self.cursor_y].len();
+    }
+
+    /// Cursor dan viewport bergeser bersama sejauh `page` baris.
+    pub fn move_page_up_selecting(&mut self, page: usize, selecting: bool) {
+        self.prepare_selection(selecting);
+        self.cursor_y = self.cursor_y.saturating_sub(page);
+        self.scroll_y = self.scroll_y.saturating_sub(page);
+        self.cursor_x = self.cursor_x.min(self.lines[self.cursor_y].len());
+    }
+
+    pub fn move_page_down_selecting(&mut self, page: usize, selecting: bool) {
+        self.prepare_selection(selecting);
+        let max_row = self.lines.len().saturating_sub(1);
+        self.cursor_y = (self.cursor_y + page).min(max_row);
+        self.scroll_y = (self.scroll_y + page).min(self.cursor_y);
+        self.cursor_x = self.cursor_x.min(self.lines[self.cursor_y].len());
+    }
+
+    pub fn move_document_start_selecting(&mut self, selecting: bool) {
+        self.set_cursor_selecting(0, 0, selecting);
+    }
+
+    pub fn move_document_end_selecting(&mut self, selecting: bool) {
+        let last = self.lines.len().saturating_sub(1);
+        self.set_cursor_selecting(last, self.lines[last].len(), selecting);
+    }
+
     pub fn ensure_cursor_visible(&mut self, viewport_height: usize) {
         if self.cursor_y < self.scroll_y {
             self.scroll_y = self.cursor_y;
@@ -183,12 +325,25 @@ impl Editor {
         &mut self,
         screen_x: usize,
         screen_y: usize,
+        tab_width: usize,
         selecting: bool,
     ) {
         self.prepare_selection(selecting);
         let max_row = self.lines.len().saturating_sub(1);
         let row = (self.scroll_y + screen_y).min(max_row);
-        let col = screen_x.min(self.lines[row].len());
+        // Kolom layar ke indeks byte; klik di tengah tab menaruh cursor di awal tab.
+        let mut column = 0;
+        let col = self.lines[row]
+            .char_indices()
+            .find(|&(_, ch)| {
+                column += if ch == '\t' {
+                    tab_advance(column, tab_width)
+                } else {
+                    1
+                };
+                column > screen_x
+            })
+            .map_or(self.lines[row].len(), |(idx, _)| idx);
         self.cursor_y = row;
         self.cursor_x = col;
     }
@@ -227,6 +382,75 @@ impl Editor {
         self.selection_anchor = None;
     }
 
+    /// Seperti `replace_text`, tetapi cursor dan scroll dipetakan lewat diff sehingga tetap
+    /// di kode yang sama setelah formatter mengubah spasi. Baris yang tidak berubah hanya
+    /// digeser; di dalam hunk, posisi dipetakan lewat diff karakter non-spasi hunk tersebut.
+    pub fn replace_text_preserving_cursor(&mut self, text: &str) {
+        let old_lines = self.lines.clone();
+        let (old_x, old_y) = self.cursor();
+        let offset = old_y.saturating_sub(self.scroll_y);
+
+        self.replace_text(text);
+        let hunks = diff_lines(&old_lines, &self.lines);
+        let (old_rows, new_rows) = match hunks
+            .iter()
+            .find(|hunk| old_y >= hunk.old_start && old_y < hunk.old_start + hunk.old_len)
+        {
+            Some(hunk) => (
+                hunk.old_start..hunk.old_start + hunk.old_len,
+                hunk.new_start..hunk.new_start + hunk.new_len,
+            ),
+            None => {
+                self.cursor_y = map_line(&hunks, old_y).min(self.lines.len().saturating_sub(1));
+                self.cursor_x = old_x.min(self.lines[self.cursor_y].len());
+                self.scroll_y = self.cursor_y.saturating_sub(offset);
+                return;
+            }
+        };
+        let last_row = self.lines.len().saturating_sub(1);
+        let new_rows = new_rows.start.min(last_row)..new_rows.end.min(last_row + 1);
+
+        let visible = |lines: &[String]| {
+            lines
+                .iter()
+                .flat_map(|line| line.chars())
+                .filter(|ch| !ch.is_whitespace())
+                .collect::<Vec<_>>()
+        };
+        let before_cursor = visible(&old_lines[old_rows.start..old_y]).len()
+            + old_lines[old_y]
+                .char_indices()
+                .take_while(|(idx, _)| *idx < old_x)
+                .filter(|(_, ch)| !ch.is_whitespace())
+                .count();
+        let mut remaining = map_index(
+            &visible(&old_lines[old_rows]),
+            &visible(&self.lines[new_rows.clone()]),
+            before_cursor,
+        );
+
+        let mut target = None;
+        'rows: for row in new_rows.clone() {
+            for (idx, ch) in self.lines[row].char_indices() {
+                if ch.is_whitespace() {
+                    continue;
+                }
+                if remaining == 0 {
+                    target = Some((row, idx));
+                    break 'rows;
+                }
+                remaining -= 1;
+            }
+        }
+        let (row, col) = target.unwrap_or_else(|| {
+            let row = new_rows.end.saturating_sub(1).max(new_rows.start);
+            (row, self.lines[row].len())
+        });
+        self.cursor_y = row;
+        self.cursor_x = col;
+        self.scroll_y = row.saturating_sub(offset);
+    }
+
     pub fn current_line_text(&self) -> String {
         self.lines.get(self.cursor_y).cloned().unwrap_or_default()
     }
@@ -301,6 +525,16 @@ impl Editor {
         self.cursor_x = last_col;
     }
 
+    pub fn set_selection(&mut self, anchor_row: usize, anchor_col: usize, row: usize, col: usize) {
+        self.set_cursor(anchor_row, anchor_col);
+        self.selection_anchor = Some(self.cursor());
+        self.set_cursor_selecting(row, col, true);
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selection_anchor = None;
+    }
+
     pub fn has_selection(&self) -> bool {
         self.selection_range().is_some()
     }
@@ -402,12 +636,237 @@ impl Editor {
         true
     }
 
+    /// Gandakan baris (atau blok baris terseleksi); `below` memindahkan cursor ke salinan bawah.
+    pub fn duplicate_lines(&mut self, below: bool) {
+        let (start_row, end_row) = self.movable_line_range();
+        self.begin_edit();
+        let block = self.lines[start_row..=end_row].to_vec();
+        let count = block.len() as isize;
+        self.lines.splice(end_row + 1..end_row + 1, block);
+        if below {
+            self.shift_cursor_row(count);
+            self.shift_selection_anchor_row(count);
+        }
+    }
+
+    /// Gabungkan baris terseleksi (atau baris cursor dengan baris berikutnya) dengan satu spasi.
+    pub fn join_lines(&mut self) -> bool {
+        let (start_row, mut end_row) = self.movable_line_range();
+        if start_row == end_row {
+            end_row += 1;
+        }
+        if end_row >= self.lines.len() {
+            return false;
+        }
+
+        self.begin_edit();
+        let mut joined = self.lines[start_row].trim_end().to_string();
+        let mut join_col = joined.len();
+        for line in self.lines.drain(start_row + 1..=end_row) {
+            let next = line.trim();
+            let glue = !joined.is_empty()
+                && !next.is_empty()
+                && !joined.ends_with(['(', '['])
+                && !next.starts_with([')', ']', ',', ';', '.']);
+            if glue {
+                joined.push(' ');
+            }
+            join_col = joined.len();
+            joined.push_str(next);
+        }
+        self.lines[start_row] = joined;
+        self.cursor_y = start_row;
+        self.cursor_x = join_col;
+        self.selection_anchor = None;
+        true
+    }
+
+    /// Tambah satu level indentasi di baris terseleksi (atau baris cursor); baris kosong dilewati.
+    pub fn indent_lines(&mut self) -> bool {
+        let range = self.movable_line_range();
+        let rows = self.non_blank_rows(range);
+        if rows.is_empty() {
+            return false;
+        }
+
+        self.begin_edit();
+        for row in rows {
+            self.lines[row].insert_str(0, INDENT);
+            self.adjust_columns(row, 0, 0, INDENT.len());
+        }
+        self.reselect_rows(range);
+        true
+    }
+
+    pub fn outdent_lines(&mut self) -> bool {
+        let range = self.movable_line_range();
+        let (start_row, end_row) = range;
+        let removals = (start_row..=end_row)
+            .map(|row| (row, outdent_width(&self.lines[row])))
+            .filter(|(_, width)| *width > 0)
+            .collect::<Vec<_>>();
+        if removals.is_empty() {
+            return false;
+        }
+
+        self.begin_edit();
+        for (row, width) in removals {
+            self.lines[row].drain(..width);
+            self.adjust_columns(row, 0, width, 0);
+        }
+        self.reselect_rows(range);
+        true
+    }
+
+    /// Komentari baris terseleksi dengan `token`, atau buka komentarnya bila semua baris yang
+    /// tidak kosong sudah diawali `token`.
+    pub fn toggle_line_comment(&mut self, token: &str) -> bool {
+        let range = self.movable_line_range();
+        let rows = self.non_blank_rows(range);
+        if rows.is_empty() {
+            return false;
+        }
+
+        self.begin_edit();
+        let commented = rows
+            .iter()
+            .all(|&row| self.lines[row].trim_start().starts_with(token));
+        if commented {
+            for row in rows {
+                let at = leading_ws(&self.lines[row]).len();
+                let mut len = token.len();
+                if self.lines[row][at + len..].starts_with(' ') {
+                    len += 1;
+                }
+                self.lines[row].drain(at..at + len);
+                self.adjust_columns(row, at, len, 0);
+            }
+        } else {
+            let at = rows
+                .iter()
+                .map(|&row| leading_ws(&self.lines[row]).len())
+                .min()
+                .unwrap_or(0);
+            let prefix = format!("{} ", token);
+            for row in rows {
+                self.lines[row].insert_str(at, &prefix);
+                self.adjust_columns(row, at, 0, prefix.len());
+            }
+        }
+        self.reselect_rows(range);
+        true
+    }
+
+    /// Bungkus seleksi (atau isi baris cursor) dengan `open`/`close`, atau lepas bungkusnya
+    /// bila sudah terbungkus. Spasi di tepi seleksi diabaikan saat memeriksa.
+    pub fn toggle_block_comment(&mut self, open: &str, close: &str) -> bool {
+        let selection = self.selection_range();
+        let (start, end) = match selection {
+            Some(range) => range,
+            None => {
+                let line = &self.lines[self.cursor_y];
+                let start = leading_ws(line).len();
+                let end = line.trim_end().len();
+                if start >= end {
+                    return false;
+                }
+                ((start, self.cursor_y), (end, self.cursor_y))
+            }
+        };
+
+        let inner_start = start.0 + leading_ws(&self.lines[start.1][start.0..]).len();
+        let inner_end = self.lines[end.1][..end.0].trim_end().len();
+        let wrapped = self.lines[start.1][inner_start..].starts_with(open)
+            && self.lines[end.1][..inner_end].ends_with(close)
+            && (start.1 != end.1 || inner_end >= inner_start + open.len() + close.len());
+
+        self.begin_edit();
+        let same_row = start.1 == end.1;
+        let end_col = if wrapped {
+            let close_at = inner_end - close.len();
+            self.lines[end.1].drain(close_at..inner_end);
+            self.adjust_columns(end.1, close_at, close.len(), 0);
+            self.lines[start.1].drain(inner_start..inner_start + open.len());
+            self.adjust_columns(start.1, inner_start, open.len(), 0);
+            end.0 - close.len() - if same_row { open.len() } else { 0 }
+        } else {
+            self.lines[end.1].insert_str(end.0, close);
+            self.adjust_columns(end.1, end.0, 0, close.len());
+            self.lines[start.1].insert_str(start.0, open);
+            self.adjust_columns(start.1, start.0, 0, open.len());
+            end.0 + close.len() + if same_row { open.len() } else { 0 }
+        };
+        if selection.is_some() {
+            self.selection_anchor = Some(start);
+            self.cursor_x = end_col;
+            self.cursor_y = end.1;
+        }
+        true
+    }
+
+    /// Ganti teks dari `start` sampai `end` (baris, kolom) lalu seleksi hasilnya sebagai satu
+    /// langkah undo.
+    pub fn replace_range(&mut self, start: (usize, usize), end: (usize, usize), text: &str) {
+        self.begin_undo_group();
+        self.set_selection(start.0, start.1, end.0, end.1);
+        if text.is_empty() {
+            if self.has_selection() {
+                self.delete_forward();
+            }
+        } else {
+            self.insert_text(text);
+        }
+        let (row, col) = (self.cursor_y, self.cursor_x);
+        self.set_selection(start.0, start.1, row, col);
+        self.end_undo_group();
+    }
+
+    /// Semua edit sampai `end_undo_group` menjadi satu langkah undo.
+    pub fn begin_undo_group(&mut self) {
+        if self.undo_group == 0 {
+            self.push_undo_snapshot();
+        }
+        self.undo_group += 1;
+    }
+
+    pub fn end_undo_group(&mut self) {
+        if self.undo_group == 0 {
+            return;
+        }
+        self.undo_group -= 1;
+        if self.undo_group > 0 {
+            return;
+        }
+        // Grup tanpa perubahan tidak meninggalkan langkah undo kosong.
+        if self
+            .undo_stack
+            .last()
+            .is_some_and(|snapshot| snapshot.lines == self.lines)
+        {
+            self.undo_stack.pop();
+        }
+    }
+
+    /// Catat posisi riwayat undo sebelum rangkaian edit yang nanti digabung (sesi insert Vim).
+    /// Berbeda dengan grup undo, tanda ini aman ditinggal bila pengguna pindah tab di tengah jalan.
+    pub fn mark_undo(&mut self) {
+        self.undo_mark = Some(self.undo_stack.len());
+    }
+
+    /// Gabungkan semua langkah undo sejak `mark_undo` menjadi satu langkah.
+    pub fn merge_undo_since_mark(&mut self) {
+        if let Some(mark) = self.undo_mark.take() {
+            self.undo_stack.truncate(mark + 1);
+        }
+    }
+
     pub fn undo(&mut self) -> bool {
         let Some(snapshot) = self.undo_stack.pop() else {
             return false;
         };
         self.redo_stack.push(self.snapshot());
         self.apply_snapshot(snapshot);
+        self.revision += 1;
         true
     }
 
@@ -417,6 +876,7 @@ impl Editor {
         };
         self.undo_stack.push(self.snapshot());
         self.apply_snapshot(snapshot);
+        self.revision += 1;
         true
     }
 
@@ -432,6 +892,10 @@ impl Editor {
         self.scroll_y
     }
 
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
     fn cursor(&self) -> Cursor {
         (self.cursor_x, self.cursor_y)
     }
@@ -445,7 +909,8 @@ impl Editor {
         Some((col, row))
     }
 
-    fn movable_line_range(&self) -> (usize, usize) {
+    /// Rentang baris seleksi (baris akhir tidak dihitung bila seleksi berakhir di kolom 0).
+    pub fn movable_line_range(&self) -> (usize, usize) {
         if let Some((start, end)) = self.selection_range() {
             let mut end_row = end.1;
             if end.0 == 0 && end.1 > start.1 {
@@ -470,6 +935,43 @@ impl Editor {
         }
     }
 
+    fn non_blank_rows(&self, (start_row, end_row): (usize, usize)) -> Vec<usize> {
+        (start_row..=end_row)
+            .filter(|&row| !self.lines[row].trim().is_empty())
+            .collect()
+    }
+
+    // Setelah indent/komentar blok baris, seleksi dijadikan baris penuh supaya perintah
+    // berikutnya tetap mengenai baris yang sama walau kolomnya bergeser.
+    fn reselect_rows(&mut self, (start_row, end_row): (usize, usize)) {
+        if self.selection_anchor.is_none() || start_row == end_row {
+            return;
+        }
+        self.selection_anchor = Some((0, start_row));
+        self.cursor_y = end_row;
+        self.cursor_x = self.lines[end_row].len();
+    }
+
+    // Geser kolom cursor/anchor di `row` setelah `removed` byte di `at` diganti `inserted`
+    // byte. Kolom 0 tidak ikut bergeser supaya seleksi baris penuh tetap penuh.
+    fn adjust_columns(&mut self, row: usize, at: usize, removed: usize, inserted: usize) {
+        let adjust = |col: usize| {
+            if col < at || col == 0 {
+                col
+            } else {
+                at + inserted + col.saturating_sub(at + removed)
+            }
+        };
+        if self.cursor_y == row {
+            self.cursor_x = adjust(self.cursor_x);
+        }
+        if let Some((x, y)) = self.selection_anchor
+            && y == row
+        {
+            self.selection_anchor = Some((adjust(x), y));
+        }
+    }
+
     fn insert_char_raw(&mut self, ch: char) {
         if let Some(line) = self.lines.get_mut(self.cursor_y) {
             line.insert(self.cursor_x, ch);
@@ -555,11 +1057,19 @@ impl Editor {
     }
 
     fn begin_edit(&mut self) {
+        self.revision += 1;
+        if self.undo_group == 0 {
+            self.push_undo_snapshot();
+        }
+        self.redo_stack.clear();
+    }
+
+    fn push_undo_snapshot(&mut self) {
         self.undo_stack.push(self.snapshot());
         if self.undo_stack.len() > HISTORY_LIMIT {
             self.undo_stack.remove(0);
+            self.undo_mark = self.undo_mark.and_then(|mark| mark.checked_sub(1));
         }
-        self.redo_stack.clear();
     }
 
     fn prepare_selection(&mut self, selecting: bool) {
@@ -665,11 +1175,11 @@ impl Editor {
         }
         if let Some(line) = self.lines.get_mut(self.cursor_y) {
             let start = self.cursor_x - remove;
-            if let Some(slice) = line.get(start..self.cursor_x) {
-                if slice.chars().all(|c| c == ' ') {
-                    line.drain(start..self.cursor_x);
-                    self.cursor_x = start;
-                }
+            if let Some(slice) = line.get(start..self.cursor_x)
+                && slice.chars().all(|c| c == ' ')
+            {
+                line.drain(start..self.cursor_x);
+                self.cursor_x = start;
             }
         }
     }
@@ -709,3 +1219,291 @@ fn shift_row(row: usize, delta: isize, total_lines: usize) -> usize {
             .min(total_lines.saturating_sub(1))
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Space,
+    Word,
+    Punct,
+}
+
+fn char_class(ch: char) -> CharClass {
+    if ch.is_whitespace() {
+        CharClass::Space
+    } else if ch.is_alphanumeric() || ch == '_' {
+        CharClass::Word
+    } else {
+        CharClass::Punct
+    }
+}
+
+// Batas subword di antara `prev` dan `cur`: `foo|_|bar`, `foo|Bar`, `HTTP|Server`.
+fn is_subword_boundary(prev: char, cur: char, next: Option<char>) -> bool {
+    ((prev == '_') != (cur == '_'))
+        || ((prev.is_lowercase() || prev.is_ascii_digit()) && cur.is_uppercase())
+        || (prev.is_uppercase() && cur.is_uppercase() && next.is_some_and(char::is_lowercase))
+}
+
+/// Kolom (byte) awal kata sebelum `col`; spasi di antaranya dilewati.
+fn word_start(line: &str, col: usize, subword: bool) -> usize {
+    let chars = line[..col].char_indices().collect::<Vec<_>>();
+    let mut idx = chars.len();
+    while idx > 0 && char_class(chars[idx - 1].1) == CharClass::Space {
+        idx -= 1;
+    }
+    if idx == 0 {
+        return 0;
+    }
+
+    idx -= 1;
+    let class = char_class(chars[idx].1);
+    while idx > 0 && char_class(chars[idx - 1].1) == class {
+        let next = chars.get(idx + 1).map(|(_, ch)| *ch);
+        if subword
+            && class == CharClass::Word
+            && is_subword_boundary(chars[idx - 1].1, chars[idx].1, next)
+        {
+            break;
+        }
+        idx -= 1;
+    }
+    chars[idx].0
+}
+
+/// Kolom (byte) akhir kata sesudah `col`; spasi di antaranya dilewati.
+fn word_end(line: &str, col: usize, subword: bool) -> usize {
+    let chars = line[col..].chars().collect::<Vec<_>>();
+    let mut idx = 0;
+    while idx < chars.len() && char_class(chars[idx]) == CharClass::Space {
+        idx += 1;
+    }
+    if idx == chars.len() {
+        return line.len();
+    }
+
+    let class = char_class(chars[idx]);
+    idx += 1;
+    while idx < chars.len() && char_class(chars[idx]) == class {
+        if subword
+            && class == CharClass::Word
+            && is_subword_boundary(chars[idx - 1], chars[idx], chars.get(idx + 1).copied())
+        {
+            break;
+        }
+        idx += 1;
+    }
+    col + chars[..idx].iter().map(|ch| ch.len_utf8()).sum::<usize>()
+}
+
+// Lebar indentasi yang dibuang satu kali outdent: satu tab atau sampai empat spasi.
+fn outdent_width(line: &str) -> usize {
+    if line.starts_with('\t') {
+        return 1;
+    }
+    line.bytes()
+        .take(INDENT.len())
+        .take_while(|&byte| byte == b' ')
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CALL: &str = "let x = foo(\n    a,\n    b\n);";
+
+    #[test]
+    fn join_selection_undoes_in_one_step() {
+        let mut editor = Editor::from_text(CALL);
+        editor.set_selection(0, 0, 3, 2);
+        assert!(editor.join_lines());
+        assert_eq!(editor.text(), "let x = foo(a, b);");
+        assert!(!editor.has_selection());
+
+        assert!(editor.undo());
+        assert_eq!(editor.text(), CALL);
+        assert!(!editor.undo());
+    }
+
+    #[test]
+    fn join_without_selection_needs_next_line() {
+        let mut editor = Editor::from_text("a\n  b");
+        assert!(editor.join_lines());
+        assert_eq!(editor.text(), "a b");
+        assert_eq!(editor.cursor_x(), 2);
+        assert!(!editor.join_lines());
+    }
+
+    #[test]
+    fn line_comment_round_trips_on_selection() {
+        let text = "fn a() {\n  x();\n\n    y();\n}";
+        let mut editor = Editor::from_text(text);
+        editor.set_selection(1, 0, 3, 8);
+        assert!(editor.toggle_line_comment("//"));
+        // Komentar disejajarkan di indentasi terkecil; baris kosong dilewati.
+        assert_eq!(editor.text(), "fn a() {\n  // x();\n\n  //   y();\n}");
+        assert!(editor.toggle_line_comment("//"));
+        assert_eq!(editor.text(), text);
+
+        assert!(editor.undo());
+        assert_eq!(editor.text(), "fn a() {\n  // x();\n\n  //   y();\n}");
+        assert!(editor.undo());
+        assert_eq!(editor.text(), text);
+        assert!(!editor.undo());
+    }
+
+    #[test]
+    fn block_comment_wraps_multi_line_selection() {
+        let text = "a(1);\nb(2);";
+        let mut editor = Editor::from_text(text);
+        editor.set_selection(0, 0, 1, 5);
+        assert!(editor.toggle_block_comment("/*", "*/"));
+        assert_eq!(editor.text(), "/*a(1);\nb(2);*/");
+        assert_eq!(editor.selected_text().as_deref(), Some("/*a(1);\nb(2);*/"));
+        assert!(editor.toggle_block_comment("/*", "*/"));
+        assert_eq!(editor.text(), text);
+
+        assert!(editor.undo());
+        assert_eq!(editor.text(), "/*a(1);\nb(2);*/");
+        assert!(editor.undo());
+        assert_eq!(editor.text(), text);
+    }
+
+    #[test]
+    fn outdent_removes_partial_indent() {
+        let mut editor = Editor::from_text("  x\n      y\n\tz\nw");
+        editor.set_selection(0, 0, 3, 1);
+        assert!(editor.outdent_lines());
+        assert_eq!(editor.text(), "x\n  y\nz\nw");
+
+        let mut editor = Editor::from_text("  x");
+        editor.set_cursor(0, 3);
+        assert!(editor.outdent_lines());
+        assert_eq!((editor.text().as_str(), editor.cursor_x()), ("x", 1));
+        assert!(!editor.outdent_lines());
+        assert!(editor.undo());
+        assert_eq!(editor.text(), "  x");
+    }
+
+    #[test]
+    fn duplicate_last_line() {
+        let mut editor = Editor::from_text("a\nb");
+        editor.set_cursor(1, 1);
+        editor.duplicate_lines(true);
+        assert_eq!(editor.text(), "a\nb\nb");
+        assert_eq!((editor.cursor_y(), editor.cursor_x()), (2, 1));
+
+        editor.duplicate_lines(false);
+        assert_eq!(editor.text(), "a\nb\nb\nb");
+        assert_eq!(editor.cursor_y(), 2);
+        assert!(editor.undo());
+        assert!(editor.undo());
+        assert_eq!(editor.text(), "a\nb");
+    }
+
+    #[test]
+    fn word_motions_cross_line_edges() {
+        let mut editor = Editor::from_text("fooBar_baz qux\nnext");
+        editor.move_word_right_selecting(false, false);
+        assert_eq!(editor.cursor_x(), 10);
+        editor.move_word_right_selecting(false, false);
+        assert_eq!(editor.cursor_x(), 14);
+        // Di akhir baris pindah ke awal baris berikutnya, dan sebaliknya.
+        editor.move_word_right_selecting(false, false);
+        assert_eq!((editor.cursor_y(), editor.cursor_x()), (1, 0));
+        editor.move_word_left_selecting(false, false);
+        assert_eq!((editor.cursor_y(), editor.cursor_x()), (0, 14));
+        editor.move_word_left_selecting(false, true);
+        assert_eq!(editor.selected_text().as_deref(), Some("qux"));
+    }
+
+    #[test]
+    fn subword_boundaries() {
+        let line = "fooBar_baz HTTPServer";
+        assert_eq!(word_end(line, 0, true), 3);
+        assert_eq!(word_end(line, 3, true), 6);
+        assert_eq!(word_end(line, 6, true), 7);
+        assert_eq!(word_end(line, 0, false), 10);
+        assert_eq!(word_start(line, line.len(), true), 15);
+        assert_eq!(word_start(line, 15, true), 11);
+        assert_eq!(word_start(line, 11, false), 0);
+        assert_eq!(word_start(line, 0, true), 0);
+        assert_eq!(word_end(line, line.len(), true), line.len());
+    }
+
+    #[test]
+    fn delete_word_left_stops_at_line_start() {
+        let mut editor = Editor::from_text("ab\n  cd ef");
+        editor.set_cursor(1, 7);
+        editor.delete_word_left();
+        assert_eq!(editor.text(), "ab\n  cd ");
+        editor.delete_word_left();
+        assert_eq!(editor.text(), "ab\n  ");
+        editor.delete_word_left();
+        assert_eq!(editor.text(), "ab\n");
+        // Di kolom 0 sama dengan Backspace: gabung dengan baris sebelumnya.
+        editor.delete_word_left();
+        assert_eq!(editor.text(), "ab");
+    }
+
+    #[test]
+    fn smart_home_paragraph_and_page_motions() {
+        let mut editor = Editor::from_text("a\nb\n\n    c\nd\n\ne");
+        editor.set_cursor(3, 6);
+        editor.move_home_selecting(false);
+        assert_eq!(editor.cursor_x(), 4);
+        editor.move_home_selecting(false);
+        assert_eq!(editor.cursor_x(), 0);
+        editor.move_home_selecting(false);
+        assert_eq!(editor.cursor_x(), 4);
+
+        editor.move_paragraph_up_selecting(false);
+        assert_eq!(editor.cursor_y(), 2);
+        editor.move_paragraph_up_selecting(false);
+        assert_eq!(editor.cursor_y(), 0);
+        editor.move_paragraph_down_selecting(false);
+        assert_eq!(editor.cursor_y(), 2);
+        editor.move_paragraph_down_selecting(false);
+        assert_eq!(editor.cursor_y(), 5);
+        editor.move_paragraph_down_selecting(false);
+        assert_eq!((editor.cursor_y(), editor.cursor_x()), (6, 1));
+
+        editor.move_page_up_selecting(4, false);
+        assert_eq!((editor.cursor_y(), editor.cursor_x()), (2, 0));
+        editor.move_page_down_selecting(100, false);
+        assert_eq!(editor.cursor_y(), 6);
+    }
+
+    #[test]
+    fn screen_column_maps_into_tabs() {
+        let mut editor = Editor::from_text("\tx\ty");
+        let column = |editor: &mut Editor, screen_x: usize, tab_width: usize| {
+            editor.set_cursor_from_screen_selecting(screen_x, 0, tab_width, false);
+            editor.cursor_x()
+        };
+        // Klik di mana pun dalam tab menaruh cursor di awal tab.
+        assert_eq!(column(&mut editor, 0, 4), 0);
+        assert_eq!(column(&mut editor, 3, 4), 0);
+        assert_eq!(column(&mut editor, 4, 4), 1);
+        assert_eq!(column(&mut editor, 5, 4), 2);
+        assert_eq!(column(&mut editor, 7, 4), 2);
+        assert_eq!(column(&mut editor, 8, 4), 3);
+        assert_eq!(column(&mut editor, 9, 4), 4);
+        assert_eq!(column(&mut editor, 2, 2), 1);
+        assert_eq!(column(&mut editor, 3, 2), 2);
+        assert_eq!(column(&mut editor, 4, 2), 3);
+        assert_eq!(column(&mut editor, 7, 8), 0);
+        assert_eq!(column(&mut editor, 8, 8), 1);
+    }
+
+    #[test]
+    fn replace_range_selects_result_as_one_undo_step() {
+        let mut editor = Editor::from_text("b\na\nc");
+        editor.replace_range((0, 0), (2, 1), "a\nb\nc");
+        assert_eq!(editor.text(), "a\nb\nc");
+        assert_eq!(editor.selected_text().as_deref(), Some("a\nb\nc"));
+        assert!(editor.undo());
+        assert_eq!(editor.text(), "b\na\nc");
+        assert!(!editor.undo());
+    }
+}
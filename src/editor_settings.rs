@@ -0,0 +1,113 @@
+use anyhow::Result;
+use ratatui::style::Color;
+use serde_json::{Value, json};
+
+use crate::config;
+
+const SETTINGS_FILE: &str = "editor.json";
+const DEFAULT_TAB_WIDTH: usize = 4;
+
+/// Latar baris cursor: warna tema, dimatikan, atau warna sendiri (`"#rrggbb"` / nama warna).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CurrentLineHighlight {
+    #[default]
+    Theme,
+    Off,
+    Color(Color),
+}
+
+impl CurrentLineHighlight {
+    fn from_json(value: &str) -> Option<Self> {
+        match value {
+            "theme" => Some(Self::Theme),
+            "off" => Some(Self::Off),
+            color => color.parse().ok().map(Self::Color),
+        }
+    }
+
+    fn to_json(self) -> String {
+        match self {
+            Self::Theme => String::from("theme"),
+            Self::Off => String::from("off"),
+            Self::Color(color) => color.to_string(),
+        }
+    }
+}
+
+/// Preferensi editor yang disimpan di `editor.json` pada folder konfigurasi.
+#[derive(Debug, Clone)]
+pub struct EditorSettings {
+    pub vim_mode: bool,
+    /// Lebar tab saat ditampilkan, dalam kolom.
+    pub tab_width: usize,
+    /// Tampilkan tab, spasi di akhir baris, dan non-breaking space sebagai glyph.
+    pub show_whitespace: bool,
+    pub indent_guides: bool,
+    /// Kolom (1-based) yang diberi garis, mis. `[100]` untuk `max_width` rustfmt.
+    pub rulers: Vec<usize>,
+    pub current_line: CurrentLineHighlight,
+}
+
+impl Default for EditorSettings {
+    fn default() -> Self {
+        Self {
+            vim_mode: false,
+            tab_width: DEFAULT_TAB_WIDTH,
+            show_whitespace: false,
+            indent_guides: false,
+            rulers: Vec::new(),
+            current_line: CurrentLineHighlight::default(),
+        }
+    }
+}
+
+impl EditorSettings {
+    pub fn load() -> Self {
+        let mut settings = Self::default();
+        let Some(value) = config::load_json(SETTINGS_FILE) else {
+            return settings;
+        };
+        if let Some(enabled) = value["vim_mode"].as_bool() {
+            settings.vim_mode = enabled;
+        }
+        if let Some(width) = value["tab_width"]
+            .as_u64()
+            .filter(|width| (1..=16).contains(width))
+        {
+            settings.tab_width = width as usize;
+        }
+        if let Some(enabled) = value["show_whitespace"].as_bool() {
+            settings.show_whitespace = enabled;
+        }
+        if let Some(enabled) = value["indent_guides"].as_bool() {
+            settings.indent_guides = enabled;
+        }
+        if let Some(rulers) = value["rulers"].as_array() {
+            settings.rulers = rulers
+                .iter()
+                .filter_map(Value::as_u64)
+                .filter(|column| *column > 0)
+                .map(|column| column as usize)
+                .collect();
+        }
+        if let Some(highlight) = value["current_line"]
+            .as_str()
+            .and_then(CurrentLineHighlight::from_json)
+        {
+            settings.current_line = highlight;
+        }
+        settings
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let value = json!({
+            "vim_mode": self.vim_mode,
+            "tab_width": self.tab_width,
+            "show_whitespace": self.show_whitespace,
+            "indent_guides": self.indent_guides,
+            "rulers": self.rulers,
+            "current_line": self.current_line.to_json(),
+        });
+        config::save_json(SETTINGS_FILE, &value)
+    }
+}
@@ -23,6 +23,9 @@ pub struct FileTree {
 
 impl FileTree {
     pub fn new(root: PathBuf) -> Result<Self> {
+        // Dikanonikalkan sekali di sini agar path entry cocok dengan path dari git tanpa
+        // `canonicalize` per baris saat menggambar.
+        let root = root.canonicalize().unwrap_or(root);
         let mut tree = Self {
             expanded_dirs: HashSet::from([root.clone()]),
             root,
@@ -0,0 +1,573 @@
+use anyhow::Result;
+use serde_json::{Map, Value, json};
+
+use crate::config;
+use crate::editor::Editor;
+use crate::editor_settings::EditorSettings;
+use crate::transform::{self, Transform};
+use crate::vim::VimState;
+
+const MACROS_FILE: &str = "macros.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Motion {
+    Left,
+    Right,
+    Up,
+    Down,
+    WordLeft,
+    WordRight,
+    SubwordLeft,
+    SubwordRight,
+    ParagraphUp,
+    ParagraphDown,
+    Home,
+    End,
+    DocumentStart,
+    DocumentEnd,
+    PageUp(usize),
+    PageDown(usize),
+}
+
+impl Motion {
+    const NAMED: [(&'static str, Self); 14] = [
+        ("left", Self::Left),
+        ("right", Self::Right),
+        ("up", Self::Up),
+        ("down", Self::Down),
+        ("word-left", Self::WordLeft),
+        ("word-right", Self::WordRight),
+        ("subword-left", Self::SubwordLeft),
+        ("subword-right", Self::SubwordRight),
+        ("paragraph-up", Self::ParagraphUp),
+        ("paragraph-down", Self::ParagraphDown),
+        ("home", Self::Home),
+        ("end", Self::End),
+        ("document-start", Self::DocumentStart),
+        ("document-end", Self::DocumentEnd),
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::PageUp(_) => "page-up",
+            Self::PageDown(_) => "page-down",
+            motion => Self::NAMED
+                .iter()
+                .find(|(_, named)| *named == motion)
+                .map_or("", |(name, _)| name),
+        }
+    }
+
+    fn from_name(name: &str, lines: usize) -> Option<Self> {
+        match name {
+            "page-up" => Some(Self::PageUp(lines)),
+            "page-down" => Some(Self::PageDown(lines)),
+            _ => Self::NAMED
+                .iter()
+                .find(|(named, _)| *named == name)
+                .map(|(_, motion)| *motion),
+        }
+    }
+
+    fn apply(self, editor: &mut Editor, select: bool) {
+        match self {
+            Self::Left => editor.move_left_selecting(select),
+            Self::Right => editor.move_right_selecting(select),
+            Self::Up => editor.move_up_selecting(select),
+            Self::Down => editor.move_down_selecting(select),
+            Self::WordLeft => editor.move_word_left_selecting(false, select),
+            Self::WordRight => editor.move_word_right_selecting(false, select),
+            Self::SubwordLeft => editor.move_word_left_selecting(true, select),
+            Self::SubwordRight => editor.move_word_right_selecting(true, select),
+            Self::ParagraphUp => editor.move_paragraph_up_selecting(select),
+            Self::ParagraphDown => editor.move_paragraph_down_selecting(select),
+            Self::Home => editor.move_home_selecting(select),
+            Self::End => editor.move_end_selecting(select),
+            Self::DocumentStart => editor.move_document_start_selecting(select),
+            Self::DocumentEnd => editor.move_document_end_selecting(select),
+            Self::PageUp(lines) => editor.move_page_up_selecting(lines, select),
+            Self::PageDown(lines) => editor.move_page_down_selecting(lines, select),
+        }
+    }
+}
+
+/// Perintah editor yang direkam macro. Disimpan per perintah (bukan per tombol) supaya
+/// macro tetap berlaku walau keymap berubah.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditorCommand {
+    /// Teks yang diketik (dengan auto-pair dan lompat penutup seperti saat mengetik).
+    Type(String),
+    /// Teks yang ditempel apa adanya.
+    Insert(String),
+    Newline,
+    Backspace,
+    Delete,
+    DeleteWordLeft,
+    DeleteWordRight,
+    CutLine,
+    SelectAll,
+    Move {
+        motion: Motion,
+        select: bool,
+    },
+    DuplicateLines {
+        below: bool,
+    },
+    MoveLinesUp,
+    MoveLinesDown,
+    JoinLines,
+    Indent,
+    Outdent,
+    ToggleLineComment(String),
+    ToggleBlockComment(String, String),
+    /// Transformasi teks; `arg` berisi token perataan untuk `Transform::Align` dan `tab_width`
+    /// lebar tab dari `editor.json` saat perintah dijalankan atau direkam.
+    Transform {
+        kind: Transform,
+        arg: String,
+        tab_width: usize,
+    },
+    /// Input mode Vim: karakter hasil `VimState::key_char`, bukan event tombol terminal, jadi
+    /// tetap tidak bergantung keymap. Bahasa perintah Vim (count, operator, motion, text object,
+    /// register, `.`) sendiri sudah tingkat perintah dan statusnya dipegang `VimState`, sehingga
+    /// diputar ulang lewat `VimState::feed` alih-alih diurai menjadi operasi editor.
+    Vim(String),
+}
+
+impl EditorCommand {
+    /// Jalankan perintah; `false` bila perintah tidak mengubah apa-apa (mis. join di baris terakhir).
+    pub fn apply(&self, editor: &mut Editor, vim: &mut VimState) -> bool {
+        match self {
+            Self::Type(text) => text.chars().for_each(|ch| editor.type_char_smart(ch)),
+            Self::Insert(text) => editor.insert_text(text),
+            Self::Newline => editor.insert_newline_smart(),
+            Self::Backspace => editor.backspace(),
+            Self::Delete => editor.delete_forward(),
+            Self::DeleteWordLeft => editor.delete_word_left(),
+            Self::DeleteWordRight => editor.delete_word_right(),
+            Self::CutLine => {
+                editor.cut_current_line();
+            }
+            Self::SelectAll => editor.select_all(),
+            Self::Move { motion, select } => motion.apply(editor, *select),
+            Self::DuplicateLines { below } => editor.duplicate_lines(*below),
+            Self::MoveLinesUp => return editor.move_selected_lines_up(),
+            Self::MoveLinesDown => return editor.move_selected_lines_down(),
+            Self::JoinLines => return editor.join_lines(),
+            Self::Indent => return editor.indent_lines(),
+            Self::Outdent => return editor.outdent_lines(),
+            Self::ToggleLineComment(token) => return editor.toggle_line_comment(token),
+            Self::ToggleBlockComment(open, close) => {
+                return editor.toggle_block_comment(open, close);
+            }
+            Self::Transform {
+                kind,
+                arg,
+                tab_width,
+            } => return transform::apply(*kind, arg, *tab_width, editor),
+            Self::Vim(keys) => {
+                for key in keys.chars() {
+                    vim.feed(key, editor);
+                }
+            }
+        }
+        true
+    }
+
+    fn to_json(&self) -> Value {
+        match self {
+            Self::Type(text) => json!({ "type": text }),
+            Self::Insert(text) => json!({ "insert": text }),
+            Self::Vim(keys) => json!({ "vim": keys }),
+            Self::Move { motion, select } => {
+                let mut value = json!({ "move": motion.name(), "select": select });
+                if let Motion::PageUp(lines) | Motion::PageDown(lines) = motion {
+                    value["lines"] = json!(lines);
+                }
+                value
+            }
+            Self::DuplicateLines { below } => {
+                json!({ "duplicate": if *below { "below" } else { "above" } })
+            }
+            Self::ToggleLineComment(token) => json!({ "line-comment": token }),
+            Self::ToggleBlockComment(open, close) => json!({ "block-comment": [open, close] }),
+            Self::Transform {
+                kind,
+                arg,
+                tab_width,
+            } => json!({ "transform": kind.name(), "arg": arg, "tab_width": tab_width }),
+            Self::Newline => json!("newline"),
+            Self::Backspace => json!("backspace"),
+            Self::Delete => json!("delete"),
+            Self::DeleteWordLeft => json!("delete-word-left"),
+            Self::DeleteWordRight => json!("delete-word-right"),
+            Self::CutLine => json!("cut-line"),
+            Self::SelectAll => json!("select-all"),
+            Self::MoveLinesUp => json!("move-lines-up"),
+            Self::MoveLinesDown => json!("move-lines-down"),
+            Self::JoinLines => json!("join-lines"),
+            Self::Indent => json!("indent"),
+            Self::Outdent => json!("outdent"),
+        }
+    }
+
+    fn from_json(value: &Value) -> Option<Self> {
+        if let Some(name) = value.as_str() {
+            return Some(match name {
+                "newline" => Self::Newline,
+                "backspace" => Self::Backspace,
+                "delete" => Self::Delete,
+                "delete-word-left" => Self::DeleteWordLeft,
+                "delete-word-right" => Self::DeleteWordRight,
+                "cut-line" => Self::CutLine,
+                "select-all" => Self::SelectAll,
+                "move-lines-up" => Self::MoveLinesUp,
+                "move-lines-down" => Self::MoveLinesDown,
+                "join-lines" => Self::JoinLines,
+                "indent" => Self::Indent,
+                "outdent" => Self::Outdent,
+                _ => return None,
+            });
+        }
+        let text = |key: &str| value[key].as_str().map(str::to_string);
+        if let Some(name) = text("move") {
+            let lines = value["lines"].as_u64().unwrap_or(1) as usize;
+            return Some(Self::Move {
+                motion: Motion::from_name(&name, lines)?,
+                select: value["select"].as_bool().unwrap_or(false),
+            });
+        }
+        if let Some(text) = text("type") {
+            return Some(Self::Type(text));
+        }
+        if let Some(text) = text("insert") {
+            return Some(Self::Insert(text));
+        }
+        if let Some(keys) = text("vim") {
+            return Some(Self::Vim(keys));
+        }
+        if let Some(token) = text("line-comment") {
+            return Some(Self::ToggleLineComment(token));
+        }
+        if let Some(direction) = text("duplicate") {
+            return Some(Self::DuplicateLines {
+                below: direction == "below",
+            });
+        }
+        if let Some(name) = text("transform") {
+            return Some(Self::Transform {
+                kind: Transform::from_name(&name)?,
+                arg: text("arg").unwrap_or_default(),
+                tab_width: value["tab_width"]
+                    .as_u64()
+                    .map_or(EditorSettings::default().tab_width, |width| width as usize),
+            });
+        }
+        if let [open, close] = value["block-comment"].as_array()?.as_slice() {
+            return Some(Self::ToggleBlockComment(
+                open.as_str()?.to_string(),
+                close.as_str()?.to_string(),
+            ));
+        }
+        None
+    }
+}
+
+/// Cara memutar macro: diulang N kali di posisi cursor, atau sekali per baris seleksi.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Playback {
+    Times(usize),
+    EachLine,
+}
+
+/// Putar `commands` sebagai satu langkah undo.
+pub fn play(
+    commands: &[EditorCommand],
+    playback: Playback,
+    editor: &mut Editor,
+    vim: &mut VimState,
+) {
+    editor.begin_undo_group();
+    match playback {
+        Playback::Times(times) => {
+            for _ in 0..times {
+                for command in commands {
+                    command.apply(editor, vim);
+                }
+            }
+        }
+        Playback::EachLine => {
+            let (start, end) = editor.movable_line_range();
+            let mut row = start;
+            let mut last = end;
+            // Macro bisa menambah/menghapus baris; sisa rentang digeser sesuai selisihnya.
+            while row <= last && row < editor.lines().len() {
+                let before = editor.lines().len();
+                editor.set_cursor(row, 0);
+                for command in commands {
+                    command.apply(editor, vim);
+                }
+                let delta = editor.lines().len() as isize - before as isize;
+                last = last.saturating_add_signed(delta);
+                row = (row + 1).saturating_add_signed(delta);
+            }
+        }
+    }
+    editor.end_undo_group();
+}
+
+/// Perekam macro: perintah berurutan digabung (ketikan beruntun jadi satu `Type`).
+#[derive(Debug, Default)]
+pub struct MacroRecorder {
+    recording: Option<Vec<EditorCommand>>,
+    last: Option<Vec<EditorCommand>>,
+}
+
+impl MacroRecorder {
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    pub fn start(&mut self) {
+        self.recording = Some(Vec::new());
+    }
+
+    /// Hentikan rekaman; jumlah perintah yang terekam (macro kosong dibuang).
+    pub fn stop(&mut self) -> usize {
+        let commands = self.recording.take().unwrap_or_default();
+        let count = commands.len();
+        if count > 0 {
+            self.last = Some(commands);
+        }
+        count
+    }
+
+    pub fn last(&self) -> Option<&[EditorCommand]> {
+        self.last.as_deref()
+    }
+
+    pub fn record(&mut self, command: EditorCommand) {
+        let Some(commands) = self.recording.as_mut() else {
+            return;
+        };
+        match (commands.last_mut(), command) {
+            (Some(EditorCommand::Type(text)), EditorCommand::Type(more)) => text.push_str(&more),
+            (Some(EditorCommand::Vim(keys)), EditorCommand::Vim(more)) => keys.push_str(&more),
+            (_, command) => commands.push(command),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Macro {
+    pub name: String,
+    pub commands: Vec<EditorCommand>,
+}
+
+/// Macro bernama, disimpan di `macros.json` pada folder konfigurasi.
+#[derive(Debug, Default)]
+pub struct MacroStore {
+    macros: Vec<Macro>,
+}
+
+impl MacroStore {
+    pub fn load() -> Self {
+        let mut store = Self::default();
+        let Some(Value::Object(macros)) = config::load_json(MACROS_FILE) else {
+            return store;
+        };
+        for (name, commands) in macros {
+            // Macro dengan perintah yang tidak dikenal dilewati utuh, bukan diputar sebagian.
+            let commands = commands
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(EditorCommand::from_json)
+                .collect::<Option<Vec<_>>>();
+            if let Some(commands) = commands.filter(|commands| !commands.is_empty()) {
+                store.macros.push(Macro { name, commands });
+            }
+        }
+        store
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let macros = self
+            .macros
+            .iter()
+            .map(|item| {
+                let commands = item.commands.iter().map(EditorCommand::to_json).collect();
+                (item.name.clone(), Value::Array(commands))
+            })
+            .collect::<Map<_, _>>();
+        config::save_json(MACROS_FILE, &Value::Object(macros))
+    }
+
+    pub fn all(&self) -> &[Macro] {
+        &self.macros
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Macro> {
+        self.macros.iter().find(|item| item.name == name)
+    }
+
+    /// Simpan dengan nama; macro lama dengan nama sama ditimpa.
+    pub fn insert(&mut self, name: String, commands: Vec<EditorCommand>) {
+        self.remove(&name);
+        self.macros.push(Macro { name, commands });
+        self.macros.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.macros.len();
+        self.macros.retain(|item| item.name != name);
+        self.macros.len() != before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(command: EditorCommand) {
+        let value = command.to_json();
+        assert_eq!(EditorCommand::from_json(&value), Some(command), "{}", value);
+    }
+
+    #[test]
+    fn every_command_round_trips() {
+        let commands = [
+            EditorCommand::Type(String::from("fn main() {")),
+            EditorCommand::Insert(String::from("a\nb")),
+            EditorCommand::Vim(String::from("ciwx\x1b")),
+            EditorCommand::Newline,
+            EditorCommand::Backspace,
+            EditorCommand::Delete,
+            EditorCommand::DeleteWordLeft,
+            EditorCommand::DeleteWordRight,
+            EditorCommand::CutLine,
+            EditorCommand::SelectAll,
+            EditorCommand::MoveLinesUp,
+            EditorCommand::MoveLinesDown,
+            EditorCommand::JoinLines,
+            EditorCommand::Indent,
+            EditorCommand::Outdent,
+            EditorCommand::DuplicateLines { below: true },
+            EditorCommand::DuplicateLines { below: false },
+            EditorCommand::ToggleLineComment(String::from("//")),
+            EditorCommand::ToggleBlockComment(String::from("/*"), String::from("*/")),
+            EditorCommand::Move {
+                motion: Motion::PageDown(17),
+                select: true,
+            },
+        ];
+        for command in commands {
+            round_trip(command);
+        }
+        for (_, motion) in Motion::NAMED {
+            round_trip(EditorCommand::Move {
+                motion,
+                select: false,
+            });
+        }
+        for kind in Transform::ALL {
+            round_trip(EditorCommand::Transform {
+                kind,
+                arg: String::from("="),
+                tab_width: 2,
+            });
+        }
+    }
+
+    #[test]
+    fn transform_without_tab_width_uses_default() {
+        // Macro yang direkam sebelum `tab_width` disimpan tetap bisa diputar.
+        let value = json!({ "transform": "tabs-to-spaces" });
+        let Some(EditorCommand::Transform { tab_width, arg, .. }) =
+            EditorCommand::from_json(&value)
+        else {
+            panic!("transform tidak terbaca");
+        };
+        assert_eq!(tab_width, EditorSettings::default().tab_width);
+        assert_eq!(arg, "");
+    }
+
+    #[test]
+    fn unknown_commands_are_rejected() {
+        for value in [
+            json!("teleport"),
+            json!({ "move": "sideways" }),
+            json!({ "transform": "rot13" }),
+            json!({ "block-comment": ["/*"] }),
+            json!(42),
+        ] {
+            assert_eq!(EditorCommand::from_json(&value), None, "{}", value);
+        }
+    }
+
+    #[test]
+    fn recorder_merges_consecutive_typing() {
+        let mut recorder = MacroRecorder::default();
+        recorder.record(EditorCommand::Newline);
+        assert_eq!(recorder.stop(), 0);
+
+        recorder.start();
+        recorder.record(EditorCommand::Type(String::from("ab")));
+        recorder.record(EditorCommand::Type(String::from("c")));
+        recorder.record(EditorCommand::Newline);
+        recorder.record(EditorCommand::Type(String::from("d")));
+        assert_eq!(recorder.stop(), 3);
+        assert_eq!(
+            recorder.last().unwrap(),
+            [
+                EditorCommand::Type(String::from("abc")),
+                EditorCommand::Newline,
+                EditorCommand::Type(String::from("d")),
+            ]
+        );
+    }
+
+    #[test]
+    fn recorded_vim_keys_replay_on_other_lines() {
+        let mut editor = Editor::from_text("let a = 1;\nlet b = 2;\nlet c = 3;");
+        let mut vim = VimState::default();
+        let mut recorder = MacroRecorder::default();
+        recorder.start();
+        for ch in "cwvar\x1bj0".chars() {
+            vim.feed(ch, &mut editor);
+            recorder.record(EditorCommand::Vim(ch.to_string()));
+        }
+        assert_eq!(recorder.stop(), 1);
+        let commands = recorder.last().unwrap().to_vec();
+        assert_eq!(commands, [EditorCommand::Vim(String::from("cwvar\x1bj0"))]);
+
+        // Diputar dari mode Normal, hasilnya sama dengan mengetik ulang tombol yang sama.
+        play(&commands, Playback::Times(2), &mut editor, &mut vim);
+        assert_eq!(editor.text(), "var a = 1;\nvar b = 2;\nvar c = 3;");
+        assert_eq!(vim.status_label(), VimState::default().status_label());
+        editor.undo();
+        assert_eq!(editor.text(), "var a = 1;\nlet b = 2;\nlet c = 3;");
+    }
+
+    #[test]
+    fn play_each_line_as_one_undo_step() {
+        let mut editor = Editor::from_text("a\nb\nc");
+        editor.select_all();
+        let commands = [
+            EditorCommand::Move {
+                motion: Motion::End,
+                select: false,
+            },
+            EditorCommand::Insert(String::from(";")),
+        ];
+        play(
+            &commands,
+            Playback::EachLine,
+            &mut editor,
+            &mut VimState::default(),
+        );
+        assert_eq!(editor.text(), "a;\nb;\nc;");
+        editor.undo();
+        assert_eq!(editor.text(), "a\nb\nc");
+    }
+}
@@ -1,16 +1,41 @@
 use anyhow::Result;
 use std::path::PathBuf;
 mod app;
+mod config;
+mod diff;
 mod editor;
+mod editor_settings;
 mod file_tree;
+mod macros;
 mod manager;
+mod navigation;
 mod signature;
 mod syntax;
 mod tab_manager;
 mod terminal;
+mod terminal_pane;
+mod transform;
 mod ui;
+mod vcs;
+mod vim;
 
 fn main() -> Result<()> {
+    let mut args = std::env::args_os().skip(1);
+    if args.next().is_some_and(|arg| arg == "verify") {
+        let Some(path) = args.next() else {
+            eprintln!("Pemakaian: action-codex verify <path>");
+            std::process::exit(2);
+        };
+        if !signature::verify::run_verify_command(&PathBuf::from(path))? {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    if std::env::args_os().nth(1).is_some_and(|arg| arg == "signatures") {
+        let args = std::env::args_os().skip(2).collect::<Vec<_>>();
+        return signature::manifest::run_signatures_command(&args);
+    }
+
     let startup_files = std::env::args_os()
         .skip(1)
         .map(PathBuf::from)
@@ -1,8 +1,11 @@
 use std::collections::HashSet;
-use std::fs;
+use std::path::Path;
 use std::process::Command;
 
-use anyhow::{Context, Result, anyhow};
+use anyhow::{Result, anyhow};
+
+use super::jobs::Job;
+use super::manifest::{CargoManifest, DependencyEntry, DependencyKind, DependencySpec};
 
 #[derive(Debug, Clone)]
 pub struct CargoPackage {
@@ -11,98 +14,127 @@ pub struct CargoPackage {
     pub description: String,
 }
 
-pub fn search_crate(query: &str) -> Result<String> {
+pub fn search_crate(query: &str) -> Result<Job> {
     if query.trim().is_empty() {
         return Err(anyhow!("keyword pencarian tidak boleh kosong"));
     }
-    run_cargo(["search", query.trim(), "--limit", "10"])
+    spawn_cargo(&["search", query.trim(), "--limit", "10"], &[])
 }
 
-pub fn search_crate_packages(query: &str) -> Result<Vec<CargoPackage>> {
-    let output = search_crate(query)?;
-    Ok(output
+pub fn parse_search_output(output: &str) -> Vec<CargoPackage> {
+    output
         .lines()
         .filter_map(parse_search_line)
-        .collect::<Vec<_>>())
+        .collect::<Vec<_>>()
 }
 
-pub fn add_crate(crate_name: &str) -> Result<String> {
-    if crate_name.trim().is_empty() {
-        return Err(anyhow!("nama crate tidak boleh kosong"));
+pub fn cargo_add_args(spec: &DependencySpec) -> Vec<String> {
+    let mut args = vec![String::from("add")];
+    let name = spec.name.trim();
+    let requirement = spec.requirement.trim();
+    // Tanpa `@versi`, `cargo add` memilih versi terbaru yang kompatibel.
+    args.push(if requirement.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}@{}", name, requirement)
+    });
+    args.extend(kind_flag(spec.kind));
+    if !spec.default_features {
+        args.push(String::from("--no-default-features"));
+    }
+    if !spec.features.is_empty() {
+        args.push(String::from("--features"));
+        args.push(spec.features.join(","));
+    }
+    if spec.optional {
+        args.push(String::from("--optional"));
     }
-    run_cargo(["add", crate_name.trim()])
+    args
 }
 
-pub fn remove_crate(crate_name: &str) -> Result<String> {
-    if crate_name.trim().is_empty() {
+pub fn add_dependency(spec: &DependencySpec, target_args: &[String]) -> Result<Job> {
+    if spec.name.trim().is_empty() {
         return Err(anyhow!("nama crate tidak boleh kosong"));
     }
-    run_cargo(["remove", crate_name.trim()])
+    let args = cargo_add_args(spec);
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+    spawn_cargo(&args, target_args)
+}
+
+/// Isi manifest sebelum dan sesudah dependency ditambahkan, tanpa menulis ke disk.
+pub fn preview_dependency(spec: &DependencySpec, manifest_path: &Path) -> Result<(String, String)> {
+    let mut manifest = CargoManifest::load(manifest_path)?;
+    let before = manifest.text();
+    manifest.insert_dependency(spec)?;
+    Ok((before, manifest.text()))
 }
 
-pub fn installed_packages() -> Result<HashSet<String>> {
-    let content = fs::read_to_string("Cargo.toml").context("gagal membaca Cargo.toml")?;
-    let mut in_dep_block = false;
-    let mut set = HashSet::new();
-
-    for raw in content.lines() {
-        let line = raw.trim();
-        if line.is_empty() || line.starts_with('#') {
-            continue;
-        }
-
-        if line.starts_with('[') && line.ends_with(']') {
-            let section = &line[1..line.len() - 1];
-            in_dep_block = matches!(
-                section,
-                "dependencies"
-                    | "dev-dependencies"
-                    | "build-dependencies"
-                    | "workspace.dependencies"
-            );
-            continue;
-        }
-
-        if !in_dep_block {
-            continue;
-        }
-
-        let Some(eq_idx) = line.find('=') else {
-            continue;
-        };
-        let name = line[..eq_idx].trim();
-        if name.is_empty() || name.contains(' ') || name.starts_with('\"') {
-            continue;
-        }
-        set.insert(name.to_string());
+pub fn upgrade_dependency(
+    dependency: &DependencyEntry,
+    version: &str,
+    target_args: &[String],
+) -> Result<Job> {
+    let mut args = vec![
+        String::from("add"),
+        format!("{}@{}", dependency.crate_name(), version),
+    ];
+    if dependency.package.is_some() {
+        args.push(String::from("--rename"));
+        args.push(dependency.name.clone());
     }
+    args.extend(location_args(dependency));
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+    spawn_cargo(&args, target_args)
+}
 
-    Ok(set)
+pub fn remove_dependency(dependency: &DependencyEntry, target_args: &[String]) -> Result<Job> {
+    let mut args = vec![String::from("remove"), dependency.name.clone()];
+    args.extend(location_args(dependency));
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+    spawn_cargo(&args, target_args)
 }
 
-fn run_cargo<const N: usize>(args: [&str; N]) -> Result<String> {
-    let output = Command::new("cargo")
-        .args(args)
-        .output()
-        .context("gagal menjalankan cargo command")?;
-
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-
-    if output.status.success() {
-        if stdout.trim().is_empty() {
-            Ok(format!("Berhasil: {}", args.join(" ")))
-        } else {
-            Ok(stdout)
-        }
-    } else {
-        let msg = if stderr.trim().is_empty() {
-            stdout
-        } else {
-            stderr
-        };
-        Err(anyhow!("cargo {} gagal: {}", args.join(" "), msg.trim()))
+fn location_args(dependency: &DependencyEntry) -> Vec<String> {
+    let mut args = kind_flag(dependency.kind).into_iter().collect::<Vec<_>>();
+    if let Some(target) = &dependency.target {
+        args.push(String::from("--target"));
+        args.push(target.clone());
     }
+    args
+}
+
+fn kind_flag(kind: DependencyKind) -> Option<String> {
+    match kind {
+        DependencyKind::Dev => Some(String::from("--dev")),
+        DependencyKind::Build => Some(String::from("--build")),
+        DependencyKind::Normal | DependencyKind::Workspace => None,
+    }
+}
+
+pub fn remove_crate(crate_name: &str, target_args: &[String]) -> Result<Job> {
+    if crate_name.trim().is_empty() {
+        return Err(anyhow!("nama crate tidak boleh kosong"));
+    }
+    spawn_cargo(&["remove", crate_name.trim()], target_args)
+}
+
+pub fn installed_packages(manifest_path: &Path) -> Result<HashSet<String>> {
+    let manifest = CargoManifest::load(manifest_path)?;
+    Ok(manifest
+        .dependencies()
+        .into_iter()
+        .map(|dependency| dependency.crate_name().to_string())
+        .collect())
+}
+
+fn spawn_cargo(args: &[&str], target_args: &[String]) -> Result<Job> {
+    let mut command = Command::new("cargo");
+    command.args(args).args(target_args);
+    let label = format!(
+        "cargo {}",
+        args.iter().take(2).copied().collect::<Vec<_>>().join(" ")
+    );
+    Job::spawn(label, command, None)
 }
 
 fn parse_search_line(line: &str) -> Option<CargoPackage> {
@@ -0,0 +1,367 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use super::jobs::{Job, JobEvent, JobStatus};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CargoCommand {
+    Check,
+    Build,
+    Test,
+    Clippy,
+    Run,
+}
+
+impl CargoCommand {
+    pub fn subcommand(self) -> &'static str {
+        match self {
+            Self::Check => "check",
+            Self::Build => "build",
+            Self::Test => "test",
+            Self::Clippy => "clippy",
+            Self::Run => "run",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DiagnosticLevel {
+    Error,
+    Warning,
+    Note,
+}
+
+impl DiagnosticLevel {
+    fn from_cargo(level: &str) -> Option<Self> {
+        match level {
+            "error" | "error: internal compiler error" => Some(Self::Error),
+            "warning" => Some(Self::Warning),
+            "note" | "help" => Some(Self::Note),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Note => "note",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub level: DiagnosticLevel,
+    pub message: String,
+    pub code: Option<String>,
+    pub file: PathBuf,
+    pub line_start: usize,
+    pub column_start: usize,
+    pub line_end: usize,
+    pub column_end: usize,
+}
+
+impl Diagnostic {
+    pub fn summary(&self) -> String {
+        let code = self
+            .code
+            .as_ref()
+            .map(|code| format!("[{}] ", code))
+            .unwrap_or_default();
+        format!(
+            "{}:{}:{} {}: {}{}",
+            self.file.display(),
+            self.line_start,
+            self.column_start,
+            self.level.label(),
+            code,
+            self.message
+        )
+    }
+
+    /// `file` sudah dikanonikalisasi saat parsing; `path` harus hasil [`canonical_path`] juga.
+    pub fn is_for_path(&self, path: &Path) -> bool {
+        self.file == path
+    }
+}
+
+/// Path kanonik untuk mencocokkan diagnostik; jatuh ke path asli bila file tidak ada.
+pub fn canonical_path(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[derive(Debug)]
+pub enum RunnerEvent {
+    Output(String),
+    Diagnostic(Diagnostic),
+    Finished { success: bool, cancelled: bool },
+}
+
+pub struct CargoRun {
+    command: CargoCommand,
+    /// `span.file_name` dari rustc relatif terhadap root workspace, bukan folder manifest.
+    workspace_root: PathBuf,
+    job: Job,
+}
+
+impl CargoRun {
+    pub fn command(&self) -> CargoCommand {
+        self.command
+    }
+
+    pub fn status(&self) -> &JobStatus {
+        self.job.status()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.job.is_finished()
+    }
+
+    /// Kill proses cargo (mis. `cargo run` yang tidak pernah selesai).
+    pub fn cancel(&mut self) {
+        self.job.cancel();
+    }
+
+    pub fn poll(&mut self) -> Vec<RunnerEvent> {
+        let mut events = Vec::new();
+        for event in self.job.poll() {
+            match event {
+                JobEvent::Output(line) => events.push(RunnerEvent::Output(line)),
+                JobEvent::Stdout(line) => {
+                    parse_stdout_line(&self.workspace_root, line, &mut events)
+                }
+                JobEvent::Finished(outcome) => events.push(RunnerEvent::Finished {
+                    success: outcome.success,
+                    cancelled: outcome.cancelled,
+                }),
+            }
+        }
+        events
+    }
+}
+
+pub fn spawn_cargo_command(command: CargoCommand, target_args: &[String]) -> Result<CargoRun> {
+    let workdir = match target_args
+        .iter()
+        .position(|arg| arg == "--manifest-path")
+        .and_then(|idx| target_args.get(idx + 1))
+        .and_then(|manifest| Path::new(manifest).parent())
+    {
+        Some(root) => root.to_path_buf(),
+        None => std::env::current_dir().context("gagal membaca direktori kerja")?,
+    };
+    let mut cargo = Command::new("cargo");
+    cargo
+        .arg(command.subcommand())
+        .arg("--message-format=json")
+        .args(target_args)
+        .current_dir(&workdir);
+    let job = Job::spawn_streaming(format!("cargo {}", command.subcommand()), cargo)?;
+
+    Ok(CargoRun {
+        command,
+        workspace_root: workspace_root(&workdir).unwrap_or(workdir),
+        job,
+    })
+}
+
+/// `workspace_root` dari `cargo metadata` untuk manifest di `dir`.
+fn workspace_root(dir: &Path) -> Option<PathBuf> {
+    let output = Command::new("cargo")
+        .args([
+            "metadata",
+            "--no-deps",
+            "--format-version",
+            "1",
+            "--offline",
+        ])
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    serde_json::from_slice::<Value>(&output.stdout)
+        .ok()?
+        .get("workspace_root")?
+        .as_str()
+        .map(PathBuf::from)
+}
+
+fn parse_stdout_line(workspace_root: &Path, line: String, events: &mut Vec<RunnerEvent>) {
+    let Ok(value) = serde_json::from_str::<Value>(&line) else {
+        events.push(RunnerEvent::Output(line));
+        return;
+    };
+
+    match value.get("reason").and_then(Value::as_str) {
+        Some("compiler-message") => {
+            let Some(message) = value.get("message") else {
+                return;
+            };
+            if let Some(rendered) = message.get("rendered").and_then(Value::as_str) {
+                events.extend(
+                    rendered
+                        .trim_end()
+                        .lines()
+                        .map(|rendered_line| RunnerEvent::Output(rendered_line.to_string())),
+                );
+            }
+            if let Some(diagnostic) = parse_compiler_message(message, workspace_root) {
+                events.push(RunnerEvent::Diagnostic(diagnostic));
+            }
+        }
+        Some("build-finished") => {
+            let ok = value.get("success").and_then(Value::as_bool) == Some(true);
+            let text = if ok {
+                "Build selesai tanpa error"
+            } else {
+                "Build selesai dengan error"
+            };
+            events.push(RunnerEvent::Output(text.to_string()));
+        }
+        Some(_) => {}
+        None => events.push(RunnerEvent::Output(line)),
+    }
+}
+
+fn parse_compiler_message(message: &Value, workspace_root: &Path) -> Option<Diagnostic> {
+    let level = DiagnosticLevel::from_cargo(message.get("level")?.as_str()?)?;
+    let text = message.get("message")?.as_str()?.to_string();
+    let spans = message.get("spans")?.as_array()?;
+    let span = spans
+        .iter()
+        .find(|span| span.get("is_primary").and_then(Value::as_bool) == Some(true))
+        .or_else(|| spans.first())?;
+
+    let file_name = span.get("file_name")?.as_str()?;
+    let number = |key: &str| {
+        span.get(key)
+            .and_then(Value::as_u64)
+            .map(|value| value as usize)
+            .unwrap_or(1)
+    };
+    let code = message
+        .get("code")
+        .and_then(|code| code.get("code"))
+        .and_then(Value::as_str)
+        .map(str::to_string);
+
+    Some(Diagnostic {
+        level,
+        message: text,
+        code,
+        file: canonical_path(&workspace_root.join(file_name)),
+        line_start: number("line_start"),
+        column_start: number("column_start"),
+        line_end: number("line_end"),
+        column_end: number("column_end"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message_line(file_name: &str) -> String {
+        serde_json::json!({
+            "reason": "compiler-message",
+            "message": {
+                "level": "error",
+                "message": "mismatched types",
+                "code": { "code": "E0308" },
+                "rendered": "error[E0308]: mismatched types\n --> src/lib.rs:3:5\n",
+                "spans": [
+                    { "file_name": "src/other.rs", "is_primary": false, "line_start": 9,
+                      "column_start": 1, "line_end": 9, "column_end": 2 },
+                    { "file_name": file_name, "is_primary": true, "line_start": 3,
+                      "column_start": 5, "line_end": 3, "column_end": 12 }
+                ]
+            }
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn compiler_message_uses_primary_span_relative_to_workspace_root() {
+        let root = std::env::temp_dir().join(format!("ac-runner-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("member/src")).unwrap();
+        std::fs::write(root.join("member/src/lib.rs"), "").unwrap();
+
+        let mut events = Vec::new();
+        parse_stdout_line(&root, message_line("member/src/lib.rs"), &mut events);
+        let diagnostics = events
+            .iter()
+            .filter_map(|event| match event {
+                RunnerEvent::Diagnostic(diagnostic) => Some(diagnostic),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(diagnostics.len(), 1);
+        let diagnostic = diagnostics[0];
+        assert_eq!(diagnostic.level, DiagnosticLevel::Error);
+        assert_eq!(diagnostic.code.as_deref(), Some("E0308"));
+        assert_eq!((diagnostic.line_start, diagnostic.column_start), (3, 5));
+        assert_eq!((diagnostic.line_end, diagnostic.column_end), (3, 12));
+        assert!(diagnostic.is_for_path(&canonical_path(&root.join("member/src/lib.rs"))));
+        assert!(!diagnostic.is_for_path(&canonical_path(&root.join("member/src/other.rs"))));
+        assert!(events.iter().any(|event| matches!(
+            event,
+            RunnerEvent::Output(line) if line.starts_with("error[E0308]")
+        )));
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn unknown_levels_and_spanless_messages_are_not_diagnostics() {
+        let root = Path::new("/nonexistent");
+        let spanless = serde_json::json!({
+            "reason": "compiler-message",
+            "message": { "level": "warning", "message": "2 warnings emitted", "spans": [] }
+        });
+        let mut events = Vec::new();
+        parse_stdout_line(root, spanless.to_string(), &mut events);
+        let value = serde_json::json!({
+            "reason": "compiler-message",
+            "message": { "level": "failure-note", "message": "x", "spans": [
+                { "file_name": "src/lib.rs", "is_primary": true }
+            ] }
+        });
+        parse_stdout_line(root, value.to_string(), &mut events);
+        assert!(
+            !events
+                .iter()
+                .any(|event| matches!(event, RunnerEvent::Diagnostic(_)))
+        );
+    }
+
+    #[test]
+    fn non_json_and_build_finished_lines_become_output() {
+        let root = Path::new("/nonexistent");
+        let mut events = Vec::new();
+        parse_stdout_line(root, String::from("plain text"), &mut events);
+        parse_stdout_line(
+            root,
+            String::from(r#"{"reason":"build-finished","success":false}"#),
+            &mut events,
+        );
+        parse_stdout_line(
+            root,
+            String::from(r#"{"reason":"compiler-artifact"}"#),
+            &mut events,
+        );
+        let lines = events
+            .iter()
+            .map(|event| match event {
+                RunnerEvent::Output(line) => line.as_str(),
+                _ => panic!("unexpected event {:?}", event),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(lines, ["plain text", "Build selesai dengan error"]);
+    }
+}
@@ -0,0 +1,251 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use super::lockfile::Lockfile;
+use super::manifest::{DependencyEntry, DependencyKind};
+use super::registry_index::{self, compare_versions, is_prerelease};
+use super::workspace_manager::{CargoWorkspace, WorkspacePackage};
+
+#[derive(Debug, Clone)]
+pub struct ReportEntry {
+    pub package: String,
+    pub dependency: DependencyEntry,
+    pub locked: Vec<String>,
+    pub latest: Option<String>,
+    pub yanked: bool,
+    pub duplicate: bool,
+    pub unused: bool,
+}
+
+impl ReportEntry {
+    pub fn outdated(&self) -> bool {
+        match (
+            &self.latest,
+            self.locked.iter().max_by(|a, b| compare_versions(a, b)),
+        ) {
+            (Some(latest), Some(locked)) => compare_versions(latest, locked).is_gt(),
+            _ => false,
+        }
+    }
+
+    pub fn flags(&self) -> Vec<&'static str> {
+        let mut flags = Vec::new();
+        if self.outdated() {
+            flags.push("outdated");
+        }
+        if self.yanked {
+            flags.push("yanked");
+        }
+        if self.duplicate {
+            flags.push("duplikat");
+        }
+        if self.unused {
+            flags.push("tidak dipakai?");
+        }
+        flags
+    }
+}
+
+/// Bandingkan dependency langsung tiap member dengan `Cargo.lock` dan cache index lokal.
+pub fn build_report(workspace: &CargoWorkspace, package: Option<&str>) -> Result<Vec<ReportEntry>> {
+    let lock_path = workspace
+        .root_manifest
+        .parent()
+        .map(|root| root.join("Cargo.lock"))
+        .unwrap_or_else(|| PathBuf::from("Cargo.lock"));
+    let lockfile = Lockfile::load(&lock_path).unwrap_or_default();
+
+    let mut entries = Vec::new();
+    for member in workspace
+        .packages
+        .iter()
+        .filter(|member| package.is_none_or(|name| member.name == name))
+    {
+        let sources = SourceScan::new(member);
+        for dependency in &member.dependencies {
+            if dependency.kind == DependencyKind::Workspace {
+                continue;
+            }
+            let crate_name = dependency.crate_name();
+            let locked_packages = lockfile.versions_of(crate_name);
+            let locked = locked_packages
+                .iter()
+                .map(|locked| locked.version.clone())
+                .collect::<Vec<_>>();
+
+            let cached = if locked_packages.iter().any(|locked| locked.is_registry()) {
+                registry_index::cached_versions(crate_name).unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+            let latest = cached
+                .iter()
+                .find(|version| !version.yanked && !is_prerelease(&version.version))
+                .map(|version| version.version.clone());
+            let yanked = cached
+                .iter()
+                .any(|version| version.yanked && locked.contains(&version.version));
+
+            entries.push(ReportEntry {
+                package: member.name.clone(),
+                dependency: dependency.clone(),
+                duplicate: lockfile.has_duplicates(crate_name),
+                unused: !sources.references(dependency),
+                locked,
+                latest,
+                yanked,
+            });
+        }
+    }
+    Ok(entries)
+}
+
+struct SourceScan {
+    library: String,
+    build_script: String,
+    dev: String,
+}
+
+impl SourceScan {
+    fn new(member: &WorkspacePackage) -> Self {
+        let root = member
+            .manifest_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let mut library = String::new();
+        read_rust_sources(&root.join("src"), &mut library);
+        let mut build_script = String::new();
+        read_rust_sources(&root.join("build.rs"), &mut build_script);
+        let mut dev = library.clone();
+        for dir in ["tests", "examples", "benches"] {
+            read_rust_sources(&root.join(dir), &mut dev);
+        }
+        Self {
+            library,
+            build_script,
+            dev,
+        }
+    }
+
+    fn references(&self, dependency: &DependencyEntry) -> bool {
+        let ident = dependency.name.replace('-', "_");
+        let sources = match dependency.kind {
+            DependencyKind::Build => &self.build_script,
+            DependencyKind::Dev => &self.dev,
+            DependencyKind::Normal | DependencyKind::Workspace => &self.library,
+        };
+        mentions_crate(sources, &ident)
+    }
+}
+
+fn read_rust_sources(path: &Path, out: &mut String) {
+    if path.is_file() {
+        if path.extension().is_some_and(|ext| ext == "rs")
+            && let Ok(content) = fs::read_to_string(path)
+        {
+            out.push_str(&content);
+            out.push('\n');
+        }
+        return;
+    }
+    let Ok(entries) = fs::read_dir(path) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        read_rust_sources(&entry.path(), out);
+    }
+}
+
+// Crate dianggap dipakai bila muncul sebagai path (`ident::`), di `use ident`,
+// atau `extern crate ident`.
+fn mentions_crate(sources: &str, ident: &str) -> bool {
+    let is_ident_char = |ch: char| ch.is_alphanumeric() || ch == '_';
+    sources.match_indices(ident).any(|(start, _)| {
+        let before = &sources[..start];
+        let after = &sources[start + ident.len()..];
+        if before.chars().next_back().is_some_and(is_ident_char)
+            || after.chars().next().is_some_and(is_ident_char)
+        {
+            return false;
+        }
+        let before = before.trim_end();
+        after.starts_with("::")
+            || before.ends_with("use")
+            || before.ends_with("extern crate")
+            || before.ends_with("use ::")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dependency(name: &str, package: Option<&str>, kind: DependencyKind) -> DependencyEntry {
+        DependencyEntry {
+            name: name.to_string(),
+            package: package.map(str::to_string),
+            kind,
+            target: None,
+            requirement: String::from("1"),
+        }
+    }
+
+    fn entry(locked: &[&str], latest: Option<&str>) -> ReportEntry {
+        ReportEntry {
+            package: String::from("app"),
+            dependency: dependency("foo", None, DependencyKind::Normal),
+            locked: locked.iter().map(|version| version.to_string()).collect(),
+            latest: latest.map(str::to_string),
+            yanked: false,
+            duplicate: false,
+            unused: false,
+        }
+    }
+
+    #[test]
+    fn crate_mentions_require_path_or_use() {
+        assert!(mentions_crate("use foo;", "foo"));
+        assert!(mentions_crate("use foo::{bar, baz};", "foo"));
+        assert!(mentions_crate("use ::foo::bar;", "foo"));
+        assert!(mentions_crate("extern crate foo;", "foo"));
+        assert!(mentions_crate("let x = foo::bar();", "foo"));
+        assert!(!mentions_crate("let x = foobar::baz();", "foo"));
+        assert!(!mentions_crate("let x = my_foo::baz();", "foo"));
+        assert!(!mentions_crate("let foo = 1;", "foo"));
+        assert!(!mentions_crate("", "foo"));
+    }
+
+    #[test]
+    fn references_use_underscored_key_and_dependency_kind() {
+        let scan = SourceScan {
+            library: String::from("use serde_json::Value;\nfn f() { tok::spawn(); }\n"),
+            build_script: String::from("fn main() { cc::Build::new(); }\n"),
+            dev: String::from("use pretty_assertions::assert_eq;\n"),
+        };
+        assert!(scan.references(&dependency("serde-json", None, DependencyKind::Normal)));
+        // Dependency yang di-rename dipakai lewat nama key-nya, bukan nama package.
+        assert!(scan.references(&dependency("tok", Some("tokio"), DependencyKind::Normal)));
+        assert!(!scan.references(&dependency("tokio", None, DependencyKind::Normal)));
+        assert!(scan.references(&dependency("cc", None, DependencyKind::Build)));
+        assert!(!scan.references(&dependency("cc", None, DependencyKind::Normal)));
+        assert!(scan.references(&dependency("pretty-assertions", None, DependencyKind::Dev)));
+        assert!(!scan.references(&dependency(
+            "pretty-assertions",
+            None,
+            DependencyKind::Normal
+        )));
+    }
+
+    #[test]
+    fn outdated_compares_latest_with_highest_locked_version() {
+        assert!(entry(&["1.9.0"], Some("1.10.0")).outdated());
+        assert!(!entry(&["1.2.0", "1.10.0"], Some("1.10.0")).outdated());
+        assert!(!entry(&["2.0.0"], Some("1.10.0")).outdated());
+        assert!(!entry(&["1.0.0"], None).outdated());
+        assert!(!entry(&[], Some("1.0.0")).outdated());
+        assert_eq!(entry(&["1.0.0"], Some("1.0.1")).flags(), ["outdated"]);
+    }
+}
@@ -0,0 +1,482 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use toml_edit::{DocumentMut, Item};
+
+use super::lockfile::{LockPackage, Lockfile};
+use super::workspace_manager::CargoWorkspace;
+
+#[derive(Debug, Clone)]
+pub struct DependencyTreeEntry {
+    key: Vec<usize>,
+    depth: usize,
+    has_children: bool,
+    cycle: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct PackageInfo {
+    pub source: String,
+    pub license: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct DependencyTree {
+    lockfile: Lockfile,
+    edges: Vec<Vec<usize>>,
+    reverse: Vec<Vec<usize>>,
+    roots: Vec<usize>,
+    inverse_of: Option<usize>,
+    expanded: HashSet<Vec<usize>>,
+    entries: Vec<DependencyTreeEntry>,
+    selected: usize,
+    scroll: usize,
+    local_manifests: Vec<(String, PathBuf)>,
+    info: Option<PackageInfo>,
+}
+
+impl DependencyTree {
+    pub fn load(workspace: &CargoWorkspace) -> Result<Self> {
+        let root_dir = workspace
+            .root_manifest
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let lockfile = Lockfile::load(&root_dir.join("Cargo.lock"))
+            .map_err(|err| anyhow!("{} (jalankan cargo generate-lockfile)", err))?;
+
+        let edges = (0..lockfile.packages.len())
+            .map(|index| lockfile.dependencies_of(index))
+            .collect::<Vec<_>>();
+        let mut reverse = vec![Vec::new(); edges.len()];
+        for (from, targets) in edges.iter().enumerate() {
+            for to in targets {
+                reverse[*to].push(from);
+            }
+        }
+        let roots = lockfile
+            .packages
+            .iter()
+            .enumerate()
+            .filter(|(_, package)| workspace.package(&package.name).is_some())
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        let local_manifests = workspace
+            .packages
+            .iter()
+            .map(|package| (package.name.clone(), package.manifest_path.clone()))
+            .collect();
+
+        let mut tree = Self {
+            lockfile,
+            edges,
+            reverse,
+            expanded: roots.iter().map(|root| vec![*root]).collect(),
+            roots,
+            inverse_of: None,
+            entries: Vec::new(),
+            selected: 0,
+            scroll: 0,
+            local_manifests,
+            info: None,
+        };
+        tree.rebuild();
+        Ok(tree)
+    }
+
+    pub fn entries(&self) -> &[DependencyTreeEntry] {
+        &self.entries
+    }
+
+    pub fn package(&self, entry: &DependencyTreeEntry) -> &LockPackage {
+        &self.lockfile.packages[entry.index()]
+    }
+
+    pub fn selected_index(&self) -> usize {
+        self.selected
+    }
+
+    pub fn selected_package(&self) -> Option<&LockPackage> {
+        self.entries
+            .get(self.selected)
+            .map(|entry| self.package(entry))
+    }
+
+    pub fn info(&self) -> Option<&PackageInfo> {
+        self.info.as_ref()
+    }
+
+    pub fn scroll(&self) -> usize {
+        self.scroll
+    }
+
+    pub fn is_duplicate(&self, entry: &DependencyTreeEntry) -> bool {
+        self.lockfile.has_duplicates(&self.package(entry).name)
+    }
+
+    pub fn inverse_target(&self) -> Option<&LockPackage> {
+        self.inverse_of.map(|index| &self.lockfile.packages[index])
+    }
+
+    pub fn ensure_visible(&mut self, viewport_height: usize) {
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        } else if viewport_height > 0 && self.selected >= self.scroll + viewport_height {
+            self.scroll = self.selected + 1 - viewport_height;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        if self.selected > 0 {
+            self.selected -= 1;
+            self.load_info();
+        }
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected + 1 < self.entries.len() {
+            self.selected += 1;
+            self.load_info();
+        }
+    }
+
+    pub fn toggle_selected(&mut self) {
+        let Some(entry) = self.entries.get(self.selected) else {
+            return;
+        };
+        if !entry.has_children {
+            return;
+        }
+        if !self.expanded.remove(&entry.key) {
+            self.expanded.insert(entry.key.clone());
+        }
+        self.rebuild();
+    }
+
+    pub fn collapse_selected(&mut self) {
+        let Some(entry) = self.entries.get(self.selected) else {
+            return;
+        };
+        if self.expanded.remove(&entry.key) {
+            self.rebuild();
+            return;
+        }
+        // Sudah tertutup: lompat ke parent seperti explorer.
+        let parent = entry.key[..entry.key.len().saturating_sub(1)].to_vec();
+        if let Some(position) = self.entries.iter().position(|entry| entry.key == parent) {
+            self.selected = position;
+            self.load_info();
+        }
+    }
+
+    /// Balik tree: akar menjadi package terpilih, anak-anaknya package yang memakainya.
+    pub fn show_dependents_of_selected(&mut self) {
+        let Some(index) = self
+            .entries
+            .get(self.selected)
+            .map(DependencyTreeEntry::index)
+        else {
+            return;
+        };
+        self.inverse_of = Some(index);
+        self.expanded = HashSet::from([vec![index]]);
+        self.selected = 0;
+        self.scroll = 0;
+        self.rebuild();
+    }
+
+    pub fn show_dependencies(&mut self) {
+        let previous = self.inverse_of.take();
+        self.expanded = self.roots.iter().map(|root| vec![*root]).collect();
+        self.selected = 0;
+        self.scroll = 0;
+        self.rebuild();
+        if let Some(index) = previous
+            && let Some(position) = self.entries.iter().position(|entry| entry.index() == index)
+        {
+            self.selected = position;
+            self.load_info();
+        }
+    }
+
+    fn rebuild(&mut self) {
+        let roots = match self.inverse_of {
+            Some(index) => vec![index],
+            None => self.roots.clone(),
+        };
+        let mut entries = Vec::new();
+        for root in roots {
+            self.walk(vec![root], &mut entries);
+        }
+        self.entries = entries;
+        self.selected = self.selected.min(self.entries.len().saturating_sub(1));
+        self.scroll = self.scroll.min(self.selected);
+        self.load_info();
+    }
+
+    fn walk(&self, key: Vec<usize>, output: &mut Vec<DependencyTreeEntry>) {
+        let index = *key.last().unwrap_or(&0);
+        let cycle = key[..key.len() - 1].contains(&index);
+        let children = self.children(index);
+        let expanded = !cycle && self.expanded.contains(&key);
+        output.push(DependencyTreeEntry {
+            depth: key.len() - 1,
+            has_children: !children.is_empty() && !cycle,
+            cycle,
+            key: key.clone(),
+        });
+        if expanded {
+            for child in children {
+                let mut child_key = key.clone();
+                child_key.push(child);
+                self.walk(child_key, output);
+            }
+        }
+    }
+
+    fn children(&self, index: usize) -> Vec<usize> {
+        let mut children = if self.inverse_of.is_some() {
+            self.reverse[index].clone()
+        } else {
+            self.edges[index].clone()
+        };
+        children.sort_by(|a, b| {
+            let (a, b) = (&self.lockfile.packages[*a], &self.lockfile.packages[*b]);
+            a.name.cmp(&b.name).then_with(|| a.version.cmp(&b.version))
+        });
+        children
+    }
+
+    pub fn is_expanded(&self, entry: &DependencyTreeEntry) -> bool {
+        self.expanded.contains(&entry.key)
+    }
+
+    fn load_info(&mut self) {
+        self.info = self.selected_package().map(|package| {
+            let local = self
+                .local_manifests
+                .iter()
+                .find(|(name, _)| *name == package.name)
+                .map(|(_, path)| path.clone());
+            let source = match (&package.source, &local) {
+                (Some(source), _) => source.clone(),
+                (None, Some(path)) => format!("path {}", path.display()),
+                (None, None) => String::from("path"),
+            };
+            let manifest = local.or_else(|| registry_manifest(package));
+            PackageInfo {
+                source,
+                license: manifest.as_deref().and_then(read_license),
+            }
+        });
+    }
+}
+
+impl DependencyTreeEntry {
+    pub fn index(&self) -> usize {
+        *self.key.last().unwrap_or(&0)
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn has_children(&self) -> bool {
+        self.has_children
+    }
+
+    pub fn is_cycle(&self) -> bool {
+        self.cycle
+    }
+}
+
+// Sumber crate registry yang sudah diunduh ada di `~/.cargo/registry/src/*/nama-versi`.
+fn registry_manifest(package: &LockPackage) -> Option<PathBuf> {
+    if !package.is_registry() {
+        return None;
+    }
+    let cargo_home = std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".cargo")))?;
+    let dir_name = format!("{}-{}", package.name, package.version);
+    fs::read_dir(cargo_home.join("registry").join("src"))
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path().join(&dir_name).join("Cargo.toml"))
+        .find(|path| path.is_file())
+}
+
+fn read_license(manifest: &Path) -> Option<String> {
+    let doc = fs::read_to_string(manifest)
+        .ok()?
+        .parse::<DocumentMut>()
+        .ok()?;
+    let package = doc.get("package")?;
+    let license = package.get("license");
+    if license
+        .and_then(|license| license.get("workspace"))
+        .and_then(Item::as_bool)
+        == Some(true)
+    {
+        return Some(String::from("diwarisi dari workspace"));
+    }
+    license
+        .and_then(Item::as_str)
+        .map(str::to_string)
+        .or_else(|| {
+            package
+                .get("license-file")
+                .and_then(Item::as_str)
+                .map(|file| format!("file {}", file))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manager::workspace_manager::WorkspacePackage;
+
+    // `app` dan `tool` memakai dua versi `rand` yang sama-sama bergantung pada `libc`.
+    const LOCK: &str = r#"version = 3
+
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = [
+ "log",
+ "rand 0.8.5",
+]
+
+[[package]]
+name = "libc"
+version = "0.2.150"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "log"
+version = "0.4.20"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "rand"
+version = "0.7.3"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+dependencies = [
+ "libc",
+]
+
+[[package]]
+name = "rand"
+version = "0.8.5"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+dependencies = [
+ "libc",
+]
+
+[[package]]
+name = "tool"
+version = "0.1.0"
+dependencies = [
+ "rand 0.7.3",
+]
+"#;
+
+    fn load(name: &str) -> DependencyTree {
+        let root =
+            std::env::temp_dir().join(format!("action-codex-tree-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("Cargo.lock"), LOCK).unwrap();
+        let member = |name: &str| WorkspacePackage {
+            name: name.to_string(),
+            manifest_path: root.join(name).join("Cargo.toml"),
+            dependencies: Vec::new(),
+        };
+        let workspace = CargoWorkspace {
+            root_manifest: root.join("Cargo.toml"),
+            packages: vec![member("app"), member("tool")],
+        };
+        let tree = DependencyTree::load(&workspace);
+        let _ = fs::remove_dir_all(&root);
+        tree.unwrap()
+    }
+
+    fn rows(tree: &DependencyTree) -> Vec<String> {
+        tree.entries()
+            .iter()
+            .map(|entry| {
+                let package = tree.package(entry);
+                format!(
+                    "{}{} {}",
+                    "  ".repeat(entry.depth()),
+                    package.name,
+                    package.version
+                )
+            })
+            .collect()
+    }
+
+    fn select(tree: &mut DependencyTree, row: &str) {
+        let position = rows(tree).iter().position(|line| line == row).unwrap();
+        while tree.selected_index() > position {
+            tree.move_up();
+        }
+        while tree.selected_index() < position {
+            tree.move_down();
+        }
+    }
+
+    #[test]
+    fn members_are_expanded_roots_and_duplicates_are_flagged() {
+        let tree = load("roots");
+        assert_eq!(
+            rows(&tree),
+            [
+                "app 0.1.0",
+                "  log 0.4.20",
+                "  rand 0.8.5",
+                "tool 0.1.0",
+                "  rand 0.7.3",
+            ]
+        );
+        let duplicates = tree
+            .entries()
+            .iter()
+            .filter(|entry| tree.is_duplicate(entry))
+            .map(|entry| tree.package(entry).version.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(duplicates, ["0.8.5", "0.7.3"]);
+    }
+
+    #[test]
+    fn dependents_view_answers_why_a_crate_is_here() {
+        let mut tree = load("inverse");
+        select(&mut tree, "  rand 0.8.5");
+        tree.toggle_selected();
+        select(&mut tree, "    libc 0.2.150");
+
+        tree.show_dependents_of_selected();
+        assert_eq!(tree.inverse_target().map(|p| p.name.as_str()), Some("libc"));
+        assert_eq!(
+            rows(&tree),
+            ["libc 0.2.150", "  rand 0.7.3", "  rand 0.8.5"]
+        );
+        select(&mut tree, "  rand 0.7.3");
+        tree.toggle_selected();
+        assert_eq!(
+            rows(&tree),
+            [
+                "libc 0.2.150",
+                "  rand 0.7.3",
+                "    tool 0.1.0",
+                "  rand 0.8.5",
+            ]
+        );
+
+        // Kembali ke tree biasa; libc tidak terlihat lagi karena rand tertutup.
+        tree.show_dependencies();
+        assert!(tree.inverse_target().is_none());
+        assert_eq!(rows(&tree).len(), 5);
+    }
+}
@@ -0,0 +1,55 @@
+use anyhow::Result;
+use serde_json::{Value, json};
+
+use crate::config;
+
+const SETTINGS_FILE: &str = "formatter.json";
+
+#[derive(Debug, Clone, Default)]
+pub struct FormatSettings {
+    pub format_on_save: bool,
+    /// Perintah formatter eksternal per bahasa, mis. `("markdown", ["prettier", ...])`.
+    pub external: Vec<(String, Vec<String>)>,
+}
+
+impl FormatSettings {
+    pub fn load() -> Self {
+        let mut settings = Self::default();
+        let Some(value) = config::load_json(SETTINGS_FILE) else {
+            return settings;
+        };
+        if let Some(enabled) = value["format_on_save"].as_bool() {
+            settings.format_on_save = enabled;
+        }
+        if let Some(external) = value["external"].as_object() {
+            for (language, command) in external {
+                // Bentuk string dipecah per spasi, bentuk array dipakai apa adanya.
+                let command = match command {
+                    Value::String(command) => {
+                        command.split_whitespace().map(String::from).collect()
+                    }
+                    Value::Array(args) => args
+                        .iter()
+                        .filter_map(|arg| arg.as_str().map(String::from))
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                settings.external.push((language.to_lowercase(), command));
+            }
+        }
+        settings
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let external = self
+            .external
+            .iter()
+            .map(|(language, command)| (language.clone(), json!(command)))
+            .collect::<serde_json::Map<_, _>>();
+        let value = json!({
+            "format_on_save": self.format_on_save,
+            "external": external,
+        });
+        config::save_json(SETTINGS_FILE, &value)
+    }
+}
@@ -0,0 +1,469 @@
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Result, anyhow};
+use toml_edit::{DocumentMut, Item, RawString, Table, Value};
+
+use super::format_settings::FormatSettings;
+use super::jobs::Job;
+use super::rust_formatter::{self, FormatRange, RustfmtContext};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Rust,
+    Toml,
+    Json,
+    Markdown,
+    Shell,
+    C,
+}
+
+impl Language {
+    pub fn all() -> [Self; 6] {
+        [
+            Self::Rust,
+            Self::Toml,
+            Self::Json,
+            Self::Markdown,
+            Self::Shell,
+            Self::C,
+        ]
+    }
+
+    /// Tanpa path (buffer baru) dianggap Rust, sama seperti sebelum ada registry.
+    pub fn from_path(path: Option<&Path>) -> Option<Self> {
+        let Some(path) = path else {
+            return Some(Self::Rust);
+        };
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "rs" => Some(Self::Rust),
+            "toml" => Some(Self::Toml),
+            "json" => Some(Self::Json),
+            "md" | "markdown" => Some(Self::Markdown),
+            "sh" | "bash" => Some(Self::Shell),
+            "c" | "h" => Some(Self::C),
+            _ => None,
+        }
+    }
+
+    /// Nama kunci di `formatter.json`.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Rust => "rust",
+            Self::Toml => "toml",
+            Self::Json => "json",
+            Self::Markdown => "markdown",
+            Self::Shell => "shell",
+            Self::C => "c",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Rust => "Rust",
+            Self::Toml => "TOML",
+            Self::Json => "JSON",
+            Self::Markdown => "Markdown",
+            Self::Shell => "Shell",
+            Self::C => "C",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Formatter {
+    Rustfmt,
+    Json,
+    Toml,
+    /// Program eksternal stdin→stdout; `{file}` di argumen diganti path file.
+    External(Vec<String>),
+}
+
+impl Formatter {
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Rustfmt => String::from("rustfmt (bawaan)"),
+            Self::Json => String::from("JSON in-process (bawaan)"),
+            Self::Toml => String::from("TOML in-process (bawaan)"),
+            Self::External(command) => command.join(" "),
+        }
+    }
+
+    /// Hanya rustfmt yang bisa memformat potongan baris; lainnya selalu seluruh file.
+    pub fn supports_selection(&self) -> bool {
+        matches!(self, Self::Rustfmt)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FormatterRegistry {
+    entries: Vec<(Language, Formatter)>,
+}
+
+impl FormatterRegistry {
+    /// Formatter bawaan lebih dulu; perintah eksternal dari pengaturan menimpanya.
+    pub fn new(settings: &FormatSettings) -> Self {
+        let mut entries = vec![
+            (Language::Rust, Formatter::Rustfmt),
+            (Language::Toml, Formatter::Toml),
+            (Language::Json, Formatter::Json),
+        ];
+        for (key, command) in &settings.external {
+            let Some(language) = Language::all()
+                .into_iter()
+                .find(|language| language.key() == key)
+            else {
+                continue;
+            };
+            if command.is_empty() {
+                continue;
+            }
+            entries.retain(|(existing, _)| *existing != language);
+            entries.push((language, Formatter::External(command.clone())));
+        }
+        Self { entries }
+    }
+
+    pub fn formatter_for(&self, path: Option<&Path>) -> Option<&Formatter> {
+        let language = Language::from_path(path)?;
+        self.entries
+            .iter()
+            .find(|(existing, _)| *existing == language)
+            .map(|(_, formatter)| formatter)
+    }
+
+    /// Seleksi diabaikan bila formatter tidak mendukungnya.
+    pub fn effective_range(&self, path: Option<&Path>, range: FormatRange) -> FormatRange {
+        match self.formatter_for(path) {
+            Some(formatter) if formatter.supports_selection() => range,
+            _ => FormatRange::Whole,
+        }
+    }
+
+    pub fn spawn(&self, lines: &[String], range: &FormatRange, path: Option<&Path>) -> Result<Job> {
+        let formatter = self.formatter_for(path).ok_or_else(|| {
+            match path.and_then(|path| Language::from_path(Some(path))) {
+                Some(language) => anyhow!(
+                    "belum ada formatter untuk {}; atur di formatter.json",
+                    language.label()
+                ),
+                None => anyhow!("tidak ada formatter untuk file ini"),
+            }
+        })?;
+        let mut source = lines.join("\n");
+        source.push('\n');
+        match formatter {
+            Formatter::Rustfmt => rust_formatter::spawn_rustfmt(lines, range, path),
+            Formatter::Json => Ok(Job::run_in_thread(String::from("json"), move || {
+                format_json(&source)
+            })),
+            Formatter::Toml => Ok(Job::run_in_thread(String::from("toml"), move || {
+                format_toml(&source)
+            })),
+            Formatter::External(command) => {
+                let file = path.map(|path| path.to_string_lossy().into_owned());
+                let args = command[1..]
+                    .iter()
+                    .map(|arg| match &file {
+                        Some(file) => arg.replace("{file}", file),
+                        None => arg.clone(),
+                    })
+                    .collect::<Vec<_>>();
+                let mut process = Command::new(&command[0]);
+                process
+                    .args(args)
+                    .current_dir(RustfmtContext::detect(path).workdir);
+                Job::spawn(command[0].clone(), process, Some(source))
+            }
+        }
+    }
+
+    pub fn describe(&self) -> Vec<String> {
+        Language::all()
+            .into_iter()
+            .map(|language| {
+                let formatter = self
+                    .entries
+                    .iter()
+                    .find(|(existing, _)| *existing == language)
+                    .map(|(_, formatter)| formatter.describe())
+                    .unwrap_or_else(|| String::from("-"));
+                format!("{:<9} {}", language.label(), formatter)
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatProblem {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+/// Cari lokasi error di stderr formatter: `<stdin>:3:5`, `(3:5)`, atau `line 3, column 5`.
+pub fn parse_problem(stderr: &str) -> Option<FormatProblem> {
+    let error_line = stderr
+        .lines()
+        .map(str::trim)
+        .find(|line| line.starts_with("error") || line.contains("error:"));
+    stderr.lines().find_map(|text| {
+        let (line, column, rest) = find_location(text)?;
+        let rest = rest
+            .trim_start_matches([':', ')', ',', ' '])
+            .trim()
+            .to_string();
+        let message = if rest.is_empty() {
+            error_line.unwrap_or(text.trim()).to_string()
+        } else {
+            rest
+        };
+        Some(FormatProblem {
+            line,
+            column,
+            message,
+        })
+    })
+}
+
+fn find_location(text: &str) -> Option<(usize, usize, &str)> {
+    let bytes = text.as_bytes();
+    for (idx, &byte) in bytes.iter().enumerate() {
+        if !byte.is_ascii_digit() || idx == 0 || !matches!(bytes[idx - 1], b':' | b'(') {
+            continue;
+        }
+        let (line, after_line) = take_number(text, idx)?;
+        if !text[after_line..].starts_with(':') {
+            continue;
+        }
+        if let Some((column, end)) = take_number(text, after_line + 1) {
+            return Some((line, column, &text[end..]));
+        }
+    }
+    let lower = text.to_ascii_lowercase();
+    let line_at = lower.find("line ")? + "line ".len();
+    let (line, after_line) = take_number(text, line_at)?;
+    let column_at = lower[after_line..].find("column ")? + after_line + "column ".len();
+    let (column, end) = take_number(text, column_at)?;
+    Some((line, column, &text[end..]))
+}
+
+fn take_number(text: &str, start: usize) -> Option<(usize, usize)> {
+    let digits = text[start..].bytes().take_while(u8::is_ascii_digit).count();
+    let value = text[start..start + digits].parse().ok()?;
+    Some((value, start + digits))
+}
+
+// Hanya whitespace di luar string yang diubah: angka (`1e3`, integer besar) dan key duplikat
+// ditulis ulang persis seperti sumbernya. `serde_json` dipakai untuk validasi dan pesan error.
+fn format_json(source: &str) -> Result<String, String> {
+    serde_json::from_str::<serde_json::Value>(source).map_err(|err| {
+        let message = err.to_string();
+        let message = message.split(" at line ").next().unwrap_or_default();
+        format!("<stdin>:{}:{}: {}", err.line(), err.column(), message)
+    })?;
+
+    const INDENT: &str = "  ";
+    let mut formatted = String::with_capacity(source.len());
+    let mut depth = 0usize;
+    let mut chars = source.chars().peekable();
+    let newline = |formatted: &mut String, depth: usize| {
+        formatted.push('\n');
+        formatted.push_str(&INDENT.repeat(depth));
+    };
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' => {
+                formatted.push(ch);
+                while let Some(ch) = chars.next() {
+                    formatted.push(ch);
+                    match ch {
+                        '\\' => formatted.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '{' | '[' => {
+                formatted.push(ch);
+                while chars.next_if(|ch| ch.is_ascii_whitespace()).is_some() {}
+                if let Some(close) =
+                    chars.next_if(|next| matches!((ch, next), ('{', '}') | ('[', ']')))
+                {
+                    formatted.push(close);
+                } else {
+                    depth += 1;
+                    newline(&mut formatted, depth);
+                }
+            }
+            '}' | ']' => {
+                depth = depth.saturating_sub(1);
+                newline(&mut formatted, depth);
+                formatted.push(ch);
+            }
+            ',' => {
+                formatted.push(ch);
+                newline(&mut formatted, depth);
+            }
+            ':' => formatted.push_str(": "),
+            ch if ch.is_ascii_whitespace() => {}
+            ch => formatted.push(ch),
+        }
+    }
+    formatted.push('\n');
+    Ok(formatted)
+}
+
+fn format_toml(source: &str) -> Result<String, String> {
+    let mut doc = source.parse::<DocumentMut>().map_err(|err| {
+        let offset = err.span().map(|span| span.start).unwrap_or(0);
+        let before = &source[..offset.min(source.len())];
+        let line = before.matches('\n').count() + 1;
+        let column = before
+            .rsplit('\n')
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .count()
+            + 1;
+        format!("<stdin>:{}:{}: {}", line, column, err.message())
+    })?;
+    normalize_table(doc.as_table_mut());
+    let trailing = doc
+        .trailing()
+        .as_str()
+        .map(|text| comment_lines(text, false))
+        .unwrap_or_default();
+    doc.set_trailing(trailing);
+
+    let mut formatted = doc.to_string().trim_start_matches('\n').to_string();
+    while formatted.ends_with('\n') {
+        formatted.pop();
+    }
+    formatted.push('\n');
+    Ok(formatted)
+}
+
+// Indentasi dan spasi di sekitar `=` dirapikan, komentar tetap; array multi-baris dan
+// dotted key dibiarkan karena tata letaknya biasanya disengaja.
+fn normalize_table(table: &mut Table) {
+    let mut first = true;
+    for (mut key, item) in table.iter_mut() {
+        match item {
+            Item::Value(value) => {
+                let prefix = raw_str(key.leaf_decor().prefix());
+                key.leaf_decor_mut()
+                    .set_prefix(comment_lines(&prefix, first));
+                key.leaf_decor_mut().set_suffix(" ");
+                normalize_value(value);
+                let suffix = raw_str(value.decor().suffix());
+                value.decor_mut().set_prefix(" ");
+                value.decor_mut().set_suffix(trailing_comment(&suffix));
+                first = false;
+            }
+            Item::Table(child) if child.is_dotted() => first = false,
+            Item::Table(child) => normalize_child_table(child),
+            Item::ArrayOfTables(tables) => {
+                for child in tables.iter_mut() {
+                    normalize_child_table(child);
+                }
+            }
+            Item::None => {}
+        }
+    }
+}
+
+fn normalize_child_table(table: &mut Table) {
+    if !table.is_implicit() {
+        let prefix = raw_str(table.decor().prefix());
+        let suffix = raw_str(table.decor().suffix());
+        let decor = table.decor_mut();
+        decor.set_prefix(format!("\n{}", comment_lines(&prefix, true)));
+        decor.set_suffix(trailing_comment(&suffix));
+    }
+    normalize_table(table);
+}
+
+fn normalize_value(value: &mut Value) {
+    match value {
+        Value::Array(array) if !array.to_string().contains('\n') => {
+            array.fmt();
+            array.iter_mut().for_each(normalize_value);
+        }
+        Value::InlineTable(table) => {
+            table.fmt();
+            table
+                .iter_mut()
+                .for_each(|(_, value)| normalize_value(value));
+        }
+        _ => {}
+    }
+}
+
+fn raw_str(raw: Option<&RawString>) -> String {
+    raw.and_then(RawString::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+// Pertahankan komentar dan maksimal satu baris kosong; spasi indentasi dibuang.
+fn comment_lines(prefix: &str, first: bool) -> String {
+    let mut segments = prefix.split('\n').collect::<Vec<_>>();
+    segments.pop();
+    let mut result = String::new();
+    let mut blank = false;
+    for segment in segments {
+        let segment = segment.trim();
+        if segment.is_empty() {
+            blank = !result.is_empty() || !first;
+            continue;
+        }
+        if blank {
+            result.push('\n');
+            blank = false;
+        }
+        result.push_str(segment);
+        result.push('\n');
+    }
+    if blank {
+        result.push('\n');
+    }
+    result
+}
+
+fn trailing_comment(suffix: &str) -> String {
+    match suffix.find('#') {
+        Some(idx) => format!(" {}", suffix[idx..].trim_end()),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_layout_matches_serde_pretty() {
+        let source = r#"{"a":[1,2,{"b":null}],"c":{},"d":[ ],"e":"x"}"#;
+        let value = serde_json::from_str::<serde_json::Value>(source).unwrap();
+        let expected = serde_json::to_string_pretty(&value).unwrap() + "\n";
+        assert_eq!(format_json(source).unwrap(), expected);
+        assert_eq!(format_json(&expected).unwrap(), expected);
+    }
+
+    #[test]
+    fn json_values_are_kept_verbatim() {
+        let source = "{\"n\": 1e3, \"big\": 123456789012345678901234567890, \"n\": -0.10,\n\"s\": \"a, b: {\\\"c\\\"} \\\\\"}";
+        assert_eq!(
+            format_json(source).unwrap(),
+            "{\n  \"n\": 1e3,\n  \"big\": 123456789012345678901234567890,\n  \"n\": -0.10,\n  \"s\": \"a, b: {\\\"c\\\"} \\\\\"\n}\n"
+        );
+    }
+
+    #[test]
+    fn json_errors_report_position() {
+        let err = format_json("{\n  \"a\" 1\n}").unwrap_err();
+        assert!(err.starts_with("<stdin>:2:"), "{}", err);
+    }
+}
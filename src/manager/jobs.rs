@@ -0,0 +1,394 @@
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+const WAIT_INTERVAL: Duration = Duration::from_millis(50);
+const READER_GRACE: Duration = Duration::from_millis(500);
+
+static NEXT_JOB_ID: AtomicU64 = AtomicU64::new(1);
+
+pub type JobId = u64;
+
+#[derive(Debug)]
+pub enum JobEvent {
+    /// Baris stderr; cargo menulis progres (`Updating`, `Adding`, ...) di sana.
+    Output(String),
+    /// Baris stdout, hanya untuk job dari `spawn_streaming`.
+    Stdout(String),
+    Finished(JobOutcome),
+}
+
+#[derive(Debug, Clone)]
+pub struct JobOutcome {
+    pub success: bool,
+    pub cancelled: bool,
+    /// Reader stdout/stderr belum selesai saat job diakhiri; `stdout` bisa terpotong sehingga
+    /// job tidak dianggap sukses walau exit status-nya 0.
+    pub incomplete: bool,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl JobOutcome {
+    /// Satu baris untuk status bar; stderr lengkap sudah di-stream lewat `JobEvent::Output`.
+    pub fn summary(&self, label: &str) -> String {
+        if self.cancelled {
+            return format!("{} dibatalkan", label);
+        }
+        if self.success {
+            return format!("{} selesai", label);
+        }
+        if self.incomplete {
+            return format!("{} gagal: output tidak lengkap", label);
+        }
+        let mut lines = self
+            .stderr
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty());
+        match lines
+            .clone()
+            .find(|line| line.starts_with("error"))
+            .or_else(|| lines.next_back())
+        {
+            Some(reason) => format!("{} gagal: {}", label, reason),
+            None => format!("{} gagal", label),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct JobStatus {
+    pub id: JobId,
+    pub label: String,
+    pub started: Instant,
+    pub progress: String,
+}
+
+impl JobStatus {
+    pub fn spinner(&self) -> char {
+        SPINNER[(self.started.elapsed().as_millis() / 100) as usize % SPINNER.len()]
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{} {} {}s",
+            self.spinner(),
+            self.label,
+            self.started.elapsed().as_secs()
+        )
+    }
+}
+
+pub struct Job {
+    status: JobStatus,
+    child: Option<Arc<Mutex<Child>>>,
+    receiver: Receiver<JobEvent>,
+    cancelled: bool,
+    finished: bool,
+}
+
+impl Job {
+    /// Jalankan `command` di thread terpisah. `stdin` ditulis lalu ditutup bila ada.
+    pub fn spawn(label: String, command: Command, stdin: Option<String>) -> Result<Self> {
+        Self::spawn_with(label, command, stdin, false)
+    }
+
+    /// Seperti `spawn`, tapi tiap baris stdout juga dikirim sebagai `JobEvent::Stdout` selagi
+    /// proses berjalan (mis. `cargo --message-format=json`).
+    pub fn spawn_streaming(label: String, command: Command) -> Result<Self> {
+        Self::spawn_with(label, command, None, true)
+    }
+
+    fn spawn_with(
+        label: String,
+        mut command: Command,
+        stdin: Option<String>,
+        stream_stdout: bool,
+    ) -> Result<Self> {
+        command
+            .stdin(if stdin.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        // Process group sendiri supaya cancel juga menghentikan proses turunan (test binary,
+        // program dari `cargo run`, rustc).
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut command, 0);
+        let mut child = command
+            .spawn()
+            .with_context(|| format!("gagal menjalankan {}", label))?;
+
+        if let (Some(input), Some(mut pipe)) = (stdin, child.stdin.take()) {
+            thread::spawn(move || {
+                let _ = pipe.write_all(input.as_bytes());
+            });
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        let stdout_text = Arc::new(Mutex::new(String::new()));
+        let stderr_text = Arc::new(Mutex::new(String::new()));
+        let stdout = child.stdout.take().map(|stdout| {
+            let sender = sender.clone();
+            let text = Arc::clone(&stdout_text);
+            thread::spawn(move || {
+                for line in BufReader::new(stdout).lines().map_while(|line| line.ok()) {
+                    if let Ok(mut text) = text.lock() {
+                        text.push_str(&line);
+                        text.push('\n');
+                    }
+                    if stream_stdout {
+                        let _ = sender.send(JobEvent::Stdout(line));
+                    }
+                }
+            })
+        });
+        let stderr = child.stderr.take().map(|stderr| {
+            let sender = sender.clone();
+            let text = Arc::clone(&stderr_text);
+            thread::spawn(move || {
+                for line in BufReader::new(stderr).lines().map_while(|line| line.ok()) {
+                    if let Ok(mut text) = text.lock() {
+                        text.push_str(&line);
+                        text.push('\n');
+                    }
+                    let _ = sender.send(JobEvent::Output(line));
+                }
+            })
+        });
+
+        let child = Arc::new(Mutex::new(child));
+        let waiter = Arc::clone(&child);
+        thread::spawn(move || {
+            // Child tidak di-`wait()` sambil memegang lock supaya `cancel` tetap bisa kill.
+            let exited_ok = loop {
+                let status = match waiter.lock() {
+                    Ok(mut child) => child.try_wait(),
+                    Err(_) => break false,
+                };
+                match status {
+                    Ok(Some(status)) => break status.success(),
+                    Ok(None) => thread::sleep(WAIT_INTERVAL),
+                    Err(_) => break false,
+                }
+            };
+            // Proses turunan yang lolos dari process group bisa tetap memegang pipe; reader
+            // hanya ditunggu sebentar supaya `Finished` selalu terkirim setelah child keluar.
+            let readers_busy = || {
+                [&stdout, &stderr]
+                    .into_iter()
+                    .flatten()
+                    .any(|reader| !reader.is_finished())
+            };
+            let deadline = Instant::now() + READER_GRACE;
+            while readers_busy() && Instant::now() < deadline {
+                thread::sleep(WAIT_INTERVAL);
+            }
+            let incomplete = readers_busy();
+            let collect =
+                |text: &Mutex<String>| text.lock().map(|text| text.clone()).unwrap_or_default();
+            let _ = sender.send(JobEvent::Finished(JobOutcome {
+                success: exited_ok && !incomplete,
+                cancelled: false,
+                incomplete,
+                stdout: collect(&stdout_text),
+                stderr: collect(&stderr_text),
+            }));
+        });
+
+        Ok(Self {
+            status: JobStatus {
+                id: NEXT_JOB_ID.fetch_add(1, Ordering::Relaxed),
+                label,
+                started: Instant::now(),
+                progress: String::new(),
+            },
+            child: Some(child),
+            receiver,
+            cancelled: false,
+            finished: false,
+        })
+    }
+
+    /// Pekerjaan in-process (mis. formatter bawaan) lewat alur job yang sama. `Ok` menjadi
+    /// stdout, `Err` menjadi stderr. Cancel hanya membuang hasilnya.
+    pub fn run_in_thread<F>(label: String, work: F) -> Self
+    where
+        F: FnOnce() -> Result<String, String> + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let (success, stdout, stderr) = match work() {
+                Ok(stdout) => (true, stdout, String::new()),
+                Err(stderr) => (false, String::new(), stderr),
+            };
+            for line in stderr.lines() {
+                let _ = sender.send(JobEvent::Output(line.to_string()));
+            }
+            let _ = sender.send(JobEvent::Finished(JobOutcome {
+                success,
+                cancelled: false,
+                incomplete: false,
+                stdout,
+                stderr,
+            }));
+        });
+        Self {
+            status: JobStatus {
+                id: NEXT_JOB_ID.fetch_add(1, Ordering::Relaxed),
+                label,
+                started: Instant::now(),
+                progress: String::new(),
+            },
+            child: None,
+            receiver,
+            cancelled: false,
+            finished: false,
+        }
+    }
+
+    pub fn id(&self) -> JobId {
+        self.status.id
+    }
+
+    pub fn label(&self) -> &str {
+        &self.status.label
+    }
+
+    pub fn status(&self) -> &JobStatus {
+        &self.status
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Hentikan proses beserta process group-nya; boleh dipanggil ulang bila job belum selesai.
+    pub fn cancel(&mut self) {
+        if self.finished {
+            return;
+        }
+        self.cancelled = true;
+        if let Some(Ok(mut child)) = self.child.as_ref().map(|child| child.lock()) {
+            kill_process_group(&mut child);
+        }
+    }
+
+    pub fn poll(&mut self) -> Vec<JobEvent> {
+        let mut events = Vec::new();
+        loop {
+            match self.receiver.try_recv() {
+                Ok(JobEvent::Output(line)) => {
+                    if !line.trim().is_empty() {
+                        self.status.progress = line.trim().to_string();
+                    }
+                    events.push(JobEvent::Output(line));
+                }
+                Ok(JobEvent::Stdout(line)) => events.push(JobEvent::Stdout(line)),
+                Ok(JobEvent::Finished(mut outcome)) => {
+                    self.finished = true;
+                    outcome.cancelled = self.cancelled;
+                    events.push(JobEvent::Finished(outcome));
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    if !self.finished {
+                        self.finished = true;
+                        events.push(JobEvent::Finished(JobOutcome {
+                            success: false,
+                            cancelled: self.cancelled,
+                            incomplete: false,
+                            stdout: String::new(),
+                            stderr: String::new(),
+                        }));
+                    }
+                    break;
+                }
+            }
+        }
+        events
+    }
+}
+
+#[cfg(unix)]
+fn kill_process_group(child: &mut Child) {
+    // Child adalah pemimpin group-nya sendiri (`process_group(0)`), jadi pgid == pid.
+    if let Ok(pid) = libc::pid_t::try_from(child.id()) {
+        unsafe {
+            libc::kill(-pid, libc::SIGKILL);
+        }
+    }
+    let _ = child.kill();
+}
+
+#[cfg(not(unix))]
+fn kill_process_group(child: &mut Child) {
+    let _ = child.kill();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wait_finished(job: &mut Job) -> Option<JobOutcome> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Instant::now() < deadline {
+            for event in job.poll() {
+                if let JobEvent::Finished(outcome) = event {
+                    return Some(outcome);
+                }
+            }
+            thread::sleep(WAIT_INTERVAL);
+        }
+        None
+    }
+
+    #[test]
+    fn collects_output() {
+        let mut command = Command::new("sh");
+        command.args(["-c", "echo keluar; echo galat >&2"]);
+        let mut job = Job::spawn_streaming(String::from("sh"), command).unwrap();
+        let outcome = wait_finished(&mut job).unwrap();
+        assert!(outcome.success);
+        assert!(!outcome.incomplete);
+        assert_eq!(outcome.stdout, "keluar\n");
+        assert_eq!(outcome.stderr, "galat\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn output_still_open_after_grace_is_not_success() {
+        // Proses latar belakang memegang stdout lebih lama dari `READER_GRACE`.
+        let mut command = Command::new("sh");
+        command.args(["-c", "(sleep 2; echo telat) & echo awal"]);
+        let mut job = Job::spawn(String::from("sh"), command, None).unwrap();
+        let outcome = wait_finished(&mut job).expect("Finished tidak pernah terkirim");
+        assert!(outcome.incomplete);
+        assert!(!outcome.success);
+        assert_eq!(outcome.summary("sh"), "sh gagal: output tidak lengkap");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn cancel_kills_grandchildren_holding_pipes() {
+        // `sleep` di latar belakang mewarisi stdout; tanpa kill per group, reader tidak selesai.
+        let mut command = Command::new("sh");
+        command.args(["-c", "sleep 30 & sleep 30"]);
+        let mut job = Job::spawn(String::from("sh"), command, None).unwrap();
+        thread::sleep(Duration::from_millis(200));
+        job.cancel();
+        let outcome = wait_finished(&mut job).expect("Finished tidak pernah terkirim");
+        assert!(outcome.cancelled);
+        assert!(!outcome.success);
+    }
+}
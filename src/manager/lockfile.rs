@@ -0,0 +1,221 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use toml_edit::{DocumentMut, Item};
+
+#[derive(Debug, Clone)]
+pub struct LockPackage {
+    pub name: String,
+    pub version: String,
+    pub source: Option<String>,
+    pub dependencies: Vec<LockDependency>,
+}
+
+impl LockPackage {
+    pub fn is_registry(&self) -> bool {
+        self.source
+            .as_deref()
+            .is_some_and(|source| source.starts_with("registry+") || source.starts_with("sparse+"))
+    }
+}
+
+/// Referensi dependency di `Cargo.lock`: `"nama"`, `"nama versi"`, atau
+/// `"nama versi (source)"` bila nama saja ambigu.
+#[derive(Debug, Clone)]
+pub struct LockDependency {
+    pub name: String,
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Lockfile {
+    pub packages: Vec<LockPackage>,
+}
+
+impl Lockfile {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("gagal membaca {}", path.display()))?;
+        let doc = content
+            .parse::<DocumentMut>()
+            .with_context(|| format!("{} bukan TOML yang valid", path.display()))?;
+
+        let mut packages = Vec::new();
+        if let Some(tables) = doc.get("package").and_then(Item::as_array_of_tables) {
+            for table in tables.iter() {
+                let field = |key: &str| table.get(key).and_then(Item::as_str).map(str::to_string);
+                let (Some(name), Some(version)) = (field("name"), field("version")) else {
+                    continue;
+                };
+                let dependencies = table
+                    .get("dependencies")
+                    .and_then(Item::as_array)
+                    .map(|deps| {
+                        deps.iter()
+                            .filter_map(|dep| dep.as_str())
+                            .map(parse_dependency_ref)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                packages.push(LockPackage {
+                    name,
+                    version,
+                    source: field("source"),
+                    dependencies,
+                });
+            }
+        }
+        Ok(Self { packages })
+    }
+
+    pub fn versions_of(&self, name: &str) -> Vec<&LockPackage> {
+        self.packages
+            .iter()
+            .filter(|package| package.name == name)
+            .collect()
+    }
+
+    pub fn has_duplicates(&self, name: &str) -> bool {
+        self.versions_of(name).len() > 1
+    }
+
+    /// Index package yang dirujuk `dep`; versi dipakai bila nama saja ambigu.
+    pub fn resolve(&self, dep: &LockDependency) -> Option<usize> {
+        self.packages.iter().position(|package| {
+            package.name == dep.name
+                && dep
+                    .version
+                    .as_deref()
+                    .is_none_or(|version| package.version == version)
+        })
+    }
+
+    pub fn dependencies_of(&self, index: usize) -> Vec<usize> {
+        self.packages
+            .get(index)
+            .map(|package| {
+                package
+                    .dependencies
+                    .iter()
+                    .filter_map(|dep| self.resolve(dep))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+fn parse_dependency_ref(text: &str) -> LockDependency {
+    let mut parts = text.split_whitespace();
+    LockDependency {
+        name: parts.next().unwrap_or_default().to_string(),
+        version: parts.next().map(str::to_string),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Dua versi `rand` memaksa referensi memakai versi; `tanpa-versi` harus dilewati.
+    const LOCK: &str = r#"version = 3
+
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = [
+ "rand 0.7.3",
+ "rand 0.8.5",
+ "serde",
+]
+
+[[package]]
+name = "rand"
+version = "0.7.3"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "rand"
+version = "0.8.5"
+source = "sparse+https://index.crates.io/"
+
+[[package]]
+name = "serde"
+version = "1.0.0"
+source = "git+https://github.com/serde-rs/serde"
+
+[[package]]
+name = "tanpa-versi"
+"#;
+
+    fn load(name: &str, content: &str) -> Lockfile {
+        let path =
+            std::env::temp_dir().join(format!("action-codex-lock-{}-{}", std::process::id(), name));
+        fs::write(&path, content).unwrap();
+        let lockfile = Lockfile::load(&path);
+        let _ = fs::remove_file(&path);
+        lockfile.unwrap()
+    }
+
+    #[test]
+    fn parses_packages_and_skips_incomplete_entries() {
+        let lockfile = load("packages", LOCK);
+        let names: Vec<_> = lockfile
+            .packages
+            .iter()
+            .map(|package| format!("{} {}", package.name, package.version))
+            .collect();
+        assert_eq!(
+            names,
+            ["app 0.1.0", "rand 0.7.3", "rand 0.8.5", "serde 1.0.0"]
+        );
+        assert_eq!(lockfile.packages[0].source, None);
+    }
+
+    #[test]
+    fn registry_sources() {
+        let lockfile = load("registry", LOCK);
+        let registry: Vec<_> = lockfile
+            .packages
+            .iter()
+            .map(LockPackage::is_registry)
+            .collect();
+        assert_eq!(registry, [false, true, true, false]);
+    }
+
+    #[test]
+    fn duplicate_versions() {
+        let lockfile = load("duplicates", LOCK);
+        assert!(lockfile.has_duplicates("rand"));
+        assert!(!lockfile.has_duplicates("serde"));
+        assert!(lockfile.versions_of("tidak-ada").is_empty());
+    }
+
+    #[test]
+    fn dependency_refs_resolve_by_version_when_ambiguous() {
+        let lockfile = load("resolve", LOCK);
+        let deps = &lockfile.packages[0].dependencies;
+        assert_eq!(deps[0].name, "rand");
+        assert_eq!(deps[0].version.as_deref(), Some("0.7.3"));
+        assert_eq!(deps[2].version, None);
+        assert_eq!(lockfile.dependencies_of(0), [1, 2, 3]);
+        assert!(lockfile.dependencies_of(9).is_empty());
+    }
+
+    #[test]
+    fn dependency_ref_with_source() {
+        let dep = parse_dependency_ref("rand 0.8.5 (registry+https://example.com)");
+        assert_eq!(dep.name, "rand");
+        assert_eq!(dep.version.as_deref(), Some("0.8.5"));
+    }
+
+    #[test]
+    fn invalid_toml_is_an_error() {
+        let path =
+            std::env::temp_dir().join(format!("action-codex-lock-{}-invalid", std::process::id()));
+        fs::write(&path, "[[package]\n").unwrap();
+        let result = Lockfile::load(&path);
+        let _ = fs::remove_file(&path);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,503 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow};
+use toml_edit::{Array, DocumentMut, Item, Table, TableLike, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DependencyKind {
+    Normal,
+    Dev,
+    Build,
+    Workspace,
+}
+
+impl DependencyKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Dev => "dev",
+            Self::Build => "build",
+            Self::Workspace => "workspace",
+        }
+    }
+
+    fn table_key(self) -> &'static str {
+        match self {
+            Self::Normal => "dependencies",
+            Self::Dev => "dev-dependencies",
+            Self::Build => "build-dependencies",
+            Self::Workspace => "workspace.dependencies",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DependencyEntry {
+    pub name: String,
+    pub package: Option<String>,
+    pub kind: DependencyKind,
+    pub target: Option<String>,
+    pub requirement: String,
+}
+
+impl DependencyEntry {
+    pub fn crate_name(&self) -> &str {
+        self.package.as_deref().unwrap_or(&self.name)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DependencySpec {
+    pub name: String,
+    pub requirement: String,
+    pub kind: DependencyKind,
+    pub features: Vec<String>,
+    pub default_features: bool,
+    pub optional: bool,
+}
+
+impl DependencySpec {
+    /// Requirement yang ditulis ke manifest (preview). Kosong hanya bila versi terbaru tidak
+    /// diketahui; `*` menjaga preview tetap TOML dependency yang valid.
+    pub fn requirement(&self) -> &str {
+        match self.requirement.trim() {
+            "" => "*",
+            requirement => requirement,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CargoManifest {
+    path: PathBuf,
+    doc: DocumentMut,
+}
+
+impl CargoManifest {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("gagal membaca {}", path.display()))?;
+        let doc = content
+            .parse::<DocumentMut>()
+            .with_context(|| format!("{} bukan TOML yang valid", path.display()))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            doc,
+        })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::write(&self.path, self.doc.to_string())
+            .with_context(|| format!("gagal menulis {}", self.path.display()))
+    }
+
+    pub fn text(&self) -> String {
+        self.doc.to_string()
+    }
+
+    /// Tulis dependency seperti yang dilakukan `cargo add`, hanya di memori.
+    pub fn insert_dependency(&mut self, spec: &DependencySpec) -> Result<()> {
+        let key = spec.kind.table_key();
+        let deps = match spec.kind {
+            DependencyKind::Workspace => self
+                .doc
+                .entry("workspace")
+                .or_insert(Item::Table(Table::new()))
+                .as_table_like_mut()
+                .ok_or_else(|| anyhow!("[workspace] bukan tabel"))?
+                .entry("dependencies")
+                .or_insert(Item::Table(Table::new())),
+            _ => self.doc.entry(key).or_insert(Item::Table(Table::new())),
+        }
+        .as_table_like_mut()
+        .ok_or_else(|| anyhow!("[{}] bukan tabel", key))?;
+
+        let requirement = spec.requirement();
+        let simple = spec.features.is_empty() && spec.default_features && !spec.optional;
+        let value = if simple {
+            Value::from(requirement)
+        } else {
+            let mut table = toml_edit::InlineTable::new();
+            table.insert("version", Value::from(requirement));
+            if !spec.default_features {
+                table.insert("default-features", Value::from(false));
+            }
+            if !spec.features.is_empty() {
+                table.insert(
+                    "features",
+                    Value::Array(spec.features.iter().map(String::as_str).collect()),
+                );
+            }
+            if spec.optional {
+                table.insert("optional", Value::from(true));
+            }
+            Value::InlineTable(table)
+        };
+        deps.insert(&spec.name, Item::Value(value));
+        Ok(())
+    }
+
+    pub fn package_name(&self) -> Option<&str> {
+        self.doc.get("package")?.get("name")?.as_str()
+    }
+
+    pub fn package_edition(&self) -> Option<&str> {
+        self.doc.get("package")?.get("edition")?.as_str()
+    }
+
+    /// `edition.workspace = true`: edition diambil dari `[workspace.package]` root.
+    pub fn inherits_edition(&self) -> bool {
+        self.doc
+            .get("package")
+            .and_then(|package| package.get("edition"))
+            .and_then(|edition| edition.get("workspace"))
+            .and_then(Item::as_bool)
+            == Some(true)
+    }
+
+    /// Root workspace: punya tabel `workspace` dalam bentuk apa pun (header, dotted key, inline).
+    pub fn is_workspace_root(&self) -> bool {
+        self.doc.get("workspace").is_some()
+    }
+
+    pub fn workspace_edition(&self) -> Option<&str> {
+        self.doc
+            .get("workspace")?
+            .get("package")?
+            .get("edition")?
+            .as_str()
+    }
+
+    pub fn workspace_members(&self) -> Vec<String> {
+        self.doc
+            .get("workspace")
+            .and_then(|workspace| workspace.get("members"))
+            .and_then(Item::as_array)
+            .map(|members| {
+                members
+                    .iter()
+                    .filter_map(|value| value.as_str().map(ToOwned::to_owned))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn dependencies(&self) -> Vec<DependencyEntry> {
+        let mut entries = Vec::new();
+        collect_dependency_tables(self.doc.as_table(), None, &mut entries);
+        if let Some(targets) = self.doc.get("target").and_then(Item::as_table_like) {
+            for (cfg, item) in targets.iter() {
+                if let Some(table) = item.as_table_like() {
+                    collect_dependency_tables(table, Some(cfg), &mut entries);
+                }
+            }
+        }
+        if let Some(workspace_deps) = self
+            .doc
+            .get("workspace")
+            .and_then(|workspace| workspace.get("dependencies"))
+            .and_then(Item::as_table_like)
+        {
+            collect_dependencies(
+                workspace_deps,
+                DependencyKind::Workspace,
+                None,
+                &mut entries,
+            );
+        }
+        entries
+    }
+
+    pub fn add_workspace_member(&mut self, member: &str) -> Result<bool> {
+        if !self.doc.contains_key("workspace") {
+            self.doc.insert("workspace", Item::Table(Table::new()));
+        }
+        let workspace = self
+            .doc
+            .get_mut("workspace")
+            .and_then(Item::as_table_like_mut)
+            .ok_or_else(|| anyhow!("[workspace] bukan tabel"))?;
+        if !workspace.contains_key("members") {
+            let mut members = Array::new();
+            members.push(member);
+            workspace.insert("members", Item::Value(Value::Array(members)));
+            return Ok(true);
+        }
+
+        let members = workspace
+            .get_mut("members")
+            .and_then(Item::as_array_mut)
+            .ok_or_else(|| anyhow!("workspace.members bukan array"))?;
+        if members.iter().any(|value| value.as_str() == Some(member)) {
+            return Ok(false);
+        }
+
+        // Komentar setelah koma tersimpan di prefix elemen berikutnya, jadi hanya
+        // indentasi baris terakhir yang disalin ke elemen baru.
+        let prefix = members
+            .iter()
+            .last()
+            .and_then(|value| value.decor().prefix())
+            .and_then(|prefix| prefix.as_str())
+            .map(|prefix| match prefix.rfind('\n') {
+                Some(idx) => format!("\n{}", &prefix[idx + 1..]),
+                None => String::from(" "),
+            });
+        let mut value = Value::from(member);
+        if let Some(prefix) = prefix {
+            value.decor_mut().set_prefix(prefix);
+        }
+        members.push_formatted(value);
+        Ok(true)
+    }
+
+    pub fn remove_workspace_member(&mut self, member: &str) -> Result<bool> {
+        let Some(members) = self
+            .doc
+            .get_mut("workspace")
+            .and_then(|workspace| workspace.get_mut("members"))
+            .and_then(Item::as_array_mut)
+        else {
+            return Ok(false);
+        };
+        let Some(index) = members
+            .iter()
+            .position(|value| value.as_str() == Some(member))
+        else {
+            return Ok(false);
+        };
+
+        let removed_prefix = members
+            .get(index)
+            .and_then(|value| value.decor().prefix())
+            .and_then(|prefix| prefix.as_str())
+            .unwrap_or_default()
+            .to_string();
+        members.remove(index);
+
+        // Bagian sebelum newline pertama adalah komentar milik elemen sebelumnya;
+        // pertahankan bagian itu dan indentasi milik penerusnya.
+        let next_prefix = match members.get(index) {
+            Some(next) => next
+                .decor()
+                .prefix()
+                .and_then(|prefix| prefix.as_str())
+                .unwrap_or_default(),
+            None => members.trailing().as_str().unwrap_or_default(),
+        }
+        .to_string();
+        let merged = match (removed_prefix.find('\n'), next_prefix.find('\n')) {
+            (Some(removed_nl), Some(next_nl)) if index > 0 => format!(
+                "{}{}",
+                &removed_prefix[..=removed_nl],
+                &next_prefix[next_nl + 1..]
+            ),
+            (Some(_), Some(next_nl)) => format!("\n{}", &next_prefix[next_nl + 1..]),
+            _ if index == 0 => removed_prefix,
+            _ => next_prefix,
+        };
+        match members.get_mut(index) {
+            Some(next) => next.decor_mut().set_prefix(merged),
+            None => members.set_trailing(merged),
+        }
+        Ok(true)
+    }
+}
+
+fn collect_dependency_tables(
+    table: &dyn TableLike,
+    target: Option<&str>,
+    entries: &mut Vec<DependencyEntry>,
+) {
+    for (key, kind) in [
+        ("dependencies", DependencyKind::Normal),
+        ("dev-dependencies", DependencyKind::Dev),
+        ("dev_dependencies", DependencyKind::Dev),
+        ("build-dependencies", DependencyKind::Build),
+        ("build_dependencies", DependencyKind::Build),
+    ] {
+        if let Some(deps) = table.get(key).and_then(Item::as_table_like) {
+            collect_dependencies(deps, kind, target, entries);
+        }
+    }
+}
+
+fn collect_dependencies(
+    deps: &dyn TableLike,
+    kind: DependencyKind,
+    target: Option<&str>,
+    entries: &mut Vec<DependencyEntry>,
+) {
+    for (name, item) in deps.iter() {
+        let detail = item.as_table_like();
+        let package = detail
+            .and_then(|detail| detail.get("package"))
+            .and_then(Item::as_str)
+            .map(ToOwned::to_owned);
+        let requirement = match (item.as_str(), detail) {
+            (Some(version), _) => version.to_string(),
+            (None, Some(detail)) => dependency_source(detail),
+            (None, None) => String::from("*"),
+        };
+        entries.push(DependencyEntry {
+            name: name.to_string(),
+            package,
+            kind,
+            target: target.map(ToOwned::to_owned),
+            requirement,
+        });
+    }
+}
+
+fn dependency_source(detail: &dyn TableLike) -> String {
+    if detail
+        .get("workspace")
+        .and_then(Item::as_bool)
+        .unwrap_or(false)
+    {
+        return String::from("workspace");
+    }
+    if let Some(version) = detail.get("version").and_then(Item::as_str) {
+        return version.to_string();
+    }
+    if let Some(path) = detail.get("path").and_then(Item::as_str) {
+        return format!("path {}", path);
+    }
+    if let Some(git) = detail.get("git").and_then(Item::as_str) {
+        return format!("git {}", git);
+    }
+    String::from("*")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> CargoManifest {
+        CargoManifest {
+            path: PathBuf::from("Cargo.toml"),
+            doc: text.parse().unwrap(),
+        }
+    }
+
+    // Komentar `# pertama` tersimpan di prefix "b", bukan di suffix "a".
+    const MEMBERS: &str = "[workspace]\nmembers = [\n    \"a\", # pertama\n    \"b\",\n]\n";
+
+    #[test]
+    fn workspace_root_is_detected_in_any_form() {
+        assert!(parse("[workspace] # root\nmembers = []\n").is_workspace_root());
+        assert!(parse("workspace.members = [\"a\"]\n").is_workspace_root());
+        assert!(parse("[workspace.package]\nedition = \"2021\"\n").is_workspace_root());
+        assert!(!parse("[package]\nname = \"a\"\n# [workspace]\n").is_workspace_root());
+    }
+
+    #[test]
+    fn add_member_copies_indentation_not_comment() {
+        let mut manifest = parse(MEMBERS);
+        assert!(manifest.add_workspace_member("c").unwrap());
+        assert_eq!(
+            manifest.doc.to_string(),
+            "[workspace]\nmembers = [\n    \"a\", # pertama\n    \"b\",\n    \"c\",\n]\n"
+        );
+    }
+
+    #[test]
+    fn add_member_to_single_line_array() {
+        let mut manifest = parse("[workspace]\nmembers = [\"a\"]\n");
+        assert!(manifest.add_workspace_member("b").unwrap());
+        assert_eq!(
+            manifest.doc.to_string(),
+            "[workspace]\nmembers = [\"a\", \"b\"]\n"
+        );
+    }
+
+    #[test]
+    fn add_member_creates_workspace_table() {
+        let mut manifest = parse("[package]\nname = \"x\"\n");
+        assert!(manifest.add_workspace_member("a").unwrap());
+        assert_eq!(
+            manifest.doc.to_string(),
+            "[package]\nname = \"x\"\n\n[workspace]\nmembers = [\"a\"]\n"
+        );
+    }
+
+    #[test]
+    fn add_existing_member_is_noop() {
+        let mut manifest = parse(MEMBERS);
+        assert!(!manifest.add_workspace_member("a").unwrap());
+        assert_eq!(manifest.doc.to_string(), MEMBERS);
+    }
+
+    #[test]
+    fn remove_last_member_keeps_previous_comment() {
+        let mut manifest = parse(MEMBERS);
+        assert!(manifest.remove_workspace_member("b").unwrap());
+        assert_eq!(
+            manifest.doc.to_string(),
+            "[workspace]\nmembers = [\n    \"a\", # pertama\n]\n"
+        );
+    }
+
+    #[test]
+    fn remove_first_member_drops_its_comment() {
+        let mut manifest = parse(MEMBERS);
+        assert!(manifest.remove_workspace_member("a").unwrap());
+        assert_eq!(
+            manifest.doc.to_string(),
+            "[workspace]\nmembers = [\n    \"b\",\n]\n"
+        );
+    }
+
+    #[test]
+    fn remove_missing_member_is_noop() {
+        let mut manifest = parse(MEMBERS);
+        assert!(!manifest.remove_workspace_member("z").unwrap());
+        assert_eq!(manifest.doc.to_string(), MEMBERS);
+        let mut empty = parse("[package]\nname = \"x\"\n");
+        assert!(!empty.remove_workspace_member("a").unwrap());
+    }
+
+    fn spec(requirement: &str) -> DependencySpec {
+        DependencySpec {
+            name: String::from("serde"),
+            requirement: requirement.to_string(),
+            kind: DependencyKind::Normal,
+            features: Vec::new(),
+            default_features: true,
+            optional: false,
+        }
+    }
+
+    #[test]
+    fn empty_requirement_is_written_as_star() {
+        let mut manifest = parse("[package]\nname = \"x\"\n");
+        manifest.insert_dependency(&spec("  ")).unwrap();
+        assert_eq!(
+            manifest.text(),
+            "[package]\nname = \"x\"\n\n[dependencies]\nserde = \"*\"\n"
+        );
+
+        let mut detailed = spec("");
+        detailed.features = vec![String::from("derive")];
+        manifest.insert_dependency(&detailed).unwrap();
+        let entry = manifest
+            .dependencies()
+            .into_iter()
+            .find(|entry| entry.name == "serde")
+            .unwrap();
+        assert_eq!(entry.requirement, "*");
+    }
+
+    #[test]
+    fn workspace_dependency_goes_under_workspace() {
+        let mut manifest = parse(MEMBERS);
+        let mut spec = spec("1");
+        spec.kind = DependencyKind::Workspace;
+        manifest.insert_dependency(&spec).unwrap();
+        let entries = manifest.dependencies();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].kind, DependencyKind::Workspace);
+        assert_eq!(entries[0].requirement, "1");
+    }
+}
@@ -1,3 +1,12 @@
 pub mod cargo_manager;
+pub mod cargo_runner;
+pub mod dependency_report;
+pub mod dependency_tree;
+pub mod format_settings;
+pub mod formatter;
+pub mod jobs;
+pub mod lockfile;
+pub mod manifest;
+pub mod registry_index;
 pub mod rust_formatter;
 pub mod workspace_manager;
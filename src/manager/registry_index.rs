@@ -0,0 +1,199 @@
+use std::cmp::Ordering;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+#[derive(Debug, Clone)]
+pub struct IndexVersion {
+    pub version: String,
+    pub yanked: bool,
+    pub features: Vec<String>,
+}
+
+/// Versi crate dari cache index registry lokal (`~/.cargo/registry/index/*/.cache`),
+/// terbaru lebih dulu. Kosong bila crate belum pernah di-resolve di mesin ini atau `name` bukan
+/// nama crate yang valid.
+pub fn cached_versions(name: &str) -> Result<Vec<IndexVersion>> {
+    // Path cache index selalu huruf kecil.
+    let name = name.trim().to_ascii_lowercase();
+    if !is_crate_name(&name) {
+        return Ok(Vec::new());
+    }
+    let mut versions: Vec<IndexVersion> = Vec::new();
+    for path in cache_paths(&name) {
+        let Ok(bytes) = fs::read(&path) else {
+            continue;
+        };
+        for version in parse_cache_file(&bytes)
+            .with_context(|| format!("cache index {} rusak", path.display()))?
+        {
+            if !versions
+                .iter()
+                .any(|known| known.version == version.version)
+            {
+                versions.push(version);
+            }
+        }
+    }
+    versions.sort_by(|a, b| compare_versions(&b.version, &a.version));
+    Ok(versions)
+}
+
+fn cache_paths(name: &str) -> Vec<PathBuf> {
+    let Some(cargo_home) = std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".cargo")))
+    else {
+        return Vec::new();
+    };
+    let Ok(registries) = fs::read_dir(cargo_home.join("registry").join("index")) else {
+        return Vec::new();
+    };
+    let relative = index_relative_path(name);
+    registries
+        .flatten()
+        .map(|entry| entry.path().join(".cache").join(&relative))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+// Nama crate hanya ASCII, jadi `index_relative_path` aman memotong per byte.
+fn is_crate_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+}
+
+fn index_relative_path(name: &str) -> PathBuf {
+    match name.len() {
+        1 => PathBuf::from("1").join(name),
+        2 => PathBuf::from("2").join(name),
+        3 => PathBuf::from("3").join(&name[..1]).join(name),
+        _ => PathBuf::from(&name[..2]).join(&name[2..4]).join(name),
+    }
+}
+
+// Format cache: [versi cache u8][versi index u32][string versi index]\0, lalu pasangan
+// `versi\0json\0` untuk tiap rilis.
+fn parse_cache_file(bytes: &[u8]) -> Result<Vec<IndexVersion>> {
+    let body = bytes.get(5..).unwrap_or_default();
+    let mut parts = body.split(|byte| *byte == 0).skip(1);
+    let mut versions = Vec::new();
+    while let (Some(_), Some(json)) = (parts.next(), parts.next()) {
+        if json.is_empty() {
+            continue;
+        }
+        let value = serde_json::from_slice::<Value>(json).context("entri index bukan JSON")?;
+        if let Some(version) = parse_index_entry(&value) {
+            versions.push(version);
+        }
+    }
+    Ok(versions)
+}
+
+fn parse_index_entry(value: &Value) -> Option<IndexVersion> {
+    let version = value.get("vers")?.as_str()?.to_string();
+    let yanked = value
+        .get("yanked")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+
+    let mut features = Vec::new();
+    let mut explicit_deps = Vec::new();
+    for key in ["features", "features2"] {
+        let Some(table) = value.get(key).and_then(Value::as_object) else {
+            continue;
+        };
+        for (feature, enables) in table {
+            features.push(feature.clone());
+            explicit_deps.extend(
+                enables
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str)
+                    .filter_map(|item| item.strip_prefix("dep:"))
+                    .map(ToOwned::to_owned),
+            );
+        }
+    }
+    // Dependency optional tanpa `dep:` otomatis menjadi feature bernama sama.
+    for dep in value
+        .get("deps")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        if dep.get("optional").and_then(Value::as_bool) != Some(true) {
+            continue;
+        }
+        let Some(name) = dep.get("name").and_then(Value::as_str) else {
+            continue;
+        };
+        if !explicit_deps.iter().any(|explicit| explicit == name) {
+            features.push(name.to_string());
+        }
+    }
+    features.retain(|feature| feature != "default");
+    features.sort();
+    features.dedup();
+
+    Some(IndexVersion {
+        version,
+        yanked,
+        features,
+    })
+}
+
+pub fn is_prerelease(version: &str) -> bool {
+    version.split('+').next().unwrap_or(version).contains('-')
+}
+
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let split = |version: &str| {
+        let version = version.split('+').next().unwrap_or_default();
+        let (core, pre) = match version.split_once('-') {
+            Some((core, pre)) => (core, Some(pre.to_string())),
+            None => (version, None),
+        };
+        let numbers = core
+            .split('.')
+            .map(|part| part.parse::<u64>().unwrap_or(0))
+            .collect::<Vec<_>>();
+        (numbers, pre)
+    };
+    let (a_numbers, a_pre) = split(a);
+    let (b_numbers, b_pre) = split(b);
+    a_numbers
+        .cmp(&b_numbers)
+        .then_with(|| match (a_pre, b_pre) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            (Some(a), Some(b)) => a.cmp(&b),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_ascii_names_are_rejected_without_panicking() {
+        for name in ["é", "sérde", "日本語", "a b", "", "serde/../x"] {
+            assert!(cached_versions(name).unwrap().is_empty(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn index_paths_follow_cargo_layout() {
+        assert!(is_crate_name("serde_json-2"));
+        assert_eq!(index_relative_path("a"), PathBuf::from("1/a"));
+        assert_eq!(index_relative_path("ab"), PathBuf::from("2/ab"));
+        assert_eq!(index_relative_path("syn"), PathBuf::from("3/s/syn"));
+        assert_eq!(index_relative_path("serde"), PathBuf::from("se/rd/serde"));
+    }
+}
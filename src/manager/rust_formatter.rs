@@ -1,33 +1,342 @@
-use std::io::Write;
-use std::process::{Command, Stdio};
-
-use anyhow::{Context, Result, anyhow};
-
-pub fn format_rust_source(source: &str) -> Result<String> {
-    let mut child = Command::new("rustfmt")
-        .args(["--edition", "2024", "--emit", "stdout"])
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .context("gagal menjalankan rustfmt")?;
-
-    if let Some(stdin) = child.stdin.as_mut() {
-        stdin
-            .write_all(source.as_bytes())
-            .context("gagal menulis source ke rustfmt")?;
-    }
-
-    let output = child
-        .wait_with_output()
-        .context("gagal membaca output rustfmt")?;
-
-    if !output.status.success() {
-        return Err(anyhow!(
-            "rustfmt gagal: {}",
-            String::from_utf8_lossy(&output.stderr)
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Result, anyhow};
+
+use super::jobs::Job;
+use super::manifest::CargoManifest;
+
+const RANGE_WRAPPER: &str = "fn __action_codex_range() {";
+const CONFIG_FILES: [&str; 2] = ["rustfmt.toml", ".rustfmt.toml"];
+
+/// Bagian buffer yang diformat. Seleksi selalu diperluas ke baris penuh.
+#[derive(Debug, Clone)]
+pub enum FormatRange {
+    Whole,
+    Lines {
+        start: usize,
+        end: usize,
+        indent: String,
+    },
+}
+
+impl FormatRange {
+    pub fn lines(lines: &[String], start: usize, end: usize) -> Self {
+        let indent = lines[start..=end]
+            .iter()
+            .find(|line| !line.trim().is_empty())
+            .map(|line| leading_whitespace(line).to_string())
+            .unwrap_or_default();
+        Self::Lines { start, end, indent }
+    }
+
+    /// Petakan baris (1-based) pada input formatter kembali ke baris buffer.
+    pub fn buffer_line(&self, line: usize) -> usize {
+        match self {
+            Self::Whole => line,
+            Self::Lines { start, indent, .. } => {
+                start + line.saturating_sub(wrapper_depth(indent)).max(1)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RustfmtContext {
+    pub workdir: PathBuf,
+    pub edition: String,
+    pub config: Option<PathBuf>,
+}
+
+impl RustfmtContext {
+    /// rustfmt membaca `rustfmt.toml` dari direktori kerja ke atas, jadi dijalankan dari
+    /// direktori file; edition diambil dari manifest terdekat seperti `cargo fmt`.
+    pub fn detect(file: Option<&Path>) -> Self {
+        let workdir = file
+            .and_then(Path::parent)
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .map(Path::to_path_buf)
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_default();
+        let config = workdir.ancestors().find_map(|dir| {
+            CONFIG_FILES
+                .iter()
+                .map(|name| dir.join(name))
+                .find(|path| path.is_file())
+        });
+        Self {
+            edition: detect_edition(&workdir).unwrap_or_else(|| String::from("2024")),
+            workdir,
+            config,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self
+            .config
+            .as_deref()
+            .and_then(Path::file_name)
+            .and_then(|name| name.to_str())
+        {
+            Some(config) => format!("rustfmt {} ({})", self.edition, config),
+            None => format!("rustfmt {}", self.edition),
+        }
+    }
+}
+
+fn detect_edition(dir: &Path) -> Option<String> {
+    let mut inherit = false;
+    for dir in dir.ancestors() {
+        let path = dir.join("Cargo.toml");
+        if !path.is_file() {
+            continue;
+        }
+        let Ok(manifest) = CargoManifest::load(&path) else {
+            continue;
+        };
+        if inherit {
+            if let Some(edition) = manifest.workspace_edition() {
+                return Some(edition.to_string());
+            }
+            continue;
+        }
+        if manifest.inherits_edition() {
+            inherit = true;
+            if let Some(edition) = manifest.workspace_edition() {
+                return Some(edition.to_string());
+            }
+        } else if let Some(edition) = manifest.package_edition() {
+            return Some(edition.to_string());
+        } else if manifest.package_name().is_some() {
+            // Tanpa `edition`, cargo memakai 2015.
+            return Some(String::from("2015"));
+        }
+    }
+    None
+}
+
+/// Hasil format ada di stdout job; gabungkan dengan `splice_formatted`.
+pub fn spawn_rustfmt(lines: &[String], range: &FormatRange, file: Option<&Path>) -> Result<Job> {
+    let context = RustfmtContext::detect(file);
+    let source = match range {
+        FormatRange::Whole => lines.join("\n"),
+        FormatRange::Lines { start, end, indent } => {
+            let selected = &lines[*start..=*end];
+            if selected.iter().all(|line| line.trim().is_empty()) {
+                return Err(anyhow!("seleksi kosong"));
+            }
+            // Indentasi potongan dilepas lalu dipasang ulang, jadi baris lanjutan string
+            // multi-baris akan ikut berubah isinya.
+            let inside = string_continuations(lines);
+            if (*start..=*end + 1).any(|row| inside.get(row).copied().unwrap_or(false)) {
+                return Err(anyhow!(
+                    "seleksi mengenai string literal multi-baris, format seluruh file"
+                ));
+            }
+            wrap_range(selected, indent)
+        }
+    };
+    let mut command = Command::new("rustfmt");
+    command
+        .args(["--edition", &context.edition, "--emit", "stdout"])
+        .current_dir(&context.workdir);
+    Job::spawn(context.label(), command, Some(source))
+}
+
+pub fn splice_formatted(lines: &[String], range: &FormatRange, output: &str) -> Result<String> {
+    let FormatRange::Lines { start, end, indent } = range else {
+        return Ok(output.to_string());
+    };
+    let depth = wrapper_depth(indent);
+    let formatted = output.lines().collect::<Vec<_>>();
+    if formatted.len() < depth * 2 + 1 || !formatted[0].trim_start().starts_with(RANGE_WRAPPER) {
+        return Err(anyhow!("hasil rustfmt untuk seleksi tidak terduga"));
+    }
+    let body = &formatted[depth..formatted.len() - depth];
+    let nested = body
+        .iter()
+        .find(|line| !line.trim().is_empty())
+        .map(|line| leading_whitespace(line))
+        .unwrap_or_default();
+
+    let mut result = lines[..*start].to_vec();
+    for line in body {
+        if line.trim().is_empty() {
+            result.push(String::new());
+        } else {
+            result.push(format!(
+                "{}{}",
+                indent,
+                line.strip_prefix(nested).unwrap_or(line)
+            ));
+        }
+    }
+    result.extend_from_slice(&lines[end + 1..]);
+    Ok(result.join("\n"))
+}
+
+// Potongan baris dibungkus fungsi (item dan statement sama-sama valid di dalam blok),
+// ditambah blok kosong sebanyak level indentasi agar lebar baris tetap mendekati aslinya.
+fn wrap_range(selected: &[String], indent: &str) -> String {
+    let depth = wrapper_depth(indent);
+    let mut source = String::from(RANGE_WRAPPER);
+    source.push('\n');
+    for _ in 1..depth {
+        source.push_str("{\n");
+    }
+    for line in selected {
+        let line = line
+            .strip_prefix(indent)
+            .unwrap_or_else(|| line.trim_start());
+        source.push_str(line);
+        source.push('\n');
+    }
+    for _ in 0..depth {
+        source.push_str("}\n");
+    }
+    source
+}
+
+/// `true` untuk baris yang diawali di dalam string literal, yaitu lanjutan literal multi-baris.
+fn string_continuations(lines: &[String]) -> Vec<bool> {
+    #[derive(Clone, Copy)]
+    enum State {
+        Code,
+        Comment(usize),
+        Str,
+        RawStr(usize),
+    }
+
+    let mut state = State::Code;
+    let mut result = Vec::with_capacity(lines.len());
+    for line in lines {
+        result.push(matches!(state, State::Str | State::RawStr(_)));
+        let chars = line.chars().collect::<Vec<_>>();
+        let mut idx = 0;
+        while idx < chars.len() {
+            let next = chars.get(idx + 1).copied();
+            match state {
+                State::Code => match chars[idx] {
+                    '/' if next == Some('/') => break,
+                    '/' if next == Some('*') => {
+                        state = State::Comment(1);
+                        idx += 1;
+                    }
+                    '"' => state = State::Str,
+                    '\'' if next == Some('\\') => {
+                        idx += 3;
+                        while idx < chars.len() && chars[idx] != '\'' {
+                            idx += 1;
+                        }
+                    }
+                    // Char literal satu karakter; selain itu lifetime atau label.
+                    '\'' if chars.get(idx + 2) == Some(&'\'') => idx += 2,
+                    ch if ch.is_alphanumeric() || ch == '_' => {
+                        let word_start = idx;
+                        while chars
+                            .get(idx + 1)
+                            .is_some_and(|ch| ch.is_alphanumeric() || *ch == '_')
+                        {
+                            idx += 1;
+                        }
+                        let word = chars[word_start..=idx].iter().collect::<String>();
+                        if matches!(word.as_str(), "r" | "br" | "cr") {
+                            let hashes =
+                                chars[idx + 1..].iter().take_while(|ch| **ch == '#').count();
+                            if chars.get(idx + 1 + hashes) == Some(&'"') {
+                                state = State::RawStr(hashes);
+                                idx += 1 + hashes;
+                            }
+                        }
+                    }
+                    _ => {}
+                },
+                State::Comment(depth) => {
+                    if chars[idx] == '*' && next == Some('/') {
+                        state = if depth == 1 {
+                            State::Code
+                        } else {
+                            State::Comment(depth - 1)
+                        };
+                        idx += 1;
+                    } else if chars[idx] == '/' && next == Some('*') {
+                        state = State::Comment(depth + 1);
+                        idx += 1;
+                    }
+                }
+                State::Str => match chars[idx] {
+                    '\\' => idx += 1,
+                    '"' => state = State::Code,
+                    _ => {}
+                },
+                State::RawStr(hashes) => {
+                    if chars[idx] == '"'
+                        && chars[idx + 1..]
+                            .iter()
+                            .take(hashes)
+                            .filter(|ch| **ch == '#')
+                            .count()
+                            == hashes
+                    {
+                        state = State::Code;
+                        idx += hashes;
+                    }
+                }
+            }
+            idx += 1;
+        }
+    }
+    result
+}
+
+fn wrapper_depth(indent: &str) -> usize {
+    let width = indent
+        .chars()
+        .map(|ch| if ch == '\t' { 4 } else { 1 })
+        .sum::<usize>();
+    (width / 4).max(1)
+}
+
+fn leading_whitespace(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn detects_multiline_string_continuations() {
+        let source = lines(concat!(
+            "fn a() {\n",
+            "    let s = \"satu\n",
+            "  dua \\\" // bukan komentar\n",
+            "tiga\";\n",
+            "    let c = '\"'; let q = '\\''; // \"\n",
+            "    let r = r#\"x\"\n",
+            "y\"#;\n",
+            "    let l: &'static str = \"\";\n",
+            "}",
         ));
+        assert_eq!(
+            string_continuations(&source),
+            vec![false, false, true, true, false, false, true, false, false]
+        );
     }
 
-    String::from_utf8(output.stdout).context("output rustfmt bukan UTF-8 valid")
+    #[test]
+    fn range_through_multiline_string_is_rejected() {
+        let source = lines("fn a() {\n    let s = \"satu\n  dua\";\n    let x = 1;\n}");
+        for (start, end) in [(1, 1), (1, 2), (2, 3)] {
+            let range = FormatRange::lines(&source, start, end);
+            assert!(spawn_rustfmt(&source, &range, None).is_err());
+        }
+        // Baris setelah literal tetap boleh diformat sendiri.
+        let range = FormatRange::lines(&source, 3, 3);
+        assert!(spawn_rustfmt(&source, &range, None).is_ok());
+    }
 }
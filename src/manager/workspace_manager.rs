@@ -1,217 +1,206 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, anyhow};
+use serde_json::Value;
 
-pub fn add_workspace_member(member: &str) -> Result<String> {
-    edit_workspace_members(member, true)
+use super::manifest::{CargoManifest, DependencyEntry};
+
+#[derive(Debug, Clone)]
+pub struct WorkspacePackage {
+    pub name: String,
+    pub manifest_path: PathBuf,
+    pub dependencies: Vec<DependencyEntry>,
 }
 
-pub fn remove_workspace_member(member: &str) -> Result<String> {
-    edit_workspace_members(member, false)
+#[derive(Debug, Clone)]
+pub struct CargoWorkspace {
+    pub root_manifest: PathBuf,
+    pub packages: Vec<WorkspacePackage>,
 }
 
-fn edit_workspace_members(member: &str, add: bool) -> Result<String> {
-    let member = member.trim();
-    if member.is_empty() {
-        anyhow::bail!("path member workspace tidak boleh kosong");
+impl CargoWorkspace {
+    pub fn package(&self, name: &str) -> Option<&WorkspacePackage> {
+        self.packages.iter().find(|package| package.name == name)
     }
 
-    let manifest = PathBuf::from("Cargo.toml");
-    let content = fs::read_to_string(&manifest)
-        .with_context(|| format!("gagal membaca {}", manifest.display()))?;
+    pub fn is_multi_package(&self) -> bool {
+        self.packages.len() > 1
+    }
 
-    let mut lines = content.lines().map(str::to_string).collect::<Vec<_>>();
-    let ws_start = find_section_start(&lines, "workspace");
+    pub fn cargo_args(&self, package: Option<&str>) -> Vec<String> {
+        let mut args = vec![
+            String::from("--manifest-path"),
+            self.root_manifest.display().to_string(),
+        ];
+        if let Some(package) = package {
+            args.push(String::from("-p"));
+            args.push(package.to_string());
+        }
+        args
+    }
+}
 
-    let changed = if let Some(start) = ws_start {
-        let end = find_section_end(&lines, start);
-        upsert_member_in_workspace_block(&mut lines, start, end, member, add)
-    } else {
-        append_workspace_block(&mut lines, member, add)
+pub fn discover_workspace() -> Result<CargoWorkspace> {
+    let (root_manifest, manifests) = match metadata_manifests() {
+        Ok(found) => found,
+        Err(_) => manifest_scan()?,
     };
 
-    if !changed {
-        return Ok(if add {
-            format!("Member '{}' sudah ada", member)
-        } else {
-            format!("Member '{}' tidak ditemukan", member)
+    let mut packages = Vec::new();
+    for manifest_path in manifests {
+        let Ok(manifest) = CargoManifest::load(&manifest_path) else {
+            continue;
+        };
+        let Some(name) = manifest.package_name().map(ToOwned::to_owned) else {
+            continue;
+        };
+        packages.push(WorkspacePackage {
+            name,
+            dependencies: manifest.dependencies(),
+            manifest_path,
         });
     }
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
 
-    let new_content = lines.join("\n") + "\n";
-    fs::write(&manifest, new_content)
-        .with_context(|| format!("gagal menulis {}", manifest.display()))?;
-
-    Ok(if add {
-        format!("Member workspace ditambahkan: {}", member)
-    } else {
-        format!("Member workspace dihapus: {}", member)
+    Ok(CargoWorkspace {
+        root_manifest,
+        packages,
     })
 }
 
-fn append_workspace_block(lines: &mut Vec<String>, member: &str, add: bool) -> bool {
-    if !add {
-        return false;
-    }
-    if !lines.is_empty() && !lines.last().is_some_and(|l| l.is_empty()) {
-        lines.push(String::new());
+fn metadata_manifests() -> Result<(PathBuf, Vec<PathBuf>)> {
+    let output = Command::new("cargo")
+        .args([
+            "metadata",
+            "--no-deps",
+            "--format-version",
+            "1",
+            "--offline",
+        ])
+        .output()
+        .context("gagal menjalankan cargo metadata")?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "cargo metadata gagal: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
     }
-    lines.push("[workspace]".to_string());
-    lines.push("members = [".to_string());
-    lines.push(format!("    \"{}\",", member));
-    lines.push("]".to_string());
-    true
-}
-
-fn upsert_member_in_workspace_block(
-    lines: &mut Vec<String>,
-    start: usize,
-    end: usize,
-    member: &str,
-    add: bool,
-) -> bool {
-    let members_line = (start + 1..end)
-        .find(|&idx| lines[idx].trim_start().starts_with("members") && lines[idx].contains('='));
-
-    let Some(members_start) = members_line else {
-        if !add {
-            return false;
-        }
-        lines.insert(end, "members = [".to_string());
-        lines.insert(end + 1, format!("    \"{}\",", member));
-        lines.insert(end + 2, "]".to_string());
-        return true;
-    };
 
-    let open = members_start;
-    let mut close = members_start;
-
-    if !lines[members_start].contains('[') {
-        return false;
-    }
+    let value = serde_json::from_slice::<Value>(&output.stdout)
+        .context("output cargo metadata tidak valid")?;
+    let root = value
+        .get("workspace_root")
+        .and_then(Value::as_str)
+        .map(PathBuf::from)
+        .ok_or_else(|| anyhow!("cargo metadata tanpa workspace_root"))?;
+    let manifests = value
+        .get("packages")
+        .and_then(Value::as_array)
+        .map(|packages| {
+            packages
+                .iter()
+                .filter_map(|package| package.get("manifest_path").and_then(Value::as_str))
+                .map(PathBuf::from)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    Ok((root.join("Cargo.toml"), manifests))
+}
 
-    if lines[members_start].contains(']') {
-        let parsed = parse_member_list_inline(&lines[members_start]);
-        return write_inline_members(lines, members_start, parsed, member, add);
+// Cadangan saat cargo metadata gagal (mis. manifest member rusak): baca manifest langsung.
+fn manifest_scan() -> Result<(PathBuf, Vec<PathBuf>)> {
+    let root_manifest = find_root_manifest()?;
+    let root_dir = root_manifest
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    let root = CargoManifest::load(&root_manifest)?;
+
+    let mut manifests = Vec::new();
+    if root.package_name().is_some() {
+        manifests.push(root_manifest.clone());
     }
-
-    for idx in members_start + 1..end {
-        if lines[idx].contains(']') {
-            close = idx;
-            break;
+    for member in root.workspace_members() {
+        for dir in expand_member_pattern(&root_dir, &member) {
+            let manifest = dir.join("Cargo.toml");
+            if manifest.is_file() && !manifests.contains(&manifest) {
+                manifests.push(manifest);
+            }
         }
     }
+    Ok((root_manifest, manifests))
+}
 
-    if close == open {
-        return false;
-    }
-
-    let mut existing = Vec::new();
-    for line in lines.iter().take(close).skip(open + 1) {
-        if let Some(value) = parse_quoted_value(line) {
-            existing.push(value);
+fn find_root_manifest() -> Result<PathBuf> {
+    let cwd = std::env::current_dir().context("gagal membaca direktori kerja")?;
+    let mut nearest = None;
+    for dir in cwd.ancestors() {
+        let candidate = dir.join("Cargo.toml");
+        if !candidate.is_file() {
+            continue;
         }
-    }
-
-    let changed = if add {
-        if existing.iter().any(|v| v == member) {
-            false
-        } else {
-            existing.push(member.to_string());
-            true
+        if nearest.is_none() {
+            nearest = Some(candidate.clone());
         }
-    } else {
-        let prev = existing.len();
-        existing.retain(|v| v != member);
-        existing.len() != prev
-    };
-
-    if !changed {
-        return false;
-    }
-
-    existing.sort();
-    let mut rebuilt = Vec::new();
-    rebuilt.push(lines[open].clone());
-    rebuilt.extend(existing.into_iter().map(|m| format!("    \"{}\",", m)));
-    rebuilt.push("]".to_string());
-
-    lines.splice(open..=close, rebuilt);
-    true
-}
-
-fn write_inline_members(
-    lines: &mut [String],
-    at: usize,
-    mut existing: Vec<String>,
-    member: &str,
-    add: bool,
-) -> bool {
-    let changed = if add {
-        if existing.iter().any(|v| v == member) {
-            false
-        } else {
-            existing.push(member.to_string());
-            true
+        if CargoManifest::load(&candidate).is_ok_and(|manifest| manifest.is_workspace_root()) {
+            return Ok(candidate);
         }
-    } else {
-        let prev = existing.len();
-        existing.retain(|v| v != member);
-        existing.len() != prev
-    };
-
-    if !changed {
-        return false;
     }
-
-    existing.sort();
-    let body = existing
-        .into_iter()
-        .map(|m| format!("\"{}\"", m))
-        .collect::<Vec<_>>()
-        .join(", ");
-    lines[at] = format!("members = [{}]", body);
-    true
+    nearest.ok_or_else(|| anyhow!("Cargo.toml tidak ditemukan"))
 }
 
-fn parse_member_list_inline(line: &str) -> Vec<String> {
-    let Some(open) = line.find('[') else {
-        return Vec::new();
+fn expand_member_pattern(root: &Path, pattern: &str) -> Vec<PathBuf> {
+    let Some(prefix) = pattern.strip_suffix("/*") else {
+        return vec![root.join(pattern)];
     };
-    let Some(close) = line.rfind(']') else {
+    let Ok(entries) = fs::read_dir(root.join(prefix)) else {
         return Vec::new();
     };
-    line[open + 1..close]
-        .split(',')
-        .filter_map(|item| {
-            let v = item.trim().trim_matches('"');
-            if v.is_empty() {
-                None
-            } else {
-                Some(v.to_string())
-            }
-        })
-        .collect::<Vec<_>>()
+    let mut dirs = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect::<Vec<_>>();
+    dirs.sort();
+    dirs
 }
 
-fn parse_quoted_value(line: &str) -> Option<String> {
-    let start = line.find('"')?;
-    let end = line[start + 1..].find('"')? + start + 1;
-    Some(line[start + 1..end].to_string())
+pub fn add_workspace_member(member: &str) -> Result<String> {
+    edit_workspace_members(member, true)
 }
 
-fn find_section_start(lines: &[String], name: &str) -> Option<usize> {
-    let needle = format!("[{}]", name);
-    lines.iter().position(|line| line.trim() == needle)
+pub fn remove_workspace_member(member: &str) -> Result<String> {
+    edit_workspace_members(member, false)
 }
 
-fn find_section_end(lines: &[String], start: usize) -> usize {
-    for (idx, line) in lines.iter().enumerate().skip(start + 1) {
-        let t = line.trim();
-        if t.starts_with('[') && t.ends_with(']') {
-            return idx;
-        }
+fn edit_workspace_members(member: &str, add: bool) -> Result<String> {
+    let member = member.trim();
+    if member.is_empty() {
+        anyhow::bail!("path member workspace tidak boleh kosong");
+    }
+
+    let mut manifest = CargoManifest::load(&find_root_manifest()?)?;
+    let changed = if add {
+        manifest.add_workspace_member(member)?
+    } else {
+        manifest.remove_workspace_member(member)?
+    };
+
+    if !changed {
+        return Ok(if add {
+            format!("Member '{}' sudah ada", member)
+        } else {
+            format!("Member '{}' tidak ditemukan", member)
+        });
     }
-    lines.len()
+
+    manifest.save()?;
+
+    Ok(if add {
+        format!("Member workspace ditambahkan: {}", member)
+    } else {
+        format!("Member workspace dihapus: {}", member)
+    })
 }
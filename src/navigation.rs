@@ -0,0 +1,330 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde_json::{Value, json};
+
+use crate::config;
+
+const BOOKMARKS_FILE: &str = "bookmarks.json";
+const JUMP_LIMIT: usize = 100;
+/// Perpindahan cursor sejauh ini (atau ke file lain) dicatat di jump list.
+const JUMP_DISTANCE: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub path: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Location {
+    pub fn is_far_from(&self, other: &Location) -> bool {
+        self.path != other.path || self.line.abs_diff(other.line) >= JUMP_DISTANCE
+    }
+}
+
+/// Riwayat back/forward ala browser: `position == entries.len()` berarti berada di ujung.
+#[derive(Debug, Default)]
+pub struct JumpList {
+    entries: Vec<Location>,
+    position: usize,
+}
+
+impl JumpList {
+    /// Catat asal lompatan; riwayat forward dibuang.
+    pub fn record(&mut self, from: Location) {
+        self.entries.truncate(self.position);
+        if self.entries.last() != Some(&from) {
+            self.entries.push(from);
+        }
+        if self.entries.len() > JUMP_LIMIT {
+            self.entries.remove(0);
+        }
+        self.position = self.entries.len();
+    }
+
+    pub fn back(&mut self, current: Location) -> Option<Location> {
+        if self.position == 0 {
+            return None;
+        }
+        // Posisi sekarang disimpan supaya forward bisa kembali ke sini.
+        if self.position == self.entries.len() {
+            self.entries.push(current);
+        } else {
+            self.entries[self.position] = current;
+        }
+        self.position -= 1;
+        Some(self.entries[self.position].clone())
+    }
+
+    pub fn forward(&mut self, current: Location) -> Option<Location> {
+        if self.position + 1 >= self.entries.len() {
+            return None;
+        }
+        self.entries[self.position] = current;
+        self.position += 1;
+        Some(self.entries[self.position].clone())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bookmark {
+    pub name: String,
+    pub line: usize,
+    /// Isi baris saat bookmark dibuat; dipakai untuk menemukan baris lagi setelah diedit.
+    pub text: String,
+}
+
+impl Bookmark {
+    /// Baris bookmark di buffer sekarang: baris tersimpan bila isinya masih sama, kalau
+    /// tidak baris terdekat yang isinya sama, kalau tidak ada tetap baris tersimpan.
+    pub fn resolve(&self, lines: &[String]) -> usize {
+        let last = lines.len().saturating_sub(1);
+        let matches = |row: usize| lines.get(row).is_some_and(|line| line.trim() == self.text);
+        if self.text.is_empty() || matches(self.line) {
+            return self.line.min(last);
+        }
+        (1..lines.len())
+            .flat_map(|distance| [self.line.checked_sub(distance), Some(self.line + distance)])
+            .flatten()
+            .find(|&row| matches(row))
+            .unwrap_or(self.line)
+            .min(last)
+    }
+}
+
+/// Bookmark bernama per file, disimpan di `bookmarks.json` pada folder konfigurasi.
+#[derive(Debug, Default)]
+pub struct BookmarkStore {
+    files: BTreeMap<PathBuf, Vec<Bookmark>>,
+}
+
+impl BookmarkStore {
+    pub fn load() -> Self {
+        let mut store = Self::default();
+        let Some(Value::Object(files)) = config::load_json(BOOKMARKS_FILE) else {
+            return store;
+        };
+        for (path, bookmarks) in files {
+            let bookmarks = bookmarks
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|bookmark| {
+                    Some(Bookmark {
+                        name: bookmark["name"].as_str()?.to_string(),
+                        line: bookmark["line"].as_u64()? as usize,
+                        text: bookmark["text"].as_str().unwrap_or_default().to_string(),
+                    })
+                })
+                .collect::<Vec<_>>();
+            if !bookmarks.is_empty() {
+                store.files.insert(PathBuf::from(path), bookmarks);
+            }
+        }
+        store
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let files = self
+            .files
+            .iter()
+            .map(|(path, bookmarks)| {
+                let bookmarks = bookmarks
+                    .iter()
+                    .map(|bookmark| {
+                        json!({
+                            "name": bookmark.name,
+                            "line": bookmark.line,
+                            "text": bookmark.text,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                (path.to_string_lossy().into_owned(), json!(bookmarks))
+            })
+            .collect::<serde_json::Map<_, _>>();
+        config::save_json(BOOKMARKS_FILE, &Value::Object(files))
+    }
+
+    pub fn for_file(&self, path: &Path) -> &[Bookmark] {
+        self.files
+            .get(&absolute(path))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Semua bookmark, urut per file lalu baris.
+    pub fn all(&self) -> Vec<(PathBuf, Bookmark)> {
+        self.files
+            .iter()
+            .flat_map(|(path, bookmarks)| {
+                bookmarks
+                    .iter()
+                    .map(|bookmark| (path.clone(), bookmark.clone()))
+            })
+            .collect()
+    }
+
+    /// Bookmark yang ada di baris `row` buffer sekarang.
+    pub fn at_line(&self, path: &Path, lines: &[String], row: usize) -> Option<&Bookmark> {
+        self.for_file(path)
+            .iter()
+            .find(|bookmark| bookmark.resolve(lines) == row)
+    }
+
+    pub fn add(&mut self, path: &Path, name: String, lines: &[String], row: usize) {
+        let text = lines
+            .get(row)
+            .map(|line| line.trim().to_string())
+            .unwrap_or_default();
+        let bookmarks = self.files.entry(absolute(path)).or_default();
+        bookmarks.retain(|bookmark| bookmark.name != name);
+        bookmarks.push(Bookmark {
+            name,
+            line: row,
+            text,
+        });
+        bookmarks.sort_by_key(|bookmark| bookmark.line);
+    }
+
+    pub fn remove(&mut self, path: &Path, name: &str) -> bool {
+        let path = absolute(path);
+        let Some(bookmarks) = self.files.get_mut(&path) else {
+            return false;
+        };
+        let before = bookmarks.len();
+        bookmarks.retain(|bookmark| bookmark.name != name);
+        let removed = bookmarks.len() != before;
+        if bookmarks.is_empty() {
+            self.files.remove(&path);
+        }
+        removed
+    }
+
+    /// Simpan posisi baris terbaru (dipanggil saat file disimpan). `true` bila ada yang berubah.
+    pub fn reanchor(&mut self, path: &Path, lines: &[String]) -> bool {
+        let Some(bookmarks) = self.files.get_mut(&absolute(path)) else {
+            return false;
+        };
+        let mut changed = false;
+        for bookmark in bookmarks.iter_mut() {
+            let line = bookmark.resolve(lines);
+            let text = lines
+                .get(line)
+                .map(|line| line.trim().to_string())
+                .unwrap_or_default();
+            if line != bookmark.line || text != bookmark.text {
+                bookmark.line = line;
+                bookmark.text = text;
+                changed = true;
+            }
+        }
+        bookmarks.sort_by_key(|bookmark| bookmark.line);
+        changed
+    }
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(line: usize) -> Location {
+        Location {
+            path: PathBuf::from("a.rs"),
+            line,
+            column: 0,
+        }
+    }
+
+    fn lines(text: &[&str]) -> Vec<String> {
+        text.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn back_then_forward_round_trips() {
+        let mut jumps = JumpList::default();
+        jumps.record(at(1));
+        jumps.record(at(20));
+
+        assert_eq!(jumps.back(at(40)), Some(at(20)));
+        assert_eq!(jumps.back(at(21)), Some(at(1)));
+        assert_eq!(jumps.back(at(2)), None);
+        // Posisi saat back dipanggil menggantikan entri lama, jadi forward kembali ke sana.
+        assert_eq!(jumps.forward(at(2)), Some(at(21)));
+        assert_eq!(jumps.forward(at(21)), Some(at(40)));
+        assert_eq!(jumps.forward(at(40)), None);
+    }
+
+    #[test]
+    fn record_truncates_forward_history() {
+        let mut jumps = JumpList::default();
+        jumps.record(at(1));
+        jumps.record(at(20));
+        jumps.record(at(40));
+        assert_eq!(jumps.back(at(60)), Some(at(40)));
+        assert_eq!(jumps.back(at(40)), Some(at(20)));
+
+        jumps.record(at(25));
+        assert_eq!(jumps.forward(at(80)), None);
+        assert_eq!(jumps.back(at(80)), Some(at(25)));
+        assert_eq!(jumps.back(at(25)), Some(at(1)));
+        assert_eq!(jumps.back(at(1)), None);
+    }
+
+    #[test]
+    fn record_skips_duplicates_and_caps_history() {
+        let mut jumps = JumpList::default();
+        jumps.record(at(0));
+        jumps.record(at(0));
+        assert_eq!(jumps.entries.len(), 1);
+
+        for line in 1..JUMP_LIMIT + 5 {
+            jumps.record(at(line * JUMP_DISTANCE));
+        }
+        assert_eq!(jumps.entries.len(), JUMP_LIMIT);
+        let mut oldest = None;
+        let mut current = at(usize::MAX);
+        while let Some(location) = jumps.back(current) {
+            current = location.clone();
+            oldest = Some(location);
+        }
+        assert_eq!(oldest, Some(at(5 * JUMP_DISTANCE)));
+    }
+
+    #[test]
+    fn bookmark_follows_line_after_insertions_above() {
+        let bookmark = Bookmark {
+            name: String::from("main"),
+            line: 1,
+            text: String::from("fn main() {"),
+        };
+        let before = lines(&["use std::fs;", "fn main() {", "}"]);
+        assert_eq!(bookmark.resolve(&before), 1);
+
+        let after = lines(&["use std::fs;", "use std::io;", "", "fn main() {", "}"]);
+        assert_eq!(bookmark.resolve(&after), 3);
+        // Baris yang hilang tetap di posisi tersimpan, dibatasi panjang buffer.
+        assert_eq!(bookmark.resolve(&lines(&["x"])), 0);
+    }
+
+    #[test]
+    fn reanchor_stores_resolved_line() {
+        let path = Path::new("/tmp/action-codex-navigation.rs");
+        let mut store = BookmarkStore::default();
+        store.add(path, String::from("b"), &lines(&["a", "  target", "c"]), 1);
+
+        let edited = lines(&["baru", "a", "  target", "c"]);
+        assert!(store.reanchor(path, &edited));
+        assert_eq!(store.for_file(path)[0].line, 2);
+        assert_eq!(
+            store.at_line(path, &edited, 2).map(|b| b.name.as_str()),
+            Some("b")
+        );
+        assert!(!store.reanchor(path, &edited));
+    }
+}
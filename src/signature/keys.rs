@@ -0,0 +1,682 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow};
+use ed25519_dalek::pkcs8::spki::der::pem::LineEnding;
+use ed25519_dalek::pkcs8::{DecodePrivateKey, DecodePublicKey, EncodePrivateKey, EncodePublicKey};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use pkcs8::EncryptedPrivateKeyInfo;
+use pkcs8::rand_core::OsRng;
+use serde_json::{Value, json};
+
+use super::verify::key_fingerprint;
+use super::{KEY_DIR, create_private_dir, restrict_permissions, write_private_file};
+use crate::config;
+
+const SETTINGS_FILE: &str = "signing.json";
+const TRUSTED_KEYS_FILE: &str = "trusted_keys";
+const GLOBAL_KEY_DIR: &str = "keys";
+pub const DEFAULT_KEY_NAME: &str = "ed25519_signing_key";
+const ENCRYPTED_LABEL: &str = "ENCRYPTED PRIVATE KEY";
+const PUBLIC_SUFFIX: &str = ".pub.pem";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyLocation {
+    Workspace,
+    Global,
+}
+
+impl KeyLocation {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Workspace => "workspace",
+            Self::Global => "global",
+        }
+    }
+
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::Workspace => Self::Global,
+            Self::Global => Self::Workspace,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SigningSettings {
+    pub enabled: bool,
+    pub location: KeyLocation,
+    pub active_key: String,
+}
+
+impl Default for SigningSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            location: KeyLocation::Global,
+            active_key: String::from(DEFAULT_KEY_NAME),
+        }
+    }
+}
+
+impl SigningSettings {
+    /// `None` bila `signing.json` belum pernah disimpan.
+    pub fn load() -> Option<Self> {
+        config::load_json(SETTINGS_FILE).map(|value| Self::from_value(&value))
+    }
+
+    fn from_value(value: &Value) -> Self {
+        let mut settings = Self::default();
+        if let Some(enabled) = value["enabled"].as_bool() {
+            settings.enabled = enabled;
+        }
+        settings.location = match value["location"].as_str() {
+            Some("workspace") => KeyLocation::Workspace,
+            Some("global") => KeyLocation::Global,
+            _ => settings.location,
+        };
+        if let Some(name) = value["active_key"].as_str()
+            && is_valid_key_name(name)
+        {
+            settings.active_key = name.to_string();
+        }
+        settings
+    }
+
+    fn to_value(&self) -> Value {
+        json!({
+            "enabled": self.enabled,
+            "location": self.location.label(),
+            "active_key": self.active_key,
+        })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        config::save_json(SETTINGS_FILE, &self.to_value())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KeyInfo {
+    pub name: String,
+    pub fingerprint: Option<String>,
+    pub encrypted: bool,
+    pub unlocked: bool,
+    pub active: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct TrustedKey {
+    pub key: [u8; 32],
+    pub comment: String,
+}
+
+impl TrustedKey {
+    pub fn fingerprint(&self) -> String {
+        key_fingerprint(&self.key)
+    }
+}
+
+pub enum ActiveKey {
+    Ready(SigningKey),
+    Disabled,
+    /// Kunci aktif belum dibuat; `hint` hanya terisi sekali agar tiap simpan tidak dianggap gagal.
+    Missing {
+        hint: Option<String>,
+    },
+}
+
+#[derive(Debug, Default)]
+pub struct KeyStore {
+    settings: SigningSettings,
+    workspace: PathBuf,
+    unlocked: HashMap<PathBuf, SigningKey>,
+    missing_hint_shown: bool,
+}
+
+impl KeyStore {
+    /// Kunci lokasi `workspace` disimpan di `.action-codex` milik `workspace` (root explorer),
+    /// bukan folder tempat editor dijalankan.
+    pub fn load(workspace: &Path) -> Self {
+        let saved = SigningSettings::load();
+        let mut store = Self {
+            settings: saved.clone().unwrap_or_default(),
+            workspace: workspace.to_path_buf(),
+            unlocked: HashMap::new(),
+            missing_hint_shown: false,
+        };
+        if saved.is_none() {
+            store.adopt_legacy_key();
+        }
+        store
+    }
+
+    /// Sebelum ada `signing.json`, kunci dibuat otomatis di `.action-codex/ed25519_signing_key.pem`
+    /// workspace. Kunci itu disalin sekali ke folder kunci global agar tetap dipakai dan signature
+    /// lamanya tetap dipercaya; bila tidak bisa, lokasi workspace dipakai apa adanya.
+    fn adopt_legacy_key(&mut self) {
+        let legacy = self
+            .workspace
+            .join(KEY_DIR)
+            .join(format!("{}.pem", DEFAULT_KEY_NAME));
+        if !legacy.is_file() {
+            return;
+        }
+        let migrated = config::user_dir()
+            .ok_or_else(|| anyhow!("folder konfigurasi pengguna tidak ditemukan"))
+            .and_then(|dir| migrate_legacy_key(&legacy, &dir.join(GLOBAL_KEY_DIR)));
+        if migrated.is_err() {
+            self.settings.location = KeyLocation::Workspace;
+        }
+        let _ = self.settings.save();
+    }
+
+    pub fn settings(&self) -> &SigningSettings {
+        &self.settings
+    }
+
+    pub fn key_dir(&self) -> PathBuf {
+        match self.settings.location {
+            KeyLocation::Workspace => self.workspace.join(KEY_DIR),
+            KeyLocation::Global => config::dir().join(GLOBAL_KEY_DIR),
+        }
+    }
+
+    fn key_path(&self, name: &str) -> PathBuf {
+        self.key_dir().join(format!("{}.pem", name))
+    }
+
+    fn public_path(&self, name: &str) -> PathBuf {
+        self.key_dir().join(format!("{}{}", name, PUBLIC_SUFFIX))
+    }
+
+    pub fn keys(&self) -> Vec<KeyInfo> {
+        let mut names = fs::read_dir(self.key_dir())
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|entry| {
+                        let name = entry.file_name().to_string_lossy().to_string();
+                        if name.ends_with(PUBLIC_SUFFIX) {
+                            return None;
+                        }
+                        name.strip_suffix(".pem").map(ToOwned::to_owned)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        names.sort();
+
+        names
+            .into_iter()
+            .map(|name| {
+                let path = self.key_path(&name);
+                let encrypted = fs::read_to_string(&path)
+                    .map(|pem| is_encrypted_pem(&pem))
+                    .unwrap_or(false);
+                KeyInfo {
+                    fingerprint: self
+                        .public_key(&name)
+                        .map(|key| key_fingerprint(&key.to_bytes())),
+                    encrypted,
+                    unlocked: self.unlocked.contains_key(&path),
+                    active: name == self.settings.active_key,
+                    name,
+                }
+            })
+            .collect()
+    }
+
+    pub fn public_key(&self, name: &str) -> Option<VerifyingKey> {
+        if let Some(key) = self.unlocked.get(&self.key_path(name)) {
+            return Some(key.verifying_key());
+        }
+        if let Ok(pem) = fs::read_to_string(self.public_path(name))
+            && let Ok(key) = VerifyingKey::from_public_key_pem(&pem)
+        {
+            return Some(key);
+        }
+        let pem = fs::read_to_string(self.key_path(name)).ok()?;
+        SigningKey::from_pkcs8_pem(&pem)
+            .ok()
+            .map(|key| key.verifying_key())
+    }
+
+    fn own_public_keys(&self) -> Vec<[u8; 32]> {
+        self.keys()
+            .iter()
+            .filter_map(|info| self.public_key(&info.name))
+            .map(|key| key.to_bytes())
+            .collect()
+    }
+
+    /// Kunci aktif untuk menandatangani. Kunci tidak pernah dibuat diam-diam di sini; pengguna
+    /// membuatnya sendiri lewat dialog kunci.
+    pub fn active_signing_key(&mut self) -> Result<ActiveKey> {
+        if !self.settings.enabled {
+            return Ok(ActiveKey::Disabled);
+        }
+        let name = self.settings.active_key.clone();
+        if !self.key_path(&name).exists() {
+            let hint = (!self.missing_hint_shown)
+                .then(|| format!("belum ada kunci signing {}, buat lewat dialog kunci", name));
+            self.missing_hint_shown = true;
+            return Ok(ActiveKey::Missing { hint });
+        }
+        self.signing_key(&name).map(ActiveKey::Ready)
+    }
+
+    fn signing_key(&mut self, name: &str) -> Result<SigningKey> {
+        let path = self.key_path(name);
+        if let Some(key) = self.unlocked.get(&path) {
+            return Ok(key.clone());
+        }
+        let pem = fs::read_to_string(&path)
+            .with_context(|| format!("gagal membaca kunci {}", path.display()))?;
+        if is_encrypted_pem(&pem) {
+            return Err(anyhow!(
+                "kunci {} terproteksi passphrase, buka dulu lewat dialog kunci",
+                name
+            ));
+        }
+        restrict_permissions(&path, 0o600)?;
+        SigningKey::from_pkcs8_pem(&pem)
+            .map_err(|err| anyhow!("kunci {} tidak valid: {}", path.display(), err))
+    }
+
+    pub fn generate(&mut self, name: &str, passphrase: Option<&str>) -> Result<()> {
+        validate_key_name(name)?;
+        let path = self.key_path(name);
+        if path.exists() {
+            return Err(anyhow!("kunci {} sudah ada", name));
+        }
+        create_private_dir(&self.key_dir())?;
+        let key = SigningKey::from_bytes(&rand::random::<[u8; 32]>());
+        write_private_file(&path, encode_private_pem(&key, passphrase)?.as_bytes())?;
+        let public = key.verifying_key().to_bytes();
+        self.remember(name, key)?;
+        // Kunci workspace bukan dasar kepercayaan, jadi kunci yang baru dibuat di sini dicatat ke
+        // daftar dipercaya global agar signature milik sendiri tetap terverifikasi.
+        if self.settings.location == KeyLocation::Workspace && Self::trusted_keys_path().is_some() {
+            let mut trusted = self.trusted_keys();
+            if !trusted.iter().any(|entry| entry.key == public) {
+                trusted.push(TrustedKey {
+                    key: public,
+                    comment: format!("{} (workspace)", name),
+                });
+                self.write_trusted_keys(&trusted)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn import(&mut self, source: &Path) -> Result<String> {
+        let pem = fs::read_to_string(source)
+            .with_context(|| format!("gagal membaca {}", source.display()))?;
+        let name = source
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .map(|name| {
+                name.strip_suffix(".pem")
+                    .unwrap_or(name.as_str())
+                    .replace(|ch: char| !ch.is_ascii_alphanumeric() && ch != '-', "_")
+            })
+            .ok_or_else(|| anyhow!("path kunci tidak valid"))?;
+        validate_key_name(&name)?;
+        let path = self.key_path(&name);
+        if path.exists() {
+            return Err(anyhow!("kunci {} sudah ada", name));
+        }
+
+        let plain = if is_encrypted_pem(&pem) {
+            let (_, der) = pkcs8::der::pem::decode_vec(pem.as_bytes())
+                .map_err(|err| anyhow!("PEM tidak valid: {}", err))?;
+            EncryptedPrivateKeyInfo::try_from(der.as_slice())
+                .map_err(|err| anyhow!("kunci terenkripsi tidak valid: {}", err))?;
+            None
+        } else {
+            Some(
+                SigningKey::from_pkcs8_pem(&pem)
+                    .map_err(|err| anyhow!("bukan kunci ED25519 PKCS#8: {}", err))?,
+            )
+        };
+
+        create_private_dir(&self.key_dir())?;
+        write_private_file(&path, pem.as_bytes())?;
+        if let Some(key) = plain {
+            self.remember(&name, key)?;
+        }
+        Ok(name)
+    }
+
+    pub fn export_public(&self, name: &str, dest: &Path) -> Result<()> {
+        let key = self
+            .public_key(name)
+            .ok_or_else(|| anyhow!("public key {} belum diketahui, buka kunci dulu", name))?;
+        let pem = key
+            .to_public_key_pem(LineEnding::LF)
+            .map_err(|err| anyhow!("gagal encode public key: {}", err))?;
+        fs::write(dest, pem).with_context(|| format!("gagal menulis {}", dest.display()))
+    }
+
+    pub fn export_private(&self, name: &str, dest: &Path) -> Result<()> {
+        let pem = fs::read(self.key_path(name))
+            .with_context(|| format!("gagal membaca kunci {}", name))?;
+        write_private_file(dest, &pem)
+    }
+
+    pub fn unlock(&mut self, name: &str, passphrase: &str) -> Result<()> {
+        let pem = fs::read_to_string(self.key_path(name))
+            .with_context(|| format!("gagal membaca kunci {}", name))?;
+        let key = if is_encrypted_pem(&pem) {
+            SigningKey::from_pkcs8_encrypted_pem(&pem, passphrase)
+                .map_err(|_| anyhow!("passphrase salah"))?
+        } else {
+            SigningKey::from_pkcs8_pem(&pem).map_err(|err| anyhow!("kunci tidak valid: {}", err))?
+        };
+        self.remember(name, key)
+    }
+
+    pub fn set_passphrase(&mut self, name: &str, passphrase: Option<&str>) -> Result<()> {
+        let key = self.signing_key(name)?;
+        let path = self.key_path(name);
+        fs::write(&path, encode_private_pem(&key, passphrase)?)
+            .with_context(|| format!("gagal menulis {}", path.display()))?;
+        restrict_permissions(&path, 0o600)
+    }
+
+    pub fn set_active(&mut self, name: &str) -> Result<()> {
+        self.missing_hint_shown = false;
+        self.settings.active_key = name.to_string();
+        self.settings.save()
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) -> Result<()> {
+        self.settings.enabled = enabled;
+        self.settings.save()
+    }
+
+    pub fn set_location(&mut self, location: KeyLocation) -> Result<()> {
+        self.missing_hint_shown = false;
+        self.settings.location = location;
+        self.settings.save()
+    }
+
+    /// Daftar kunci dipercaya selalu di folder konfigurasi pengguna, bukan di `.action-codex`
+    /// workspace yang bisa ikut ter-clone bersama repository.
+    fn trusted_keys_path() -> Option<PathBuf> {
+        config::user_dir().map(|dir| dir.join(TRUSTED_KEYS_FILE))
+    }
+
+    pub fn trusted_keys(&self) -> Vec<TrustedKey> {
+        Self::trusted_keys_path()
+            .map(|path| read_trusted_keys(&path))
+            .unwrap_or_default()
+    }
+
+    /// Public key kunci di folder kunci global, apa pun lokasi kunci aktif.
+    pub fn global_public_keys(&self) -> Vec<[u8; 32]> {
+        if config::user_dir().is_none() {
+            return Vec::new();
+        }
+        let global = Self {
+            settings: SigningSettings {
+                location: KeyLocation::Global,
+                ..self.settings.clone()
+            },
+            workspace: PathBuf::new(),
+            unlocked: HashMap::new(),
+            missing_hint_shown: false,
+        };
+        global.own_public_keys()
+    }
+
+    pub fn trust(&self, input: &str) -> Result<TrustedKey> {
+        let input = input.trim();
+        let key = match decode_hex_key(input) {
+            Some(key) => key,
+            None => {
+                let pem = fs::read_to_string(input)
+                    .with_context(|| format!("bukan hex 64 karakter atau file PEM: {}", input))?;
+                VerifyingKey::from_public_key_pem(&pem)
+                    .map_err(|err| anyhow!("public key PEM tidak valid: {}", err))?
+                    .to_bytes()
+            }
+        };
+        let comment = Path::new(input)
+            .file_name()
+            .filter(|_| input.len() != 64)
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let mut trusted = self.trusted_keys();
+        if trusted.iter().any(|entry| entry.key == key) {
+            return Err(anyhow!("kunci sudah dipercaya"));
+        }
+        let entry = TrustedKey { key, comment };
+        trusted.push(entry.clone());
+        self.write_trusted_keys(&trusted)?;
+        Ok(entry)
+    }
+
+    pub fn untrust(&self, key: &[u8; 32]) -> Result<()> {
+        let mut trusted = self.trusted_keys();
+        trusted.retain(|entry| &entry.key != key);
+        self.write_trusted_keys(&trusted)
+    }
+
+    fn write_trusted_keys(&self, trusted: &[TrustedKey]) -> Result<()> {
+        let path = Self::trusted_keys_path()
+            .ok_or_else(|| anyhow!("folder konfigurasi pengguna tidak ditemukan"))?;
+        if let Some(dir) = path.parent() {
+            create_private_dir(dir)?;
+        }
+        let mut text = String::from("# public key ED25519 (hex) yang dipercaya, satu per baris\n");
+        for entry in trusted {
+            let hex = entry
+                .key
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect::<String>();
+            if entry.comment.is_empty() {
+                text.push_str(&format!("{}\n", hex));
+            } else {
+                text.push_str(&format!("{} {}\n", hex, entry.comment));
+            }
+        }
+        fs::write(&path, text).with_context(|| format!("gagal menulis {}", path.display()))
+    }
+
+    fn remember(&mut self, name: &str, key: SigningKey) -> Result<()> {
+        let public_pem = key
+            .verifying_key()
+            .to_public_key_pem(LineEnding::LF)
+            .map_err(|err| anyhow!("gagal encode public key: {}", err))?;
+        let public_path = self.public_path(name);
+        fs::write(&public_path, public_pem)
+            .with_context(|| format!("gagal menulis {}", public_path.display()))?;
+        self.unlocked.insert(self.key_path(name), key);
+        Ok(())
+    }
+}
+
+/// Salin kunci workspace lama ke `global_dir` beserta public key-nya. Kunci global bernama sama
+/// yang sudah ada tidak ditimpa.
+fn migrate_legacy_key(legacy: &Path, global_dir: &Path) -> Result<()> {
+    let pem = fs::read_to_string(legacy)
+        .with_context(|| format!("gagal membaca kunci {}", legacy.display()))?;
+    let key = SigningKey::from_pkcs8_pem(&pem)
+        .map_err(|err| anyhow!("kunci {} tidak valid: {}", legacy.display(), err))?;
+    let path = global_dir.join(format!("{}.pem", DEFAULT_KEY_NAME));
+    if path.exists() {
+        return Ok(());
+    }
+    create_private_dir(global_dir)?;
+    write_private_file(&path, pem.as_bytes())?;
+    let public_pem = key
+        .verifying_key()
+        .to_public_key_pem(LineEnding::LF)
+        .map_err(|err| anyhow!("gagal encode public key: {}", err))?;
+    let public_path = global_dir.join(format!("{}{}", DEFAULT_KEY_NAME, PUBLIC_SUFFIX));
+    fs::write(&public_path, public_pem)
+        .with_context(|| format!("gagal menulis {}", public_path.display()))
+}
+
+pub fn read_trusted_keys(path: &Path) -> Vec<TrustedKey> {
+    let Ok(text) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let mut trusted: Vec<TrustedKey> = Vec::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (hex, comment) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        if let Some(key) = decode_hex_key(hex)
+            && !trusted.iter().any(|entry| entry.key == key)
+        {
+            trusted.push(TrustedKey {
+                key,
+                comment: comment.trim().to_string(),
+            });
+        }
+    }
+    trusted
+}
+
+fn encode_private_pem(key: &SigningKey, passphrase: Option<&str>) -> Result<String> {
+    let pem = match passphrase.filter(|passphrase| !passphrase.is_empty()) {
+        Some(passphrase) => key.to_pkcs8_encrypted_pem(OsRng, passphrase, LineEnding::LF),
+        None => key.to_pkcs8_pem(LineEnding::LF),
+    }
+    .map_err(|err| anyhow!("gagal encode kunci ED25519: {}", err))?;
+    Ok(pem.to_string())
+}
+
+fn is_encrypted_pem(pem: &str) -> bool {
+    pem.contains(&format!("BEGIN {}", ENCRYPTED_LABEL))
+}
+
+fn is_valid_key_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+}
+
+fn validate_key_name(name: &str) -> Result<()> {
+    if is_valid_key_name(name) {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "nama kunci hanya boleh huruf, angka, '-' dan '_': {}",
+            name
+        ))
+    }
+}
+
+fn decode_hex_key(hex: &str) -> Option<[u8; 32]> {
+    if hex.len() != 64 {
+        return None;
+    }
+    let mut key = [0u8; 32];
+    for (idx, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(idx * 2..idx * 2 + 2)?, 16).ok()?;
+    }
+    Some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace_store(name: &str) -> KeyStore {
+        let workspace =
+            std::env::temp_dir().join(format!("action-codex-keys-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&workspace);
+        fs::create_dir_all(&workspace).unwrap();
+        KeyStore {
+            settings: SigningSettings {
+                location: KeyLocation::Workspace,
+                ..SigningSettings::default()
+            },
+            workspace,
+            unlocked: HashMap::new(),
+            missing_hint_shown: false,
+        }
+    }
+
+    #[test]
+    fn workspace_keys_live_under_explorer_root() {
+        let store = workspace_store("dir");
+        assert_eq!(store.key_dir(), store.workspace.join(KEY_DIR));
+    }
+
+    #[test]
+    fn settings_round_trip_keeps_location() {
+        for location in [KeyLocation::Workspace, KeyLocation::Global] {
+            let settings = SigningSettings {
+                enabled: false,
+                location,
+                active_key: String::from("kerja"),
+            };
+            let loaded = SigningSettings::from_value(&settings.to_value());
+            assert_eq!(loaded.location, location);
+            assert!(!loaded.enabled);
+            assert_eq!(loaded.active_key, "kerja");
+        }
+        let fallback = SigningSettings::from_value(&json!({ "location": "lain" }));
+        assert_eq!(fallback.location, SigningSettings::default().location);
+    }
+
+    #[test]
+    fn missing_active_key_is_not_generated_on_save() {
+        let mut store = workspace_store("missing");
+        assert!(matches!(
+            store.active_signing_key(),
+            Ok(ActiveKey::Missing { hint: Some(_) })
+        ));
+        // Petunjuk hanya sekali; simpan berikutnya tidak dilaporkan gagal.
+        assert!(matches!(
+            store.active_signing_key(),
+            Ok(ActiveKey::Missing { hint: None })
+        ));
+        // Tidak ada kunci privat yang dibuat diam-diam.
+        assert!(!store.key_dir().exists());
+    }
+
+    #[test]
+    fn legacy_workspace_key_is_copied_to_global_dir() {
+        let store = workspace_store("legacy");
+        let key = SigningKey::from_bytes(&[5; 32]);
+        let legacy = store.key_dir().join(format!("{}.pem", DEFAULT_KEY_NAME));
+        create_private_dir(&store.key_dir()).unwrap();
+        write_private_file(&legacy, encode_private_pem(&key, None).unwrap().as_bytes()).unwrap();
+
+        let global = store.workspace.join("global-keys");
+        migrate_legacy_key(&legacy, &global).unwrap();
+        let migrated =
+            fs::read_to_string(global.join(format!("{}.pem", DEFAULT_KEY_NAME))).unwrap();
+        assert_eq!(
+            SigningKey::from_pkcs8_pem(&migrated).unwrap().to_bytes(),
+            key.to_bytes()
+        );
+        let public =
+            fs::read_to_string(global.join(format!("{}{}", DEFAULT_KEY_NAME, PUBLIC_SUFFIX)))
+                .unwrap();
+        assert_eq!(
+            VerifyingKey::from_public_key_pem(&public).unwrap(),
+            key.verifying_key()
+        );
+
+        // Kunci global yang sudah ada tidak ditimpa.
+        let other = SigningKey::from_bytes(&[6; 32]);
+        fs::write(&legacy, encode_private_pem(&other, None).unwrap()).unwrap();
+        migrate_legacy_key(&legacy, &global).unwrap();
+        let kept = fs::read_to_string(global.join(format!("{}.pem", DEFAULT_KEY_NAME))).unwrap();
+        assert_eq!(kept, migrated);
+        let _ = fs::remove_dir_all(&store.workspace);
+    }
+}
@@ -0,0 +1,355 @@
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow};
+
+use super::verify::{
+    MarkerBlob, SignatureBlob, is_marker_name, marker_dir, parse_signature_blob, read_marker_blobs,
+};
+use super::{KEY_DIR, MANIFEST_FILE, create_private_dir, manifest_key, project_root};
+
+const MANIFEST_MAGIC: &[u8; 4] = b"ACSM";
+const MANIFEST_VERSION: u8 = 1;
+
+#[derive(Debug, Clone)]
+pub struct ManifestEntry {
+    pub key: String,
+    pub raw: Vec<u8>,
+    pub blob: SignatureBlob,
+}
+
+/// Manifest `<root>/.action-codex/signatures.acsm`; key entri relatif terhadap `root`.
+#[derive(Debug, Clone, Default)]
+pub struct SignatureManifest {
+    root: PathBuf,
+    entries: Vec<ManifestEntry>,
+}
+
+#[derive(Debug, Default)]
+pub struct CompactStats {
+    pub kept: usize,
+    pub removed_history: usize,
+    pub removed_missing: usize,
+}
+
+impl SignatureManifest {
+    pub fn path(root: &Path) -> PathBuf {
+        root.join(KEY_DIR).join(MANIFEST_FILE)
+    }
+
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = Self::path(root);
+        let entries = match fs::read(&path) {
+            Ok(bytes) => {
+                Self::parse(&bytes).with_context(|| format!("manifest {} rusak", path.display()))?
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => {
+                return Err(err).with_context(|| format!("gagal membaca {}", path.display()));
+            }
+        };
+        Ok(Self {
+            root: root.to_path_buf(),
+            entries,
+        })
+    }
+
+    fn parse(bytes: &[u8]) -> Result<Vec<ManifestEntry>> {
+        if bytes.len() < 5 || &bytes[..4] != MANIFEST_MAGIC {
+            return Err(anyhow!("bukan manifest ACSM"));
+        }
+        if bytes[4] != MANIFEST_VERSION {
+            return Err(anyhow!("versi manifest tidak didukung: {}", bytes[4]));
+        }
+
+        let mut entries = Vec::new();
+        let mut pos = 5;
+        while pos < bytes.len() {
+            let key_len = read_len(bytes, &mut pos, 2)?;
+            let key = String::from_utf8(take(bytes, &mut pos, key_len)?.to_vec())
+                .context("key manifest bukan UTF-8")?;
+            let raw_len = read_len(bytes, &mut pos, 4)?;
+            let raw = take(bytes, &mut pos, raw_len)?.to_vec();
+            // Entri dengan versi blob yang tidak dikenal dilewati supaya manifest tetap terbaca.
+            if let Ok(blob) = parse_signature_blob(&raw) {
+                entries.push(ManifestEntry { key, raw, blob });
+            }
+        }
+        Ok(entries)
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn latest(&self, key: &str) -> Option<&ManifestEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.key == key)
+            .max_by_key(|entry| entry.blob.timestamp)
+    }
+
+    pub fn history(&self, key: &str) -> Vec<&ManifestEntry> {
+        let mut history = self
+            .entries
+            .iter()
+            .filter(|entry| entry.key == key)
+            .collect::<Vec<_>>();
+        history.sort_by_key(|entry| std::cmp::Reverse(entry.blob.timestamp));
+        history
+    }
+
+    pub fn append(root: &Path, key: &str, raw: &[u8]) -> Result<()> {
+        create_private_dir(&root.join(KEY_DIR))?;
+        let path = Self::path(root);
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("gagal membuka {}", path.display()))?;
+        let mut out = Vec::new();
+        if file.metadata().map(|meta| meta.len()).unwrap_or(0) == 0 {
+            out.extend_from_slice(MANIFEST_MAGIC);
+            out.push(MANIFEST_VERSION);
+        }
+        encode_entry(&mut out, key, raw)?;
+        file.write_all(&out)
+            .with_context(|| format!("gagal menulis {}", path.display()))
+    }
+
+    pub fn compact(&mut self, keep: usize) -> Result<CompactStats> {
+        let keep = keep.max(1);
+        let mut stats = CompactStats::default();
+        let mut by_key: HashMap<String, Vec<ManifestEntry>> = HashMap::new();
+        for entry in self.entries.drain(..) {
+            by_key.entry(entry.key.clone()).or_default().push(entry);
+        }
+
+        let mut keys = by_key.keys().cloned().collect::<Vec<_>>();
+        keys.sort();
+        for key in keys {
+            let mut history = by_key.remove(&key).unwrap_or_default();
+            if !self.root.join(&key).exists() {
+                stats.removed_missing += history.len();
+                continue;
+            }
+            history.sort_by_key(|entry| entry.blob.timestamp);
+            let excess = history.len().saturating_sub(keep);
+            stats.removed_history += excess;
+            stats.kept += history.len() - excess;
+            self.entries.extend(history.into_iter().skip(excess));
+        }
+
+        self.save()?;
+        Ok(stats)
+    }
+
+    fn save(&self) -> Result<()> {
+        create_private_dir(&self.root.join(KEY_DIR))?;
+        let mut out = Vec::new();
+        out.extend_from_slice(MANIFEST_MAGIC);
+        out.push(MANIFEST_VERSION);
+        for entry in &self.entries {
+            encode_entry(&mut out, &entry.key, &entry.raw)?;
+        }
+
+        let path = Self::path(&self.root);
+        let staging = path.with_extension("acsm.new");
+        fs::write(&staging, out).with_context(|| format!("gagal menulis {}", staging.display()))?;
+        fs::rename(&staging, &path).with_context(|| format!("gagal mengganti {}", path.display()))
+    }
+}
+
+/// Pindahkan marker ACSV v1 di bawah `dir` ke manifest milik `root`.
+pub fn migrate_legacy_markers(root: &Path, dir: &Path) -> Result<usize> {
+    let mut markers = Vec::new();
+    collect_legacy_markers(dir, &mut markers)?;
+
+    let mut migrated = 0;
+    for (marker, blob) in markers {
+        let dir = marker.parent().unwrap_or_else(|| Path::new("."));
+        let Some(file_name) = Path::new(&blob.target).file_name() else {
+            continue;
+        };
+        let raw =
+            fs::read(&marker).with_context(|| format!("gagal membaca {}", marker.display()))?;
+        SignatureManifest::append(root, &manifest_key(root, &dir.join(file_name)), &raw)?;
+        fs::remove_file(&marker)
+            .with_context(|| format!("gagal menghapus marker {}", marker.display()))?;
+        migrated += 1;
+    }
+    Ok(migrated)
+}
+
+fn collect_legacy_markers(dir: &Path, out: &mut Vec<MarkerBlob>) -> Result<()> {
+    out.extend(read_marker_blobs(dir)?);
+    let entries = fs::read_dir(dir).with_context(|| format!("gagal membaca {}", dir.display()))?;
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name == KEY_DIR || name == ".git" || name == "target" || is_marker_name(&name) {
+            continue;
+        }
+        if entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+            collect_legacy_markers(&entry.path(), out)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn run_signatures_command(args: &[OsString]) -> Result<()> {
+    let command = args
+        .first()
+        .and_then(|arg| arg.to_str())
+        .unwrap_or_default();
+    match command {
+        "log" => {
+            let path = args
+                .get(1)
+                .map(PathBuf::from)
+                .ok_or_else(|| anyhow!("Pemakaian: action-codex signatures log <file>"))?;
+            let root = project_root(&path).unwrap_or_else(|| marker_dir(&path).to_path_buf());
+            let key = manifest_key(&root, &path);
+            let manifest = SignatureManifest::load(&root)?;
+            let history = manifest.history(&key);
+            if history.is_empty() {
+                println!("Belum ada riwayat signature untuk {}", key);
+            }
+            for entry in history {
+                println!(
+                    "{} | waktu {} | ACSV v{}",
+                    entry.key, entry.blob.timestamp, entry.blob.version
+                );
+            }
+        }
+        "compact" => {
+            let keep = match args.get(1).and_then(|arg| arg.to_str()) {
+                Some("--keep") => args
+                    .get(2)
+                    .and_then(|arg| arg.to_str())
+                    .and_then(|value| value.parse::<usize>().ok())
+                    .ok_or_else(|| anyhow!("--keep butuh angka"))?,
+                Some(other) => return Err(anyhow!("argumen tidak dikenal: {}", other)),
+                None => 1,
+            };
+            let cwd = std::env::current_dir().context("gagal membaca direktori kerja")?;
+            let mut manifest = SignatureManifest::load(&project_root(&cwd).unwrap_or(cwd))?;
+            let stats = manifest.compact(keep)?;
+            println!(
+                "Manifest dipadatkan: {} entri disimpan, {} riwayat lama dihapus, {} entri file hilang dihapus",
+                stats.kept, stats.removed_history, stats.removed_missing
+            );
+        }
+        "migrate" => {
+            let dir = args
+                .get(1)
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from("."));
+            let root = project_root(&dir).unwrap_or_else(|| dir.clone());
+            let migrated = migrate_legacy_markers(&root, &dir)?;
+            println!(
+                "{} marker ACSV v1 dipindahkan ke {}",
+                migrated,
+                SignatureManifest::path(&root).display()
+            );
+        }
+        _ => {
+            return Err(anyhow!(
+                "Pemakaian: action-codex signatures <log <file> | compact [--keep N] | migrate [dir]>"
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn encode_entry(out: &mut Vec<u8>, key: &str, raw: &[u8]) -> Result<()> {
+    let key_len: u16 = key
+        .len()
+        .try_into()
+        .context("path terlalu panjang untuk manifest")?;
+    let raw_len: u32 = raw
+        .len()
+        .try_into()
+        .context("blob terlalu besar untuk manifest")?;
+    out.extend_from_slice(&key_len.to_le_bytes());
+    out.extend_from_slice(key.as_bytes());
+    out.extend_from_slice(&raw_len.to_le_bytes());
+    out.extend_from_slice(raw);
+    Ok(())
+}
+
+fn read_len(bytes: &[u8], pos: &mut usize, width: usize) -> Result<usize> {
+    let slice = take(bytes, pos, width)?;
+    Ok(match width {
+        2 => u16::from_le_bytes(slice.try_into()?) as usize,
+        _ => u32::from_le_bytes(slice.try_into()?) as usize,
+    })
+}
+
+fn take<'a>(bytes: &'a [u8], pos: &mut usize, len: usize) -> Result<&'a [u8]> {
+    let end = pos
+        .checked_add(len)
+        .filter(|end| *end <= bytes.len())
+        .ok_or_else(|| anyhow!("manifest terpotong"))?;
+    let slice = &bytes[*pos..end];
+    *pos = end;
+    Ok(slice)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signature::build_binary_blob;
+
+    fn temp_project(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "action-codex-manifest-{}-{}",
+            std::process::id(),
+            name
+        ));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/a.rs"), "fn a() {}").unwrap();
+        fs::canonicalize(root).unwrap()
+    }
+
+    fn blob(target: &str, timestamp: u64) -> Vec<u8> {
+        build_binary_blob(timestamp, target, &[0; 32], &[1; 44], &[2; 64]).unwrap()
+    }
+
+    #[test]
+    fn keys_are_relative_to_project_root() {
+        let root = temp_project("keys");
+        let file = root.join("src/a.rs");
+        assert_eq!(project_root(&file), Some(root.clone()));
+        assert_eq!(project_root(&root.join("src")), Some(root.clone()));
+        assert_eq!(manifest_key(&root, &file), "src/a.rs");
+        assert_eq!(
+            manifest_key(&root, Path::new("/tmp")),
+            fs::canonicalize("/tmp").unwrap().to_string_lossy()
+        );
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn compact_checks_files_against_root_not_cwd() {
+        let root = temp_project("compact");
+        SignatureManifest::append(&root, "src/a.rs", &blob("src/a.rs", 1)).unwrap();
+        SignatureManifest::append(&root, "src/a.rs", &blob("src/a.rs", 2)).unwrap();
+        SignatureManifest::append(&root, "hilang.rs", &blob("hilang.rs", 1)).unwrap();
+
+        let mut manifest = SignatureManifest::load(&root).unwrap();
+        let stats = manifest.compact(1).unwrap();
+        assert_eq!(
+            (stats.kept, stats.removed_history, stats.removed_missing),
+            (1, 1, 1)
+        );
+        let manifest = SignatureManifest::load(&root).unwrap();
+        assert_eq!(manifest.latest("src/a.rs").unwrap().blob.timestamp, 2);
+        assert!(manifest.latest("hilang.rs").is_none());
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,176 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result, anyhow};
+use ed25519_dalek::pkcs8::EncodePublicKey;
+use ed25519_dalek::{Signer, SigningKey};
+use sha2::{Digest, Sha256};
+
+use manifest::SignatureManifest;
+
+pub mod keys;
+pub mod manifest;
+pub mod verify;
+
+const KEY_DIR: &str = ".action-codex";
+const MANIFEST_FILE: &str = "signatures.acsm";
+const BLOB_MAGIC: &[u8; 4] = b"ACSV";
+const LEGACY_BLOB_VERSION: u8 = 1;
+const BLOB_VERSION: u8 = 2;
+
+/// Signature untuk `target_path` dicatat di manifest milik `root` (lihat `project_root`).
+pub fn write_signature_blob_for_file(
+    root: &Path,
+    target_path: &Path,
+    content: &[u8],
+    signing_key: &SigningKey,
+) -> Result<String> {
+    let public_key_der = signing_key
+        .verifying_key()
+        .to_public_key_der()
+        .map_err(|err| anyhow!("gagal encode public key: {}", err))?;
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .context("waktu sistem sebelum UNIX_EPOCH")?
+        .as_secs();
+
+    let mut hasher = Sha256::new();
+    hasher.update(content);
+    let hash: [u8; 32] = hasher.finalize().into();
+
+    let key = manifest_key(root, target_path);
+    let payload = build_payload_to_sign(timestamp, &key, &hash)?;
+    let signature = signing_key.sign(&payload).to_bytes();
+
+    let blob = build_binary_blob(
+        timestamp,
+        &key,
+        &hash,
+        public_key_der.as_bytes(),
+        &signature,
+    )?;
+    SignatureManifest::append(root, &key, &blob)?;
+
+    Ok(key)
+}
+
+/// Root project tempat manifest disimpan: folder terdekat dari `start` yang sudah punya
+/// manifest, atau root repo git. Tidak bergantung pada folder kerja proses, jadi editor dan
+/// `action-codex verify` selalu memakai manifest yang sama.
+pub fn project_root(start: &Path) -> Option<PathBuf> {
+    let start = fs::canonicalize(start).unwrap_or_else(|_| start.to_path_buf());
+    let dir = if start.is_dir() {
+        start.as_path()
+    } else {
+        start.parent()?
+    };
+    dir.ancestors()
+        .find(|dir| dir.join(KEY_DIR).join(MANIFEST_FILE).is_file())
+        .or_else(|| dir.ancestors().find(|dir| dir.join(".git").exists()))
+        .map(Path::to_path_buf)
+}
+
+/// Key manifest: path relatif terhadap `root` dengan pemisah `/`, atau path absolut bila file
+/// berada di luar root.
+pub fn manifest_key(root: &Path, path: &Path) -> String {
+    let absolute = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let relative = fs::canonicalize(root)
+        .ok()
+        .and_then(|root| absolute.strip_prefix(root).ok().map(Path::to_path_buf));
+    match relative {
+        Some(relative) => relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+        None => absolute.to_string_lossy().to_string(),
+    }
+}
+
+pub(super) fn create_private_dir(dir: &Path) -> Result<()> {
+    if !dir.exists() {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("gagal membuat direktori {}", dir.display()))?;
+        restrict_permissions(dir, 0o700)?;
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+pub(super) fn write_private_file(path: &Path, data: &[u8]) -> Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)
+        .with_context(|| format!("gagal membuat file {}", path.display()))?;
+    file.write_all(data)
+        .with_context(|| format!("gagal menulis file {}", path.display()))
+}
+
+#[cfg(not(unix))]
+pub(super) fn write_private_file(path: &Path, data: &[u8]) -> Result<()> {
+    fs::write(path, data).with_context(|| format!("gagal menulis file {}", path.display()))
+}
+
+#[cfg(unix)]
+pub(super) fn restrict_permissions(path: &Path, mode: u32) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let metadata =
+        fs::metadata(path).with_context(|| format!("gagal membaca {}", path.display()))?;
+    if metadata.permissions().mode() & 0o777 & !mode != 0 {
+        fs::set_permissions(path, fs::Permissions::from_mode(mode))
+            .with_context(|| format!("gagal mengubah permission {}", path.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub(super) fn restrict_permissions(_path: &Path, _mode: u32) -> Result<()> {
+    Ok(())
+}
+
+fn build_payload_to_sign(timestamp: u64, target: &str, hash: &[u8; 32]) -> Result<Vec<u8>> {
+    let mut payload = Vec::new();
+    payload.extend_from_slice(b"ACSIGP1");
+    payload.extend_from_slice(&timestamp.to_le_bytes());
+    push_u16_and_bytes(&mut payload, target.as_bytes())?;
+    payload.extend_from_slice(hash);
+    Ok(payload)
+}
+
+fn build_binary_blob(
+    timestamp: u64,
+    target: &str,
+    hash: &[u8; 32],
+    public_key_der: &[u8],
+    signature: &[u8],
+) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    out.extend_from_slice(BLOB_MAGIC);
+    out.push(BLOB_VERSION);
+    out.extend_from_slice(&timestamp.to_le_bytes());
+
+    push_u16_and_bytes(&mut out, target.as_bytes())?;
+    push_u16_and_bytes(&mut out, hash)?;
+    push_u16_and_bytes(&mut out, public_key_der)?;
+    push_u16_and_bytes(&mut out, signature)?;
+
+    Ok(out)
+}
+
+fn push_u16_and_bytes(out: &mut Vec<u8>, data: &[u8]) -> Result<()> {
+    let len: u16 = data
+        .len()
+        .try_into()
+        .context("data terlalu besar untuk format biner")?;
+    out.extend_from_slice(&len.to_le_bytes());
+    out.extend_from_slice(data);
+    Ok(())
+}
@@ -0,0 +1,639 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{Context, Result, anyhow};
+use ed25519_dalek::pkcs8::DecodePublicKey;
+use ed25519_dalek::{Signature, VerifyingKey};
+use sha2::{Digest, Sha256};
+
+use super::keys::KeyStore;
+use super::manifest::SignatureManifest;
+use super::{
+    BLOB_MAGIC, BLOB_VERSION, LEGACY_BLOB_VERSION, build_payload_to_sign, manifest_key,
+    project_root,
+};
+
+pub(super) type MarkerBlob = (PathBuf, SignatureBlob);
+
+#[derive(Debug, Clone)]
+pub struct SignatureBlob {
+    pub version: u8,
+    pub timestamp: u64,
+    pub target: String,
+    pub marker_id: Option<String>,
+    pub hash: [u8; 32],
+    pub public_key_der: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureStatus {
+    Trusted,
+    Untrusted,
+    Tampered,
+    Invalid,
+    Unsigned,
+}
+
+impl SignatureStatus {
+    pub fn badge(self) -> &'static str {
+        match self {
+            Self::Trusted => "✓",
+            Self::Untrusted => "~",
+            Self::Tampered | Self::Invalid => "✗",
+            Self::Unsigned => "·",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Trusted => "signed",
+            Self::Untrusted => "signed (kunci tidak dipercaya)",
+            Self::Tampered => "tampered",
+            Self::Invalid => "signature tidak valid",
+            Self::Unsigned => "unsigned",
+        }
+    }
+
+    pub fn is_failure(self) -> bool {
+        matches!(self, Self::Tampered | Self::Invalid)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VerifyReport {
+    pub status: SignatureStatus,
+    pub source: Option<String>,
+    pub timestamp: Option<u64>,
+    pub fingerprint: Option<String>,
+}
+
+impl VerifyReport {
+    fn unsigned() -> Self {
+        Self {
+            status: SignatureStatus::Unsigned,
+            source: None,
+            timestamp: None,
+            fingerprint: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TrustedKeys {
+    keys: Vec<[u8; 32]>,
+}
+
+impl TrustedKeys {
+    /// Dasar kepercayaan hanya dari folder konfigurasi pengguna: kunci global dan `trusted_keys`.
+    /// Kunci di `.action-codex` workspace boleh dipakai menandatangani, tapi tidak dipercaya.
+    pub fn load() -> Self {
+        let store = KeyStore::default();
+        let mut keys = store.global_public_keys();
+        for entry in store.trusted_keys() {
+            if !keys.contains(&entry.key) {
+                keys.push(entry.key);
+            }
+        }
+        Self { keys }
+    }
+
+    pub fn contains(&self, key: &[u8; 32]) -> bool {
+        self.keys.contains(key)
+    }
+}
+
+pub fn parse_signature_blob(bytes: &[u8]) -> Result<SignatureBlob> {
+    let mut reader = BlobReader { bytes, pos: 0 };
+    if reader.take(4)? != BLOB_MAGIC {
+        return Err(anyhow!("bukan blob ACSV"));
+    }
+    let version = reader.take(1)?[0];
+    if version != LEGACY_BLOB_VERSION && version != BLOB_VERSION {
+        return Err(anyhow!("versi blob tidak didukung: {}", version));
+    }
+    let timestamp = u64::from_le_bytes(reader.take(8)?.try_into()?);
+    let target =
+        String::from_utf8(reader.take_prefixed()?.to_vec()).context("target bukan UTF-8")?;
+    let marker_id = if version == LEGACY_BLOB_VERSION {
+        Some(String::from_utf8(reader.take_prefixed()?.to_vec()).context("marker id bukan UTF-8")?)
+    } else {
+        None
+    };
+    let hash: [u8; 32] = reader
+        .take_prefixed()?
+        .try_into()
+        .map_err(|_| anyhow!("panjang hash tidak valid"))?;
+    let public_key_der = reader.take_prefixed()?.to_vec();
+    let signature = reader.take_prefixed()?.to_vec();
+    if reader.pos != bytes.len() {
+        return Err(anyhow!("data berlebih di akhir blob"));
+    }
+
+    Ok(SignatureBlob {
+        version,
+        timestamp,
+        target,
+        marker_id,
+        hash,
+        public_key_der,
+        signature,
+    })
+}
+
+pub fn verify_file(path: &Path, trusted: &TrustedKeys) -> Result<VerifyReport> {
+    let content = fs::read(path).with_context(|| format!("gagal membaca {}", path.display()))?;
+    let root = project_root(path).unwrap_or_else(|| marker_dir(path).to_path_buf());
+    let manifest = SignatureManifest::load(&root)?;
+    let legacy = read_marker_blobs(marker_dir(path))?;
+    Ok(verify_content(path, &content, &manifest, &legacy, trusted))
+}
+
+fn verify_content(
+    path: &Path,
+    content: &[u8],
+    manifest: &SignatureManifest,
+    legacy: &[MarkerBlob],
+    trusted: &TrustedKeys,
+) -> VerifyReport {
+    let key = manifest_key(manifest.root(), path);
+    let from_manifest = manifest.latest(&key).map(|entry| {
+        let target_ok = target_matches(&entry.blob, &key, path);
+        (String::from("manifest"), &entry.blob, target_ok)
+    });
+    let from_legacy = latest_blob_for(path, legacy).map(|(marker, blob)| {
+        let marker_name = marker
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let marker_ok = blob
+            .marker_id
+            .as_ref()
+            .is_some_and(|id| marker_name == format!(".{}", id));
+        (
+            marker_name,
+            blob,
+            marker_ok && target_matches(blob, &key, path),
+        )
+    });
+    let candidate = match (from_manifest, from_legacy) {
+        (Some(current), Some(old)) if old.1.timestamp > current.1.timestamp => Some(old),
+        (Some(current), _) => Some(current),
+        (None, old) => old,
+    };
+    let Some((source, blob, bound_ok)) = candidate else {
+        return VerifyReport::unsigned();
+    };
+
+    let mut report = VerifyReport {
+        status: SignatureStatus::Invalid,
+        source: Some(source),
+        timestamp: Some(blob.timestamp),
+        fingerprint: None,
+    };
+    if !bound_ok {
+        return report;
+    }
+    let Ok(verifying_key) = VerifyingKey::from_public_key_der(&blob.public_key_der) else {
+        return report;
+    };
+    report.fingerprint = Some(key_fingerprint(&verifying_key.to_bytes()));
+    let Ok(signature) = Signature::from_slice(&blob.signature) else {
+        return report;
+    };
+    let Ok(payload) = build_payload_to_sign(blob.timestamp, &blob.target, &blob.hash) else {
+        return report;
+    };
+    if verifying_key.verify_strict(&payload, &signature).is_err() {
+        return report;
+    }
+
+    let hash: [u8; 32] = Sha256::digest(content).into();
+    report.status = if hash != blob.hash {
+        SignatureStatus::Tampered
+    } else if trusted.contains(&verifying_key.to_bytes()) {
+        SignatureStatus::Trusted
+    } else {
+        SignatureStatus::Untrusted
+    };
+    report
+}
+
+pub fn run_verify_command(path: &Path) -> Result<bool> {
+    let trusted = TrustedKeys::load();
+    let mut files = Vec::new();
+    if path.is_dir() {
+        collect_files(path, &mut files)?;
+    } else {
+        files.push(path.to_path_buf());
+    }
+
+    let mut ok = true;
+    for file in &files {
+        let report = verify_file(file, &trusted)?;
+        ok &= !report.status.is_failure();
+        let mut line = format!(
+            "{} {}: {}",
+            report.status.badge(),
+            file.display(),
+            report.status.label()
+        );
+        if let Some(fingerprint) = &report.fingerprint {
+            line.push_str(&format!(" | kunci {}", fingerprint));
+        }
+        if let Some(timestamp) = report.timestamp {
+            line.push_str(&format!(" | waktu {}", timestamp));
+        }
+        if let Some(source) = &report.source {
+            line.push_str(&format!(" | sumber {}", source));
+        }
+        println!("{}", line);
+    }
+    if files.is_empty() {
+        println!("Tidak ada file untuk diverifikasi: {}", path.display());
+    }
+    Ok(ok)
+}
+
+fn collect_files(dir: &Path, out: &mut Vec<PathBuf>) -> Result<()> {
+    let mut entries = fs::read_dir(dir)
+        .with_context(|| format!("gagal membaca {}", dir.display()))?
+        .flatten()
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+    entries.sort();
+    for entry in entries {
+        if entry.is_dir() {
+            collect_files(&entry, out)?;
+        } else {
+            out.push(entry);
+        }
+    }
+    Ok(())
+}
+
+pub fn key_fingerprint(key: &[u8; 32]) -> String {
+    Sha256::digest(key)
+        .iter()
+        .take(8)
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Target yang ikut ditandatangani harus menunjuk file yang diverifikasi, supaya signature yang
+/// disalin ke entri atau folder lain tidak ikut menjamin file dengan isi yang sama. Blob v2
+/// menyimpan key manifest; blob v1 (marker atau hasil migrasi) hanya menyimpan nama file.
+fn target_matches(blob: &SignatureBlob, key: &str, path: &Path) -> bool {
+    if blob.version == LEGACY_BLOB_VERSION {
+        Path::new(&blob.target).file_name() == path.file_name()
+    } else {
+        blob.target == key
+    }
+}
+
+fn latest_blob_for<'a>(path: &Path, blobs: &'a [MarkerBlob]) -> Option<&'a MarkerBlob> {
+    let file_name = path.file_name()?;
+    blobs
+        .iter()
+        .filter(|(_, blob)| Path::new(&blob.target).file_name() == Some(file_name))
+        .max_by_key(|(_, blob)| blob.timestamp)
+}
+
+pub(super) fn marker_dir(path: &Path) -> &Path {
+    path.parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."))
+}
+
+pub(super) fn read_marker_blobs(dir: &Path) -> Result<Vec<MarkerBlob>> {
+    let mut blobs = Vec::new();
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(blobs),
+        Err(err) => return Err(err).with_context(|| format!("gagal membaca {}", dir.display())),
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !is_marker_name(&name) {
+            continue;
+        }
+        let path = entry.path();
+        if let Ok(bytes) = fs::read(&path)
+            && let Ok(blob) = parse_signature_blob(&bytes)
+        {
+            blobs.push((path, blob));
+        }
+    }
+    Ok(blobs)
+}
+
+pub(super) fn is_marker_name(name: &str) -> bool {
+    name.strip_prefix('.')
+        .is_some_and(|id| id.len() == 16 && id.chars().all(|ch| ch.is_ascii_hexdigit()))
+}
+
+struct BlobReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BlobReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| anyhow!("blob terpotong"))?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn take_prefixed(&mut self) -> Result<&'a [u8]> {
+        let len = u16::from_le_bytes(self.take(2)?.try_into()?);
+        self.take(len as usize)
+    }
+}
+
+/// Status signature file explorer. Tiap folder memakai manifest milik project root-nya;
+/// `root` dipakai untuk folder di luar repo git yang belum punya manifest.
+#[derive(Debug, Default)]
+pub struct SignatureCache {
+    root: PathBuf,
+    trusted: TrustedKeys,
+    manifests: HashMap<PathBuf, (Option<SystemTime>, SignatureManifest)>,
+    dirs: HashMap<PathBuf, (Option<SystemTime>, PathBuf, Vec<MarkerBlob>)>,
+    files: HashMap<PathBuf, (Option<SystemTime>, SignatureStatus)>,
+}
+
+impl SignatureCache {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            trusted: TrustedKeys::load(),
+            ..Self::default()
+        }
+    }
+
+    // Sama dengan root yang dipakai saat menyimpan signature di editor.
+    fn root_for(&self, path: &Path) -> PathBuf {
+        project_root(path).unwrap_or_else(|| self.root.clone())
+    }
+
+    /// Dipanggil setelah dialog kunci ditutup: daftar kunci dipercaya dimuat ulang dan semua
+    /// status dihitung ulang pada refresh berikutnya.
+    pub fn invalidate(&mut self) {
+        self.trusted = TrustedKeys::load();
+        self.files.clear();
+        self.dirs.clear();
+    }
+
+    pub fn status_for(&self, path: &Path) -> Option<SignatureStatus> {
+        self.files.get(path).map(|(_, status)| *status)
+    }
+
+    pub fn refresh<'a>(&mut self, paths: impl IntoIterator<Item = &'a Path>) {
+        let mut checked_roots = HashSet::new();
+        let mut reloaded_roots = HashSet::new();
+        let mut seen = HashSet::new();
+        for path in paths {
+            let dir = marker_dir(path).to_path_buf();
+            let dir_modified = modified_time(&dir);
+            let dir_changed = match self.dirs.get(&dir) {
+                Some((cached, _, _)) => *cached != dir_modified,
+                None => true,
+            };
+            if dir_changed {
+                let root = self.root_for(&dir);
+                let blobs = read_marker_blobs(&dir).unwrap_or_default();
+                self.dirs.insert(dir.clone(), (dir_modified, root, blobs));
+            }
+            let Some((_, root, blobs)) = self.dirs.get(&dir) else {
+                continue;
+            };
+
+            if checked_roots.insert(root.clone()) {
+                let manifest_modified = modified_time(&SignatureManifest::path(root));
+                let cached = self.manifests.get(root).map(|(modified, _)| *modified);
+                if cached != Some(manifest_modified) {
+                    let manifest = SignatureManifest::load(root).unwrap_or_default();
+                    self.manifests
+                        .insert(root.clone(), (manifest_modified, manifest));
+                    reloaded_roots.insert(root.clone());
+                }
+            }
+
+            let file_modified = modified_time(path);
+            let unchanged = !dir_changed
+                && !reloaded_roots.contains(root)
+                && self
+                    .files
+                    .get(path)
+                    .is_some_and(|(cached, _)| *cached == file_modified);
+            if !unchanged {
+                let status = match (fs::read(path), self.manifests.get(root)) {
+                    (Ok(content), Some((_, manifest))) => {
+                        verify_content(path, &content, manifest, blobs, &self.trusted).status
+                    }
+                    _ => SignatureStatus::Unsigned,
+                };
+                self.files
+                    .insert(path.to_path_buf(), (file_modified, status));
+            }
+            seen.insert(path.to_path_buf());
+        }
+        self.files.retain(|path, _| seen.contains(path));
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::pkcs8::EncodePublicKey;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    use super::*;
+    use crate::signature::build_binary_blob;
+
+    const TIMESTAMP: u64 = 1_700_000_000;
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[7; 32])
+    }
+
+    fn public_key_der(key: &SigningKey) -> Vec<u8> {
+        key.verifying_key()
+            .to_public_key_der()
+            .map(|der| der.as_bytes().to_vec())
+            .unwrap()
+    }
+
+    fn push_prefixed(out: &mut Vec<u8>, data: &[u8]) {
+        out.extend_from_slice(&(data.len() as u16).to_le_bytes());
+        out.extend_from_slice(data);
+    }
+
+    /// Blob ACSV v1 lama: sama dengan v2 ditambah marker id setelah target.
+    fn legacy_blob(target: &str, marker_id: &str, content: &[u8], key: &SigningKey) -> Vec<u8> {
+        let hash: [u8; 32] = Sha256::digest(content).into();
+        let payload = build_payload_to_sign(TIMESTAMP, target, &hash).unwrap();
+        let mut out = Vec::new();
+        out.extend_from_slice(BLOB_MAGIC);
+        out.push(LEGACY_BLOB_VERSION);
+        out.extend_from_slice(&TIMESTAMP.to_le_bytes());
+        push_prefixed(&mut out, target.as_bytes());
+        push_prefixed(&mut out, marker_id.as_bytes());
+        push_prefixed(&mut out, &hash);
+        push_prefixed(&mut out, &public_key_der(key));
+        push_prefixed(&mut out, &key.sign(&payload).to_bytes());
+        out
+    }
+
+    #[test]
+    fn parses_v2_blob_round_trip() {
+        let key = signing_key();
+        let der = public_key_der(&key);
+        let hash = [3; 32];
+        let bytes = build_binary_blob(TIMESTAMP, "src/main.rs", &hash, &der, &[9; 64]).unwrap();
+
+        let blob = parse_signature_blob(&bytes).unwrap();
+        assert_eq!(blob.version, BLOB_VERSION);
+        assert_eq!(blob.timestamp, TIMESTAMP);
+        assert_eq!(blob.target, "src/main.rs");
+        assert_eq!(blob.marker_id, None);
+        assert_eq!(blob.hash, hash);
+        assert_eq!(blob.public_key_der, der);
+        assert_eq!(blob.signature, vec![9; 64]);
+    }
+
+    #[test]
+    fn parses_legacy_v1_blob_with_marker_id() {
+        let bytes = legacy_blob("a.rs", "0123456789abcdef", b"fn main() {}", &signing_key());
+        let blob = parse_signature_blob(&bytes).unwrap();
+        assert_eq!(blob.version, LEGACY_BLOB_VERSION);
+        assert_eq!(blob.target, "a.rs");
+        assert_eq!(blob.marker_id.as_deref(), Some("0123456789abcdef"));
+        assert_eq!(blob.hash, <[u8; 32]>::from(Sha256::digest(b"fn main() {}")));
+    }
+
+    #[test]
+    fn rejects_malformed_blobs() {
+        let bytes = build_binary_blob(TIMESTAMP, "a.rs", &[0; 32], &[1; 44], &[2; 64]).unwrap();
+
+        let mut wrong_magic = bytes.clone();
+        wrong_magic[0] = b'X';
+        assert!(parse_signature_blob(&wrong_magic).is_err());
+
+        let mut wrong_version = bytes.clone();
+        wrong_version[4] = 9;
+        assert!(parse_signature_blob(&wrong_version).is_err());
+
+        assert!(parse_signature_blob(&bytes[..bytes.len() - 1]).is_err());
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(parse_signature_blob(&trailing).is_err());
+    }
+
+    #[test]
+    fn rejects_v2_blob_with_short_hash() {
+        let bytes = build_binary_blob(TIMESTAMP, "a.rs", &[0; 32], &[1; 44], &[2; 64]).unwrap();
+        // Ganti prefix panjang hash (setelah magic, versi, timestamp, dan target) menjadi 31.
+        let hash_len = 4 + 1 + 8 + 2 + "a.rs".len();
+        let mut short = bytes[..hash_len].to_vec();
+        push_prefixed(&mut short, &[0; 31]);
+        short.extend_from_slice(&bytes[hash_len + 2 + 32..]);
+        assert!(parse_signature_blob(&short).is_err());
+    }
+
+    #[test]
+    fn verifies_signed_legacy_marker() {
+        let key = signing_key();
+        let content = b"fn main() {}";
+        let marker = (
+            PathBuf::from(".0123456789abcdef"),
+            parse_signature_blob(&legacy_blob("a.rs", "0123456789abcdef", content, &key)).unwrap(),
+        );
+        let manifest = SignatureManifest::default();
+        let verify = |content: &[u8], trusted: &TrustedKeys| {
+            verify_content(
+                Path::new("a.rs"),
+                content,
+                &manifest,
+                std::slice::from_ref(&marker),
+                trusted,
+            )
+            .status
+        };
+
+        let trusted = TrustedKeys {
+            keys: vec![key.verifying_key().to_bytes()],
+        };
+        assert_eq!(verify(content, &trusted), SignatureStatus::Trusted);
+        assert_eq!(
+            verify(content, &TrustedKeys::default()),
+            SignatureStatus::Untrusted
+        );
+        assert_eq!(verify(b"changed", &trusted), SignatureStatus::Tampered);
+    }
+
+    #[test]
+    fn signature_copied_to_another_target_is_invalid() {
+        let root = std::env::temp_dir().join(format!("action-codex-verify-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        let root = fs::canonicalize(root).unwrap();
+        let content = b"fn main() {}";
+        fs::write(root.join("src/a.rs"), content).unwrap();
+        fs::write(root.join("src/b.rs"), content).unwrap();
+
+        let key = signing_key();
+        let trusted = TrustedKeys {
+            keys: vec![key.verifying_key().to_bytes()],
+        };
+        crate::signature::write_signature_blob_for_file(
+            &root,
+            &root.join("src/a.rs"),
+            content,
+            &key,
+        )
+        .unwrap();
+        let manifest = SignatureManifest::load(&root).unwrap();
+        let raw = manifest.latest("src/a.rs").unwrap().raw.clone();
+        SignatureManifest::append(&root, "src/b.rs", &raw).unwrap();
+
+        let manifest = SignatureManifest::load(&root).unwrap();
+        let verify = |name: &str, legacy: &[MarkerBlob]| {
+            verify_content(&root.join(name), content, &manifest, legacy, &trusted).status
+        };
+        assert_eq!(verify("src/a.rs", &[]), SignatureStatus::Trusted);
+        assert_eq!(verify("src/b.rs", &[]), SignatureStatus::Invalid);
+
+        // Marker v1 dari folder lain tetap hanya berlaku untuk nama file yang ditandatangani.
+        let marker = (
+            root.join("src/.0123456789abcdef"),
+            parse_signature_blob(&legacy_blob("a.rs", "0123456789abcdef", content, &key)).unwrap(),
+        );
+        let empty = SignatureManifest::default();
+        let legacy_status = |name: &str| {
+            verify_content(
+                Path::new(name),
+                content,
+                &empty,
+                std::slice::from_ref(&marker),
+                &trusted,
+            )
+            .status
+        };
+        assert_eq!(legacy_status("a.rs"), SignatureStatus::Trusted);
+        assert_eq!(legacy_status("b.rs"), SignatureStatus::Unsigned);
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,40 @@
+use std::path::Path;
+
+/// Token komentar sebuah bahasa untuk toggle komentar baris/blok.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommentStyle {
+    pub line: Option<&'static str>,
+    pub block: Option<(&'static str, &'static str)>,
+}
+
+impl CommentStyle {
+    const C_LIKE: Self = Self {
+        line: Some("//"),
+        block: Some(("/*", "*/")),
+    };
+    const HASH: Self = Self {
+        line: Some("#"),
+        block: None,
+    };
+
+    /// Tanpa path (buffer baru) dianggap Rust; ekstensi yang tidak dikenal `None`.
+    pub fn for_path(path: Option<&Path>) -> Option<Self> {
+        let Some(path) = path else {
+            return Some(Self::C_LIKE);
+        };
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "rs" | "c" | "h" | "cpp" | "hpp" | "js" | "ts" | "go" | "java" => Some(Self::C_LIKE),
+            "toml" | "sh" | "bash" | "py" | "yml" | "yaml" => Some(Self::HASH),
+            "md" | "markdown" | "html" | "xml" => Some(Self {
+                line: None,
+                block: Some(("<!--", "-->")),
+            }),
+            "css" => Some(Self {
+                line: None,
+                block: Some(("/*", "*/")),
+            }),
+            _ => None,
+        }
+    }
+}
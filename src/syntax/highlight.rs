@@ -1,7 +1,7 @@
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 
-const RUST_KEYWORDS: &[&str] = &[
+pub(super) const RUST_KEYWORDS: &[&str] = &[
     "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
     "extern", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
     "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "type",
@@ -68,13 +68,13 @@ impl SyntaxPalette {
 }
 
 #[derive(Debug, Clone, Copy, Default)]
-struct ScanState {
-    block_comment_depth: usize,
-    in_string: Option<StringState>,
+pub(super) struct ScanState {
+    pub(super) block_comment_depth: usize,
+    pub(super) in_string: Option<StringState>,
 }
 
 #[derive(Debug, Clone, Copy)]
-enum StringState {
+pub(super) enum StringState {
     Standard,
     Raw { hashes: usize },
 }
@@ -154,17 +154,17 @@ fn highlight_rust_line_with_state(
             continue;
         }
 
-        if bytes[i] == b'\'' {
-            if let Some((start, end)) = scan_lifetime_or_char(line, i) {
-                let style = if is_char_literal_token(&line[start..end]) {
-                    palette.literal
-                } else {
-                    palette.lifetime
-                };
-                push_span(&mut spans, line, start, end, style);
-                i = end;
-                continue;
-            }
+        if bytes[i] == b'\''
+            && let Some((start, end)) = scan_lifetime_or_char(line, i)
+        {
+            let style = if is_char_literal_token(&line[start..end]) {
+                palette.literal
+            } else {
+                palette.lifetime
+            };
+            push_span(&mut spans, line, start, end, style);
+            i = end;
+            continue;
         }
 
         if bytes[i].is_ascii_digit() {
@@ -211,14 +211,24 @@ fn highlight_rust_line_with_state(
             continue;
         }
 
-        push_span(&mut spans, line, i, i + 1, palette.plain);
-        i += 1;
+        // Karakter multibyte (mis. non-breaking space) diambil utuh agar tidak hilang.
+        let len = line
+            .get(i..)
+            .and_then(|rest| rest.chars().next())
+            .map_or(1, char::len_utf8);
+        push_span(&mut spans, line, i, i + len, palette.plain);
+        i += len;
     }
 
     Line::from(spans)
 }
 
-fn scan_block_comment(line: &str, bytes: &[u8], mut i: usize, state: &mut ScanState) -> usize {
+pub(super) fn scan_block_comment(
+    line: &str,
+    bytes: &[u8],
+    mut i: usize,
+    state: &mut ScanState,
+) -> usize {
     while i + 1 < bytes.len() {
         if is_block_comment_start(bytes, i) {
             state.block_comment_depth += 1;
@@ -243,7 +253,7 @@ fn scan_block_comment(line: &str, bytes: &[u8], mut i: usize, state: &mut ScanSt
     }
 }
 
-fn scan_string(
+pub(super) fn scan_string(
     line: &str,
     bytes: &[u8],
     mut i: usize,
@@ -291,7 +301,7 @@ fn scan_string(
     }
 }
 
-fn try_start_raw_string(bytes: &[u8], i: usize) -> Option<(usize, usize)> {
+pub(super) fn try_start_raw_string(bytes: &[u8], i: usize) -> Option<(usize, usize)> {
     if i >= bytes.len() {
         return None;
     }
@@ -315,7 +325,7 @@ fn try_start_raw_string(bytes: &[u8], i: usize) -> Option<(usize, usize)> {
     None
 }
 
-fn scan_lifetime_or_char(line: &str, i: usize) -> Option<(usize, usize)> {
+pub(super) fn scan_lifetime_or_char(line: &str, i: usize) -> Option<(usize, usize)> {
     let bytes = line.as_bytes();
     if i + 1 >= bytes.len() {
         return None;
@@ -341,7 +351,7 @@ fn scan_lifetime_or_char(line: &str, i: usize) -> Option<(usize, usize)> {
     None
 }
 
-fn scan_number(bytes: &[u8], mut i: usize) -> usize {
+pub(super) fn scan_number(bytes: &[u8], mut i: usize) -> usize {
     if i + 1 < bytes.len() && bytes[i] == b'0' && matches!(bytes[i + 1], b'x' | b'b' | b'o') {
         i += 2;
         while i < bytes.len() && (bytes[i].is_ascii_hexdigit() || bytes[i] == b'_') {
@@ -378,7 +388,7 @@ fn scan_number(bytes: &[u8], mut i: usize) -> usize {
     i
 }
 
-fn scan_identifier(bytes: &[u8], mut i: usize) -> usize {
+pub(super) fn scan_identifier(bytes: &[u8], mut i: usize) -> usize {
     while i < bytes.len() && is_ident_continue(bytes[i]) {
         i += 1;
     }
@@ -403,11 +413,11 @@ fn looks_like_type(token: &str) -> bool {
     token.chars().next().is_some_and(|ch| ch.is_ascii_uppercase())
 }
 
-fn is_line_comment_start(bytes: &[u8], i: usize) -> bool {
+pub(super) fn is_line_comment_start(bytes: &[u8], i: usize) -> bool {
     i + 1 < bytes.len() && bytes[i] == b'/' && bytes[i + 1] == b'/'
 }
 
-fn is_block_comment_start(bytes: &[u8], i: usize) -> bool {
+pub(super) fn is_block_comment_start(bytes: &[u8], i: usize) -> bool {
     i + 1 < bytes.len() && bytes[i] == b'/' && bytes[i + 1] == b'*'
 }
 
@@ -432,7 +442,7 @@ fn is_escaped(bytes: &[u8], i: usize) -> bool {
     backslashes % 2 == 1
 }
 
-fn is_ident_start(ch: u8) -> bool {
+pub(super) fn is_ident_start(ch: u8) -> bool {
     ch == b'_' || ch.is_ascii_alphabetic()
 }
 
@@ -451,7 +461,7 @@ fn is_delimiter(ch: u8) -> bool {
     matches!(ch, b'(' | b')' | b'[' | b']' | b'{' | b'}' | b',' | b';' | b'.')
 }
 
-fn is_char_literal_token(token: &str) -> bool {
+pub(super) fn is_char_literal_token(token: &str) -> bool {
     token.len() >= 3 && token.starts_with('\'') && token.ends_with('\'')
 }
 
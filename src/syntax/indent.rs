@@ -1,4 +1,4 @@
-const INDENT: &str = "    ";
+pub const INDENT: &str = "    ";
 
 pub fn next_indent_for_rust(line_before_cursor: &str, line_after_cursor: &str) -> String {
     let before = line_before_cursor;
@@ -110,3 +110,79 @@ fn leading_ws(s: &str) -> &str {
         .unwrap_or(s.len());
     &s[..idx]
 }
+
+/// Jumlah kolom yang dipakai tab yang dimulai di kolom `column`.
+pub fn tab_advance(column: usize, tab_width: usize) -> usize {
+    let tab_width = tab_width.max(1);
+    tab_width - column % tab_width
+}
+
+/// Lebar tampilan `text` dengan tab diperluas ke kelipatan `tab_width`.
+pub fn display_width(text: &str, tab_width: usize) -> usize {
+    text.chars().fold(0, |column, ch| match ch {
+        '\t' => column + tab_advance(column, tab_width),
+        _ => column + 1,
+    })
+}
+
+/// Tebak lebar satu level indentasi dari kenaikan indentasi antar baris yang paling sering.
+pub fn detect_indent_width(lines: &[String], tab_width: usize) -> usize {
+    let mut counts = [0usize; 9];
+    let mut previous = 0;
+    for line in lines.iter().filter(|line| !line.trim().is_empty()) {
+        let width = display_width(leading_ws(line), tab_width);
+        if let Some(count) = width
+            .checked_sub(previous)
+            .and_then(|step| counts.get_mut(step))
+        {
+            *count += 1;
+        }
+        previous = width;
+    }
+    (1..counts.len())
+        .filter(|step| counts[*step] > 0)
+        .max_by_key(|step| (counts[*step], usize::MAX - step))
+        .unwrap_or(tab_width.max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &[&str]) -> Vec<String> {
+        text.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn detects_most_common_indent_step() {
+        let spaces = lines(&["a", "  b", "    c", "  d", "e", "    f"]);
+        assert_eq!(detect_indent_width(&spaces, 4), 2);
+
+        let tabs = lines(&["fn a() {", "\tx();", "\tif y {", "\t\tz();", "\t}", "}"]);
+        assert_eq!(detect_indent_width(&tabs, 4), 4);
+        assert_eq!(detect_indent_width(&tabs, 8), 8);
+    }
+
+    #[test]
+    fn mixed_tabs_and_spaces_prefer_smaller_step_on_tie() {
+        let mixed = lines(&["a", "\tb", "\t  c", "", "d", "    e"]);
+        assert_eq!(detect_indent_width(&mixed, 4), 4);
+        let tie = lines(&["a", "\tb", "\t  c"]);
+        assert_eq!(detect_indent_width(&tie, 4), 2);
+    }
+
+    #[test]
+    fn empty_input_falls_back_to_tab_width() {
+        assert_eq!(detect_indent_width(&[], 4), 4);
+        assert_eq!(detect_indent_width(&lines(&["", "   ", "\t"]), 8), 8);
+        assert_eq!(detect_indent_width(&[], 0), 1);
+    }
+
+    #[test]
+    fn display_width_expands_tabs_to_next_stop() {
+        assert_eq!(display_width("\t", 4), 4);
+        assert_eq!(display_width("ab\t", 4), 4);
+        assert_eq!(display_width("ab\tc", 2), 5);
+        assert_eq!(display_width("abcd\t", 4), 8);
+    }
+}
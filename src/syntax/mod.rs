@@ -1,2 +1,5 @@
+pub mod comment;
 pub mod highlight;
 pub mod indent;
+pub mod outline;
+pub mod symbol_index;
@@ -0,0 +1,454 @@
+use super::highlight::{
+    ScanState, StringState, is_block_comment_start, is_char_literal_token, is_ident_start,
+    is_line_comment_start, scan_block_comment, scan_identifier, scan_lifetime_or_char, scan_number,
+    scan_string, try_start_raw_string,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    Function,
+    Struct,
+    Enum,
+    Trait,
+    Impl,
+    Module,
+    Const,
+    Macro,
+}
+
+impl SymbolKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Function => "fn",
+            Self::Struct => "struct",
+            Self::Enum => "enum",
+            Self::Trait => "trait",
+            Self::Impl => "impl",
+            Self::Module => "mod",
+            Self::Const => "const",
+            Self::Macro => "macro",
+        }
+    }
+
+    // Hanya isi trait/impl/mod yang berisi item; body fn, struct, dan enum dilewati.
+    fn has_items(self) -> bool {
+        matches!(self, Self::Trait | Self::Impl | Self::Module)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub kind: SymbolKind,
+    pub name: String,
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub depth: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+    Word(&'a str),
+    Lifetime(&'a str),
+    Punct(u8),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Spanned<'a> {
+    token: Token<'a>,
+    line: usize,
+    column: usize,
+}
+
+struct Scope {
+    items: bool,
+    owner: Option<usize>,
+}
+
+struct Pending {
+    symbol: usize,
+    nesting: usize,
+    scopes: usize,
+    impl_header: bool,
+}
+
+/// Item Rust (fn, struct, enum, trait, impl, mod, const, macro_rules!) berurutan sesuai
+/// posisi, dengan kedalaman nesting di dalam mod/impl/trait.
+pub fn rust_outline(lines: &[String]) -> Vec<Symbol> {
+    let tokens = tokenize(lines);
+    let mut symbols: Vec<Symbol> = Vec::new();
+    let mut scopes = vec![Scope {
+        items: true,
+        owner: None,
+    }];
+    let mut pending: Option<Pending> = None;
+    let mut impl_name: Vec<Token> = Vec::new();
+    let mut k = 0usize;
+
+    while k < tokens.len() {
+        let Spanned {
+            token,
+            line,
+            column,
+        } = tokens[k];
+        k += 1;
+
+        if let Some(current) = pending.as_mut() {
+            let collecting = current.impl_header;
+            let is_impl = symbols[current.symbol].kind == SymbolKind::Impl;
+            match token {
+                Token::Punct(b'(' | b'[') => current.nesting += 1,
+                Token::Punct(b')' | b']') => current.nesting = current.nesting.saturating_sub(1),
+                Token::Punct(b'{')
+                    if current.nesting == 0
+                        && symbols[current.symbol].kind != SymbolKind::Const =>
+                {
+                    let symbol = current.symbol;
+                    if is_impl {
+                        symbols[symbol].name = join_tokens(&impl_name);
+                    }
+                    scopes.push(Scope {
+                        items: symbols[symbol].kind.has_items(),
+                        owner: Some(symbol),
+                    });
+                    pending = None;
+                    continue;
+                }
+                Token::Punct(b'{') => scopes.push(Scope {
+                    items: false,
+                    owner: None,
+                }),
+                Token::Punct(b'}') if scopes.len() > current.scopes => {
+                    scopes.pop();
+                }
+                Token::Punct(b';') if current.nesting == 0 && scopes.len() == current.scopes => {
+                    symbols[current.symbol].end_line = line;
+                    if is_impl {
+                        symbols[current.symbol].name = join_tokens(&impl_name);
+                    }
+                    pending = None;
+                    continue;
+                }
+                Token::Word("where") if collecting => current.impl_header = false,
+                _ => {}
+            }
+            if pending.as_ref().is_some_and(|pending| pending.impl_header) {
+                impl_name.push(token);
+            }
+            continue;
+        }
+
+        match token {
+            Token::Punct(b'{') => scopes.push(Scope {
+                items: false,
+                owner: None,
+            }),
+            Token::Punct(b'}') => {
+                if scopes.len() > 1
+                    && let Some(scope) = scopes.pop()
+                    && let Some(owner) = scope.owner
+                {
+                    symbols[owner].end_line = line;
+                }
+            }
+            Token::Word(word) if scopes.last().is_some_and(|scope| scope.items) => {
+                let depth = scopes.iter().filter(|scope| scope.owner.is_some()).count();
+                let next = tokens.get(k).map(|spanned| spanned.token);
+                let (kind, name) = match (word, next) {
+                    ("fn", Some(Token::Word(name))) => (SymbolKind::Function, name),
+                    ("struct" | "union", Some(Token::Word(name))) => (SymbolKind::Struct, name),
+                    ("enum", Some(Token::Word(name))) => (SymbolKind::Enum, name),
+                    ("trait", Some(Token::Word(name))) => (SymbolKind::Trait, name),
+                    ("mod", Some(Token::Word(name))) => (SymbolKind::Module, name),
+                    ("const", Some(Token::Word(name)))
+                        if !matches!(name, "fn" | "unsafe" | "async" | "extern") =>
+                    {
+                        (SymbolKind::Const, name)
+                    }
+                    ("macro_rules", Some(Token::Punct(b'!'))) => match tokens.get(k + 1) {
+                        Some(Spanned {
+                            token: Token::Word(name),
+                            ..
+                        }) => {
+                            k += 1;
+                            (SymbolKind::Macro, *name)
+                        }
+                        _ => continue,
+                    },
+                    ("impl", _) => (SymbolKind::Impl, ""),
+                    _ => continue,
+                };
+                let (line, column) = match (kind, tokens.get(k)) {
+                    (SymbolKind::Impl, _) | (_, None) => (line, column),
+                    (_, Some(name)) => (name.line, name.column),
+                };
+                if kind == SymbolKind::Impl {
+                    k = skip_generics(&tokens, k);
+                    impl_name.clear();
+                } else {
+                    k += 1;
+                }
+                symbols.push(Symbol {
+                    kind,
+                    name: name.to_string(),
+                    line,
+                    column,
+                    end_line: line,
+                    depth,
+                });
+                pending = Some(Pending {
+                    symbol: symbols.len() - 1,
+                    nesting: 0,
+                    scopes: scopes.len(),
+                    impl_header: kind == SymbolKind::Impl,
+                });
+            }
+            _ => {}
+        }
+    }
+    symbols
+}
+
+/// Item terdalam yang mencakup baris `row`.
+pub fn symbol_at(symbols: &[Symbol], row: usize) -> Option<usize> {
+    symbols
+        .iter()
+        .rposition(|symbol| symbol.line <= row && row <= symbol.end_line)
+}
+
+fn skip_generics(tokens: &[Spanned], mut k: usize) -> usize {
+    if !matches!(tokens.get(k).map(|t| t.token), Some(Token::Punct(b'<'))) {
+        return k;
+    }
+    let mut depth = 0usize;
+    let mut previous = None;
+    while let Some(spanned) = tokens.get(k) {
+        k += 1;
+        match spanned.token {
+            Token::Punct(b'<') => depth += 1,
+            // `->` di dalam bound `Fn() -> T` bukan penutup generic.
+            Token::Punct(b'>') if previous != Some(Token::Punct(b'-')) => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            _ => {}
+        }
+        previous = Some(spanned.token);
+    }
+    k
+}
+
+fn join_tokens(tokens: &[Token]) -> String {
+    let mut name = String::new();
+    let mut previous: Option<Token> = None;
+    for token in tokens {
+        let word_like = matches!(token, Token::Word(_) | Token::Lifetime(_));
+        let space = word_like
+            && matches!(
+                previous,
+                Some(Token::Word(_) | Token::Lifetime(_) | Token::Punct(b'>' | b','))
+            );
+        if space {
+            name.push(' ');
+        }
+        match token {
+            Token::Word(text) | Token::Lifetime(text) => name.push_str(text),
+            Token::Punct(byte) => name.push(*byte as char),
+        }
+        previous = Some(*token);
+    }
+    name
+}
+
+// Pemindai yang sama dengan highlighter: komentar, string, dan literal char dilewati
+// supaya kurung di dalamnya tidak mengacaukan nesting.
+fn tokenize(lines: &[String]) -> Vec<Spanned<'_>> {
+    let mut state = ScanState::default();
+    let mut tokens = Vec::new();
+    for (row, line) in lines.iter().enumerate() {
+        let bytes = line.as_bytes();
+        let mut i = 0usize;
+        while i < bytes.len() {
+            if state.block_comment_depth > 0 {
+                i = scan_block_comment(line, bytes, i, &mut state);
+                continue;
+            }
+            if let Some(mode) = state.in_string {
+                i = scan_string(line, bytes, i, mode, &mut state);
+                continue;
+            }
+            if is_line_comment_start(bytes, i) {
+                break;
+            }
+            if is_block_comment_start(bytes, i) {
+                state.block_comment_depth = 1;
+                i = scan_block_comment(line, bytes, i + 2, &mut state);
+                continue;
+            }
+            if let Some((start, next_i)) = try_start_raw_string(bytes, i) {
+                let mode = StringState::Raw {
+                    hashes: next_i.saturating_sub(start + 2),
+                };
+                state.in_string = Some(mode);
+                i = scan_string(line, bytes, next_i - 1, mode, &mut state);
+                continue;
+            }
+            let byte = bytes[i];
+            if byte == b'"' {
+                state.in_string = Some(StringState::Standard);
+                i = scan_string(line, bytes, i, StringState::Standard, &mut state);
+                continue;
+            }
+            if byte == b'\''
+                && let Some((start, end)) = scan_lifetime_or_char(line, i)
+            {
+                if !is_char_literal_token(&line[start..end]) {
+                    tokens.push(Spanned {
+                        token: Token::Lifetime(&line[start..end]),
+                        line: row,
+                        column: start,
+                    });
+                }
+                i = end;
+                continue;
+            }
+            if byte.is_ascii_digit() {
+                i = scan_number(bytes, i);
+                continue;
+            }
+            if is_ident_start(byte) {
+                let end = scan_identifier(bytes, i);
+                tokens.push(Spanned {
+                    token: Token::Word(&line[i..end]),
+                    line: row,
+                    column: i,
+                });
+                i = end;
+                continue;
+            }
+            if !byte.is_ascii_whitespace() {
+                tokens.push(Spanned {
+                    token: Token::Punct(byte),
+                    line: row,
+                    column: i,
+                });
+            }
+            i += 1;
+        }
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outline(source: &str) -> Vec<(SymbolKind, String, usize, usize, usize)> {
+        let lines: Vec<String> = source.lines().map(str::to_string).collect();
+        rust_outline(&lines)
+            .into_iter()
+            .map(|symbol| {
+                (
+                    symbol.kind,
+                    symbol.name,
+                    symbol.depth,
+                    symbol.line,
+                    symbol.end_line,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn items_with_nesting_and_ranges() {
+        let source = "\
+mod net {
+    pub struct Conn;
+    pub trait Read {
+        fn read(&mut self) -> usize;
+    }
+}
+enum Mode { A, B }
+fn main() {
+    fn inner() {}
+}
+";
+        assert_eq!(
+            outline(source),
+            [
+                (SymbolKind::Module, "net".into(), 0, 0, 5),
+                (SymbolKind::Struct, "Conn".into(), 1, 1, 1),
+                (SymbolKind::Trait, "Read".into(), 1, 2, 4),
+                (SymbolKind::Function, "read".into(), 2, 3, 3),
+                (SymbolKind::Enum, "Mode".into(), 0, 6, 6),
+                // `inner` ada di body fn sehingga tidak masuk outline.
+                (SymbolKind::Function, "main".into(), 0, 7, 9),
+            ]
+        );
+    }
+
+    #[test]
+    fn impl_names_include_generics_and_trait() {
+        let source = "\
+impl<T: Fn() -> u8> Display for Wrapper<'_, T> where T: Clone {
+    const LIMIT: usize = 3;
+    fn fmt(&self) {}
+}
+impl Foo {}
+";
+        let symbols = outline(source);
+        assert_eq!(symbols[0].1, "Display for Wrapper<'_, T>");
+        assert_eq!((symbols[0].3, symbols[0].4), (0, 3));
+        assert_eq!(symbols[1], (SymbolKind::Const, "LIMIT".into(), 1, 1, 1));
+        assert_eq!(symbols[2], (SymbolKind::Function, "fmt".into(), 1, 2, 2));
+        assert_eq!(symbols[3].1, "Foo");
+    }
+
+    #[test]
+    fn braces_in_strings_and_comments_are_ignored() {
+        let source = "\
+fn a() {
+    let s = \"}\"; // }
+    let r = r#\"{\"#;
+    /* } */
+    let c = '}';
+}
+fn b() {}
+";
+        let symbols = outline(source);
+        assert_eq!(symbols.len(), 2);
+        assert_eq!((symbols[0].3, symbols[0].4), (0, 5));
+        assert_eq!(symbols[1].1, "b");
+    }
+
+    #[test]
+    fn const_fn_and_macro_rules() {
+        let source = "\
+const fn zero() -> u8 { 0 }
+macro_rules! twice {
+    ($e:expr) => { $e; $e };
+}
+const MAX: [u8; 2] = [1, 2];
+";
+        let symbols = outline(source);
+        assert_eq!(symbols[0], (SymbolKind::Function, "zero".into(), 0, 0, 0));
+        assert_eq!(symbols[1], (SymbolKind::Macro, "twice".into(), 0, 1, 3));
+        assert_eq!(symbols[2], (SymbolKind::Const, "MAX".into(), 0, 4, 4));
+    }
+
+    #[test]
+    fn symbol_at_picks_innermost() {
+        let lines: Vec<String> = "impl A {\n    fn f() {\n    }\n}\nfn g() {}\n"
+            .lines()
+            .map(str::to_string)
+            .collect();
+        let symbols = rust_outline(&lines);
+        assert_eq!(symbol_at(&symbols, 0), Some(0));
+        assert_eq!(symbol_at(&symbols, 2), Some(1));
+        assert_eq!(symbol_at(&symbols, 3), Some(0));
+        assert_eq!(symbol_at(&symbols, 4), Some(2));
+        assert_eq!(symbol_at(&symbols, 5), None);
+    }
+}
@@ -0,0 +1,463 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+
+use super::highlight::RUST_KEYWORDS;
+use super::outline::{Symbol, SymbolKind, rust_outline};
+
+const MAX_FILE_BYTES: u64 = 2 * 1024 * 1024;
+const MAX_RESULTS: usize = 200;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceSymbol {
+    pub path: PathBuf,
+    pub kind: SymbolKind,
+    pub name: String,
+    /// Nama impl/trait/mod pembungkus, mis. `TabManager` untuk `fn new`.
+    pub container: Option<String>,
+    pub line: usize,
+    pub column: usize,
+}
+
+enum IndexEvent {
+    File(PathBuf, Vec<WorkspaceSymbol>),
+    Done,
+}
+
+/// Indeks definisi item Rust di seluruh folder kerja, dibangun di thread terpisah.
+pub struct SymbolIndex {
+    root: PathBuf,
+    files: HashMap<PathBuf, Vec<WorkspaceSymbol>>,
+    receiver: Option<Receiver<IndexEvent>>,
+}
+
+impl SymbolIndex {
+    pub fn spawn(root: &Path) -> Self {
+        let mut index = Self {
+            root: root.to_path_buf(),
+            files: HashMap::new(),
+            receiver: None,
+        };
+        index.rebuild();
+        index
+    }
+
+    /// Pindai ulang dari awal; hasil pemindaian sebelumnya yang belum selesai dibuang.
+    pub fn rebuild(&mut self) {
+        let (sender, receiver) = mpsc::channel();
+        let root = self.root.clone();
+        thread::spawn(move || {
+            if walk(&root, &sender) {
+                let _ = sender.send(IndexEvent::Done);
+            }
+        });
+        self.files.clear();
+        self.receiver = Some(receiver);
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn is_indexing(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn symbol_count(&self) -> usize {
+        self.files.values().map(Vec::len).sum()
+    }
+
+    /// Ambil hasil dari thread pengindeks; `true` bila ada yang berubah.
+    pub fn poll(&mut self) -> bool {
+        let Some(receiver) = self.receiver.as_ref() else {
+            return false;
+        };
+        let mut changed = false;
+        loop {
+            match receiver.try_recv() {
+                Ok(IndexEvent::File(path, symbols)) => {
+                    // Versi yang sudah diperbarui saat simpan lebih baru dari hasil scan.
+                    self.files.entry(path).or_insert(symbols);
+                    changed = true;
+                }
+                Ok(IndexEvent::Done) | Err(TryRecvError::Disconnected) => {
+                    self.receiver = None;
+                    return true;
+                }
+                Err(TryRecvError::Empty) => return changed,
+            }
+        }
+    }
+
+    /// Perbarui satu file dari isi buffer (dipanggil setelah simpan).
+    pub fn update_file(&mut self, path: &Path, lines: &[String]) {
+        let path = absolute(path);
+        if !is_rust_file(&path) || !path.starts_with(&self.root) {
+            return;
+        }
+        self.files.insert(path.clone(), collect(&path, lines));
+    }
+
+    pub fn search(&self, query: &str) -> Vec<WorkspaceSymbol> {
+        let (kind, query) = split_kind_filter(query.trim());
+        let mut scored = self
+            .symbols()
+            .filter(|symbol| kind.is_none_or(|kind| symbol.kind == kind))
+            .filter_map(|symbol| {
+                let score = if query.is_empty() {
+                    0
+                } else {
+                    fuzzy_score(query, &symbol.name)?
+                };
+                Some((score, symbol))
+            })
+            .collect::<Vec<_>>();
+        scored.sort_by(|(a_score, a), (b_score, b)| {
+            b_score
+                .cmp(a_score)
+                .then_with(|| a.name.len().cmp(&b.name.len()))
+                .then_with(|| a.name.cmp(&b.name))
+                .then_with(|| a.path.cmp(&b.path))
+                .then_with(|| a.line.cmp(&b.line))
+        });
+        scored
+            .into_iter()
+            .take(MAX_RESULTS)
+            .map(|(_, symbol)| symbol.clone())
+            .collect()
+    }
+
+    /// Semua definisi bernama `name`, yang paling mungkin dimaksud lebih dulu.
+    pub fn definitions(
+        &self,
+        name: &str,
+        usage: Usage,
+        from: Option<&Path>,
+    ) -> Vec<WorkspaceSymbol> {
+        let from = from.map(absolute);
+        let mut matches = self
+            .symbols()
+            .filter(|symbol| symbol.name == name && symbol.kind != SymbolKind::Impl)
+            .filter(|symbol| from.as_deref() != Some(symbol.path.as_path()))
+            .cloned()
+            .collect::<Vec<_>>();
+        matches.sort_by_key(|symbol| {
+            (
+                !usage.prefers(symbol.kind),
+                from.as_deref()
+                    .map(|from| path_distance(from, &symbol.path))
+                    .unwrap_or(0),
+                symbol.path.clone(),
+                symbol.line,
+            )
+        });
+        matches
+    }
+
+    pub fn relative_path<'a>(&self, path: &'a Path) -> &'a Path {
+        path.strip_prefix(&self.root).unwrap_or(path)
+    }
+
+    fn symbols(&self) -> impl Iterator<Item = &WorkspaceSymbol> {
+        self.files.values().flatten()
+    }
+}
+
+/// Konteks pemakaian identifier di bawah cursor untuk menebak jenis definisinya.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Usage {
+    Call,
+    Macro,
+    Type,
+    Other,
+}
+
+impl Usage {
+    pub fn prefers(self, kind: SymbolKind) -> bool {
+        match self {
+            Self::Call => kind == SymbolKind::Function,
+            Self::Macro => kind == SymbolKind::Macro,
+            Self::Type => matches!(
+                kind,
+                SymbolKind::Struct | SymbolKind::Enum | SymbolKind::Trait
+            ),
+            Self::Other => true,
+        }
+    }
+}
+
+/// Identifier (bukan keyword) di posisi `col` (byte) beserta tebakan pemakaiannya.
+pub fn identifier_at(line: &str, col: usize) -> Option<(usize, &str, Usage)> {
+    let bytes = line.as_bytes();
+    let is_ident = |byte: u8| byte == b'_' || byte.is_ascii_alphanumeric();
+    let mut start = col.min(bytes.len());
+    if !bytes.get(start).copied().is_some_and(is_ident) {
+        start = start.checked_sub(1).filter(|&idx| is_ident(bytes[idx]))?;
+    }
+    while start > 0 && is_ident(bytes[start - 1]) {
+        start -= 1;
+    }
+    let mut end = start;
+    while end < bytes.len() && is_ident(bytes[end]) {
+        end += 1;
+    }
+    let name = &line[start..end];
+    if name.as_bytes()[0].is_ascii_digit() || RUST_KEYWORDS.contains(&name) {
+        return None;
+    }
+    let rest = line[end..].trim_start();
+    let usage = if rest.starts_with('!') {
+        Usage::Macro
+    } else if rest.starts_with('(') || rest.starts_with("::<") {
+        Usage::Call
+    } else if name.starts_with(|ch: char| ch.is_ascii_uppercase()) {
+        Usage::Type
+    } else {
+        Usage::Other
+    };
+    Some((start, name, usage))
+}
+
+/// Skor subsequence tanpa memperhatikan huruf besar/kecil; huruf yang berurutan dan
+/// awal kata (`_`, camelCase) bernilai lebih. `None` bila tidak cocok.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let candidate_chars = candidate.chars().collect::<Vec<_>>();
+    let mut score = 0i64;
+    let mut position = 0usize;
+    let mut previous_match: Option<usize> = None;
+    for query_char in query.chars().filter(|ch| !ch.is_whitespace()) {
+        let found = (position..candidate_chars.len())
+            .find(|&idx| candidate_chars[idx].eq_ignore_ascii_case(&query_char))?;
+        let boundary = found == 0
+            || candidate_chars[found - 1] == '_'
+            || (candidate_chars[found].is_ascii_uppercase()
+                && candidate_chars[found - 1].is_ascii_lowercase());
+        score += 1;
+        if boundary {
+            score += 8;
+        }
+        if previous_match.is_some_and(|previous| previous + 1 == found) {
+            score += 5;
+        }
+        if candidate_chars[found] == query_char {
+            score += 1;
+        }
+        score -= (found - position) as i64;
+        previous_match = Some(found);
+        position = found + 1;
+    }
+    if candidate.eq_ignore_ascii_case(query) {
+        score += 100;
+    } else if candidate
+        .to_ascii_lowercase()
+        .starts_with(&query.to_ascii_lowercase())
+    {
+        score += 20;
+    }
+    Some(score)
+}
+
+// `struct Tab` → hanya struct; kata pertama dianggap filter jenis bila cocok label.
+fn split_kind_filter(query: &str) -> (Option<SymbolKind>, &str) {
+    let Some((first, rest)) = query.split_once(' ') else {
+        return (None, query);
+    };
+    let kind = [
+        SymbolKind::Function,
+        SymbolKind::Struct,
+        SymbolKind::Enum,
+        SymbolKind::Trait,
+        SymbolKind::Impl,
+        SymbolKind::Module,
+        SymbolKind::Const,
+        SymbolKind::Macro,
+    ]
+    .into_iter()
+    .find(|kind| kind.label() == first);
+    match kind {
+        Some(kind) => (Some(kind), rest.trim()),
+        None => (None, query),
+    }
+}
+
+fn collect(path: &Path, lines: &[String]) -> Vec<WorkspaceSymbol> {
+    let symbols = rust_outline(lines);
+    symbols
+        .iter()
+        .enumerate()
+        .map(|(idx, symbol)| WorkspaceSymbol {
+            path: path.to_path_buf(),
+            kind: symbol.kind,
+            name: symbol.name.clone(),
+            container: container_of(&symbols, idx),
+            line: symbol.line,
+            column: symbol.column,
+        })
+        .collect()
+}
+
+fn container_of(symbols: &[Symbol], idx: usize) -> Option<String> {
+    let depth = symbols[idx].depth.checked_sub(1)?;
+    symbols[..idx]
+        .iter()
+        .rev()
+        .find(|symbol| symbol.depth == depth)
+        .map(|symbol| symbol.name.clone())
+}
+
+// `false` bila penerima sudah dibuang (indeks di-rebuild), supaya thread berhenti lebih awal.
+fn walk(dir: &Path, sender: &Sender<IndexEvent>) -> bool {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return true;
+    };
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with('.') || name == "target" {
+            continue;
+        }
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            if !walk(&path, sender) {
+                return false;
+            }
+            continue;
+        }
+        if !file_type.is_file()
+            || !is_rust_file(&path)
+            || entry
+                .metadata()
+                .is_ok_and(|metadata| metadata.len() > MAX_FILE_BYTES)
+        {
+            continue;
+        }
+        let Ok(text) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let lines = text.lines().map(str::to_string).collect::<Vec<_>>();
+        let symbols = collect(&path, &lines);
+        if sender.send(IndexEvent::File(path, symbols)).is_err() {
+            return false;
+        }
+    }
+    true
+}
+
+fn is_rust_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "rs")
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+// Jumlah komponen yang berbeda antara dua path: definisi di crate/folder yang sama didahulukan.
+fn path_distance(from: &Path, to: &Path) -> usize {
+    let common = from
+        .components()
+        .zip(to.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+    from.components().count() + to.components().count() - 2 * common
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(str::to_string).collect()
+    }
+
+    fn names(symbols: &[WorkspaceSymbol]) -> Vec<&str> {
+        symbols.iter().map(|symbol| symbol.name.as_str()).collect()
+    }
+
+    #[test]
+    fn prefix_outranks_scattered_letters() {
+        let prefix = fuzzy_score("tab", "TabManager").unwrap();
+        let scattered = fuzzy_score("tab", "toggle_all_bookmarks").unwrap();
+        let exact = fuzzy_score("tabmanager", "TabManager").unwrap();
+        assert!(exact > prefix);
+        assert!(prefix > scattered);
+        // Huruf di awal kata (`_`, camelCase) lebih bernilai dari huruf di tengah kata.
+        assert!(fuzzy_score("tm", "TabManager") > fuzzy_score("tm", "atom"));
+        assert_eq!(fuzzy_score("xyz", "TabManager"), None);
+        assert_eq!(fuzzy_score("bat", "tab"), None);
+    }
+
+    #[test]
+    fn kind_prefix_filters_only_known_labels() {
+        assert_eq!(
+            split_kind_filter("struct Tab"),
+            (Some(SymbolKind::Struct), "Tab")
+        );
+        assert_eq!(
+            split_kind_filter("fn  new"),
+            (Some(SymbolKind::Function), "new")
+        );
+        assert_eq!(split_kind_filter("Tab"), (None, "Tab"));
+        assert_eq!(split_kind_filter("tab manager"), (None, "tab manager"));
+    }
+
+    #[test]
+    fn identifier_at_word_edges_and_gaps() {
+        let line = "let tab = TabManager::new(x);";
+        assert_eq!(identifier_at(line, 4), Some((4, "tab", Usage::Other)));
+        // Tepat setelah kata masih dihitung kata itu.
+        assert_eq!(identifier_at(line, 7), Some((4, "tab", Usage::Other)));
+        assert_eq!(
+            identifier_at(line, 10),
+            Some((10, "TabManager", Usage::Type))
+        );
+        assert_eq!(identifier_at(line, 22), Some((22, "new", Usage::Call)));
+        assert_eq!(identifier_at(line, 0), None);
+        assert_eq!(identifier_at("a  = b", 2), None);
+        assert_eq!(identifier_at("x = 42;", 5), None);
+        assert_eq!(
+            identifier_at("println!(\"{}\", x);", 3),
+            Some((0, "println", Usage::Macro))
+        );
+    }
+
+    #[test]
+    fn update_file_replaces_symbols_after_edit() {
+        let root =
+            std::env::temp_dir().join(format!("action-codex-symbols-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        let root = root.canonicalize().unwrap();
+        let path = root.join("lib.rs");
+        fs::write(&path, "fn lama() {}\nstruct Tetap;\n").unwrap();
+
+        let mut index = SymbolIndex::spawn(&root);
+        let started = Instant::now();
+        while index.is_indexing() {
+            assert!(started.elapsed() < Duration::from_secs(10));
+            index.poll();
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(names(&index.search("lama")), ["lama"]);
+
+        index.update_file(&path, &lines("fn baru() {}\nstruct Tetap;\n"));
+        assert!(index.search("lama").is_empty());
+        assert_eq!(names(&index.search("fn baru")), ["baru"]);
+        assert_eq!(index.symbol_count(), 2);
+
+        // File di luar root dan bukan `.rs` diabaikan.
+        index.update_file(Path::new("/tmp/lain.rs"), &lines("fn lain() {}"));
+        index.update_file(&root.join("catatan.txt"), &lines("fn catatan() {}"));
+        assert_eq!(index.file_count(), 1);
+        let _ = fs::remove_dir_all(root);
+    }
+}
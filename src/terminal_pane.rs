@@ -0,0 +1,404 @@
+use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use anyhow::{Context, Result, anyhow};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use portable_pty::{Child, CommandBuilder, MasterPty, PtySize, native_pty_system};
+
+const SCROLLBACK_LINES: usize = 2000;
+
+pub struct TerminalSession {
+    title: String,
+    parser: Arc<Mutex<vt100::Parser>>,
+    master: Box<dyn MasterPty + Send>,
+    writer: Box<dyn Write + Send>,
+    child: Box<dyn Child + Send + Sync>,
+    exited: Arc<AtomicBool>,
+    size: (u16, u16),
+    scroll_offset: usize,
+}
+
+impl TerminalSession {
+    pub fn spawn(title: String, cwd: &Path, rows: u16, cols: u16) -> Result<Self> {
+        let rows = rows.max(2);
+        let cols = cols.max(10);
+        let pair = native_pty_system()
+            .openpty(PtySize {
+                rows,
+                cols,
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .map_err(|err| anyhow!("gagal membuka PTY: {}", err))?;
+
+        let mut command = CommandBuilder::new_default_prog();
+        command.cwd(cwd);
+        command.env("TERM", "xterm-256color");
+        let child = pair
+            .slave
+            .spawn_command(command)
+            .map_err(|err| anyhow!("gagal menjalankan shell: {}", err))?;
+        drop(pair.slave);
+
+        let mut reader = pair
+            .master
+            .try_clone_reader()
+            .map_err(|err| anyhow!("gagal membaca PTY: {}", err))?;
+        let writer = pair
+            .master
+            .take_writer()
+            .map_err(|err| anyhow!("gagal menulis ke PTY: {}", err))?;
+
+        let parser = Arc::new(Mutex::new(vt100::Parser::new(rows, cols, SCROLLBACK_LINES)));
+        let exited = Arc::new(AtomicBool::new(false));
+        {
+            let parser = Arc::clone(&parser);
+            let exited = Arc::clone(&exited);
+            thread::spawn(move || {
+                let mut buf = [0u8; 4096];
+                loop {
+                    match reader.read(&mut buf) {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => {
+                            if let Ok(mut parser) = parser.lock() {
+                                parser.process(&buf[..n]);
+                            }
+                        }
+                    }
+                }
+                exited.store(true, Ordering::SeqCst);
+            });
+        }
+
+        Ok(Self {
+            title,
+            parser,
+            master: pair.master,
+            writer,
+            child,
+            exited,
+            size: (rows, cols),
+            scroll_offset: 0,
+        })
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn has_exited(&self) -> bool {
+        self.exited.load(Ordering::SeqCst)
+    }
+
+    pub fn parser(&self) -> &Arc<Mutex<vt100::Parser>> {
+        &self.parser
+    }
+
+    pub fn scroll_offset(&self) -> usize {
+        self.scroll_offset
+    }
+
+    pub fn resize(&mut self, rows: u16, cols: u16) -> Result<()> {
+        let rows = rows.max(2);
+        let cols = cols.max(10);
+        if self.size == (rows, cols) {
+            return Ok(());
+        }
+        self.master
+            .resize(PtySize {
+                rows,
+                cols,
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .map_err(|err| anyhow!("gagal resize PTY: {}", err))?;
+        if let Ok(mut parser) = self.parser.lock() {
+            parser.screen_mut().set_size(rows, cols);
+        }
+        self.size = (rows, cols);
+        Ok(())
+    }
+
+    pub fn send_key(&mut self, key: KeyEvent) -> Result<()> {
+        let application_cursor = self
+            .parser
+            .lock()
+            .map(|parser| parser.screen().application_cursor())
+            .unwrap_or(false);
+        let Some(bytes) = encode_key(key, application_cursor) else {
+            return Ok(());
+        };
+        self.scroll_to_bottom();
+        self.write_bytes(&bytes)
+    }
+
+    pub fn send_text(&mut self, text: &str) -> Result<()> {
+        self.scroll_to_bottom();
+        self.write_bytes(text.as_bytes())
+    }
+
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.set_scroll_offset(self.scroll_offset.saturating_add(lines));
+    }
+
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.set_scroll_offset(self.scroll_offset.saturating_sub(lines));
+    }
+
+    fn scroll_to_bottom(&mut self) {
+        if self.scroll_offset > 0 {
+            self.set_scroll_offset(0);
+        }
+    }
+
+    fn set_scroll_offset(&mut self, offset: usize) {
+        if let Ok(mut parser) = self.parser.lock() {
+            parser.screen_mut().set_scrollback(offset);
+            self.scroll_offset = parser.screen().scrollback();
+        }
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        if self.has_exited() {
+            return Err(anyhow!("proses terminal sudah selesai"));
+        }
+        self.writer
+            .write_all(bytes)
+            .context("gagal menulis ke terminal")?;
+        self.writer.flush().context("gagal flush terminal")
+    }
+}
+
+impl Drop for TerminalSession {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        // Reap shell agar tidak tertinggal sebagai zombie.
+        let _ = self.child.wait();
+    }
+}
+
+#[derive(Default)]
+pub struct TerminalPanes {
+    sessions: Vec<TerminalSession>,
+    active: usize,
+    visible: bool,
+    spawned_count: usize,
+}
+
+impl TerminalPanes {
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    pub fn sessions(&self) -> &[TerminalSession] {
+        &self.sessions
+    }
+
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    pub fn active(&self) -> Option<&TerminalSession> {
+        self.sessions.get(self.active)
+    }
+
+    pub fn active_mut(&mut self) -> Option<&mut TerminalSession> {
+        self.sessions.get_mut(self.active)
+    }
+
+    pub fn open_session(&mut self, cwd: &Path, rows: u16, cols: u16) -> Result<()> {
+        self.spawned_count += 1;
+        let title = format!("term-{}", self.spawned_count);
+        let session = TerminalSession::spawn(title, cwd, rows, cols)?;
+        self.sessions.push(session);
+        self.active = self.sessions.len() - 1;
+        self.visible = true;
+        Ok(())
+    }
+
+    pub fn close_active(&mut self) {
+        if self.sessions.is_empty() {
+            return;
+        }
+        self.sessions.remove(self.active);
+        if self.active >= self.sessions.len() {
+            self.active = self.sessions.len().saturating_sub(1);
+        }
+        if self.sessions.is_empty() {
+            self.visible = false;
+        }
+    }
+
+    pub fn next(&mut self) {
+        if !self.sessions.is_empty() {
+            self.active = (self.active + 1) % self.sessions.len();
+        }
+    }
+
+    pub fn prev(&mut self) {
+        if !self.sessions.is_empty() {
+            self.active = (self.active + self.sessions.len() - 1) % self.sessions.len();
+        }
+    }
+
+    pub fn resize_all(&mut self, rows: u16, cols: u16) -> Result<()> {
+        for session in &mut self.sessions {
+            session.resize(rows, cols)?;
+        }
+        Ok(())
+    }
+}
+
+fn encode_key(key: KeyEvent, application_cursor: bool) -> Option<Vec<u8>> {
+    let alt = key.modifiers.contains(KeyModifiers::ALT);
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let cursor = |normal: &[u8], application: &[u8]| {
+        if application_cursor {
+            application.to_vec()
+        } else {
+            normal.to_vec()
+        }
+    };
+
+    let mut bytes = match key.code {
+        KeyCode::Char(ch) if ctrl => {
+            let lower = ch.to_ascii_lowercase();
+            match lower {
+                'a'..='z' => vec![lower as u8 - b'a' + 1],
+                '@' | ' ' => vec![0],
+                '[' => vec![0x1b],
+                '\\' => vec![0x1c],
+                ']' => vec![0x1d],
+                '^' => vec![0x1e],
+                '_' => vec![0x1f],
+                _ => return None,
+            }
+        }
+        KeyCode::Char(ch) => ch.to_string().into_bytes(),
+        KeyCode::Enter => vec![b'\r'],
+        KeyCode::Backspace => vec![0x7f],
+        KeyCode::Tab => vec![b'\t'],
+        KeyCode::BackTab => b"\x1b[Z".to_vec(),
+        KeyCode::Esc => vec![0x1b],
+        KeyCode::Up => cursor(b"\x1b[A", b"\x1bOA"),
+        KeyCode::Down => cursor(b"\x1b[B", b"\x1bOB"),
+        KeyCode::Right => cursor(b"\x1b[C", b"\x1bOC"),
+        KeyCode::Left => cursor(b"\x1b[D", b"\x1bOD"),
+        KeyCode::Home => cursor(b"\x1b[H", b"\x1bOH"),
+        KeyCode::End => cursor(b"\x1b[F", b"\x1bOF"),
+        KeyCode::PageUp => b"\x1b[5~".to_vec(),
+        KeyCode::PageDown => b"\x1b[6~".to_vec(),
+        KeyCode::Insert => b"\x1b[2~".to_vec(),
+        KeyCode::Delete => b"\x1b[3~".to_vec(),
+        KeyCode::F(n) => match n {
+            1 => b"\x1bOP".to_vec(),
+            2 => b"\x1bOQ".to_vec(),
+            3 => b"\x1bOR".to_vec(),
+            4 => b"\x1bOS".to_vec(),
+            5 => b"\x1b[15~".to_vec(),
+            6 => b"\x1b[17~".to_vec(),
+            7 => b"\x1b[18~".to_vec(),
+            8 => b"\x1b[19~".to_vec(),
+            9 => b"\x1b[20~".to_vec(),
+            10 => b"\x1b[21~".to_vec(),
+            11 => b"\x1b[23~".to_vec(),
+            12 => b"\x1b[24~".to_vec(),
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    if alt {
+        bytes.insert(0, 0x1b);
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> Option<Vec<u8>> {
+        encode_key(KeyEvent::new(code, modifiers), false)
+    }
+
+    #[test]
+    fn control_letters_map_to_c0_codes() {
+        let ctrl = KeyModifiers::CONTROL;
+        assert_eq!(key(KeyCode::Char('a'), ctrl), Some(vec![0x01]));
+        assert_eq!(key(KeyCode::Char('C'), ctrl), Some(vec![0x03]));
+        assert_eq!(key(KeyCode::Char('z'), ctrl), Some(vec![0x1a]));
+        assert_eq!(key(KeyCode::Char('['), ctrl), Some(vec![0x1b]));
+        assert_eq!(key(KeyCode::Char(' '), ctrl), Some(vec![0x00]));
+        assert_eq!(key(KeyCode::Char('1'), ctrl), None);
+    }
+
+    #[test]
+    fn arrows_follow_application_cursor_mode() {
+        let up = KeyEvent::new(KeyCode::Up, KeyModifiers::NONE);
+        let home = KeyEvent::new(KeyCode::Home, KeyModifiers::NONE);
+        assert_eq!(encode_key(up, false), Some(b"\x1b[A".to_vec()));
+        assert_eq!(encode_key(up, true), Some(b"\x1bOA".to_vec()));
+        assert_eq!(encode_key(home, false), Some(b"\x1b[H".to_vec()));
+        assert_eq!(encode_key(home, true), Some(b"\x1bOH".to_vec()));
+        // PageUp tidak terpengaruh mode cursor.
+        let page_up = KeyEvent::new(KeyCode::PageUp, KeyModifiers::NONE);
+        assert_eq!(encode_key(page_up, true), Some(b"\x1b[5~".to_vec()));
+    }
+
+    #[test]
+    fn alt_prefixes_escape() {
+        let alt = KeyModifiers::ALT;
+        assert_eq!(key(KeyCode::Char('b'), alt), Some(b"\x1bb".to_vec()));
+        assert_eq!(key(KeyCode::Left, alt), Some(b"\x1b\x1b[D".to_vec()));
+        assert_eq!(
+            key(KeyCode::Char('x'), alt | KeyModifiers::CONTROL),
+            Some(vec![0x1b, 0x18])
+        );
+    }
+
+    #[test]
+    fn enter_and_backspace() {
+        let none = KeyModifiers::NONE;
+        assert_eq!(key(KeyCode::Enter, none), Some(vec![b'\r']));
+        assert_eq!(key(KeyCode::Backspace, none), Some(vec![0x7f]));
+        assert_eq!(key(KeyCode::Char('é'), none), Some("é".as_bytes().to_vec()));
+        assert_eq!(key(KeyCode::F(13), none), None);
+    }
+
+    #[test]
+    fn switching_wraps_and_closing_last_pane_moves_back() {
+        let cwd = std::env::temp_dir();
+        let mut panes = TerminalPanes::default();
+        for _ in 0..3 {
+            panes.open_session(&cwd, 5, 20).unwrap();
+        }
+        assert_eq!(panes.active_index(), 2);
+        panes.next();
+        assert_eq!(panes.active_index(), 0);
+        panes.prev();
+        assert_eq!(panes.active_index(), 2);
+
+        // Menutup pane terakhir mengaktifkan pane sebelumnya.
+        panes.close_active();
+        assert_eq!(panes.active_index(), 1);
+        assert_eq!(panes.active().map(TerminalSession::title), Some("term-2"));
+        panes.prev();
+        panes.close_active();
+        assert_eq!(panes.active().map(TerminalSession::title), Some("term-2"));
+
+        panes.close_active();
+        assert!(panes.active().is_none());
+        assert!(!panes.is_visible());
+        panes.next();
+        panes.close_active();
+        assert_eq!(panes.active_index(), 0);
+    }
+}
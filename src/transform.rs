@@ -0,0 +1,457 @@
+use std::cmp::Ordering;
+
+use crate::editor::Editor;
+
+/// (baris, kolom byte).
+pub type Pos = (usize, usize);
+
+/// Transformasi teks pada seleksi. Transformasi per baris tanpa seleksi berlaku untuk baris
+/// cursor saja; konversi huruf tanpa seleksi berlaku untuk kata di bawah cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transform {
+    SortAscending,
+    SortDescending,
+    SortNumeric,
+    SortUnique,
+    Reverse,
+    Upper,
+    Lower,
+    Snake,
+    Camel,
+    Kebab,
+    TrimTrailing,
+    TabsToSpaces,
+    SpacesToTabs,
+    Align,
+    Pipe,
+}
+
+impl Transform {
+    pub const ALL: [Self; 15] = [
+        Self::SortAscending,
+        Self::SortDescending,
+        Self::SortNumeric,
+        Self::SortUnique,
+        Self::Reverse,
+        Self::Upper,
+        Self::Lower,
+        Self::Snake,
+        Self::Camel,
+        Self::Kebab,
+        Self::TrimTrailing,
+        Self::TabsToSpaces,
+        Self::SpacesToTabs,
+        Self::Align,
+        Self::Pipe,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::SortAscending => "Urutkan baris (A-Z)",
+            Self::SortDescending => "Urutkan baris (Z-A)",
+            Self::SortNumeric => "Urutkan baris (angka)",
+            Self::SortUnique => "Urutkan baris, buang duplikat",
+            Self::Reverse => "Balik urutan baris",
+            Self::Upper => "HURUF BESAR",
+            Self::Lower => "huruf kecil",
+            Self::Snake => "snake_case",
+            Self::Camel => "CamelCase",
+            Self::Kebab => "kebab-case",
+            Self::TrimTrailing => "Hapus spasi di akhir baris",
+            Self::TabsToSpaces => "Tab ke spasi",
+            Self::SpacesToTabs => "Spasi ke tab (indentasi)",
+            Self::Align => "Ratakan pada karakter...",
+            Self::Pipe => "Pipe ke perintah shell...",
+        }
+    }
+
+    /// Nama stabil untuk disimpan di macro.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::SortAscending => "sort-asc",
+            Self::SortDescending => "sort-desc",
+            Self::SortNumeric => "sort-numeric",
+            Self::SortUnique => "sort-unique",
+            Self::Reverse => "reverse",
+            Self::Upper => "upper",
+            Self::Lower => "lower",
+            Self::Snake => "snake",
+            Self::Camel => "camel",
+            Self::Kebab => "kebab",
+            Self::TrimTrailing => "trim-trailing",
+            Self::TabsToSpaces => "tabs-to-spaces",
+            Self::SpacesToTabs => "spaces-to-tabs",
+            Self::Align => "align",
+            Self::Pipe => "pipe",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|transform| transform.name() == name)
+    }
+
+    /// Judul input tambahan yang dibutuhkan sebelum transformasi dijalankan.
+    pub fn prompt(self) -> Option<&'static str> {
+        match self {
+            Self::Align => Some(" Ratakan pada "),
+            Self::Pipe => Some(" Perintah shell "),
+            _ => None,
+        }
+    }
+
+    fn linewise(self) -> bool {
+        !matches!(
+            self,
+            Self::Upper | Self::Lower | Self::Snake | Self::Camel | Self::Kebab
+        )
+    }
+
+    /// Hasil transformasi `text`; `arg` adalah token perataan untuk `Align` dan `tab_width` lebar
+    /// tab untuk konversi tab ↔ spasi. `Pipe` dijalankan sebagai job oleh pemanggil, jadi teks
+    /// dikembalikan apa adanya.
+    pub fn apply_text(self, text: &str, arg: &str, tab_width: usize) -> String {
+        let tab = tab_width.max(1);
+        let lines = text.split('\n').collect::<Vec<_>>();
+        let map_lines = |f: &dyn Fn(&str) -> String| {
+            lines
+                .iter()
+                .map(|line| f(line))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        match self {
+            Self::SortAscending | Self::SortDescending | Self::SortUnique => {
+                let mut sorted = lines.clone();
+                sorted.sort();
+                if self == Self::SortDescending {
+                    sorted.reverse();
+                }
+                if self == Self::SortUnique {
+                    sorted.dedup();
+                }
+                sorted.join("\n")
+            }
+            Self::SortNumeric => {
+                let mut sorted = lines.clone();
+                // Baris tanpa angka di depan dipindah ke akhir dengan urutan tetap.
+                sorted.sort_by(|a, b| match (leading_number(a), leading_number(b)) {
+                    (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                });
+                sorted.join("\n")
+            }
+            Self::Reverse => lines.iter().rev().copied().collect::<Vec<_>>().join("\n"),
+            Self::Upper => text.to_uppercase(),
+            Self::Lower => text.to_lowercase(),
+            Self::Snake | Self::Camel | Self::Kebab => convert_identifiers(text, self),
+            Self::TrimTrailing => map_lines(&|line| line.trim_end().to_string()),
+            Self::TabsToSpaces => map_lines(&|line| expand_tabs(line, tab)),
+            Self::SpacesToTabs => map_lines(&|line| {
+                let indent_len = line.len() - line.trim_start().len();
+                let width = expand_tabs(&line[..indent_len], tab).len();
+                format!(
+                    "{}{}{}",
+                    "\t".repeat(width / tab),
+                    " ".repeat(width % tab),
+                    &line[indent_len..]
+                )
+            }),
+            Self::Align => align(&lines, arg),
+            Self::Pipe => text.to_string(),
+        }
+    }
+}
+
+/// Rentang yang diubah `transform`: seleksi (diperluas ke baris penuh untuk transformasi per
+/// baris), atau baris / kata di bawah cursor bila tidak ada seleksi. Seluruh buffer tidak pernah
+/// diubah (atau dikirim ke shell) tanpa diseleksi.
+pub fn target_range(transform: Transform, editor: &Editor) -> Option<(Pos, Pos)> {
+    let lines = editor.lines();
+    let last = lines.len().checked_sub(1)?;
+    if transform.linewise() {
+        let (start, end) = if editor.has_selection() {
+            editor.movable_line_range()
+        } else {
+            let row = editor.cursor_y().min(last);
+            (row, row)
+        };
+        return Some(((start, 0), (end, lines[end].len())));
+    }
+    if let Some((start, end)) = editor.selection_range() {
+        return Some(((start.1, start.0), (end.1, end.0)));
+    }
+    let row = editor.cursor_y();
+    let line = &lines[row];
+    let col = editor.cursor_x().min(line.len());
+    let is_word = |ch: char| ch.is_alphanumeric() || ch == '_' || ch == '-';
+    let start = line[..col]
+        .char_indices()
+        .rev()
+        .take_while(|&(_, ch)| is_word(ch))
+        .last()
+        .map_or(col, |(idx, _)| idx);
+    let end = line[col..]
+        .char_indices()
+        .find(|&(_, ch)| !is_word(ch))
+        .map_or(line.len(), |(idx, _)| col + idx);
+    (start < end).then_some(((row, start), (row, end)))
+}
+
+pub fn range_text(lines: &[String], start: Pos, end: Pos) -> String {
+    if start.0 == end.0 {
+        return lines[start.0][start.1..end.1].to_string();
+    }
+    let mut text = lines[start.0][start.1..].to_string();
+    for line in &lines[start.0 + 1..end.0] {
+        text.push('\n');
+        text.push_str(line);
+    }
+    text.push('\n');
+    text.push_str(&lines[end.0][..end.1]);
+    text
+}
+
+/// Jalankan transformasi (selain `Pipe`) pada editor; `false` bila tidak ada yang berubah.
+pub fn apply(transform: Transform, arg: &str, tab_width: usize, editor: &mut Editor) -> bool {
+    let Some((start, end)) = target_range(transform, editor) else {
+        return false;
+    };
+    let text = range_text(editor.lines(), start, end);
+    let replaced = transform.apply_text(&text, arg, tab_width);
+    if replaced == text {
+        return false;
+    }
+    let had_selection = editor.has_selection();
+    let cursor = (editor.cursor_y(), editor.cursor_x());
+    editor.replace_range(start, end, &replaced);
+    // Tanpa seleksi (satu baris / satu kata) cursor tetap di tempat semula.
+    if !had_selection {
+        editor.set_cursor(cursor.0, cursor.1);
+    }
+    true
+}
+
+fn leading_number(line: &str) -> Option<f64> {
+    let line = line.trim_start();
+    let end = line
+        .char_indices()
+        .find(|&(idx, ch)| !(ch.is_ascii_digit() || ch == '.' || (idx == 0 && ch == '-')))
+        .map_or(line.len(), |(idx, _)| idx);
+    line[..end].parse().ok()
+}
+
+fn expand_tabs(line: &str, tab: usize) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut width = 0;
+    for ch in line.chars() {
+        if ch == '\t' {
+            let spaces = tab - width % tab;
+            out.push_str(&" ".repeat(spaces));
+            width += spaces;
+        } else {
+            out.push(ch);
+            width += 1;
+        }
+    }
+    out
+}
+
+// Sejajarkan kemunculan pertama `token` di tiap baris; baris tanpa token tidak diubah.
+fn align(lines: &[&str], token: &str) -> String {
+    if token.is_empty() {
+        return lines.join("\n");
+    }
+    let split = |line: &str| {
+        let idx = line.find(token)?;
+        let prefix = line[..idx].trim_end();
+        (!prefix.trim().is_empty()).then_some((prefix.to_string(), line[idx..].to_string()))
+    };
+    let width = lines
+        .iter()
+        .filter_map(|line| split(line))
+        .map(|(prefix, _)| prefix.chars().count())
+        .max()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|line| match split(line) {
+            Some((prefix, rest)) => format!("{:<width$} {}", prefix, rest, width = width),
+            None => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn convert_identifiers(text: &str, style: Transform) -> String {
+    let is_ident = |ch: char| ch.is_alphanumeric() || ch == '_' || ch == '-';
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(is_ident) {
+        out.push_str(&rest[..start]);
+        let run = &rest[start..];
+        let end = run.find(|ch: char| !is_ident(ch)).unwrap_or(run.len());
+        out.push_str(&convert_identifier(&run[..end], style));
+        rest = &run[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn convert_identifier(ident: &str, style: Transform) -> String {
+    // `_` di awal/akhir (mis. `_unused`) dipertahankan.
+    let core = ident.trim_matches('_');
+    let lead = &ident[..ident.len() - ident.trim_start_matches('_').len()];
+    let trail = &ident[ident.trim_end_matches('_').len()..];
+    let words = split_words(core);
+    if words.is_empty() {
+        return ident.to_string();
+    }
+    let joined = match style {
+        Transform::Snake => words.join("_"),
+        Transform::Kebab => words.join("-"),
+        _ => words
+            .iter()
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                    .unwrap_or_default()
+            })
+            .collect(),
+    };
+    format!("{}{}{}", lead, joined, trail)
+}
+
+/// Pecah identifier menjadi kata huruf kecil: `HTTPServer_id` -> `http`, `server`, `id`.
+fn split_words(ident: &str) -> Vec<String> {
+    let chars = ident.chars().collect::<Vec<_>>();
+    let mut words = Vec::new();
+    let mut current = String::new();
+    for (idx, &ch) in chars.iter().enumerate() {
+        if ch == '_' || ch == '-' {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        let prev = idx.checked_sub(1).map(|prev| chars[prev]);
+        let next = chars.get(idx + 1).copied();
+        let boundary = ch.is_uppercase()
+            && prev.is_some_and(|prev| {
+                prev.is_lowercase()
+                    || prev.is_ascii_digit()
+                    || (prev.is_uppercase() && next.is_some_and(char::is_lowercase))
+            });
+        if boundary && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        current.extend(ch.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_words_on_case_and_separators() {
+        assert_eq!(split_words("HTTPServer_id"), ["http", "server", "id"]);
+        assert_eq!(split_words("parseJSON2Value"), ["parse", "json2", "value"]);
+        assert_eq!(split_words("kebab--case"), ["kebab", "case"]);
+        assert!(split_words("__").is_empty());
+    }
+
+    #[test]
+    fn identifier_styles_keep_outer_underscores() {
+        let text = "_fooBar, HTTPServer";
+        assert_eq!(
+            Transform::Snake.apply_text(text, "", 4),
+            "_foo_bar, http_server"
+        );
+        assert_eq!(
+            Transform::Kebab.apply_text(text, "", 4),
+            "_foo-bar, http-server"
+        );
+        assert_eq!(
+            Transform::Camel.apply_text(text, "", 4),
+            "_FooBar, HttpServer"
+        );
+    }
+
+    #[test]
+    fn align_first_token_and_skip_other_lines() {
+        let text = "let a = 1;\nlet long = 2;\n// tanpa token\n= 3";
+        assert_eq!(
+            Transform::Align.apply_text(text, "=", 4),
+            "let a    = 1;\nlet long = 2;\n// tanpa token\n= 3"
+        );
+        assert_eq!(Transform::Align.apply_text(text, "", 4), text);
+    }
+
+    #[test]
+    fn numeric_sort_moves_non_numbers_last() {
+        let text = "10 x\n-2\nb\n2.5\na\n3";
+        assert_eq!(
+            Transform::SortNumeric.apply_text(text, "", 4),
+            "-2\n2.5\n3\n10 x\nb\na"
+        );
+        assert_eq!(
+            Transform::SortAscending.apply_text("10\n9\n1", "", 4),
+            "1\n10\n9"
+        );
+        assert_eq!(Transform::SortUnique.apply_text("b\na\nb", "", 4), "a\nb");
+    }
+
+    #[test]
+    fn tab_conversion_uses_tab_width() {
+        assert_eq!(Transform::TabsToSpaces.apply_text("\tx\ta", "", 2), "  x a");
+        assert_eq!(
+            Transform::SpacesToTabs.apply_text("     x", "", 2),
+            "\t\t x"
+        );
+        assert_eq!(Transform::SpacesToTabs.apply_text("     x", "", 4), "\t x");
+    }
+
+    #[test]
+    fn without_selection_only_cursor_line_or_word_changes() {
+        let mut editor = Editor::from_text("b\na\nfoo_bar baz");
+        editor.set_cursor(1, 0);
+        assert_eq!(
+            target_range(Transform::SortAscending, &editor),
+            Some(((1, 0), (1, 1)))
+        );
+        editor.set_cursor(2, 2);
+        assert_eq!(
+            target_range(Transform::Upper, &editor),
+            Some(((2, 0), (2, 7)))
+        );
+        assert!(apply(Transform::Upper, "", 4, &mut editor));
+        assert_eq!(editor.text(), "b\na\nFOO_BAR baz");
+        assert_eq!((editor.cursor_y(), editor.cursor_x()), (2, 2));
+    }
+
+    #[test]
+    fn selection_is_expanded_to_full_lines() {
+        let mut editor = Editor::from_text("c\nb\na");
+        editor.set_cursor(0, 1);
+        // Seleksi yang berakhir di kolom 0 tidak ikut mengambil baris tersebut.
+        editor.set_cursor_selecting(2, 0, true);
+        assert!(apply(Transform::SortAscending, "", 4, &mut editor));
+        assert_eq!(editor.text(), "b\nc\na");
+
+        editor.set_cursor(0, 1);
+        editor.set_cursor_selecting(2, 1, true);
+        assert!(apply(Transform::SortAscending, "", 4, &mut editor));
+        assert_eq!(editor.text(), "a\nb\nc");
+        assert!(!apply(Transform::SortAscending, "", 4, &mut editor));
+    }
+}
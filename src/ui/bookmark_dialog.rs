@@ -0,0 +1,121 @@
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use super::theme::ThemeMode;
+use super::types::{BookmarkNameDialog, BookmarkPicker};
+
+pub fn render_bookmark_name_dialog(frame: &mut Frame, dialog: &BookmarkNameDialog) {
+    let area = centered_rect(frame.area(), 50, 7);
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Block::default()
+            .title(format!(" Bookmark Baris {} ", dialog.line + 1))
+            .borders(Borders::ALL),
+        area,
+    );
+
+    let inner = Layout::vertical([Constraint::Length(1), Constraint::Length(3)])
+        .margin(1)
+        .split(area);
+    frame.render_widget(
+        Paragraph::new("Nama bookmark, Enter: simpan, Esc: batal"),
+        inner[0],
+    );
+    frame.render_widget(
+        Paragraph::new(dialog.name.as_str())
+            .block(Block::default().title(" Nama ").borders(Borders::ALL)),
+        inner[1],
+    );
+
+    let input_width = inner[1].width.saturating_sub(2) as usize;
+    let cursor_x = inner[1].x + 1 + dialog.name.len().min(input_width.saturating_sub(1)) as u16;
+    frame.set_cursor_position((cursor_x, inner[1].y + 1));
+}
+
+pub fn render_bookmark_picker(frame: &mut Frame, picker: &BookmarkPicker, theme: ThemeMode) {
+    let palette = theme.palette();
+    let area = centered_rect(frame.area(), 70, frame.area().height.saturating_mul(2) / 3);
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Block::default().title(" Bookmark ").borders(Borders::ALL),
+        area,
+    );
+
+    let inner = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(3),
+        Constraint::Min(1),
+    ])
+    .margin(1)
+    .split(area);
+    frame.render_widget(
+        Paragraph::new("Up/Down pilih | Enter lompat | Delete hapus | Esc tutup")
+            .style(Style::default().add_modifier(Modifier::DIM)),
+        inner[0],
+    );
+    frame.render_widget(
+        Paragraph::new(picker.filter.as_str())
+            .block(Block::default().title(" Filter ").borders(Borders::ALL)),
+        inner[1],
+    );
+
+    let visible = picker.visible();
+    let height = inner[2].height.max(1) as usize;
+    let name_width = visible
+        .iter()
+        .map(|idx| picker.entries[*idx].1.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(24);
+    let start = picker.selected.saturating_sub(height - 1);
+    let mut lines = Vec::new();
+    if visible.is_empty() {
+        lines.push(Line::from(if picker.entries.is_empty() {
+            "Belum ada bookmark (Alt+B di editor untuk menambah)"
+        } else {
+            "Tidak ada yang cocok"
+        }));
+    }
+    for (position, idx) in visible.iter().enumerate().skip(start).take(height) {
+        let (path, bookmark) = &picker.entries[*idx];
+        let path = path.strip_prefix(&picker.root).unwrap_or(path);
+        let name = format!("{:<width$}  ", bookmark.name, width = name_width);
+        let location = format!("{}:{}  ", path.display(), bookmark.line + 1);
+        if position == picker.selected {
+            lines.push(Line::styled(
+                format!("{}{}{}", name, location, bookmark.text),
+                Style::default()
+                    .fg(palette.warning_text)
+                    .bg(palette.warning),
+            ));
+        } else {
+            lines.push(Line::from(vec![
+                Span::styled(name, Style::default().fg(palette.accent)),
+                Span::raw(location),
+                Span::styled(
+                    bookmark.text.clone(),
+                    Style::default().add_modifier(Modifier::DIM),
+                ),
+            ]));
+        }
+    }
+    frame.render_widget(Paragraph::new(lines), inner[2]);
+
+    let input_width = inner[1].width.saturating_sub(2) as usize;
+    let cursor_x = inner[1].x + 1 + picker.filter.len().min(input_width.saturating_sub(1)) as u16;
+    frame.set_cursor_position((cursor_x, inner[1].y + 1));
+}
+
+fn centered_rect(area: Rect, width_percent: u16, height: u16) -> Rect {
+    let max_width = area.width.saturating_sub(2).max(1);
+    let max_height = area.height.saturating_sub(2).max(1);
+    let width = area.width.saturating_mul(width_percent) / 100;
+    let width = width.max(30).min(max_width);
+    let height = height.max(7).min(max_height);
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    Rect::new(x, y, width, height)
+}
@@ -0,0 +1,222 @@
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Margin};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Block, Borders, Clear, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
+};
+
+use super::theme::{ThemeMode, ThemePalette};
+use super::types::DiffViewDialog;
+use crate::diff::{DiffRowKind, DiffSide};
+
+pub fn render_diff_view(frame: &mut Frame, dialog: &DiffViewDialog, theme: ThemeMode) {
+    let palette = theme.palette();
+    let area = frame.area().inner(Margin::new(1, 1));
+    frame.render_widget(Clear, area);
+
+    let mode_label = if dialog.inline {
+        "inline"
+    } else {
+        "side-by-side"
+    };
+    frame.render_widget(
+        Block::default()
+            .title(format!(
+                " Diff [{}] hunk {}/{} ",
+                mode_label,
+                dialog.current_hunk(),
+                dialog.document.hunk_rows.len()
+            ))
+            .borders(Borders::ALL),
+        area,
+    );
+
+    let inner = Layout::vertical([Constraint::Min(3), Constraint::Length(1)])
+        .margin(1)
+        .split(area);
+    let body = inner[0];
+    let viewport_h = body.height.saturating_sub(2).max(1) as usize;
+    let digits = dialog.document.rows.len().max(1).to_string().len();
+
+    if dialog.inline {
+        let lines = inline_lines(dialog, viewport_h, digits, &palette);
+        frame.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .title(format!(" {} -> {} ", dialog.left_title, dialog.right_title))
+                    .borders(Borders::ALL),
+            ),
+            body,
+        );
+    } else {
+        let panes = Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(body);
+        let mut left = Vec::new();
+        let mut right = Vec::new();
+        for row in dialog
+            .document
+            .rows
+            .iter()
+            .skip(dialog.scroll_y)
+            .take(viewport_h)
+        {
+            let (old_style, new_style) = match row.kind {
+                DiffRowKind::Equal => (Style::default(), Style::default()),
+                DiffRowKind::Delete | DiffRowKind::Insert | DiffRowKind::Replace => (
+                    Style::default().bg(palette.diff_removed_bg),
+                    Style::default().bg(palette.diff_added_bg),
+                ),
+            };
+            left.push(side_line(
+                row.old.as_ref(),
+                ' ',
+                digits,
+                dialog.scroll_x,
+                old_style,
+                palette.diff_removed_word_bg,
+                &palette,
+            ));
+            right.push(side_line(
+                row.new.as_ref(),
+                ' ',
+                digits,
+                dialog.scroll_x,
+                new_style,
+                palette.diff_added_word_bg,
+                &palette,
+            ));
+        }
+        frame.render_widget(
+            Paragraph::new(left).block(
+                Block::default()
+                    .title(format!(" {} ", dialog.left_title))
+                    .borders(Borders::ALL),
+            ),
+            panes[0],
+        );
+        frame.render_widget(
+            Paragraph::new(right).block(
+                Block::default()
+                    .title(format!(" {} ", dialog.right_title))
+                    .borders(Borders::ALL),
+            ),
+            panes[1],
+        );
+    }
+
+    let mut v_state = ScrollbarState::new(dialog.document.rows.len())
+        .viewport_content_length(viewport_h)
+        .position(dialog.scroll_y);
+    frame.render_stateful_widget(
+        Scrollbar::new(ScrollbarOrientation::VerticalRight),
+        body,
+        &mut v_state,
+    );
+
+    frame.render_widget(
+        Paragraph::new(
+            "Up/Down/PageUp/PageDown scroll | Left/Right geser | n/p hunk berikut/sebelum | v inline/side-by-side | Esc tutup",
+        )
+        .style(Style::default().fg(palette.status_fg).bg(palette.status_bg)),
+        inner[1],
+    );
+}
+
+fn inline_lines(
+    dialog: &DiffViewDialog,
+    viewport_h: usize,
+    digits: usize,
+    palette: &ThemePalette,
+) -> Vec<Line<'static>> {
+    let removed = Style::default().bg(palette.diff_removed_bg);
+    let added = Style::default().bg(palette.diff_added_bg);
+    let mut lines = Vec::new();
+    for row in dialog.document.rows.iter().skip(dialog.scroll_y) {
+        if lines.len() >= viewport_h {
+            break;
+        }
+        match row.kind {
+            DiffRowKind::Equal => lines.push(side_line(
+                row.new.as_ref(),
+                ' ',
+                digits,
+                dialog.scroll_x,
+                Style::default(),
+                Color::Reset,
+                palette,
+            )),
+            _ => {
+                if row.old.is_some() {
+                    lines.push(side_line(
+                        row.old.as_ref(),
+                        '-',
+                        digits,
+                        dialog.scroll_x,
+                        removed,
+                        palette.diff_removed_word_bg,
+                        palette,
+                    ));
+                }
+                if row.new.is_some() {
+                    lines.push(side_line(
+                        row.new.as_ref(),
+                        '+',
+                        digits,
+                        dialog.scroll_x,
+                        added,
+                        palette.diff_added_word_bg,
+                        palette,
+                    ));
+                }
+            }
+        }
+    }
+    lines.truncate(viewport_h);
+    lines
+}
+
+fn side_line(
+    side: Option<&DiffSide>,
+    sign: char,
+    digits: usize,
+    scroll_x: usize,
+    base: Style,
+    word_bg: Color,
+    palette: &ThemePalette,
+) -> Line<'static> {
+    let Some(side) = side else {
+        return Line::from(Span::styled(
+            format!("{:>width$} ", "", width = digits + 1),
+            Style::default().fg(palette.line_other),
+        ));
+    };
+
+    let mut spans = vec![Span::styled(
+        format!("{:>width$}{} ", side.line_no, sign, width = digits),
+        Style::default().fg(palette.line_other),
+    )];
+    let word = base.bg(word_bg);
+    let mut run = String::new();
+    let mut run_style = base;
+    for (idx, ch) in side.text.char_indices().skip(scroll_x) {
+        let style = if side
+            .changed
+            .iter()
+            .any(|(start, end)| idx >= *start && idx < *end)
+        {
+            word
+        } else {
+            base
+        };
+        if style != run_style && !run.is_empty() {
+            spans.push(Span::styled(std::mem::take(&mut run), run_style));
+        }
+        run_style = style;
+        run.push(if ch == '\t' { ' ' } else { ch });
+    }
+    if !run.is_empty() {
+        spans.push(Span::styled(run, run_style));
+    }
+    Line::from(spans).style(base)
+}
@@ -1,5 +1,5 @@
 use ratatui::layout::{Constraint, Layout, Rect};
-use ratatui::style::{Color, Style};
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{
     Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap,
@@ -7,19 +7,28 @@ use ratatui::widgets::{
 use ratatui::Frame;
 
 use crate::editor::Editor;
-use crate::file_tree::FileTree;
+use crate::editor_settings::CurrentLineHighlight;
+use crate::manager::cargo_runner::{Diagnostic, DiagnosticLevel};
+use crate::vcs::git::FileStatus;
+use crate::signature::verify::SignatureStatus;
 use crate::syntax::highlight::{highlight_rust_document, SyntaxPalette};
-use crate::tab_manager::TabManager;
+use crate::syntax::indent::{detect_indent_width, display_width, tab_advance};
 use crate::ui::gutter::build_gutter_lines;
+use crate::ui::terminal_view::{render_terminal_panel, TERMINAL_PANEL_HEIGHT};
 
 use super::FocusPane;
+use crate::ui::outline_view::render_outline_panel;
 use crate::ui::theme::ThemeMode;
-use crate::ui::types::TabHit;
+use crate::ui::types::{EditorView, TabHit};
+
+// Baris kosong mencari indentasi baris berisi terdekat paling jauh sejauh ini.
+const GUIDE_SCAN_LINES: usize = 200;
 
 pub struct EditorRenderParts {
     pub editor_content_rect: Rect,
     pub tree_content_rect: Rect,
     pub tab_content_rect: Rect,
+    pub terminal_content_rect: Rect,
     pub tab_hits: Vec<TabHit>,
     pub editor_height: usize,
     pub text_width: usize,
@@ -30,14 +39,20 @@ pub struct EditorRenderParts {
 pub fn render_editor(
     frame: &mut Frame,
     editor: &Editor,
-    status_message: &str,
-    tabs: &TabManager,
-    file_tree: &FileTree,
-    focus: FocusPane,
-    show_file_tree: bool,
+    view: EditorView,
     search_keyword: Option<&str>,
-    theme: ThemeMode,
 ) -> EditorRenderParts {
+    let EditorView {
+        status_message,
+        tabs,
+        file_tree,
+        terminal_panes,
+        outline,
+        focus,
+        show_file_tree,
+        decorations,
+        theme,
+    } = view;
     let palette = theme.palette();
     let syntax_palette = if theme == ThemeMode::Dark {
         SyntaxPalette::dark()
@@ -92,7 +107,7 @@ pub fn render_editor(
         }
     }
 
-    for idx in start..end {
+    for (idx, label) in labels.iter().enumerate().take(end).skip(start) {
         if x >= tab_content_rect.x + tab_content_rect.width {
             break;
         }
@@ -100,7 +115,7 @@ pub fn render_editor(
         if remaining == 0 {
             break;
         }
-        let mut label = labels[idx].clone();
+        let mut label = label.clone();
         if label.chars().count() as u16 > remaining {
             label = truncate_plain_with_tilde(&label, remaining as usize);
         }
@@ -134,10 +149,24 @@ pub fn render_editor(
     }
     frame.render_widget(Paragraph::new(Line::from(tab_spans)), tab_content_rect);
 
+    let (body_area, terminal_area) = if terminal_panes.is_visible() {
+        let split = Layout::vertical([
+            Constraint::Min(5),
+            Constraint::Length(TERMINAL_PANEL_HEIGHT),
+        ])
+        .split(chunks[1]);
+        (split[0], Some(split[1]))
+    } else {
+        (chunks[1], None)
+    };
+    let terminal_content_rect = terminal_area
+        .map(|area| render_terminal_panel(frame, area, terminal_panes, focus, theme))
+        .unwrap_or_default();
+
     let body = if show_file_tree {
-        Layout::horizontal([Constraint::Length(34), Constraint::Min(20)]).split(chunks[1])
+        Layout::horizontal([Constraint::Length(34), Constraint::Min(20)]).split(body_area)
     } else {
-        Layout::horizontal([Constraint::Length(0), Constraint::Min(20)]).split(chunks[1])
+        Layout::horizontal([Constraint::Length(0), Constraint::Min(20)]).split(body_area)
     };
     let tree_area = body[0];
     let editor_area = body[1];
@@ -161,7 +190,7 @@ pub fn render_editor(
     let editor_height = editor_area.height.saturating_sub(2) as usize;
     let editor_width = editor_area.width.saturating_sub(2);
     let gutter_digits = editor.lines().len().max(1).to_string().len() as u16;
-    let gutter_width = (gutter_digits + 1)
+    let gutter_width = (gutter_digits + 3)
         .min(editor_width.saturating_sub(3))
         .max(2);
     let editor_inner = Layout::horizontal([
@@ -174,7 +203,9 @@ pub fn render_editor(
     let code_rect = editor_inner[2];
     let text_width = code_rect.width.saturating_sub(1).max(1) as usize;
 
-    if show_file_tree {
+    if show_file_tree && let Some(panel) = outline {
+        render_outline_panel(frame, tree_area, panel, focus, theme);
+    } else if show_file_tree {
         let tree_height = tree_area.height.saturating_sub(2) as usize;
         let tree_width = tree_content_rect.width.max(1) as usize;
         let tree_lines = file_tree
@@ -186,11 +217,29 @@ pub fn render_editor(
             .map(|(idx, entry)| {
                 let indent = "  ".repeat(entry.depth());
                 let marker = if entry.is_dir() { ">" } else { "-" };
-                let line_text = truncate_plain_with_tilde(
-                    &format!("{}{} {}", indent, marker, entry.name()),
-                    tree_width,
+                let git_status = decorations.git.and_then(|git| git.status_for(entry.path()));
+                let badge = git_status
+                    .map(|status| format!(" {}", status.badge()))
+                    .unwrap_or_default();
+                let signature = decorations
+                    .signatures
+                    .and_then(|cache| cache.status_for(entry.path()))
+                    .filter(|status| *status != SignatureStatus::Unsigned);
+                let signature_badge = signature
+                    .map(|status| format!(" {}", status.badge()))
+                    .unwrap_or_default();
+                let name_width = tree_width
+                    .saturating_sub(badge.chars().count() + signature_badge.chars().count());
+                let line_text = format!(
+                    "{}{}",
+                    truncate_plain_with_tilde(
+                        &format!("{}{} {}", indent, marker, entry.name()),
+                        name_width,
+                    ),
+                    badge
                 );
                 if idx == file_tree.selected_index() {
+                    let line_text = format!("{}{}", line_text, signature_badge);
                     if focus == FocusPane::FileTree {
                         Line::styled(
                             line_text,
@@ -202,7 +251,17 @@ pub fn render_editor(
                         Line::styled(line_text, Style::default().bg(palette.tree_inactive_bg))
                     }
                 } else {
-                    Line::from(line_text)
+                    let name_style = git_status
+                        .map(|status| Style::default().fg(git_status_color(status, theme)))
+                        .unwrap_or_default();
+                    let mut spans = vec![Span::styled(line_text, name_style)];
+                    if let Some(status) = signature {
+                        spans.push(Span::styled(
+                            signature_badge,
+                            Style::default().fg(signature_status_color(status, theme)),
+                        ));
+                    }
+                    Line::from(spans)
                 }
             })
             .collect::<Vec<_>>();
@@ -216,6 +275,24 @@ pub fn render_editor(
         frame.render_widget(tree_widget, tree_area);
     }
 
+    let settings = decorations.settings;
+    let tab_width = settings.tab_width;
+    let line_view = LineView {
+        tab_width,
+        show_whitespace: settings.show_whitespace,
+        indent_width: settings
+            .indent_guides
+            .then(|| detect_indent_width(editor.lines(), tab_width)),
+        rulers: &settings.rulers,
+        current_line_bg: match settings.current_line {
+            CurrentLineHighlight::Theme => Some(palette.current_line_bg),
+            CurrentLineHighlight::Off => None,
+            CurrentLineHighlight::Color(color) => Some(color),
+        },
+        ruler_bg: palette.ruler_bg,
+        glyph_fg: palette.line_other,
+        width: text_width,
+    };
     let highlighted_document = highlight_rust_document(editor.lines(), &syntax_palette);
     let mut lines = Vec::new();
     let mut cursor_visual = None;
@@ -234,18 +311,34 @@ pub fn render_editor(
             highlighted =
                 apply_search_background(highlighted, source_line, keyword, palette.search_bg);
         }
+        for diagnostic in decorations.diagnostics {
+            if let Some((start, end)) = diagnostic_columns_for_row(diagnostic, row, source_line) {
+                let color = match diagnostic.level {
+                    DiagnosticLevel::Error => palette.diagnostic_error,
+                    DiagnosticLevel::Warning => palette.diagnostic_warning,
+                    DiagnosticLevel::Note => palette.diagnostic_note,
+                };
+                highlighted = apply_diagnostic_underline(highlighted, start, end, color);
+            }
+        }
         if let Some((start, end)) = editor.selection_columns_for_row(row) {
             highlighted = apply_selection_background(highlighted, start, end, palette.selection_bg);
         }
 
-        let wrapped = wrap_line_smart(highlighted, text_width.max(1));
+        let wrapped = wrap_line_smart(highlighted, text_width.max(1), tab_width);
         let mut cursor_segment_index = None;
         let mut cursor_x_in_segment = 0usize;
         if row == editor.cursor_y() {
-            let (segment_index, segment_x) = cursor_in_wrapped_line(editor.cursor_x(), &wrapped);
+            let (segment_index, segment_x) =
+                cursor_in_wrapped_line(editor.cursor_x(), source_line, &wrapped, tab_width);
             cursor_segment_index = Some(segment_index);
             cursor_x_in_segment = segment_x;
         }
+        let guide_depth = if line_view.indent_width.is_some() {
+            indent_guide_depth(editor.lines(), row, tab_width)
+        } else {
+            0
+        };
 
         for (segment_idx, segment) in wrapped.into_iter().enumerate() {
             if rendered_rows >= visible_rows {
@@ -254,7 +347,9 @@ pub fn render_editor(
             if Some(segment_idx) == cursor_segment_index {
                 cursor_visual = Some((cursor_x_in_segment, rendered_rows));
             }
-            lines.push(segment.line);
+            // Indent guide hanya di segmen pertama; lanjutan wrap tidak punya indentasi.
+            let depth = if segment_idx == 0 { guide_depth } else { 0 };
+            lines.push(line_view.render(&segment, source_line, depth, row == editor.cursor_y()));
             rendered_rows += 1;
         }
     }
@@ -292,6 +387,7 @@ pub fn render_editor(
         editor.scroll_y(),
         editor_height.max(1),
         editor.cursor_y(),
+        &decorations,
         theme,
     );
     frame.render_widget(Paragraph::new(gutter), gutter_rect);
@@ -322,10 +418,41 @@ pub fn render_editor(
     } else {
         String::new()
     };
+    let branch_status = decorations
+        .git
+        .and_then(|git| git.branch())
+        .map(|branch| format!(" | git: {}", branch))
+        .unwrap_or_default();
+    let jobs_status = if decorations.jobs.is_empty() {
+        String::new()
+    } else {
+        format!(
+            " | jobs: {}",
+            decorations
+                .jobs
+                .iter()
+                .map(|job| job.summary())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    };
+    let vim_status = decorations
+        .vim_mode
+        .map(|mode| format!("{} | ", mode))
+        .unwrap_or_default();
+    let macro_status = if decorations.macro_recording {
+        "REC | "
+    } else {
+        ""
+    };
     let status = format!(
-        "{}{} | baris {}, kolom {}",
+        "{}{}{}{}{}{} | baris {}, kolom {}",
+        macro_status,
+        vim_status,
         status_message,
         selection_status,
+        branch_status,
+        jobs_status,
         editor.cursor_y() + 1,
         editor.cursor_x() + 1
     );
@@ -337,6 +464,7 @@ pub fn render_editor(
         editor_content_rect: code_rect,
         tree_content_rect,
         tab_content_rect,
+        terminal_content_rect,
         tab_hits,
         editor_height,
         text_width,
@@ -357,6 +485,26 @@ pub fn render_editor_cursor(frame: &mut Frame, _editor: &Editor, parts: &EditorR
     frame.set_cursor_position((cursor_x, cursor_y));
 }
 
+fn git_status_color(status: FileStatus, theme: ThemeMode) -> Color {
+    let palette = theme.palette();
+    match status {
+        FileStatus::Conflicted | FileStatus::Deleted => palette.git_deleted,
+        FileStatus::Modified | FileStatus::Renamed => palette.git_modified,
+        FileStatus::Added | FileStatus::Untracked => palette.git_added,
+        FileStatus::Ignored => palette.line_other,
+    }
+}
+
+fn signature_status_color(status: SignatureStatus, theme: ThemeMode) -> Color {
+    let palette = theme.palette();
+    match status {
+        SignatureStatus::Trusted => palette.git_added,
+        SignatureStatus::Untrusted => palette.git_modified,
+        SignatureStatus::Tampered | SignatureStatus::Invalid => palette.git_deleted,
+        SignatureStatus::Unsigned => palette.line_other,
+    }
+}
+
 fn visible_tab_window(labels: &[String], active: usize, max_width: u16) -> (usize, usize) {
     if labels.is_empty() || max_width == 0 {
         return (0, 0);
@@ -404,8 +552,8 @@ fn tab_window_width(labels: &[String], start: usize, end: usize) -> u16 {
     if start > 0 {
         width = width.saturating_add(2);
     }
-    for idx in start..end {
-        width = width.saturating_add(labels[idx].chars().count() as u16);
+    for (idx, label) in labels.iter().enumerate().take(end).skip(start) {
+        width = width.saturating_add(label.chars().count() as u16);
         if idx + 1 < end {
             width = width.saturating_add(1);
         }
@@ -425,6 +573,58 @@ fn apply_selection_background(
     apply_background_color(line, start, end, bg_color)
 }
 
+fn diagnostic_columns_for_row(
+    diagnostic: &Diagnostic,
+    row: usize,
+    source_line: &str,
+) -> Option<(usize, usize)> {
+    let first = diagnostic.line_start.saturating_sub(1);
+    let last = diagnostic.line_end.saturating_sub(1).max(first);
+    if row < first || row > last {
+        return None;
+    }
+
+    let start = if row == first {
+        char_column_to_byte(source_line, diagnostic.column_start.saturating_sub(1))
+    } else {
+        0
+    };
+    let mut end = if row == last {
+        char_column_to_byte(source_line, diagnostic.column_end.saturating_sub(1))
+    } else {
+        source_line.len()
+    };
+    if end <= start {
+        end = source_line[start..]
+            .chars()
+            .next()
+            .map(|ch| start + ch.len_utf8())
+            .unwrap_or(start);
+    }
+    (end > start).then_some((start, end))
+}
+
+fn char_column_to_byte(line: &str, column: usize) -> usize {
+    line.char_indices()
+        .nth(column)
+        .map(|(idx, _)| idx)
+        .unwrap_or(line.len())
+}
+
+fn apply_diagnostic_underline(
+    line: Line<'static>,
+    start: usize,
+    end: usize,
+    color: Color,
+) -> Line<'static> {
+    apply_style_range(line, start, end, |style| {
+        style
+            .fg(color)
+            .underline_color(color)
+            .add_modifier(Modifier::UNDERLINED)
+    })
+}
+
 fn apply_search_background(
     mut line: Line<'static>,
     source_line: &str,
@@ -447,6 +647,15 @@ fn apply_background_color(
     start: usize,
     end: usize,
     bg_color: Color,
+) -> Line<'static> {
+    apply_style_range(line, start, end, |style| style.bg(bg_color))
+}
+
+fn apply_style_range(
+    line: Line<'static>,
+    start: usize,
+    end: usize,
+    restyle: impl Fn(Style) -> Style,
 ) -> Line<'static> {
     if start >= end {
         return line;
@@ -469,28 +678,25 @@ fn apply_background_color(
         let local_start = start.saturating_sub(span_start).min(len);
         let local_end = end.saturating_sub(span_start).min(len);
 
-        if local_start > 0 {
-            if let Some(prefix) = text.get(..local_start) {
-                if !prefix.is_empty() {
-                    out.push(Span::styled(prefix.to_string(), span.style));
-                }
-            }
+        if local_start > 0
+            && let Some(prefix) = text.get(..local_start)
+            && !prefix.is_empty()
+        {
+            out.push(Span::styled(prefix.to_string(), span.style));
         }
 
-        if local_end > local_start {
-            if let Some(selected) = text.get(local_start..local_end) {
-                if !selected.is_empty() {
-                    out.push(Span::styled(selected.to_string(), span.style.bg(bg_color)));
-                }
-            }
+        if local_end > local_start
+            && let Some(selected) = text.get(local_start..local_end)
+            && !selected.is_empty()
+        {
+            out.push(Span::styled(selected.to_string(), restyle(span.style)));
         }
 
-        if local_end < len {
-            if let Some(suffix) = text.get(local_end..) {
-                if !suffix.is_empty() {
-                    out.push(Span::styled(suffix.to_string(), span.style));
-                }
-            }
+        if local_end < len
+            && let Some(suffix) = text.get(local_end..)
+            && !suffix.is_empty()
+        {
+            out.push(Span::styled(suffix.to_string(), span.style));
         }
 
         offset = span_end;
@@ -522,7 +728,120 @@ struct WrappedLineSegment {
     end: usize,
 }
 
-fn wrap_line_smart(line: Line<'static>, width: usize) -> Vec<WrappedLineSegment> {
+/// Opsi tampilan teks per segmen: perluasan tab, glyph whitespace, indent guide, ruler, dan
+/// latar baris cursor. Kolom dihitung dari awal segmen, sama seperti `compute_wrap_ranges`.
+struct LineView<'a> {
+    tab_width: usize,
+    show_whitespace: bool,
+    indent_width: Option<usize>,
+    rulers: &'a [usize],
+    current_line_bg: Option<Color>,
+    ruler_bg: Color,
+    glyph_fg: Color,
+    width: usize,
+}
+
+impl LineView<'_> {
+    fn render(
+        &self,
+        segment: &WrappedLineSegment,
+        source_line: &str,
+        guide_depth: usize,
+        current: bool,
+    ) -> Line<'static> {
+        let trailing_start = source_line.trim_end_matches([' ', '\t', '\u{a0}']).len();
+        let mut cells: Vec<(char, Style)> = Vec::new();
+        let mut offset = segment.start;
+        for span in &segment.line.spans {
+            let glyph = span.style.fg(self.glyph_fg);
+            for ch in span.content.chars() {
+                let byte = offset;
+                offset += ch.len_utf8();
+                match ch {
+                    '\t' => {
+                        let width = tab_advance(cells.len(), self.tab_width);
+                        if self.show_whitespace {
+                            cells.push(('→', glyph));
+                        } else {
+                            cells.push((' ', span.style));
+                        }
+                        cells.extend(std::iter::repeat_n((' ', span.style), width - 1));
+                    }
+                    ' ' if self.show_whitespace && byte >= trailing_start => {
+                        cells.push(('·', glyph))
+                    }
+                    '\u{a0}' if self.show_whitespace => cells.push(('⍽', glyph)),
+                    _ => cells.push((ch, span.style)),
+                }
+            }
+        }
+
+        if let Some(indent_width) = self.indent_width {
+            for column in (0..guide_depth.min(self.width)).step_by(indent_width.max(1)) {
+                if cells.len() <= column {
+                    cells.resize(column + 1, (' ', Style::default()));
+                }
+                if cells[column].0 == ' ' {
+                    cells[column] = ('│', cells[column].1.fg(self.glyph_fg));
+                }
+            }
+        }
+        for column in self.rulers.iter().map(|ruler| ruler - 1) {
+            if column >= self.width {
+                continue;
+            }
+            if cells.len() <= column {
+                cells.resize(column + 1, (' ', Style::default()));
+            }
+            let style = &mut cells[column].1;
+            if style.bg.is_none() {
+                *style = style.bg(self.ruler_bg);
+            }
+        }
+        if current && let Some(bg) = self.current_line_bg {
+            if cells.len() < self.width {
+                cells.resize(self.width, (' ', Style::default()));
+            }
+            for (_, style) in &mut cells {
+                if style.bg.is_none() {
+                    *style = style.bg(bg);
+                }
+            }
+        }
+
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        for (ch, style) in cells {
+            match spans.last_mut() {
+                Some(last) if last.style == style => last.content.to_mut().push(ch),
+                _ => spans.push(Span::styled(ch.to_string(), style)),
+            }
+        }
+        Line::from(spans)
+    }
+}
+
+fn indent_guide_depth(lines: &[String], row: usize, tab_width: usize) -> usize {
+    let depth =
+        |line: &String| display_width(&line[..line.len() - line.trim_start().len()], tab_width);
+    if !lines[row].trim().is_empty() {
+        return depth(&lines[row]);
+    }
+    let is_code = |line: &&String| !line.trim().is_empty();
+    let above = lines[..row]
+        .iter()
+        .rev()
+        .take(GUIDE_SCAN_LINES)
+        .find(is_code)
+        .map_or(0, depth);
+    let below = lines[row + 1..]
+        .iter()
+        .take(GUIDE_SCAN_LINES)
+        .find(is_code)
+        .map_or(0, depth);
+    above.min(below)
+}
+
+fn wrap_line_smart(line: Line<'static>, width: usize, tab_width: usize) -> Vec<WrappedLineSegment> {
     let plain = line
         .spans
         .iter()
@@ -536,7 +855,7 @@ fn wrap_line_smart(line: Line<'static>, width: usize) -> Vec<WrappedLineSegment>
         }];
     }
 
-    let ranges = compute_wrap_ranges(&plain, width.max(1));
+    let ranges = compute_wrap_ranges(&plain, width.max(1), tab_width);
     ranges
         .into_iter()
         .map(|(start, end)| WrappedLineSegment {
@@ -547,14 +866,26 @@ fn wrap_line_smart(line: Line<'static>, width: usize) -> Vec<WrappedLineSegment>
         .collect::<Vec<_>>()
 }
 
-fn cursor_in_wrapped_line(cursor_x: usize, wrapped: &[WrappedLineSegment]) -> (usize, usize) {
+fn cursor_in_wrapped_line(
+    cursor_x: usize,
+    source_line: &str,
+    wrapped: &[WrappedLineSegment],
+    tab_width: usize,
+) -> (usize, usize) {
     if wrapped.is_empty() {
         return (0, 0);
     }
+    let columns = |start: usize, end: usize| {
+        source_line
+            .get(start..end)
+            .map_or(end.saturating_sub(start), |text| {
+                display_width(text, tab_width)
+            })
+    };
 
     for (idx, segment) in wrapped.iter().enumerate() {
         if cursor_x >= segment.start && cursor_x <= segment.end {
-            return (idx, cursor_x.saturating_sub(segment.start));
+            return (idx, columns(segment.start, cursor_x));
         }
         if cursor_x < segment.start {
             return (idx, 0);
@@ -563,15 +894,10 @@ fn cursor_in_wrapped_line(cursor_x: usize, wrapped: &[WrappedLineSegment]) -> (u
 
     let last_idx = wrapped.len() - 1;
     let last = &wrapped[last_idx];
-    (
-        last_idx,
-        cursor_x
-            .saturating_sub(last.start)
-            .min(last.end.saturating_sub(last.start)),
-    )
+    (last_idx, columns(last.start, last.end))
 }
 
-fn compute_wrap_ranges(text: &str, width: usize) -> Vec<(usize, usize)> {
+fn compute_wrap_ranges(text: &str, width: usize, tab_width: usize) -> Vec<(usize, usize)> {
     let mut ranges = Vec::new();
     if text.is_empty() {
         ranges.push((0, 0));
@@ -580,7 +906,7 @@ fn compute_wrap_ranges(text: &str, width: usize) -> Vec<(usize, usize)> {
 
     let mut start = 0usize;
     while start < text.len() {
-        let candidate = advance_boundary_by_columns(text, start, width.max(1));
+        let candidate = advance_boundary_by_columns(text, start, width.max(1), tab_width);
         if candidate >= text.len() {
             ranges.push((start, text.len()));
             break;
@@ -618,7 +944,7 @@ fn compute_wrap_ranges(text: &str, width: usize) -> Vec<(usize, usize)> {
         };
 
         if end <= start {
-            let forced = advance_boundary_by_columns(text, start, 1);
+            let forced = advance_boundary_by_columns(text, start, 1, tab_width);
             ranges.push((start, forced));
             start = forced;
         } else {
@@ -633,7 +959,7 @@ fn compute_wrap_ranges(text: &str, width: usize) -> Vec<(usize, usize)> {
     ranges
 }
 
-fn advance_boundary_by_columns(text: &str, start: usize, width: usize) -> usize {
+fn advance_boundary_by_columns(text: &str, start: usize, width: usize, tab_width: usize) -> usize {
     if start >= text.len() || width == 0 {
         return start;
     }
@@ -642,8 +968,17 @@ fn advance_boundary_by_columns(text: &str, start: usize, width: usize) -> usize
     let mut consumed = 0usize;
     while idx < text.len() && consumed < width {
         if let Some(ch) = text[idx..].chars().next() {
+            let columns = if ch == '\t' {
+                tab_advance(consumed, tab_width)
+            } else {
+                1
+            };
+            // Tab yang tidak muat pindah ke segmen berikutnya, kecuali di awal segmen.
+            if consumed > 0 && consumed + columns > width {
+                break;
+            }
             idx += ch.len_utf8();
-            consumed += 1;
+            consumed += columns;
         } else {
             break;
         }
@@ -669,12 +1004,11 @@ fn slice_styled_line(line: &Line<'static>, start: usize, end: usize) -> Line<'st
 
         let local_start = start.saturating_sub(span_start).min(text.len());
         let local_end = end.saturating_sub(span_start).min(text.len());
-        if local_end > local_start {
-            if let Some(slice) = text.get(local_start..local_end) {
-                if !slice.is_empty() {
-                    out.push(Span::styled(slice.to_string(), span.style));
-                }
-            }
+        if local_end > local_start
+            && let Some(slice) = text.get(local_start..local_end)
+            && !slice.is_empty()
+        {
+            out.push(Span::styled(slice.to_string(), span.style));
         }
         offset = span_end;
     }
@@ -685,3 +1019,73 @@ fn slice_styled_line(line: &Line<'static>, start: usize, end: usize) -> Line<'st
         Line::from(out)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TABBED: &str = "\tab\tcd";
+
+    fn text(line: &Line) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn wrap_ranges_expand_tabs_by_tab_width() {
+        assert_eq!(compute_wrap_ranges(TABBED, 6, 2), [(0, 3), (4, 6)]);
+        assert_eq!(compute_wrap_ranges(TABBED, 6, 4), [(0, 3), (3, 6)]);
+        // Tab selebar segmen mengisi satu segmen sendiri.
+        assert_eq!(
+            compute_wrap_ranges(TABBED, 6, 8),
+            [(0, 1), (1, 3), (3, 4), (4, 6)]
+        );
+        assert_eq!(compute_wrap_ranges(TABBED, 80, 8), [(0, 6)]);
+    }
+
+    #[test]
+    fn tab_that_does_not_fit_moves_to_next_segment() {
+        assert_eq!(advance_boundary_by_columns("ab\tc", 0, 5, 4), 4);
+        assert_eq!(advance_boundary_by_columns("ab\tc", 0, 3, 4), 2);
+        assert_eq!(advance_boundary_by_columns("ab\tc", 0, 3, 1), 3);
+        assert_eq!(advance_boundary_by_columns("\tc", 0, 2, 8), 1);
+    }
+
+    #[test]
+    fn cursor_after_tab_uses_display_columns() {
+        let cursor = |line: &str, x: usize, width: usize, tab_width: usize| {
+            let wrapped = wrap_line_smart(Line::from(line.to_string()), width, tab_width);
+            cursor_in_wrapped_line(x, line, &wrapped, tab_width)
+        };
+        assert_eq!(cursor("a\tb", 2, 80, 4), (0, 4));
+        assert_eq!(cursor("a\tb", 2, 80, 8), (0, 8));
+        assert_eq!(cursor("a\tb", 2, 80, 2), (0, 2));
+        // Di segmen kedua kolom tab dihitung dari awal segmen.
+        assert_eq!(cursor(TABBED, 4, 6, 4), (1, 4));
+        assert_eq!(cursor(TABBED, 6, 6, 4), (1, 6));
+    }
+
+    #[test]
+    fn line_view_draws_tabs_whitespace_and_guides() {
+        let view = LineView {
+            tab_width: 4,
+            show_whitespace: true,
+            indent_width: Some(4),
+            rulers: &[],
+            current_line_bg: None,
+            ruler_bg: Color::Reset,
+            glyph_fg: Color::DarkGray,
+            width: 20,
+        };
+        let render = |line: &str, guide_depth: usize| {
+            let segment = wrap_line_smart(Line::from(line.to_string()), 20, 4).remove(0);
+            text(&view.render(&segment, line, guide_depth, false))
+        };
+        assert_eq!(render("\tx  ", 4), "→   x··");
+        assert_eq!(render("a b", 0), "a b");
+        assert_eq!(render("        y", 8), "│   │   y");
+        assert_eq!(render("\t\ty", 8), "→   →   y");
+    }
+}
@@ -56,6 +56,10 @@ pub fn render_explorer_context_menu(
                     },
                     Style::default().fg(Color::White).bg(Color::Red),
                 ),
+                ExplorerMenuAction::CompareWith => (
+                    " Bandingkan Dengan...",
+                    Style::default().fg(Color::Black).bg(Color::Magenta),
+                ),
             };
             Line::styled(label, style)
         })
@@ -70,6 +74,7 @@ pub fn render_explorer_context_menu(
             ExplorerMenuAction::AddFile => hit.add_file_rect = Some(rect),
             ExplorerMenuAction::RenameEntry => hit.rename_entry_rect = Some(rect),
             ExplorerMenuAction::DeleteEntry => hit.delete_entry_rect = Some(rect),
+            ExplorerMenuAction::CompareWith => hit.compare_with_rect = Some(rect),
         }
     }
 
@@ -31,15 +31,18 @@ pub fn render_explorer_input_dialog(
         Paragraph::new(format!("Target: {}", dialog.base_dir.to_string_lossy())),
         inner[0],
     );
-    frame.render_widget(
-        Paragraph::new("Masukkan nama (boleh subpath), Enter: simpan, Esc: tutup"),
-        inner[1],
-    );
+    let hint = if dialog.mode == ExplorerInputMode::CompareWith {
+        "Path file pembanding (relatif ke root atau absolut), Enter: bandingkan"
+    } else {
+        "Masukkan nama (boleh subpath), Enter: simpan, Esc: tutup"
+    };
+    frame.render_widget(Paragraph::new(hint), inner[1]);
 
     let input_title = match dialog.mode {
         ExplorerInputMode::AddFolder => " Folder ",
         ExplorerInputMode::AddFile => " File ",
         ExplorerInputMode::RenameEntry => " Nama Baru ",
+        ExplorerInputMode::CompareWith => " File Pembanding ",
     };
     frame.render_widget(
         Paragraph::new(dialog.input.as_str())
@@ -51,6 +54,7 @@ pub fn render_explorer_input_dialog(
         ExplorerInputMode::AddFolder => "[ Buat Folder ]",
         ExplorerInputMode::AddFile => "[ Buat File ]",
         ExplorerInputMode::RenameEntry => "[ Simpan Nama ]",
+        ExplorerInputMode::CompareWith => "[ Bandingkan ]",
     };
     let save_style = if dialog.selected == DialogButton::Save {
         Style::default().fg(Color::Black).bg(Color::Green)
@@ -0,0 +1,184 @@
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Block, Borders, Clear, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
+};
+
+use super::theme::ThemeMode;
+use super::types::{GitDialog, GitDialogMode};
+
+pub fn render_git_dialog(frame: &mut Frame, dialog: &GitDialog, theme: ThemeMode) {
+    let palette = theme.palette();
+    let area = centered_rect(frame.area(), 80, 26);
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Block::default()
+            .title(format!(" Source Control ({}) ", dialog.branch))
+            .borders(Borders::ALL),
+        area,
+    );
+
+    let inner = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(5),
+        Constraint::Length(1),
+    ])
+    .margin(1)
+    .split(area);
+
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled("Blame: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(dialog.blame.as_str()),
+        ])),
+        inner[0],
+    );
+
+    let body = inner[1];
+    let viewport_h = body.height.saturating_sub(2).max(1) as usize;
+    match dialog.mode {
+        GitDialogMode::Files => {
+            let lines = if dialog.files.is_empty() {
+                vec![Line::from("  Tidak ada perubahan")]
+            } else {
+                dialog
+                    .files
+                    .iter()
+                    .enumerate()
+                    .skip(dialog.file_scroll_y)
+                    .take(viewport_h)
+                    .map(|(idx, file)| {
+                        let text = format!("{}{} {}", file.index, file.worktree, file.path);
+                        if idx == dialog.file_selected {
+                            Line::styled(
+                                format!("> {}", text),
+                                Style::default().fg(palette.accent_text).bg(palette.accent),
+                            )
+                        } else if file.has_staged() && !file.has_unstaged() {
+                            Line::styled(
+                                format!("  {}", text),
+                                Style::default().fg(palette.git_added),
+                            )
+                        } else {
+                            Line::styled(
+                                format!("  {}", text),
+                                Style::default().fg(palette.git_modified),
+                            )
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            };
+            let title = format!(" Perubahan ({}) ", dialog.files.len());
+            frame.render_widget(
+                Paragraph::new(lines).block(Block::default().title(title).borders(Borders::ALL)),
+                body,
+            );
+        }
+        GitDialogMode::Diff => {
+            let Some(diff) = dialog.diff.as_ref() else {
+                return;
+            };
+            let mut lines = Vec::new();
+            for (idx, hunk) in diff.hunks.iter().enumerate() {
+                let header_style = if idx == dialog.hunk_selected {
+                    Style::default().fg(palette.accent_text).bg(palette.accent)
+                } else {
+                    Style::default().fg(palette.accent)
+                };
+                let marker = if idx == dialog.hunk_selected {
+                    "> "
+                } else {
+                    "  "
+                };
+                lines.push(Line::styled(
+                    format!("{}{}", marker, hunk.header),
+                    header_style,
+                ));
+                for line in &hunk.lines {
+                    let style = match line.as_bytes().first() {
+                        Some(b'+') => Style::default().fg(palette.git_added),
+                        Some(b'-') => Style::default().fg(palette.git_deleted),
+                        _ => Style::default(),
+                    };
+                    lines.push(Line::styled(format!("  {}", line), style));
+                }
+            }
+            if lines.is_empty() {
+                lines.push(Line::from("  Tidak ada diff"));
+            }
+            let title = format!(
+                " {} [{}] hunk {}/{} ",
+                diff.path,
+                if diff.staged { "staged" } else { "unstaged" },
+                (dialog.hunk_selected + 1).min(diff.hunks.len()),
+                diff.hunks.len()
+            );
+            frame.render_widget(
+                Paragraph::new(lines)
+                    .block(Block::default().title(title).borders(Borders::ALL))
+                    .scroll((dialog.diff_scroll_y as u16, 0)),
+                body,
+            );
+
+            let mut v_state = ScrollbarState::new(diff.line_count())
+                .viewport_content_length(viewport_h)
+                .position(dialog.diff_scroll_y);
+            frame.render_stateful_widget(
+                Scrollbar::new(ScrollbarOrientation::VerticalRight),
+                body,
+                &mut v_state,
+            );
+        }
+        GitDialogMode::Commit => {
+            frame.render_widget(
+                Paragraph::new(dialog.commit_message.as_str()).block(
+                    Block::default()
+                        .title(" Pesan Commit ")
+                        .borders(Borders::ALL),
+                ),
+                body,
+            );
+            let last_line = dialog.commit_message.split('\n').next_back().unwrap_or("");
+            let row = dialog.commit_message.matches('\n').count() as u16;
+            let col = last_line.chars().count() as u16;
+            let cursor_x = (body.x + 1 + col).min(body.x + body.width.saturating_sub(2));
+            let cursor_y = (body.y + 1 + row).min(body.y + body.height.saturating_sub(2));
+            frame.set_cursor_position((cursor_x, cursor_y));
+        }
+        GitDialogMode::Output => {
+            frame.render_widget(
+                Paragraph::new(dialog.output.as_str())
+                    .block(Block::default().title(" Output ").borders(Borders::ALL)),
+                body,
+            );
+        }
+    }
+
+    let footer = match dialog.mode {
+        GitDialogMode::Files => {
+            "Enter diff | s stage | u unstage | c commit | r refresh | Esc tutup"
+        }
+        GitDialogMode::Diff => {
+            "Up/Down hunk | s stage hunk | u unstage hunk | Tab staged/unstaged | Esc kembali"
+        }
+        GitDialogMode::Commit => "Ketik pesan | Enter baris baru | Ctrl+S commit | Esc kembali",
+        GitDialogMode::Output => "Enter/Esc: kembali",
+    };
+    frame.render_widget(
+        Paragraph::new(footer).style(Style::default().fg(palette.status_fg).bg(palette.status_bg)),
+        inner[2],
+    );
+}
+
+fn centered_rect(area: Rect, width_percent: u16, height: u16) -> Rect {
+    let max_width = area.width.saturating_sub(2).max(1);
+    let max_height = area.height.saturating_sub(2).max(1);
+    let width = area.width.saturating_mul(width_percent) / 100;
+    let width = width.max(40).min(max_width);
+    let height = height.max(10).min(max_height);
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    Rect::new(x, y, width, height)
+}
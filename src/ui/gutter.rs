@@ -1,33 +1,83 @@
 use ratatui::style::Style;
-use ratatui::text::Line;
+use ratatui::text::{Line, Span};
 
 use super::theme::ThemeMode;
+use super::types::EditorDecorations;
+use crate::diff::ChangeMarker;
+use crate::manager::cargo_runner::DiagnosticLevel;
 
 pub fn build_gutter_lines(
     total_lines: usize,
     scroll_y: usize,
     editor_height: usize,
     cursor_y: usize,
+    decorations: &EditorDecorations,
     theme: ThemeMode,
 ) -> Vec<Line<'static>> {
     let palette = theme.palette();
+    let change_markers = decorations.change_markers;
+    let bookmarks = decorations.bookmarks;
     let width = total_lines.max(1).to_string().len();
     let mut lines = Vec::new();
     for row in 0..editor_height.max(1) {
         let line_no = scroll_y + row + 1;
         if line_no > total_lines {
-            lines.push(Line::from(" ".repeat(width)));
+            lines.push(Line::from(" ".repeat(width + 2)));
             continue;
         }
+
+        let marker = decorations
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.line_start == line_no)
+            .map(|diagnostic| diagnostic.level)
+            .min()
+            .map(|level| match level {
+                DiagnosticLevel::Error => {
+                    Span::styled("E", Style::default().fg(palette.diagnostic_error))
+                }
+                DiagnosticLevel::Warning => {
+                    Span::styled("W", Style::default().fg(palette.diagnostic_warning))
+                }
+                DiagnosticLevel::Note => {
+                    Span::styled("i", Style::default().fg(palette.diagnostic_note))
+                }
+            })
+            .unwrap_or_else(|| {
+                if bookmarks.contains(&(line_no - 1)) {
+                    Span::styled("◆", Style::default().fg(palette.accent))
+                } else {
+                    Span::raw(" ")
+                }
+            });
+
         let text = format!("{:>width$}", line_no, width = width);
-        if line_no - 1 == cursor_y {
-            lines.push(Line::styled(
-                text,
-                Style::default().fg(palette.line_current),
-            ));
+        let number_style = if line_no - 1 == cursor_y {
+            Style::default().fg(palette.line_current)
         } else {
-            lines.push(Line::styled(text, Style::default().fg(palette.line_other)));
-        }
+            Style::default().fg(palette.line_other)
+        };
+        // Diagnostic menimpa penanda bookmark, jadi nomor baris ikut diberi warna.
+        let number_style = if bookmarks.contains(&(line_no - 1)) {
+            number_style.fg(palette.accent)
+        } else {
+            number_style
+        };
+        let change = match change_markers.get(line_no - 1).copied().flatten() {
+            Some(ChangeMarker::Added) => Span::styled("│", Style::default().fg(palette.git_added)),
+            Some(ChangeMarker::Modified) => {
+                Span::styled("│", Style::default().fg(palette.git_modified))
+            }
+            Some(ChangeMarker::DeletedAbove) => {
+                Span::styled("‾", Style::default().fg(palette.git_deleted))
+            }
+            None => Span::raw(" "),
+        };
+        lines.push(Line::from(vec![
+            marker,
+            Span::styled(text, number_style),
+            change,
+        ]));
     }
     lines
 }
@@ -15,14 +15,33 @@ pub fn render_help_dialog(frame: &mut Frame) {
         "Ctrl+N: tab baru",
         "Ctrl+W: tutup tab aktif",
         "Ctrl+B: toggle file tree",
+        "Ctrl+O: outline simbol Rust (ketik filter, Enter lompat)",
         "Ctrl+T: ganti tema gelap/terang",
         "Ctrl+Tab / Shift+Tab: pindah tab",
         "Tab: pindah fokus editor/file tree/tabs",
         "Saat fokus tabs: Left/Right pindah tab, Enter ke editor",
         "Ctrl+S: simpan file",
         "Ctrl+F: search keyword",
-        "Ctrl+Shift+F: format Rust aktif",
-        "Ctrl+K: buka Rust/Cargo manager",
+        "Ctrl+P: cari simbol workspace (fuzzy, filter fn/struct/...)",
+        "Ctrl+D: go to definition identifier di bawah cursor",
+        "Ctrl+L: pergi ke baris[:kolom], Alt+Left/Right: back/forward jump list",
+        "Alt+B: toggle bookmark bernama, Alt+M: daftar bookmark",
+        "Home/End, Ctrl+Home/End, PageUp/PageDown: navigasi baris, file, dan layar",
+        "Ctrl+Left/Right: per kata, Ctrl+Alt+Left/Right: subword, Ctrl+Up/Down: paragraf",
+        "Delete, Ctrl+Backspace/Ctrl+Delete: hapus karakter/kata",
+        "Alt+Shift+Up/Down: gandakan baris, Ctrl+J: gabung baris",
+        "Tab/Shift+Tab (dengan seleksi): indent/outdent",
+        "Ctrl+/: toggle komentar baris, Alt+/: toggle komentar blok",
+        "Alt+V: mode Vim (normal/insert/visual, operator+motion, :w/:q, /cari)",
+        "Alt+Q: rekam macro, Alt+P: putar N kali / per baris seleksi, simpan bernama",
+        "Alt+T: transformasi seleksi (urutkan, huruf, snake/camel/kebab, ratakan, pipe shell)",
+        "Alt+.: tampilkan whitespace, Alt+I: indent guide (ruler/tab di editor.json)",
+        "Ctrl+Shift+F: format file aktif (Rust/TOML/JSON/eksternal; seleksi untuk Rust)",
+        "Ctrl+K: buka Rust/Cargo manager (Esc membatalkan job yang berjalan)",
+        "Ctrl+G: source control git (diff, stage/unstage hunk, commit, blame baris aktif)",
+        "F12 / Ctrl+`: pindah fokus editor <-> terminal (buka terminal jika belum ada)",
+        "Terminal: Alt+N baru, Alt+W tutup, Alt+Left/Right pindah, Alt+H sembunyikan",
+        "Terminal: Shift+PageUp/PageDown scrollback, Ctrl+Shift+V paste",
         "Ctrl+A: select all (block text)",
         "Shift+Arrow / drag mouse: block text",
         "Alt+Up / Alt+Down: pindah blok/baris",
@@ -30,9 +49,11 @@ pub fn render_help_dialog(frame: &mut Frame) {
         "Ctrl+Z: undo, Ctrl+Y atau Ctrl+Shift+Z: redo",
         "Editor: smart word wrap aktif untuk baris panjang",
         "F1: buka bantuan",
-        "Ctrl+M: buka start menu",
+        "Ctrl+M: buka start menu (termasuk kelola kunci signing)",
         "Klik kanan di editor: menu Select All, Copy, Paste",
-        "Klik kanan di explorer: tambah folder/file, ubah nama, hapus file/folder",
+        "Klik kanan di explorer: tambah folder/file, ubah nama, hapus, bandingkan file",
+        "Diff viewer: n/p pindah hunk, v inline/side-by-side, Esc tutup",
+        "Badge explorer: ✓ signed, ~ kunci tidak dipercaya, ✗ tampered",
         "Mouse: klik tab, tree, editor, popup simpan",
         "Esc: keluar dialog / keluar aplikasi",
         "@magercode - 2026",
@@ -0,0 +1,168 @@
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use super::theme::ThemeMode;
+use super::types::{KeyDialog, KeyDialogFocus};
+
+pub fn render_key_dialog(frame: &mut Frame, dialog: &KeyDialog, theme: ThemeMode) {
+    let palette = theme.palette();
+    let area = centered_rect(frame.area(), 75, 24);
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Block::default()
+            .title(" Kunci Signing ")
+            .borders(Borders::ALL),
+        area,
+    );
+
+    let inner = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(4),
+        Constraint::Length(6),
+        Constraint::Length(1),
+        Constraint::Length(1),
+    ])
+    .margin(1)
+    .split(area);
+
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled("Signing: ", bold),
+            Span::raw(if dialog.signing_enabled {
+                "aktif"
+            } else {
+                "nonaktif"
+            }),
+            Span::styled(" | Lokasi: ", bold),
+            Span::raw(format!("{} ({})", dialog.location.label(), dialog.key_dir)),
+        ])),
+        inner[0],
+    );
+
+    let selected_style = |focused: bool| {
+        if focused {
+            Style::default().fg(palette.accent_text).bg(palette.accent)
+        } else {
+            Style::default().bg(palette.tree_inactive_bg)
+        }
+    };
+
+    let key_lines = if dialog.keys.is_empty() {
+        vec![Line::from("  Belum ada kunci (g untuk membuat)")]
+    } else {
+        dialog
+            .keys
+            .iter()
+            .enumerate()
+            .map(|(idx, key)| {
+                let state = match (key.encrypted, key.unlocked) {
+                    (false, _) => "tanpa passphrase",
+                    (true, true) => "passphrase, terbuka",
+                    (true, false) => "passphrase, terkunci",
+                };
+                let text = format!(
+                    "{} {:<24} {:<16} {}",
+                    if key.active { "*" } else { " " },
+                    key.name,
+                    key.fingerprint.as_deref().unwrap_or("????????????????"),
+                    state
+                );
+                if idx == dialog.selected_key {
+                    Line::styled(
+                        format!("> {}", text),
+                        selected_style(dialog.focus == KeyDialogFocus::Keys),
+                    )
+                } else {
+                    Line::from(format!("  {}", text))
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    frame.render_widget(
+        Paragraph::new(key_lines).block(
+            Block::default()
+                .title(" Kunci (* aktif) ")
+                .borders(Borders::ALL),
+        ),
+        inner[1],
+    );
+
+    let trusted_lines = if dialog.trusted.is_empty() {
+        vec![Line::from("  Belum ada public key tambahan yang dipercaya")]
+    } else {
+        dialog
+            .trusted
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| {
+                let text = format!("{}  {}", entry.fingerprint(), entry.comment);
+                if idx == dialog.selected_trusted {
+                    Line::styled(
+                        format!("> {}", text),
+                        selected_style(dialog.focus == KeyDialogFocus::Trusted),
+                    )
+                } else {
+                    Line::from(format!("  {}", text))
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    frame.render_widget(
+        Paragraph::new(trusted_lines).block(
+            Block::default()
+                .title(" Public Key Dipercaya ")
+                .borders(Borders::ALL),
+        ),
+        inner[2],
+    );
+
+    if let Some(mode) = dialog.input_mode {
+        let value = if mode.is_secret() {
+            "*".repeat(dialog.input.chars().count())
+        } else {
+            dialog.input.clone()
+        };
+        let prefix = format!("{}: ", mode.label());
+        let cursor_x = inner[3].x + (prefix.chars().count() + value.chars().count()) as u16;
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled(prefix, bold),
+                Span::raw(value),
+            ])),
+            inner[3],
+        );
+        frame.set_cursor_position((
+            cursor_x.min(inner[3].x + inner[3].width.saturating_sub(1)),
+            inner[3].y,
+        ));
+    } else {
+        frame.render_widget(Paragraph::new(dialog.message.as_str()), inner[3]);
+    }
+
+    let footer = match (dialog.input_mode, dialog.focus) {
+        (Some(_), _) => "Enter konfirmasi | Esc batal",
+        (None, KeyDialogFocus::Keys) => {
+            "Enter aktifkan | g buat | i impor | e/E ekspor pub/priv | u buka | p passphrase | s signing | l lokasi | Tab | Esc"
+        }
+        (None, KeyDialogFocus::Trusted) => "a tambah | d hapus | Tab daftar kunci | Esc tutup",
+    };
+    frame.render_widget(
+        Paragraph::new(footer).style(Style::default().fg(palette.status_fg).bg(palette.status_bg)),
+        inner[4],
+    );
+}
+
+fn centered_rect(area: Rect, width_percent: u16, height: u16) -> Rect {
+    let max_width = area.width.saturating_sub(2).max(1);
+    let max_height = area.height.saturating_sub(2).max(1);
+    let width = area.width.saturating_mul(width_percent) / 100;
+    let width = width.max(40).min(max_width);
+    let height = height.max(10).min(max_height);
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    Rect::new(x, y, width, height)
+}
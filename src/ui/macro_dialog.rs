@@ -0,0 +1,87 @@
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use super::theme::ThemeMode;
+use super::types::MacroDialog;
+
+pub fn render_macro_dialog(frame: &mut Frame, dialog: &MacroDialog, theme: ThemeMode) {
+    let palette = theme.palette();
+    let area = centered_rect(frame.area(), 70, frame.area().height / 2);
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Block::default().title(" Macro ").borders(Borders::ALL),
+        area,
+    );
+
+    let inner = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(1),
+        Constraint::Length(3),
+    ])
+    .margin(1)
+    .split(area);
+    let hint = if dialog.naming.is_some() {
+        "Ketik nama | Enter simpan | Esc batal"
+    } else {
+        "Enter putar | 0-9 jumlah ulang | Tab per baris | Ctrl+S simpan | Delete hapus"
+    };
+    frame.render_widget(
+        Paragraph::new(hint).style(Style::default().add_modifier(Modifier::DIM)),
+        inner[0],
+    );
+
+    let height = inner[1].height.max(1) as usize;
+    let start = dialog.selected.saturating_sub(height - 1);
+    let mut lines = Vec::new();
+    for (idx, (name, len)) in dialog.entries.iter().enumerate().skip(start).take(height) {
+        let name = name.as_deref().unwrap_or("(rekaman terakhir)");
+        let detail = format!("{} perintah", len);
+        if idx == dialog.selected {
+            lines.push(Line::styled(
+                format!("{:<28}{}", name, detail),
+                Style::default()
+                    .fg(palette.warning_text)
+                    .bg(palette.warning),
+            ));
+        } else {
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:<28}", name), Style::default().fg(palette.accent)),
+                Span::styled(detail, Style::default().add_modifier(Modifier::DIM)),
+            ]));
+        }
+    }
+    frame.render_widget(Paragraph::new(lines), inner[1]);
+
+    let (title, input) = match dialog.naming.as_ref() {
+        Some(name) => (" Nama macro ", name.clone()),
+        None if dialog.per_line => (
+            " Putar ",
+            format!("sekali per baris seleksi ({} baris)", dialog.selected_lines),
+        ),
+        None => (" Jumlah ulang ", dialog.count.clone()),
+    };
+    frame.render_widget(
+        Paragraph::new(input.as_str()).block(Block::default().title(title).borders(Borders::ALL)),
+        inner[2],
+    );
+
+    if dialog.naming.is_some() || !dialog.per_line {
+        let input_width = inner[2].width.saturating_sub(2) as usize;
+        let cursor_x = inner[2].x + 1 + input.len().min(input_width.saturating_sub(1)) as u16;
+        frame.set_cursor_position((cursor_x, inner[2].y + 1));
+    }
+}
+
+fn centered_rect(area: Rect, width_percent: u16, height: u16) -> Rect {
+    let max_width = area.width.saturating_sub(2).max(1);
+    let max_height = area.height.saturating_sub(2).max(1);
+    let width = area.width.saturating_mul(width_percent) / 100;
+    let width = width.max(30).min(max_width);
+    let height = height.max(9).min(max_height);
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    Rect::new(x, y, width, height)
+}
@@ -7,11 +7,17 @@ use ratatui::widgets::{
 use ratatui::Frame;
 
 use super::theme::ThemeMode;
-use super::types::{ManagerAction, ManagerDialog, ManagerMode};
+use super::types::{
+    AddDependencyField, AddDependencyForm, ManagerAction, ManagerDialog, ManagerMode,
+    PreviewLineKind,
+};
+use crate::manager::cargo_manager::cargo_add_args;
+use crate::manager::dependency_tree::DependencyTree;
+use crate::manager::cargo_runner::{CargoCommand, DiagnosticLevel};
 
 pub fn render_manager_dialog(frame: &mut Frame, dialog: &ManagerDialog, theme: ThemeMode) {
     let palette = theme.palette();
-    let area = centered_rect(frame.area(), 74, 18);
+    let area = centered_rect(frame.area(), 74, 22);
     frame.render_widget(Clear, area);
     frame.render_widget(
         Block::default()
@@ -28,8 +34,20 @@ pub fn render_manager_dialog(frame: &mut Frame, dialog: &ManagerDialog, theme: T
     .margin(1)
     .split(area);
 
+    let header = match dialog.job.as_ref() {
+        Some(job) if job.progress.is_empty() => format!("{} | Esc batal", job.summary()),
+        Some(job) => format!("{} | {} | Esc batal", job.summary(), job.progress),
+        None => format!(
+            "Target: {} | Up/Down pilih | Enter jalan | Esc tutup",
+            dialog.target_label()
+        ),
+    };
     frame.render_widget(
-        Paragraph::new("Up/Down pilih | Enter jalan | Esc tutup | Ctrl+K buka"),
+        Paragraph::new(header).style(if dialog.job.is_some() {
+            Style::default().fg(palette.accent)
+        } else {
+            Style::default()
+        }),
         inner[0],
     );
 
@@ -39,7 +57,21 @@ pub fn render_manager_dialog(frame: &mut Frame, dialog: &ManagerDialog, theme: T
                 .iter()
                 .enumerate()
                 .map(|(idx, action)| {
-                    let label = action_label(*action);
+                    let label = match action {
+                        ManagerAction::SelectPackage => {
+                            format!("{} ({})", action_label(*action), dialog.target_label())
+                        }
+                        ManagerAction::FormatOnSave => format!(
+                            "{}: {}",
+                            action_label(*action),
+                            if dialog.format_on_save {
+                                "aktif"
+                            } else {
+                                "nonaktif"
+                            }
+                        ),
+                        _ => action_label(*action).to_string(),
+                    };
                     if idx == dialog.selected {
                         Line::styled(
                             format!("> {}", label),
@@ -52,7 +84,10 @@ pub fn render_manager_dialog(frame: &mut Frame, dialog: &ManagerDialog, theme: T
                     }
                 })
                 .collect::<Vec<_>>();
-            frame.render_widget(Paragraph::new(lines), inner[1]);
+            let scroll_y = dialog
+                .selected
+                .saturating_sub(inner[1].height.saturating_sub(1) as usize);
+            frame.render_widget(Paragraph::new(lines).scroll((scroll_y as u16, 0)), inner[1]);
         }
         ManagerMode::Input(action) => {
             let prompt = input_prompt(action);
@@ -142,17 +177,23 @@ pub fn render_manager_dialog(frame: &mut Frame, dialog: &ManagerDialog, theme: T
                 out_rect.width.saturating_sub(3),
                 out_rect.height.saturating_sub(2),
             );
+            let total_lines = dialog.output.lines().count().max(1);
+            let viewport_h = content_rect.height.max(1) as usize;
+            let scroll_y = if dialog.output_follow {
+                total_lines.saturating_sub(viewport_h)
+            } else {
+                dialog.output_scroll_y
+            };
             frame.render_widget(
                 Paragraph::new(dialog.output.as_str())
                     .block(Block::default().title(" Output ").borders(Borders::ALL))
-                    .scroll((dialog.output_scroll_y as u16, dialog.output_scroll_x as u16)),
+                    .scroll((scroll_y as u16, dialog.output_scroll_x as u16)),
                 out_rect,
             );
 
-            let total_lines = dialog.output.lines().count().max(1);
             let mut v_state = ScrollbarState::new(total_lines)
-                .viewport_content_length(content_rect.height.max(1) as usize)
-                .position(dialog.output_scroll_y);
+                .viewport_content_length(viewport_h)
+                .position(scroll_y);
             frame.render_stateful_widget(
                 Scrollbar::new(ScrollbarOrientation::VerticalRight),
                 out_rect,
@@ -169,15 +210,263 @@ pub fn render_manager_dialog(frame: &mut Frame, dialog: &ManagerDialog, theme: T
                 &mut h_state,
             );
         }
+        ManagerMode::Packages => {
+            let columns =
+                Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)])
+                    .split(inner[1]);
+            let packages = dialog
+                .workspace
+                .as_ref()
+                .map(|workspace| workspace.packages.as_slice())
+                .unwrap_or_default();
+            let package_lines = packages
+                .iter()
+                .enumerate()
+                .map(|(idx, package)| {
+                    let mark = if dialog.target_package.as_deref() == Some(package.name.as_str()) {
+                        "*"
+                    } else {
+                        " "
+                    };
+                    let text = format!("{} {}", mark, package.name);
+                    if idx == dialog.package_selected {
+                        Line::styled(
+                            format!("> {}", text),
+                            Style::default().fg(palette.accent_text).bg(palette.accent),
+                        )
+                    } else {
+                        Line::from(format!("  {}", text))
+                    }
+                })
+                .collect::<Vec<_>>();
+            frame.render_widget(
+                Paragraph::new(package_lines).block(
+                    Block::default()
+                        .title(format!(" Member ({}) ", packages.len()))
+                        .borders(Borders::ALL),
+                ),
+                columns[0],
+            );
+
+            let (title, dependency_lines) = match dialog.highlighted_package() {
+                Some(package) if package.dependencies.is_empty() => (
+                    format!(" Dependency {} ", package.name),
+                    vec![Line::from("  Tidak ada dependency")],
+                ),
+                Some(package) => (
+                    format!(" Dependency {} ({}) ", package.name, package.dependencies.len()),
+                    package
+                        .dependencies
+                        .iter()
+                        .map(|dependency| {
+                            let mut text = format!(
+                                "{} {} [{}]",
+                                dependency.name,
+                                dependency.requirement,
+                                dependency.kind.label()
+                            );
+                            if let Some(target) = &dependency.target {
+                                text.push_str(&format!(" {}", target));
+                            }
+                            Line::from(text)
+                        })
+                        .collect::<Vec<_>>(),
+                ),
+                None => (String::from(" Dependency "), Vec::new()),
+            };
+            frame.render_widget(
+                Paragraph::new(dependency_lines)
+                    .block(Block::default().title(title).borders(Borders::ALL))
+                    .scroll((dialog.package_scroll_y as u16, 0)),
+                columns[1],
+            );
+        }
+        ManagerMode::AddDependency => {
+            if let Some(form) = dialog.add_form.as_ref() {
+                render_add_form(frame, form, inner[1], theme);
+            }
+        }
+        ManagerMode::AddDependencyPreview => {
+            if let Some(form) = dialog.add_form.as_ref() {
+                let lines = if form.preview.is_empty() {
+                    vec![Line::from("  Manifest tidak berubah")]
+                } else {
+                    form.preview
+                        .iter()
+                        .skip(form.preview_scroll)
+                        .map(|(kind, text)| match kind {
+                            PreviewLineKind::Added => Line::styled(
+                                text.clone(),
+                                Style::default().bg(palette.diff_added_bg),
+                            ),
+                            PreviewLineKind::Removed => Line::styled(
+                                text.clone(),
+                                Style::default().bg(palette.diff_removed_bg),
+                            ),
+                            PreviewLineKind::Separator => {
+                                Line::styled(text.clone(), Style::default().fg(palette.line_other))
+                            }
+                            PreviewLineKind::Context => Line::from(text.clone()),
+                        })
+                        .collect::<Vec<_>>()
+                };
+                let title = format!(" Perubahan {} ", dialog.target_manifest().display());
+                frame.render_widget(
+                    Paragraph::new(lines).block(Block::default().title(title).borders(Borders::ALL)),
+                    inner[1],
+                );
+            }
+        }
+        ManagerMode::Tree => {
+            if let Some(tree) = dialog.dependency_tree.as_ref() {
+                render_dependency_tree(frame, tree, inner[1], theme);
+            }
+        }
+        ManagerMode::Report => {
+            let list_rect = inner[1];
+            let viewport_h = list_rect.height.saturating_sub(2).max(1) as usize;
+            let visible = if dialog.report.is_empty() {
+                vec![Line::from("  Tidak ada dependency")]
+            } else {
+                dialog
+                    .report
+                    .iter()
+                    .enumerate()
+                    .skip(dialog.report_scroll_y)
+                    .take(viewport_h)
+                    .map(|(idx, entry)| {
+                        let locked = if entry.locked.is_empty() {
+                            String::from("-")
+                        } else {
+                            entry.locked.join(",")
+                        };
+                        let latest = entry.latest.as_deref().unwrap_or("?");
+                        let flags = entry.flags();
+                        let text = format!(
+                            "{:<14} {:<20} {:<6} {:>10} -> {:<10} {}",
+                            entry.package,
+                            entry.dependency.name,
+                            entry.dependency.kind.label(),
+                            locked,
+                            latest,
+                            flags.join(" ")
+                        );
+                        if idx == dialog.report_selected {
+                            Line::styled(
+                                format!("> {}", text),
+                                Style::default().fg(palette.accent_text).bg(palette.accent),
+                            )
+                        } else if entry.yanked || entry.unused {
+                            Line::styled(
+                                format!("  {}", text),
+                                Style::default().fg(palette.diagnostic_error),
+                            )
+                        } else if !flags.is_empty() {
+                            Line::styled(
+                                format!("  {}", text),
+                                Style::default().fg(palette.diagnostic_warning),
+                            )
+                        } else {
+                            Line::from(format!("  {}", text))
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            };
+            let title = format!(
+                " Dependency: member, nama, jenis, terkunci -> terbaru ({}) ",
+                dialog.report.len()
+            );
+            frame.render_widget(
+                Paragraph::new(visible).block(Block::default().title(title).borders(Borders::ALL)),
+                list_rect,
+            );
+
+            let mut v_state = ScrollbarState::new(dialog.report.len())
+                .viewport_content_length(viewport_h)
+                .position(dialog.report_scroll_y);
+            frame.render_stateful_widget(
+                Scrollbar::new(ScrollbarOrientation::VerticalRight),
+                list_rect,
+                &mut v_state,
+            );
+        }
+        ManagerMode::Problems => {
+            let list_rect = inner[1];
+            let content_rect = Rect::new(
+                list_rect.x + 1,
+                list_rect.y + 1,
+                list_rect.width.saturating_sub(3),
+                list_rect.height.saturating_sub(2),
+            );
+            let viewport_h = content_rect.height.max(1) as usize;
+            let visible = if dialog.problems.is_empty() {
+                vec![Line::from("  Tidak ada problem")]
+            } else {
+                dialog
+                    .problems
+                    .iter()
+                    .enumerate()
+                    .skip(dialog.problem_scroll_y)
+                    .take(viewport_h)
+                    .map(|(idx, problem)| {
+                        let text = problem.summary();
+                        if idx == dialog.problem_selected {
+                            Line::styled(
+                                format!("> {}", text),
+                                Style::default().fg(palette.accent_text).bg(palette.accent),
+                            )
+                        } else {
+                            let color = match problem.level {
+                                DiagnosticLevel::Error => palette.diagnostic_error,
+                                DiagnosticLevel::Warning => palette.diagnostic_warning,
+                                DiagnosticLevel::Note => palette.diagnostic_note,
+                            };
+                            Line::styled(format!("  {}", text), Style::default().fg(color))
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            };
+            let title = format!(" Problems ({}) ", dialog.problems.len());
+            frame.render_widget(
+                Paragraph::new(visible)
+                    .block(Block::default().title(title).borders(Borders::ALL))
+                    .scroll((0, dialog.output_scroll_x as u16)),
+                list_rect,
+            );
+
+            let mut v_state = ScrollbarState::new(dialog.problems.len())
+                .viewport_content_length(viewport_h)
+                .position(dialog.problem_scroll_y);
+            frame.render_stateful_widget(
+                Scrollbar::new(ScrollbarOrientation::VerticalRight),
+                list_rect,
+                &mut v_state,
+            );
+        }
     }
 
     let footer = match dialog.mode {
+        _ if dialog.job.is_some() => "Job berjalan di background | Esc: batalkan",
         ManagerMode::Menu => "Enter: eksekusi aksi | Esc: tutup",
         ManagerMode::Input(_) => "Ketik input lalu Enter | Esc: kembali menu",
         ManagerMode::SearchResults => {
             "Up/Down pilih paket | Enter install/hapus | Left/Right scroll | Esc kembali"
         }
         ManagerMode::Output => "Up/Down/Left/Right scroll | Enter/Esc: kembali menu",
+        ManagerMode::Problems => "Up/Down pilih | Enter buka lokasi | Left/Right scroll | Esc kembali",
+        ManagerMode::Tree => {
+            "Up/Down pilih | Enter/Right buka | Left tutup | i siapa yang memakai | Esc kembali"
+        }
+        ManagerMode::Report => {
+            "Up/Down pilih | Enter/u upgrade ke terbaru | x hapus | r muat ulang | Esc kembali"
+        }
+        ManagerMode::AddDependency => {
+            "Tab/Up/Down field | Left/Right ganti nilai | Spasi toggle | Enter preview | Esc batal"
+        }
+        ManagerMode::AddDependencyPreview => "Up/Down scroll | Enter jalankan cargo add | Esc ubah form",
+        ManagerMode::Packages => {
+            "Up/Down pilih | Enter jadikan target | a semua member | PgUp/PgDn scroll | Esc kembali"
+        }
     };
     frame.render_widget(
         Paragraph::new(footer)
@@ -186,14 +475,196 @@ pub fn render_manager_dialog(frame: &mut Frame, dialog: &ManagerDialog, theme: T
     );
 }
 
+fn render_dependency_tree(frame: &mut Frame, tree: &DependencyTree, area: Rect, theme: ThemeMode) {
+    let palette = theme.palette();
+    let [list_rect, info_rect] =
+        Layout::vertical([Constraint::Min(3), Constraint::Length(2)]).areas(area);
+    let viewport_h = list_rect.height.saturating_sub(2).max(1) as usize;
+
+    let lines = tree
+        .entries()
+        .iter()
+        .enumerate()
+        .skip(tree.scroll())
+        .take(viewport_h)
+        .map(|(idx, entry)| {
+            let package = tree.package(entry);
+            let marker = match (entry.has_children(), tree.is_expanded(entry)) {
+                (true, true) => "v",
+                (true, false) => ">",
+                (false, _) => "-",
+            };
+            let mut text = format!(
+                "{}{} {} {}",
+                "  ".repeat(entry.depth()),
+                marker,
+                package.name,
+                package.version
+            );
+            if entry.is_cycle() {
+                text.push_str(" (siklus)");
+            }
+            if idx == tree.selected_index() {
+                Line::styled(
+                    format!("> {}", text),
+                    Style::default().fg(palette.accent_text).bg(palette.accent),
+                )
+            } else if tree.is_duplicate(entry) {
+                Line::styled(
+                    format!("  {}", text),
+                    Style::default().fg(palette.diagnostic_warning),
+                )
+            } else {
+                Line::from(format!("  {}", text))
+            }
+        })
+        .collect::<Vec<_>>();
+    let title = match tree.inverse_target() {
+        Some(package) => format!(" Yang memakai {} {} ", package.name, package.version),
+        None => String::from(" Pohon dependency (kuning = versi duplikat) "),
+    };
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::default().title(title).borders(Borders::ALL)),
+        list_rect,
+    );
+    let mut v_state = ScrollbarState::new(tree.entries().len())
+        .viewport_content_length(viewport_h)
+        .position(tree.scroll());
+    frame.render_stateful_widget(
+        Scrollbar::new(ScrollbarOrientation::VerticalRight),
+        list_rect,
+        &mut v_state,
+    );
+
+    if let Some(info) = tree.info() {
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from(format!(
+                    "Lisensi: {}",
+                    info.license.as_deref().unwrap_or("tidak diketahui")
+                )),
+                Line::from(format!("Sumber : {}", info.source)),
+            ]),
+            info_rect,
+        );
+    }
+}
+
+fn render_add_form(frame: &mut Frame, form: &AddDependencyForm, area: Rect, theme: ThemeMode) {
+    let palette = theme.palette();
+    let field_style = |field: AddDependencyField| {
+        if form.field == field {
+            Style::default().fg(palette.accent_text).bg(palette.accent)
+        } else {
+            Style::default()
+        }
+    };
+    let yes_no = |value: bool| if value { "ya" } else { "tidak" };
+
+    let version_note = match (form.versions.is_empty(), form.version_index) {
+        (true, _) => String::from("tidak ada di cache index lokal, isi manual"),
+        (false, Some(idx)) => format!(
+            "cache: {} dari {} versi",
+            idx + 1,
+            form.versions.len()
+        ),
+        (false, None) => format!("cache: {} versi, tidak ada yang cocok", form.versions.len()),
+    };
+    let mut lines = vec![
+        Line::from(format!("Crate: {}", form.name)),
+        Line::styled(
+            format!("Versi            : {}  ({})", form.requirement, version_note),
+            field_style(AddDependencyField::Version),
+        ),
+        Line::styled(
+            format!("Jenis            : {}", form.kind.label()),
+            field_style(AddDependencyField::Kind),
+        ),
+        Line::styled(
+            format!("Optional         : {}", yes_no(form.optional)),
+            field_style(AddDependencyField::Optional),
+        ),
+        Line::styled(
+            format!("Default features : {}", yes_no(form.default_features)),
+            field_style(AddDependencyField::DefaultFeatures),
+        ),
+        Line::styled(
+            format!("Fitur ({})", form.features.len()),
+            field_style(AddDependencyField::Features),
+        ),
+    ];
+
+    let command_height = 2usize;
+    let feature_rows = (area.height as usize)
+        .saturating_sub(lines.len() + command_height)
+        .max(1);
+    let feature_start = form
+        .feature_selected
+        .saturating_sub(feature_rows.saturating_sub(1));
+    if form.features.is_empty() {
+        lines.push(Line::from("  (tidak ada fitur yang diketahui)"));
+    }
+    for (idx, (feature, enabled)) in form
+        .features
+        .iter()
+        .enumerate()
+        .skip(feature_start)
+        .take(feature_rows)
+    {
+        let text = format!("  [{}] {}", if *enabled { "x" } else { " " }, feature);
+        if form.field == AddDependencyField::Features && idx == form.feature_selected {
+            lines.push(Line::styled(
+                text,
+                Style::default().fg(palette.accent_text).bg(palette.accent),
+            ));
+        } else {
+            lines.push(Line::from(text));
+        }
+    }
+
+    let [body, command] = Layout::vertical([
+        Constraint::Min(1),
+        Constraint::Length(command_height as u16),
+    ])
+    .areas(area);
+    frame.render_widget(Paragraph::new(lines), body);
+    frame.render_widget(
+        Paragraph::new(format!(
+            "$ cargo {}",
+            cargo_add_args(&form.spec()).join(" ")
+        ))
+        .style(Style::default().fg(palette.line_other)),
+        command,
+    );
+
+    if form.field == AddDependencyField::Version {
+        let prefix = "Versi            : ".len() + form.requirement.chars().count();
+        frame.set_cursor_position((
+            (area.x + prefix as u16).min(area.x + area.width.saturating_sub(1)),
+            area.y + 1,
+        ));
+    }
+}
+
 fn action_label(action: ManagerAction) -> &'static str {
     match action {
-        ManagerAction::FormatRust => "Format file Rust aktif",
+        ManagerAction::SelectPackage => "Pilih package target",
+        ManagerAction::Format => "Format file aktif (atau seleksi Rust)",
+        ManagerAction::FormatOnSave => "Format saat simpan",
+        ManagerAction::ListFormatters => "Daftar formatter per bahasa",
         ManagerAction::CargoSearch => "Cari crate di crates.io",
-        ManagerAction::CargoAdd => "Install crate (cargo add)",
+        ManagerAction::CargoAdd => "Tambah dependency (cargo add)",
         ManagerAction::CargoRemove => "Hapus crate (cargo remove)",
+        ManagerAction::DependencyReport => "Laporan dependency (outdated/unused)",
+        ManagerAction::DependencyTree => "Pohon dependency (Cargo.lock)",
         ManagerAction::WorkspaceAddMember => "Tambah workspace member",
         ManagerAction::WorkspaceRemoveMember => "Hapus workspace member",
+        ManagerAction::CargoRun(CargoCommand::Check) => "Jalankan cargo check",
+        ManagerAction::CargoRun(CargoCommand::Build) => "Jalankan cargo build",
+        ManagerAction::CargoRun(CargoCommand::Test) => "Jalankan cargo test",
+        ManagerAction::CargoRun(CargoCommand::Clippy) => "Jalankan cargo clippy",
+        ManagerAction::CargoRun(CargoCommand::Run) => "Jalankan cargo run",
+        ManagerAction::ShowProblems => "Lihat problems (diagnostik)",
         ManagerAction::Close => "Tutup manager",
     }
 }
@@ -201,7 +672,7 @@ fn action_label(action: ManagerAction) -> &'static str {
 fn input_prompt(action: ManagerAction) -> &'static str {
     match action {
         ManagerAction::CargoSearch => "Keyword crate:",
-        ManagerAction::CargoAdd => "Nama crate untuk di-install:",
+        ManagerAction::CargoAdd => "Nama crate yang ditambahkan:",
         ManagerAction::CargoRemove => "Nama crate untuk dihapus:",
         ManagerAction::WorkspaceAddMember => "Path member workspace (mis. crates/core):",
         ManagerAction::WorkspaceRemoveMember => "Path member workspace yang dihapus:",
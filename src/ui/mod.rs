@@ -1,80 +1,102 @@
+mod bookmark_dialog;
 mod context_menu;
+mod diff_view;
 mod editor_view;
 mod explorer_context_menu;
 mod explorer_input_dialog;
+mod git_dialog;
+mod goto_dialog;
 mod gutter;
 mod help_dialog;
+mod key_dialog;
+mod macro_dialog;
 mod manager_dialog;
+mod outline_view;
 mod save_dialog;
 mod search_dialog;
 mod start_menu;
+mod symbol_dialog;
+mod terminal_view;
 mod theme;
+mod transform_dialog;
 mod types;
+mod unsaved_prompt;
 
 use ratatui::Frame;
 
 use crate::editor::Editor;
-use crate::file_tree::FileTree;
-use crate::tab_manager::TabManager;
 
+pub use terminal_view::TERMINAL_PANEL_HEIGHT;
 pub use theme::ThemeMode;
 pub use types::{
-    CargoSearchItem, ContextMenuDialog, DialogButton, ExplorerContextMenuDialog,
-    ExplorerInputDialog, ExplorerInputMode, ExplorerMenuAction, FocusPane, ManagerAction,
-    ManagerDialog, ManagerMode, SaveDialog, SearchDialog, StartMenuAction, StartMenuDialog,
-    UiState,
+    AddDependencyField, AddDependencyForm, BookmarkNameDialog, BookmarkPicker, CargoSearchItem,
+    ContextMenuDialog, DialogButton, Dialogs, DiffViewDialog, EditorDecorations, EditorView,
+    ExplorerContextMenuDialog, ExplorerInputDialog, ExplorerInputMode, ExplorerMenuAction,
+    FocusPane, GitDialog, GitDialogMode, GoToLineDialog, KeyDialog, KeyDialogFocus, KeyDialogInput, MacroDialog, ManagerAction, ManagerDialog, ManagerMode, OutlinePanel, SaveDialog,
+    SearchDialog, StartMenuAction, StartMenuDialog, TransformDialog, UiState, UnsavedPromptChoice,
+    UnsavedPromptDialog, WorkspaceSymbolDialog,
 };
 
-pub fn editor_viewport_height(frame_height: u16) -> usize {
-    frame_height.saturating_sub(8) as usize
+pub fn editor_viewport_height(frame_height: u16, terminal_visible: bool) -> usize {
+    let terminal_height = if terminal_visible {
+        terminal_view::TERMINAL_PANEL_HEIGHT
+    } else {
+        0
+    };
+    frame_height.saturating_sub(8 + terminal_height) as usize
 }
 
-pub fn render(
-    frame: &mut Frame,
-    editor: &Editor,
-    save_dialog: Option<&SaveDialog>,
-    search_dialog: Option<&SearchDialog>,
-    manager_dialog: Option<&ManagerDialog>,
-    help_dialog_open: bool,
-    start_menu: Option<&StartMenuDialog>,
-    context_menu: Option<&ContextMenuDialog>,
-    explorer_context_menu: Option<&ExplorerContextMenuDialog>,
-    explorer_input_dialog: Option<&ExplorerInputDialog>,
-    status_message: &str,
-    tabs: &TabManager,
-    file_tree: &FileTree,
-    focus: FocusPane,
-    show_file_tree: bool,
-    theme: ThemeMode,
-) -> UiState {
+pub fn render(frame: &mut Frame, editor: &Editor, dialogs: Dialogs, view: EditorView) -> UiState {
+    let Dialogs {
+        save: save_dialog,
+        search: search_dialog,
+        symbol: symbol_dialog,
+        goto: goto_dialog,
+        bookmark_name: bookmark_name_dialog,
+        bookmark_picker,
+        macros: macro_dialog,
+        transform: transform_dialog,
+        manager: manager_dialog,
+        git: git_dialog,
+        key: key_dialog,
+        diff_view,
+        unsaved_prompt,
+        help_open: help_dialog_open,
+        start_menu,
+        context_menu,
+        explorer_context_menu,
+        explorer_input: explorer_input_dialog,
+    } = dialogs;
+    let EditorView {
+        tabs, focus, theme, ..
+    } = view;
     let active_search_keyword = search_dialog
         .map(|dialog| dialog.query.trim())
         .filter(|query| !query.is_empty());
-    let parts = editor_view::render_editor(
-        frame,
-        editor,
-        status_message,
-        tabs,
-        file_tree,
-        focus,
-        show_file_tree,
-        active_search_keyword,
-        theme,
-    );
+    let parts = editor_view::render_editor(frame, editor, view, active_search_keyword);
     let mut state = UiState {
         tab_hits: parts.tab_hits.clone(),
         tab_content_rect: parts.tab_content_rect,
         tree_content_rect: parts.tree_content_rect,
         editor_content_rect: parts.editor_content_rect,
+        terminal_content_rect: parts.terminal_content_rect,
         save_dialog_hit: None,
         context_menu_hit: None,
         explorer_context_menu_hit: None,
         explorer_input_dialog_hit: None,
     };
-    if let Some(dialog) = start_menu {
+    if let Some(dialog) = diff_view {
+        diff_view::render_diff_view(frame, dialog, theme);
+    } else if let Some(dialog) = unsaved_prompt {
+        unsaved_prompt::render_unsaved_prompt(frame, dialog, tabs.active_tab_title());
+    } else if let Some(dialog) = start_menu {
         start_menu::render_start_menu(frame, dialog);
     } else if let Some(dialog) = manager_dialog {
         manager_dialog::render_manager_dialog(frame, dialog, theme);
+    } else if let Some(dialog) = git_dialog {
+        git_dialog::render_git_dialog(frame, dialog, theme);
+    } else if let Some(dialog) = key_dialog {
+        key_dialog::render_key_dialog(frame, dialog, theme);
     } else if let Some(dialog) = save_dialog {
         state.save_dialog_hit = Some(save_dialog::render_save_dialog(frame, dialog));
     } else if let Some(dialog) = explorer_input_dialog {
@@ -87,6 +109,18 @@ pub fn render(
         state.explorer_context_menu_hit = Some(
             explorer_context_menu::render_explorer_context_menu(frame, dialog),
         );
+    } else if let Some(dialog) = goto_dialog {
+        goto_dialog::render_goto_dialog(frame, dialog);
+    } else if let Some(dialog) = bookmark_name_dialog {
+        bookmark_dialog::render_bookmark_name_dialog(frame, dialog);
+    } else if let Some(picker) = bookmark_picker {
+        bookmark_dialog::render_bookmark_picker(frame, picker, theme);
+    } else if let Some(dialog) = macro_dialog {
+        macro_dialog::render_macro_dialog(frame, dialog, theme);
+    } else if let Some(dialog) = transform_dialog {
+        transform_dialog::render_transform_dialog(frame, dialog, theme);
+    } else if let Some(dialog) = symbol_dialog {
+        symbol_dialog::render_symbol_dialog(frame, dialog, theme);
     } else if let Some(dialog) = search_dialog {
         search_dialog::render_search_dialog(frame, dialog);
     } else if help_dialog_open {
@@ -0,0 +1,109 @@
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+use crate::syntax::highlight::SyntaxPalette;
+use crate::syntax::outline::SymbolKind;
+
+use super::FocusPane;
+use super::theme::ThemeMode;
+use super::types::OutlinePanel;
+
+pub fn render_outline_panel(
+    frame: &mut Frame,
+    area: Rect,
+    panel: &OutlinePanel,
+    focus: FocusPane,
+    theme: ThemeMode,
+) {
+    let palette = theme.palette();
+    let syntax = if theme == ThemeMode::Dark {
+        SyntaxPalette::dark()
+    } else {
+        SyntaxPalette::light()
+    };
+    let focused = focus == FocusPane::Outline;
+    let width = area.width.saturating_sub(2) as usize;
+    let height = area.height.saturating_sub(3) as usize;
+
+    let filter_line = if panel.filter.is_empty() {
+        Line::styled(
+            "Filter: ketik untuk mencari",
+            Style::default().add_modifier(Modifier::DIM),
+        )
+    } else {
+        Line::from(format!("Filter: {}", panel.filter))
+    };
+    let mut lines = vec![filter_line];
+
+    let visible = panel.visible();
+    if !panel.supported {
+        lines.push(Line::from("Outline hanya untuk file Rust"));
+    } else if visible.is_empty() {
+        lines.push(Line::from(if panel.symbols.is_empty() {
+            "Tidak ada item"
+        } else {
+            "Tidak ada yang cocok"
+        }));
+    }
+    for (position, idx) in visible
+        .iter()
+        .enumerate()
+        .skip(panel.scroll)
+        .take(height.max(1))
+    {
+        let symbol = &panel.symbols[*idx];
+        let indent = if panel.filter.is_empty() {
+            "  ".repeat(symbol.depth)
+        } else {
+            String::new()
+        };
+        let label = format!("{}{} ", indent, symbol.kind.label());
+        let name = truncate(&symbol.name, width.saturating_sub(label.chars().count()));
+        if position == panel.selected {
+            let style = if focused {
+                Style::default()
+                    .fg(palette.warning_text)
+                    .bg(palette.warning)
+            } else {
+                Style::default().bg(palette.tree_inactive_bg)
+            };
+            lines.push(Line::styled(format!("{}{}", label, name), style));
+        } else {
+            let kind_style = match symbol.kind {
+                SymbolKind::Function => syntax.function,
+                SymbolKind::Struct | SymbolKind::Enum | SymbolKind::Trait => syntax.r#type,
+                SymbolKind::Macro => syntax.r#macro,
+                SymbolKind::Impl | SymbolKind::Module | SymbolKind::Const => syntax.keyword,
+            };
+            lines.push(Line::from(vec![
+                Span::styled(label, kind_style),
+                Span::raw(name),
+            ]));
+        }
+    }
+
+    let title = if focused {
+        " Outline [FOCUS] "
+    } else {
+        " Outline "
+    };
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::default().title(title).borders(Borders::ALL)),
+        area,
+    );
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut result = text
+        .chars()
+        .take(width.saturating_sub(1))
+        .collect::<String>();
+    result.push('~');
+    result
+}
@@ -31,6 +31,7 @@ pub fn render_start_menu(frame: &mut Frame, dialog: &StartMenuDialog) {
                 StartMenuAction::NewTab => "Buat tab baru",
                 StartMenuAction::FocusFileTree => "Fokus ke file tree",
                 StartMenuAction::OpenManager => "Buka Rust/Cargo manager",
+                StartMenuAction::OpenKeyManager => "Kelola kunci signing",
                 StartMenuAction::ShowHelp => "Buka bantuan",
                 StartMenuAction::ContinueEditor => "Lanjut ke editor",
             };
@@ -0,0 +1,123 @@
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::syntax::highlight::SyntaxPalette;
+use crate::syntax::outline::SymbolKind;
+
+use super::theme::ThemeMode;
+use super::types::WorkspaceSymbolDialog;
+
+pub fn render_symbol_dialog(frame: &mut Frame, dialog: &WorkspaceSymbolDialog, theme: ThemeMode) {
+    let palette = theme.palette();
+    let syntax = if theme == ThemeMode::Dark {
+        SyntaxPalette::dark()
+    } else {
+        SyntaxPalette::light()
+    };
+    let area = centered_rect(frame.area(), 70, frame.area().height.saturating_mul(2) / 3);
+    frame.render_widget(Clear, area);
+    let title = match dialog.definition_of.as_deref() {
+        Some(name) => format!(" Definisi '{}' ", name),
+        None => String::from(" Simbol Workspace "),
+    };
+    frame.render_widget(Block::default().title(title).borders(Borders::ALL), area);
+
+    let inner = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(3),
+        Constraint::Min(1),
+    ])
+    .margin(1)
+    .split(area);
+
+    let summary = if dialog.indexing {
+        format!(
+            "Mengindeks... {} file, {} simbol",
+            dialog.file_count, dialog.symbol_count
+        )
+    } else {
+        format!("{} file, {} simbol", dialog.file_count, dialog.symbol_count)
+    };
+    frame.render_widget(
+        Paragraph::new(format!(
+            "{} | Up/Down pilih | Enter buka | Esc tutup",
+            summary
+        ))
+        .style(Style::default().add_modifier(Modifier::DIM)),
+        inner[0],
+    );
+    frame.render_widget(
+        Paragraph::new(dialog.query.as_str()).block(
+            Block::default()
+                .title(" Cari (awali dengan fn/struct/... untuk filter jenis) ")
+                .borders(Borders::ALL),
+        ),
+        inner[1],
+    );
+
+    let height = inner[2].height.max(1) as usize;
+    let width = inner[2].width as usize;
+    let start = dialog.selected.saturating_sub(height - 1);
+    let mut lines = Vec::new();
+    if dialog.results.is_empty() {
+        lines.push(Line::from(if dialog.indexing {
+            "Belum ada yang cocok, indeks masih dibangun"
+        } else {
+            "Tidak ada simbol yang cocok"
+        }));
+    }
+    for (idx, symbol) in dialog.results.iter().enumerate().skip(start).take(height) {
+        let path = symbol
+            .path
+            .strip_prefix(&dialog.root)
+            .unwrap_or(&symbol.path);
+        let location = format!("{}:{}", path.display(), symbol.line + 1);
+        let kind = format!("{:<7}", symbol.kind.label());
+        let name = match symbol.container.as_deref() {
+            Some(container) => format!("{}  ({})", symbol.name, container),
+            None => symbol.name.clone(),
+        };
+        let used = kind.chars().count() + name.chars().count();
+        let gap = width.saturating_sub(used + location.chars().count()).max(2);
+        if idx == dialog.selected {
+            lines.push(Line::styled(
+                format!("{}{}{}{}", kind, name, " ".repeat(gap), location),
+                Style::default()
+                    .fg(palette.warning_text)
+                    .bg(palette.warning),
+            ));
+        } else {
+            let kind_style = match symbol.kind {
+                SymbolKind::Function => syntax.function,
+                SymbolKind::Struct | SymbolKind::Enum | SymbolKind::Trait => syntax.r#type,
+                SymbolKind::Macro => syntax.r#macro,
+                SymbolKind::Impl | SymbolKind::Module | SymbolKind::Const => syntax.keyword,
+            };
+            lines.push(Line::from(vec![
+                Span::styled(kind, kind_style),
+                Span::raw(name),
+                Span::raw(" ".repeat(gap)),
+                Span::styled(location, Style::default().add_modifier(Modifier::DIM)),
+            ]));
+        }
+    }
+    frame.render_widget(Paragraph::new(lines), inner[2]);
+
+    let input_width = inner[1].width.saturating_sub(2) as usize;
+    let cursor_x = inner[1].x + 1 + dialog.query.len().min(input_width.saturating_sub(1)) as u16;
+    frame.set_cursor_position((cursor_x, inner[1].y + 1));
+}
+
+fn centered_rect(area: Rect, width_percent: u16, height: u16) -> Rect {
+    let max_width = area.width.saturating_sub(2).max(1);
+    let max_height = area.height.saturating_sub(2).max(1);
+    let width = area.width.saturating_mul(width_percent) / 100;
+    let width = width.max(40).min(max_width);
+    let height = height.max(10).min(max_height);
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    Rect::new(x, y, width, height)
+}
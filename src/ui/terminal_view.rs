@@ -0,0 +1,143 @@
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+use super::FocusPane;
+use super::theme::ThemeMode;
+use crate::terminal_pane::TerminalPanes;
+
+pub const TERMINAL_PANEL_HEIGHT: u16 = 12;
+
+pub fn render_terminal_panel(
+    frame: &mut Frame,
+    area: Rect,
+    panes: &TerminalPanes,
+    focus: FocusPane,
+    theme: ThemeMode,
+) -> Rect {
+    let palette = theme.palette();
+    let content = Rect::new(
+        area.x + 1,
+        area.y + 1,
+        area.width.saturating_sub(2),
+        area.height.saturating_sub(2),
+    );
+
+    let mut title_spans = vec![Span::raw(" Terminal ")];
+    for (idx, session) in panes.sessions().iter().enumerate() {
+        let label = if session.has_exited() {
+            format!(" {} (selesai) ", session.title())
+        } else {
+            format!(" {} ", session.title())
+        };
+        if idx == panes.active_index() {
+            title_spans.push(Span::styled(
+                label,
+                Style::default().fg(palette.accent_text).bg(palette.accent),
+            ));
+        } else {
+            title_spans.push(Span::raw(label));
+        }
+    }
+    if let Some(session) = panes.active()
+        && session.scroll_offset() > 0
+    {
+        title_spans.push(Span::styled(
+            format!(" [scroll -{}] ", session.scroll_offset()),
+            Style::default().fg(palette.warning),
+        ));
+    }
+    if focus == FocusPane::Terminal {
+        title_spans.push(Span::raw("[FOCUS] "));
+    }
+    frame.render_widget(
+        Block::default()
+            .title(Line::from(title_spans))
+            .borders(Borders::ALL),
+        area,
+    );
+
+    let Some(session) = panes.active() else {
+        frame.render_widget(
+            Paragraph::new("Tidak ada terminal. Tekan F12 untuk membuka terminal baru."),
+            content,
+        );
+        return content;
+    };
+
+    let Ok(parser) = session.parser().lock() else {
+        return content;
+    };
+    let screen = parser.screen();
+    let (rows, cols) = screen.size();
+    let mut lines = Vec::new();
+    for row in 0..rows.min(content.height) {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        let mut run = String::new();
+        let mut run_style = Style::default();
+        for col in 0..cols.min(content.width) {
+            let Some(cell) = screen.cell(row, col) else {
+                continue;
+            };
+            if cell.is_wide_continuation() {
+                continue;
+            }
+            let style = cell_style(cell);
+            if style != run_style && !run.is_empty() {
+                spans.push(Span::styled(std::mem::take(&mut run), run_style));
+            }
+            run_style = style;
+            if cell.has_contents() {
+                run.push_str(cell.contents());
+            } else {
+                run.push(' ');
+            }
+        }
+        if !run.is_empty() {
+            spans.push(Span::styled(run, run_style));
+        }
+        lines.push(Line::from(spans));
+    }
+    frame.render_widget(Paragraph::new(lines), content);
+
+    if focus == FocusPane::Terminal && session.scroll_offset() == 0 && !screen.hide_cursor() {
+        let (cursor_row, cursor_col) = screen.cursor_position();
+        if cursor_row < content.height && cursor_col < content.width {
+            frame.set_cursor_position((content.x + cursor_col, content.y + cursor_row));
+        }
+    }
+
+    content
+}
+
+fn cell_style(cell: &vt100::Cell) -> Style {
+    let mut style = Style::default()
+        .fg(convert_color(cell.fgcolor()))
+        .bg(convert_color(cell.bgcolor()));
+    if cell.bold() {
+        style = style.add_modifier(Modifier::BOLD);
+    }
+    if cell.dim() {
+        style = style.add_modifier(Modifier::DIM);
+    }
+    if cell.italic() {
+        style = style.add_modifier(Modifier::ITALIC);
+    }
+    if cell.underline() {
+        style = style.add_modifier(Modifier::UNDERLINED);
+    }
+    if cell.inverse() {
+        style = style.add_modifier(Modifier::REVERSED);
+    }
+    style
+}
+
+fn convert_color(color: vt100::Color) -> Color {
+    match color {
+        vt100::Color::Default => Color::Reset,
+        vt100::Color::Idx(idx) => Color::Indexed(idx),
+        vt100::Color::Rgb(r, g, b) => Color::Rgb(r, g, b),
+    }
+}
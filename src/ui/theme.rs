@@ -19,6 +19,18 @@ pub struct ThemePalette {
     pub search_bg: Color,
     pub line_current: Color,
     pub line_other: Color,
+    pub current_line_bg: Color,
+    pub ruler_bg: Color,
+    pub diagnostic_error: Color,
+    pub diagnostic_warning: Color,
+    pub diagnostic_note: Color,
+    pub git_added: Color,
+    pub git_modified: Color,
+    pub git_deleted: Color,
+    pub diff_added_bg: Color,
+    pub diff_removed_bg: Color,
+    pub diff_added_word_bg: Color,
+    pub diff_removed_word_bg: Color,
 }
 
 impl ThemeMode {
@@ -50,6 +62,18 @@ impl ThemeMode {
                 search_bg: Color::Rgb(120, 95, 40),
                 line_current: Color::Yellow,
                 line_other: Color::DarkGray,
+                current_line_bg: Color::Rgb(35, 38, 46),
+                ruler_bg: Color::Rgb(55, 55, 55),
+                diagnostic_error: Color::LightRed,
+                diagnostic_warning: Color::Yellow,
+                diagnostic_note: Color::LightBlue,
+                git_added: Color::LightGreen,
+                git_modified: Color::LightBlue,
+                git_deleted: Color::LightRed,
+                diff_added_bg: Color::Rgb(30, 60, 35),
+                diff_removed_bg: Color::Rgb(70, 30, 30),
+                diff_added_word_bg: Color::Rgb(40, 110, 55),
+                diff_removed_word_bg: Color::Rgb(130, 45, 45),
             },
             Self::Light => ThemePalette {
                 accent: Color::Blue,
@@ -63,6 +87,18 @@ impl ThemeMode {
                 search_bg: Color::Rgb(255, 228, 153),
                 line_current: Color::Blue,
                 line_other: Color::Gray,
+                current_line_bg: Color::Rgb(245, 245, 230),
+                ruler_bg: Color::Rgb(225, 225, 225),
+                diagnostic_error: Color::Red,
+                diagnostic_warning: Color::Rgb(180, 120, 0),
+                diagnostic_note: Color::Blue,
+                git_added: Color::Green,
+                git_modified: Color::Rgb(0, 110, 180),
+                git_deleted: Color::Red,
+                diff_added_bg: Color::Rgb(220, 245, 220),
+                diff_removed_bg: Color::Rgb(250, 222, 222),
+                diff_added_word_bg: Color::Rgb(160, 225, 165),
+                diff_removed_word_bg: Color::Rgb(240, 165, 165),
             },
         }
     }
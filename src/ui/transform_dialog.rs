@@ -0,0 +1,82 @@
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use super::theme::ThemeMode;
+use super::types::TransformDialog;
+
+pub fn render_transform_dialog(frame: &mut Frame, dialog: &TransformDialog, theme: ThemeMode) {
+    let palette = theme.palette();
+    let area = centered_rect(frame.area(), 60, frame.area().height / 2);
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Block::default()
+            .title(" Transformasi Teks ")
+            .borders(Borders::ALL),
+        area,
+    );
+
+    let inner = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(3),
+        Constraint::Min(1),
+    ])
+    .margin(1)
+    .split(area);
+    let hint = if dialog.input.is_some() {
+        "Enter jalankan | Esc kembali"
+    } else {
+        "Ketik untuk filter | Enter jalankan | Esc tutup"
+    };
+    frame.render_widget(
+        Paragraph::new(hint).style(Style::default().add_modifier(Modifier::DIM)),
+        inner[0],
+    );
+
+    let (title, input) = match dialog.input.as_ref() {
+        Some((transform, input)) => (transform.prompt().unwrap_or(" Argumen "), input.as_str()),
+        None => (" Filter ", dialog.filter.as_str()),
+    };
+    frame.render_widget(
+        Paragraph::new(input).block(Block::default().title(title).borders(Borders::ALL)),
+        inner[1],
+    );
+    let input_width = inner[1].width.saturating_sub(2) as usize;
+    let cursor_x = inner[1].x + 1 + input.len().min(input_width.saturating_sub(1)) as u16;
+    frame.set_cursor_position((cursor_x, inner[1].y + 1));
+
+    let visible = dialog.visible();
+    let height = inner[2].height.max(1) as usize;
+    let start = dialog.selected.saturating_sub(height - 1);
+    let mut lines = Vec::new();
+    for (idx, transform) in visible.iter().enumerate().skip(start).take(height) {
+        let style = if idx == dialog.selected {
+            Style::default()
+                .fg(palette.warning_text)
+                .bg(palette.warning)
+        } else {
+            Style::default().fg(palette.accent)
+        };
+        lines.push(Line::styled(transform.label(), style));
+    }
+    if visible.is_empty() {
+        lines.push(Line::styled(
+            "Tidak ada transformasi yang cocok",
+            Style::default().add_modifier(Modifier::DIM),
+        ));
+    }
+    frame.render_widget(Paragraph::new(lines), inner[2]);
+}
+
+fn centered_rect(area: Rect, width_percent: u16, height: u16) -> Rect {
+    let max_width = area.width.saturating_sub(2).max(1);
+    let max_height = area.height.saturating_sub(2).max(1);
+    let width = area.width.saturating_mul(width_percent) / 100;
+    let width = width.max(30).min(max_width);
+    let height = height.max(9).min(max_height);
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    Rect::new(x, y, width, height)
+}
@@ -1,6 +1,30 @@
 use ratatui::layout::Rect;
 use std::collections::HashSet;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use crate::diff::{ChangeMarker, DiffDocument, DiffRowKind};
+use crate::editor_settings::EditorSettings;
+use crate::file_tree::FileTree;
+use crate::macros::Macro;
+use crate::manager::cargo_runner::{CargoCommand, Diagnostic};
+use crate::manager::dependency_report::ReportEntry;
+use crate::manager::dependency_tree::DependencyTree;
+use crate::manager::jobs::JobStatus;
+use crate::manager::manifest::{DependencyKind, DependencySpec};
+use crate::manager::registry_index::{IndexVersion, is_prerelease};
+use crate::manager::workspace_manager::{CargoWorkspace, WorkspacePackage};
+use crate::navigation::Bookmark;
+use crate::signature::keys::{KeyInfo, KeyLocation, KeyStore, TrustedKey};
+use crate::signature::verify::SignatureCache;
+use crate::syntax::outline::{Symbol, rust_outline, symbol_at};
+use crate::syntax::symbol_index::{SymbolIndex, WorkspaceSymbol};
+use crate::tab_manager::TabManager;
+use crate::terminal_pane::TerminalPanes;
+use crate::transform::Transform;
+use crate::vcs::git::GitRepository;
+use crate::vcs::git_ops::{ChangedFile, FileDiff};
+
+use super::theme::ThemeMode;
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum DialogButton {
@@ -15,6 +39,60 @@ pub enum FocusPane {
     Editor,
     FileTree,
     Tabs,
+    Terminal,
+    Outline,
+}
+
+#[derive(Clone, Copy)]
+pub struct EditorDecorations<'a> {
+    pub diagnostics: &'a [Diagnostic],
+    pub change_markers: &'a [Option<ChangeMarker>],
+    pub git: Option<&'a GitRepository>,
+    pub signatures: Option<&'a SignatureCache>,
+    pub jobs: &'a [JobStatus],
+    /// Baris (0-based) yang punya bookmark di buffer aktif.
+    pub bookmarks: &'a [usize],
+    /// Label mode Vim (mis. `NORMAL`) bila emulasi Vim aktif.
+    pub vim_mode: Option<&'a str>,
+    pub macro_recording: bool,
+    pub settings: &'a EditorSettings,
+}
+
+/// Bagian layar editor di luar dialog: tab, explorer, panel, dan dekorasi buffer.
+#[derive(Clone, Copy)]
+pub struct EditorView<'a> {
+    pub status_message: &'a str,
+    pub tabs: &'a TabManager,
+    pub file_tree: &'a FileTree,
+    pub terminal_panes: &'a TerminalPanes,
+    pub outline: Option<&'a OutlinePanel>,
+    pub focus: FocusPane,
+    pub show_file_tree: bool,
+    pub decorations: EditorDecorations<'a>,
+    pub theme: ThemeMode,
+}
+
+/// Dialog yang sedang terbuka; paling banyak satu yang digambar, sesuai urutan di `render`.
+#[derive(Clone, Copy, Default)]
+pub struct Dialogs<'a> {
+    pub save: Option<&'a SaveDialog>,
+    pub search: Option<&'a SearchDialog>,
+    pub symbol: Option<&'a WorkspaceSymbolDialog>,
+    pub goto: Option<&'a GoToLineDialog>,
+    pub bookmark_name: Option<&'a BookmarkNameDialog>,
+    pub bookmark_picker: Option<&'a BookmarkPicker>,
+    pub macros: Option<&'a MacroDialog>,
+    pub transform: Option<&'a TransformDialog>,
+    pub manager: Option<&'a ManagerDialog>,
+    pub git: Option<&'a GitDialog>,
+    pub key: Option<&'a KeyDialog>,
+    pub diff_view: Option<&'a DiffViewDialog>,
+    pub unsaved_prompt: Option<&'a UnsavedPromptDialog>,
+    pub help_open: bool,
+    pub start_menu: Option<&'a StartMenuDialog>,
+    pub context_menu: Option<&'a ContextMenuDialog>,
+    pub explorer_context_menu: Option<&'a ExplorerContextMenuDialog>,
+    pub explorer_input: Option<&'a ExplorerInputDialog>,
 }
 
 #[derive(Debug, Default)]
@@ -55,14 +133,283 @@ impl SearchDialog {
     }
 }
 
+#[derive(Debug, Default)]
+pub struct GoToLineDialog {
+    pub input: String,
+    pub total_lines: usize,
+}
+
+impl GoToLineDialog {
+    pub fn new(total_lines: usize) -> Self {
+        Self {
+            input: String::new(),
+            total_lines,
+        }
+    }
+
+    pub fn push_char(&mut self, ch: char) {
+        if ch.is_ascii_digit() || ch == ':' || ch == ',' {
+            self.input.push(ch);
+        }
+    }
+
+    pub fn pop_char(&mut self) {
+        self.input.pop();
+    }
+
+    /// `baris` atau `baris:kolom`, keduanya mulai dari 1.
+    pub fn target(&self) -> Option<(usize, Option<usize>)> {
+        let input = self.input.trim();
+        let (line, column) = match input.split_once([':', ',']) {
+            Some((line, column)) => (line, Some(column.trim())),
+            None => (input, None),
+        };
+        let parse = |text: &str| text.trim().parse::<usize>().ok().filter(|value| *value > 0);
+        let line = parse(line)?;
+        let column = match column {
+            Some(column) if !column.is_empty() => Some(parse(column)?),
+            _ => None,
+        };
+        Some((line, column))
+    }
+}
+
+#[derive(Debug)]
+pub struct BookmarkNameDialog {
+    pub name: String,
+    pub path: PathBuf,
+    pub line: usize,
+}
+
+impl BookmarkNameDialog {
+    pub fn push_char(&mut self, ch: char) {
+        self.name.push(ch);
+    }
+
+    pub fn pop_char(&mut self) {
+        self.name.pop();
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct BookmarkPicker {
+    pub filter: String,
+    pub selected: usize,
+    pub entries: Vec<(PathBuf, Bookmark)>,
+    pub root: PathBuf,
+}
+
+impl BookmarkPicker {
+    pub fn new(entries: Vec<(PathBuf, Bookmark)>, root: &Path) -> Self {
+        Self {
+            entries,
+            root: root.to_path_buf(),
+            ..Self::default()
+        }
+    }
+
+    pub fn set_entries(&mut self, entries: Vec<(PathBuf, Bookmark)>) {
+        self.entries = entries;
+        self.selected = self.selected.min(self.visible().len().saturating_sub(1));
+    }
+
+    /// Filter mencocokkan nama bookmark atau path file.
+    pub fn visible(&self) -> Vec<usize> {
+        let filter = self.filter.to_lowercase();
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, (path, bookmark))| {
+                filter.is_empty()
+                    || bookmark.name.to_lowercase().contains(&filter)
+                    || path.to_string_lossy().to_lowercase().contains(&filter)
+            })
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    pub fn selected_entry(&self) -> Option<&(PathBuf, Bookmark)> {
+        self.visible()
+            .get(self.selected)
+            .map(|idx| &self.entries[*idx])
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected + 1 < self.visible().len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn push_char(&mut self, ch: char) {
+        self.filter.push(ch);
+        self.selected = 0;
+    }
+
+    pub fn pop_char(&mut self) {
+        self.filter.pop();
+        self.selected = 0;
+    }
+}
+
+/// Dialog putar/simpan macro. Entri bernama `None` adalah rekaman terakhir yang belum disimpan.
+#[derive(Debug, Default)]
+pub struct MacroDialog {
+    pub entries: Vec<(Option<String>, usize)>,
+    pub selected: usize,
+    pub count: String,
+    pub per_line: bool,
+    /// Jumlah baris seleksi di editor; putar per baris hanya tersedia bila lebih dari satu.
+    pub selected_lines: usize,
+    /// Nama yang sedang diketik saat menyimpan macro terpilih.
+    pub naming: Option<String>,
+}
+
+impl MacroDialog {
+    pub fn new(recent: Option<usize>, saved: &[Macro], selected_lines: usize) -> Self {
+        let entries = recent
+            .map(|len| (None, len))
+            .into_iter()
+            .chain(
+                saved
+                    .iter()
+                    .map(|item| (Some(item.name.clone()), item.commands.len())),
+            )
+            .collect();
+        Self {
+            entries,
+            selected_lines,
+            per_line: selected_lines > 1,
+            ..Self::default()
+        }
+    }
+
+    pub fn selected_entry(&self) -> Option<&(Option<String>, usize)> {
+        self.entries.get(self.selected)
+    }
+
+    /// Jumlah pengulangan dari input; kosong berarti sekali.
+    pub fn times(&self) -> usize {
+        self.count.parse().unwrap_or(1).max(1)
+    }
+
+    pub fn toggle_per_line(&mut self) {
+        self.per_line = !self.per_line && self.selected_lines > 1;
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected + 1 < self.entries.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn push_char(&mut self, ch: char) {
+        match self.naming.as_mut() {
+            Some(name) => name.push(ch),
+            None if ch.is_ascii_digit() && self.count.len() < 4 => self.count.push(ch),
+            None => {}
+        }
+    }
+
+    pub fn pop_char(&mut self) {
+        match self.naming.as_mut() {
+            Some(name) => {
+                name.pop();
+            }
+            None => {
+                self.count.pop();
+            }
+        }
+    }
+
+    pub fn remove_selected(&mut self) {
+        if self.selected < self.entries.len() {
+            self.entries.remove(self.selected);
+            self.selected = self.selected.min(self.entries.len().saturating_sub(1));
+        }
+    }
+}
+
+/// Pemilih transformasi teks; `input` terisi saat transformasi butuh argumen (token/perintah).
+#[derive(Debug, Default)]
+pub struct TransformDialog {
+    pub filter: String,
+    pub selected: usize,
+    pub input: Option<(Transform, String)>,
+}
+
+impl TransformDialog {
+    /// Filter mencocokkan label atau nama transformasi (mis. `sort-unique`).
+    pub fn visible(&self) -> Vec<Transform> {
+        let filter = self.filter.to_lowercase();
+        Transform::ALL
+            .into_iter()
+            .filter(|transform| {
+                transform.label().to_lowercase().contains(&filter)
+                    || transform.name().contains(&filter)
+            })
+            .collect()
+    }
+
+    pub fn selected_transform(&self) -> Option<Transform> {
+        self.visible().get(self.selected).copied()
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected + 1 < self.visible().len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn push_char(&mut self, ch: char) {
+        match self.input.as_mut() {
+            Some((_, input)) => input.push(ch),
+            None => {
+                self.filter.push(ch);
+                self.selected = 0;
+            }
+        }
+    }
+
+    pub fn pop_char(&mut self) {
+        match self.input.as_mut() {
+            Some((_, input)) => {
+                input.pop();
+            }
+            None => {
+                self.filter.pop();
+                self.selected = 0;
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ManagerAction {
-    FormatRust,
+    Format,
+    FormatOnSave,
+    ListFormatters,
     CargoSearch,
     CargoAdd,
     CargoRemove,
     WorkspaceAddMember,
     WorkspaceRemoveMember,
+    SelectPackage,
+    DependencyReport,
+    DependencyTree,
+    CargoRun(CargoCommand),
+    ShowProblems,
     Close,
 }
 
@@ -72,6 +419,286 @@ pub enum ManagerMode {
     Input(ManagerAction),
     SearchResults,
     Output,
+    Problems,
+    Packages,
+    AddDependency,
+    AddDependencyPreview,
+    Report,
+    Tree,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddDependencyField {
+    Version,
+    Kind,
+    Optional,
+    DefaultFeatures,
+    Features,
+}
+
+impl AddDependencyField {
+    const ALL: [Self; 5] = [
+        Self::Version,
+        Self::Kind,
+        Self::Optional,
+        Self::DefaultFeatures,
+        Self::Features,
+    ];
+
+    fn index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|field| *field == self)
+            .unwrap_or(0)
+    }
+}
+
+fn requirement_for(version: &str) -> String {
+    version.split('+').next().unwrap_or(version).to_string()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreviewLineKind {
+    Context,
+    Added,
+    Removed,
+    Separator,
+}
+
+#[derive(Debug, Clone)]
+pub struct AddDependencyForm {
+    pub name: String,
+    pub requirement: String,
+    pub versions: Vec<IndexVersion>,
+    pub version_index: Option<usize>,
+    /// Versi yang akan dipilih `cargo add` bila requirement dikosongkan.
+    pub latest: Option<String>,
+    pub kind: DependencyKind,
+    pub optional: bool,
+    pub default_features: bool,
+    pub features: Vec<(String, bool)>,
+    pub feature_selected: usize,
+    pub field: AddDependencyField,
+    pub preview: Vec<(PreviewLineKind, String)>,
+    pub preview_scroll: usize,
+}
+
+impl AddDependencyForm {
+    pub fn new(name: &str, versions: Vec<IndexVersion>, fallback_version: Option<&str>) -> Self {
+        let version_index = versions
+            .iter()
+            .position(|version| !version.yanked && !is_prerelease(&version.version))
+            .or_else(|| versions.iter().position(|version| !version.yanked));
+        let latest = version_index
+            .map(|idx| requirement_for(&versions[idx].version))
+            .or_else(|| fallback_version.map(requirement_for));
+        let mut form = Self {
+            name: name.trim().to_string(),
+            requirement: latest.clone().unwrap_or_default(),
+            versions,
+            version_index,
+            latest,
+            kind: DependencyKind::Normal,
+            optional: false,
+            default_features: true,
+            features: Vec::new(),
+            feature_selected: 0,
+            field: AddDependencyField::Version,
+            preview: Vec::new(),
+            preview_scroll: 0,
+        };
+        form.refresh_features();
+        form
+    }
+
+    pub fn next_field(&mut self) {
+        let next = (self.field.index() + 1) % AddDependencyField::ALL.len();
+        self.field = AddDependencyField::ALL[next];
+    }
+
+    pub fn prev_field(&mut self) {
+        let len = AddDependencyField::ALL.len();
+        let prev = (self.field.index() + len - 1) % len;
+        self.field = AddDependencyField::ALL[prev];
+    }
+
+    pub fn cycle_version(&mut self, forward: bool) {
+        let candidates = self
+            .versions
+            .iter()
+            .enumerate()
+            .filter(|(_, version)| !version.yanked)
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            return;
+        }
+        let current = self
+            .version_index
+            .and_then(|idx| candidates.iter().position(|candidate| *candidate == idx));
+        // Daftar versi terurut terbaru dulu, jadi "maju" berarti versi yang lebih lama.
+        let next = match current {
+            Some(pos) if forward => (pos + 1).min(candidates.len() - 1),
+            Some(pos) => pos.saturating_sub(1),
+            None => 0,
+        };
+        self.version_index = Some(candidates[next]);
+        self.requirement = requirement_for(&self.versions[candidates[next]].version);
+        self.refresh_features();
+    }
+
+    pub fn push_requirement_char(&mut self, ch: char) {
+        self.requirement.push(ch);
+        self.sync_version_index();
+    }
+
+    pub fn pop_requirement_char(&mut self) {
+        self.requirement.pop();
+        self.sync_version_index();
+    }
+
+    fn sync_version_index(&mut self) {
+        let requirement = self.requirement.trim().trim_start_matches(['^', '=', '~']);
+        self.version_index = self
+            .versions
+            .iter()
+            .position(|version| !version.yanked && version.version.starts_with(requirement));
+        self.refresh_features();
+    }
+
+    fn refresh_features(&mut self) {
+        let available = self
+            .version_index
+            .and_then(|idx| self.versions.get(idx))
+            .map(|version| version.features.clone())
+            .unwrap_or_default();
+        let enabled = self
+            .features
+            .iter()
+            .filter(|(_, on)| *on)
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        self.features = available
+            .into_iter()
+            .map(|feature| {
+                let on = enabled.contains(&feature);
+                (feature, on)
+            })
+            .collect();
+        self.feature_selected = self
+            .feature_selected
+            .min(self.features.len().saturating_sub(1));
+    }
+
+    pub fn cycle_kind(&mut self) {
+        self.kind = match self.kind {
+            DependencyKind::Normal => DependencyKind::Dev,
+            DependencyKind::Dev => DependencyKind::Build,
+            DependencyKind::Build | DependencyKind::Workspace => DependencyKind::Normal,
+        };
+    }
+
+    pub fn toggle(&mut self) {
+        match self.field {
+            AddDependencyField::Version => {}
+            AddDependencyField::Kind => self.cycle_kind(),
+            AddDependencyField::Optional => self.optional = !self.optional,
+            AddDependencyField::DefaultFeatures => {
+                self.default_features = !self.default_features
+            }
+            AddDependencyField::Features => {
+                if let Some((_, on)) = self.features.get_mut(self.feature_selected) {
+                    *on = !*on;
+                }
+            }
+        }
+    }
+
+    pub fn move_feature_up(&mut self) {
+        self.feature_selected = self.feature_selected.saturating_sub(1);
+    }
+
+    pub fn move_feature_down(&mut self) {
+        if self.feature_selected + 1 < self.features.len() {
+            self.feature_selected += 1;
+        }
+    }
+
+    pub fn spec(&self) -> DependencySpec {
+        DependencySpec {
+            name: self.name.clone(),
+            requirement: self.requirement.trim().to_string(),
+            kind: self.kind,
+            features: self
+                .features
+                .iter()
+                .filter(|(_, on)| *on)
+                .map(|(name, _)| name.clone())
+                .collect(),
+            default_features: self.default_features,
+            optional: self.optional,
+        }
+    }
+
+    /// Seperti `spec`, tapi requirement kosong diisi versi terbaru yang diketahui, sama dengan
+    /// yang akan ditulis `cargo add` tanpa `@versi`.
+    pub fn preview_spec(&self) -> DependencySpec {
+        let mut spec = self.spec();
+        if spec.requirement.is_empty()
+            && let Some(latest) = self.latest.as_ref()
+        {
+            spec.requirement = latest.clone();
+        }
+        spec
+    }
+
+    pub fn set_preview(&mut self, before: &str, after: &str) {
+        const CONTEXT: usize = 2;
+        let document = DiffDocument::build(before, after);
+        let changed = document
+            .rows
+            .iter()
+            .map(|row| row.kind != DiffRowKind::Equal)
+            .collect::<Vec<_>>();
+        let near_change = |idx: usize| {
+            let start = idx.saturating_sub(CONTEXT);
+            let end = (idx + CONTEXT + 1).min(changed.len());
+            changed[start..end].iter().any(|changed| *changed)
+        };
+
+        self.preview.clear();
+        self.preview_scroll = 0;
+        let mut skipped = false;
+        for (idx, row) in document.rows.iter().enumerate() {
+            if !near_change(idx) {
+                skipped = true;
+                continue;
+            }
+            if skipped && !self.preview.is_empty() {
+                self.preview
+                    .push((PreviewLineKind::Separator, String::from("...")));
+            }
+            skipped = false;
+            match row.kind {
+                DiffRowKind::Equal => {
+                    if let Some(side) = &row.new {
+                        self.preview
+                            .push((PreviewLineKind::Context, format!("  {}", side.text)));
+                    }
+                }
+                _ => {
+                    if let Some(side) = &row.old {
+                        self.preview
+                            .push((PreviewLineKind::Removed, format!("- {}", side.text)));
+                    }
+                    if let Some(side) = &row.new {
+                        self.preview
+                            .push((PreviewLineKind::Added, format!("+ {}", side.text)));
+                    }
+                }
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -95,6 +722,21 @@ pub struct ManagerDialog {
     pub search_scroll_y: usize,
     pub search_scroll_x: usize,
     pub installed_packages: HashSet<String>,
+    pub output_follow: bool,
+    pub problems: Vec<Diagnostic>,
+    pub problem_selected: usize,
+    pub problem_scroll_y: usize,
+    pub workspace: Option<CargoWorkspace>,
+    pub target_package: Option<String>,
+    pub package_selected: usize,
+    pub package_scroll_y: usize,
+    pub add_form: Option<AddDependencyForm>,
+    pub report: Vec<ReportEntry>,
+    pub report_selected: usize,
+    pub report_scroll_y: usize,
+    pub dependency_tree: Option<DependencyTree>,
+    pub job: Option<JobStatus>,
+    pub format_on_save: bool,
 }
 
 impl Default for ManagerDialog {
@@ -111,17 +753,43 @@ impl Default for ManagerDialog {
             search_scroll_y: 0,
             search_scroll_x: 0,
             installed_packages: HashSet::new(),
+            output_follow: false,
+            problems: Vec::new(),
+            problem_selected: 0,
+            problem_scroll_y: 0,
+            workspace: None,
+            target_package: None,
+            package_selected: 0,
+            package_scroll_y: 0,
+            add_form: None,
+            report: Vec::new(),
+            report_selected: 0,
+            report_scroll_y: 0,
+            dependency_tree: None,
+            job: None,
+            format_on_save: false,
         }
     }
 }
 
 impl ManagerDialog {
-    pub fn actions() -> [ManagerAction; 7] {
+    pub fn actions() -> [ManagerAction; 18] {
         [
-            ManagerAction::FormatRust,
+            ManagerAction::SelectPackage,
+            ManagerAction::Format,
+            ManagerAction::FormatOnSave,
+            ManagerAction::ListFormatters,
+            ManagerAction::CargoRun(CargoCommand::Check),
+            ManagerAction::CargoRun(CargoCommand::Build),
+            ManagerAction::CargoRun(CargoCommand::Test),
+            ManagerAction::CargoRun(CargoCommand::Clippy),
+            ManagerAction::CargoRun(CargoCommand::Run),
+            ManagerAction::ShowProblems,
             ManagerAction::CargoSearch,
             ManagerAction::CargoAdd,
             ManagerAction::CargoRemove,
+            ManagerAction::DependencyReport,
+            ManagerAction::DependencyTree,
             ManagerAction::WorkspaceAddMember,
             ManagerAction::WorkspaceRemoveMember,
             ManagerAction::Close,
@@ -148,9 +816,53 @@ impl ManagerDialog {
         self.output = text;
         self.output_scroll_y = 0;
         self.output_scroll_x = 0;
+        self.output_follow = false;
         self.mode = ManagerMode::Output;
     }
 
+    pub fn start_streaming_output(&mut self, text: String) {
+        self.set_output(text);
+        self.output_follow = true;
+    }
+
+    pub fn append_output_line(&mut self, line: &str) {
+        if !self.output.is_empty() {
+            self.output.push('\n');
+        }
+        self.output.push_str(line);
+    }
+
+    pub fn set_problems(&mut self, problems: Vec<Diagnostic>) {
+        self.problems = problems;
+        self.problem_selected = 0;
+        self.problem_scroll_y = 0;
+        self.mode = ManagerMode::Problems;
+    }
+
+    pub fn move_problem_up(&mut self) {
+        if self.problem_selected > 0 {
+            self.problem_selected -= 1;
+            if self.problem_selected < self.problem_scroll_y {
+                self.problem_scroll_y = self.problem_selected;
+            }
+        }
+    }
+
+    pub fn move_problem_down(&mut self, viewport_height: usize) {
+        if self.problem_selected + 1 < self.problems.len() {
+            self.problem_selected += 1;
+            if viewport_height > 0
+                && self.problem_selected >= self.problem_scroll_y.saturating_add(viewport_height)
+            {
+                self.problem_scroll_y = self.problem_selected + 1 - viewport_height;
+            }
+        }
+    }
+
+    pub fn selected_problem(&self) -> Option<&Diagnostic> {
+        self.problems.get(self.problem_selected)
+    }
+
     pub fn set_search_results(
         &mut self,
         mut results: Vec<CargoSearchItem>,
@@ -197,8 +909,125 @@ impl ManagerDialog {
         self.search_results.get(self.search_selected)
     }
 
-    pub fn selected_search_name(&self) -> Option<String> {
-        self.selected_search_item().map(|item| item.name.clone())
+    pub fn set_report(&mut self, report: Vec<ReportEntry>) {
+        self.report = report;
+        self.report_selected = self
+            .report_selected
+            .min(self.report.len().saturating_sub(1));
+        self.report_scroll_y = self.report_scroll_y.min(self.report_selected);
+        self.mode = ManagerMode::Report;
+    }
+
+    pub fn selected_report(&self) -> Option<&ReportEntry> {
+        self.report.get(self.report_selected)
+    }
+
+    pub fn move_report_up(&mut self) {
+        if self.report_selected > 0 {
+            self.report_selected -= 1;
+            if self.report_selected < self.report_scroll_y {
+                self.report_scroll_y = self.report_selected;
+            }
+        }
+    }
+
+    pub fn move_report_down(&mut self, viewport_height: usize) {
+        if self.report_selected + 1 < self.report.len() {
+            self.report_selected += 1;
+            if viewport_height > 0
+                && self.report_selected >= self.report_scroll_y.saturating_add(viewport_height)
+            {
+                self.report_scroll_y = self.report_selected + 1 - viewport_height;
+            }
+        }
+    }
+
+    pub fn open_add_form(&mut self, form: AddDependencyForm) {
+        self.add_form = Some(form);
+        self.mode = ManagerMode::AddDependency;
+    }
+
+    pub fn set_workspace(&mut self, workspace: CargoWorkspace) {
+        // Workspace dengan satu package langsung ditargetkan ke package itu.
+        self.target_package = match workspace.packages.as_slice() {
+            [only] => Some(only.name.clone()),
+            _ => self
+                .target_package
+                .take()
+                .filter(|name| workspace.package(name).is_some()),
+        };
+        self.workspace = Some(workspace);
+    }
+
+    pub fn target(&self) -> Option<&WorkspacePackage> {
+        let workspace = self.workspace.as_ref()?;
+        workspace.package(self.target_package.as_deref()?)
+    }
+
+    pub fn target_label(&self) -> String {
+        match (&self.workspace, self.target()) {
+            (_, Some(package)) => package.name.clone(),
+            (Some(workspace), None) if workspace.is_multi_package() => {
+                String::from("semua member")
+            }
+            _ => String::from("-"),
+        }
+    }
+
+    /// Argumen `--manifest-path`/`-p` untuk perintah cargo sesuai package target.
+    pub fn target_args(&self) -> Vec<String> {
+        self.workspace
+            .as_ref()
+            .map(|workspace| workspace.cargo_args(self.target().map(|package| package.name.as_str())))
+            .unwrap_or_default()
+    }
+
+    pub fn target_manifest(&self) -> PathBuf {
+        self.target()
+            .map(|package| package.manifest_path.clone())
+            .or_else(|| {
+                self.workspace
+                    .as_ref()
+                    .map(|workspace| workspace.root_manifest.clone())
+            })
+            .unwrap_or_else(|| PathBuf::from("Cargo.toml"))
+    }
+
+    pub fn open_packages(&mut self) {
+        self.package_selected = self
+            .workspace
+            .as_ref()
+            .zip(self.target_package.as_deref())
+            .and_then(|(workspace, name)| {
+                workspace
+                    .packages
+                    .iter()
+                    .position(|package| package.name == name)
+            })
+            .unwrap_or(0);
+        self.package_scroll_y = 0;
+        self.mode = ManagerMode::Packages;
+    }
+
+    pub fn highlighted_package(&self) -> Option<&WorkspacePackage> {
+        self.workspace.as_ref()?.packages.get(self.package_selected)
+    }
+
+    pub fn move_package_up(&mut self) {
+        self.package_selected = self.package_selected.saturating_sub(1);
+        self.package_scroll_y = 0;
+    }
+
+    pub fn move_package_down(&mut self) {
+        let count = self
+            .workspace
+            .as_ref()
+            .map(|workspace| workspace.packages.len())
+            .unwrap_or(0);
+        if self.package_selected + 1 < count {
+            self.package_selected += 1;
+            self.package_scroll_y = 0;
+        }
     }
 
     pub fn mark_installed(&mut self, package: &str, installed: bool) {
@@ -211,32 +1040,356 @@ impl ManagerDialog {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitDialogMode {
+    Files,
+    Diff,
+    Commit,
+    Output,
+}
+
+#[derive(Debug)]
+pub struct GitDialog {
+    pub mode: GitDialogMode,
+    pub branch: String,
+    pub blame: String,
+    pub files: Vec<ChangedFile>,
+    pub file_selected: usize,
+    pub file_scroll_y: usize,
+    pub diff: Option<FileDiff>,
+    pub hunk_selected: usize,
+    pub diff_scroll_y: usize,
+    pub commit_message: String,
+    pub output: String,
+}
+
+impl GitDialog {
+    pub fn new(branch: String, blame: String, files: Vec<ChangedFile>) -> Self {
+        Self {
+            mode: GitDialogMode::Files,
+            branch,
+            blame,
+            files,
+            file_selected: 0,
+            file_scroll_y: 0,
+            diff: None,
+            hunk_selected: 0,
+            diff_scroll_y: 0,
+            commit_message: String::new(),
+            output: String::new(),
+        }
+    }
+
+    pub fn set_files(&mut self, files: Vec<ChangedFile>) {
+        self.files = files;
+        if self.file_selected >= self.files.len() {
+            self.file_selected = self.files.len().saturating_sub(1);
+        }
+        self.file_scroll_y = self.file_scroll_y.min(self.file_selected);
+    }
+
+    pub fn selected_file(&self) -> Option<&ChangedFile> {
+        self.files.get(self.file_selected)
+    }
+
+    pub fn move_file_up(&mut self) {
+        if self.file_selected > 0 {
+            self.file_selected -= 1;
+            if self.file_selected < self.file_scroll_y {
+                self.file_scroll_y = self.file_selected;
+            }
+        }
+    }
+
+    pub fn move_file_down(&mut self, viewport_height: usize) {
+        if self.file_selected + 1 < self.files.len() {
+            self.file_selected += 1;
+            if viewport_height > 0
+                && self.file_selected >= self.file_scroll_y.saturating_add(viewport_height)
+            {
+                self.file_scroll_y = self.file_selected + 1 - viewport_height;
+            }
+        }
+    }
+
+    pub fn set_diff(&mut self, diff: FileDiff) {
+        self.hunk_selected = self
+            .hunk_selected
+            .min(diff.hunks.len().saturating_sub(1));
+        self.diff_scroll_y = diff.hunk_line_offset(self.hunk_selected);
+        self.diff = Some(diff);
+        self.mode = GitDialogMode::Diff;
+    }
+
+    pub fn select_hunk(&mut self, index: usize) {
+        let Some(diff) = self.diff.as_ref() else {
+            return;
+        };
+        if index < diff.hunks.len() {
+            self.hunk_selected = index;
+            self.diff_scroll_y = diff.hunk_line_offset(index);
+        }
+    }
+
+    pub fn set_output(&mut self, text: String) {
+        self.output = text;
+        self.mode = GitDialogMode::Output;
+    }
+}
+
+#[derive(Debug)]
+pub struct DiffViewDialog {
+    pub left_title: String,
+    pub right_title: String,
+    pub document: DiffDocument,
+    pub inline: bool,
+    pub scroll_y: usize,
+    pub scroll_x: usize,
+}
+
+impl DiffViewDialog {
+    pub fn new(left_title: String, right_title: String, document: DiffDocument) -> Self {
+        let scroll_y = document
+            .hunk_rows
+            .first()
+            .map(|row| row.saturating_sub(3))
+            .unwrap_or(0);
+        Self {
+            left_title,
+            right_title,
+            document,
+            inline: false,
+            scroll_y,
+            scroll_x: 0,
+        }
+    }
+
+    pub fn scroll_up(&mut self, rows: usize) {
+        self.scroll_y = self.scroll_y.saturating_sub(rows);
+    }
+
+    pub fn scroll_down(&mut self, rows: usize) {
+        let max = self.document.rows.len().saturating_sub(1);
+        self.scroll_y = self.scroll_y.saturating_add(rows).min(max);
+    }
+
+    pub fn next_hunk(&mut self) -> bool {
+        match self.document.next_hunk(self.scroll_y + 3) {
+            Some(row) => {
+                self.scroll_y = row.saturating_sub(3);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn prev_hunk(&mut self) -> bool {
+        match self.document.prev_hunk(self.scroll_y + 3) {
+            Some(row) => {
+                self.scroll_y = row.saturating_sub(3);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn current_hunk(&self) -> usize {
+        self.document
+            .hunk_rows
+            .iter()
+            .filter(|row| row.saturating_sub(3) <= self.scroll_y)
+            .count()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnsavedPromptChoice {
+    #[default]
+    Save,
+    DiffDisk,
+    Cancel,
+}
+
+#[derive(Debug, Default)]
+pub struct UnsavedPromptDialog {
+    pub selected: UnsavedPromptChoice,
+    pub quitting: bool,
+}
+
+impl UnsavedPromptDialog {
+    pub fn new(quitting: bool) -> Self {
+        Self {
+            selected: UnsavedPromptChoice::Save,
+            quitting,
+        }
+    }
+
+    pub fn next(&mut self) {
+        self.selected = match self.selected {
+            UnsavedPromptChoice::Save => UnsavedPromptChoice::DiffDisk,
+            UnsavedPromptChoice::DiffDisk => UnsavedPromptChoice::Cancel,
+            UnsavedPromptChoice::Cancel => UnsavedPromptChoice::Save,
+        };
+    }
+
+    pub fn prev(&mut self) {
+        self.selected = match self.selected {
+            UnsavedPromptChoice::Save => UnsavedPromptChoice::Cancel,
+            UnsavedPromptChoice::DiffDisk => UnsavedPromptChoice::Save,
+            UnsavedPromptChoice::Cancel => UnsavedPromptChoice::DiffDisk,
+        };
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyDialogFocus {
+    Keys,
+    Trusted,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyDialogInput {
+    GenerateName,
+    GeneratePassphrase,
+    Import,
+    ExportPublic,
+    ExportPrivate,
+    Unlock,
+    Passphrase,
+    Trust,
+}
+
+impl KeyDialogInput {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::GenerateName => "Nama kunci baru",
+            Self::GeneratePassphrase => "Passphrase (kosong = tanpa passphrase)",
+            Self::Import => "Path file PEM kunci",
+            Self::ExportPublic => "Ekspor public key ke",
+            Self::ExportPrivate => "Ekspor private key ke",
+            Self::Unlock => "Passphrase untuk membuka kunci",
+            Self::Passphrase => "Passphrase baru (kosong = hapus proteksi)",
+            Self::Trust => "Public key hex / path PEM",
+        }
+    }
+
+    pub fn is_secret(self) -> bool {
+        matches!(
+            self,
+            Self::GeneratePassphrase | Self::Unlock | Self::Passphrase
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct KeyDialog {
+    pub keys: Vec<KeyInfo>,
+    pub trusted: Vec<TrustedKey>,
+    pub focus: KeyDialogFocus,
+    pub selected_key: usize,
+    pub selected_trusted: usize,
+    pub signing_enabled: bool,
+    pub location: KeyLocation,
+    pub key_dir: String,
+    pub input_mode: Option<KeyDialogInput>,
+    pub input: String,
+    pub pending_name: String,
+    pub message: String,
+}
+
+impl KeyDialog {
+    pub fn new(store: &KeyStore) -> Self {
+        let mut dialog = Self {
+            keys: Vec::new(),
+            trusted: Vec::new(),
+            focus: KeyDialogFocus::Keys,
+            selected_key: 0,
+            selected_trusted: 0,
+            signing_enabled: true,
+            location: KeyLocation::Workspace,
+            key_dir: String::new(),
+            input_mode: None,
+            input: String::new(),
+            pending_name: String::new(),
+            message: String::new(),
+        };
+        dialog.reload(store);
+        dialog.selected_key = dialog.keys.iter().position(|key| key.active).unwrap_or(0);
+        dialog
+    }
+
+    pub fn reload(&mut self, store: &KeyStore) {
+        self.keys = store.keys();
+        self.trusted = store.trusted_keys();
+        self.signing_enabled = store.settings().enabled;
+        self.location = store.settings().location;
+        self.key_dir = store.key_dir().to_string_lossy().to_string();
+        self.selected_key = self.selected_key.min(self.keys.len().saturating_sub(1));
+        self.selected_trusted = self
+            .selected_trusted
+            .min(self.trusted.len().saturating_sub(1));
+    }
+
+    pub fn selected_key(&self) -> Option<&KeyInfo> {
+        self.keys.get(self.selected_key)
+    }
+
+    pub fn selected_trusted(&self) -> Option<&TrustedKey> {
+        self.trusted.get(self.selected_trusted)
+    }
+
+    pub fn start_input(&mut self, mode: KeyDialogInput, initial: String) {
+        self.input_mode = Some(mode);
+        self.input = initial;
+    }
+
+    pub fn move_up(&mut self) {
+        match self.focus {
+            KeyDialogFocus::Keys => self.selected_key = self.selected_key.saturating_sub(1),
+            KeyDialogFocus::Trusted => {
+                self.selected_trusted = self.selected_trusted.saturating_sub(1)
+            }
+        }
+    }
+
+    pub fn move_down(&mut self) {
+        match self.focus {
+            KeyDialogFocus::Keys => {
+                if self.selected_key + 1 < self.keys.len() {
+                    self.selected_key += 1;
+                }
+            }
+            KeyDialogFocus::Trusted => {
+                if self.selected_trusted + 1 < self.trusted.len() {
+                    self.selected_trusted += 1;
+                }
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum StartMenuAction {
     NewTab,
     FocusFileTree,
     OpenManager,
+    OpenKeyManager,
     ShowHelp,
     ContinueEditor,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct StartMenuDialog {
     pub selected: usize,
 }
 
-impl Default for StartMenuDialog {
-    fn default() -> Self {
-        Self { selected: 0 }
-    }
-}
-
 impl StartMenuDialog {
-    pub fn actions() -> [StartMenuAction; 5] {
+    pub fn actions() -> [StartMenuAction; 6] {
         [
             StartMenuAction::NewTab,
             StartMenuAction::FocusFileTree,
             StartMenuAction::OpenManager,
+            StartMenuAction::OpenKeyManager,
             StartMenuAction::ShowHelp,
             StartMenuAction::ContinueEditor,
         ]
@@ -259,6 +1412,160 @@ impl StartMenuDialog {
     }
 }
 
+#[derive(Debug, Default)]
+pub struct OutlinePanel {
+    pub filter: String,
+    pub selected: usize,
+    pub scroll: usize,
+    pub symbols: Vec<Symbol>,
+    pub supported: bool,
+    source: Option<(usize, u64, Option<PathBuf>)>,
+}
+
+impl OutlinePanel {
+    /// Dipindai ulang hanya bila tab, revisi buffer, atau path berubah.
+    pub fn refresh(&mut self, tab: usize, revision: u64, path: Option<&Path>, lines: &[String]) {
+        let source = (tab, revision, path.map(Path::to_path_buf));
+        if self.source.as_ref() == Some(&source) {
+            return;
+        }
+        self.supported = path.is_none_or(|path| path.extension().is_some_and(|ext| ext == "rs"));
+        self.symbols = if self.supported {
+            rust_outline(lines)
+        } else {
+            Vec::new()
+        };
+        self.source = Some(source);
+        self.selected = self.selected.min(self.visible().len().saturating_sub(1));
+    }
+
+    pub fn visible(&self) -> Vec<usize> {
+        let filter = self.filter.to_lowercase();
+        self.symbols
+            .iter()
+            .enumerate()
+            .filter(|(_, symbol)| filter.is_empty() || symbol.name.to_lowercase().contains(&filter))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    pub fn selected_symbol(&self) -> Option<&Symbol> {
+        self.visible()
+            .get(self.selected)
+            .map(|idx| &self.symbols[*idx])
+    }
+
+    /// Pilih item yang memuat baris cursor editor.
+    pub fn follow_cursor(&mut self, row: usize) {
+        if let Some(current) = symbol_at(&self.symbols, row)
+            && let Some(position) = self.visible().iter().position(|idx| *idx == current)
+        {
+            self.selected = position;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected + 1 < self.visible().len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn push_char(&mut self, ch: char) {
+        self.filter.push(ch);
+        self.selected = 0;
+    }
+
+    pub fn pop_char(&mut self) {
+        self.filter.pop();
+        self.selected = 0;
+    }
+
+    pub fn ensure_visible(&mut self, height: usize) {
+        let height = height.max(1);
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        } else if self.selected >= self.scroll + height {
+            self.scroll = self.selected + 1 - height;
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct WorkspaceSymbolDialog {
+    pub query: String,
+    pub selected: usize,
+    pub results: Vec<WorkspaceSymbol>,
+    /// Diisi bila dibuka dari go-to-definition; hilang begitu query diubah.
+    pub definition_of: Option<String>,
+    pub root: PathBuf,
+    pub indexing: bool,
+    pub file_count: usize,
+    pub symbol_count: usize,
+}
+
+impl WorkspaceSymbolDialog {
+    pub fn search(index: &SymbolIndex) -> Self {
+        let mut dialog = Self::default();
+        dialog.refresh(index);
+        dialog
+    }
+
+    pub fn definitions(name: &str, results: Vec<WorkspaceSymbol>, index: &SymbolIndex) -> Self {
+        let mut dialog = Self {
+            query: name.to_string(),
+            results,
+            definition_of: Some(name.to_string()),
+            ..Self::default()
+        };
+        dialog.refresh(index);
+        dialog
+    }
+
+    /// Hitung ulang hasil (kecuali daftar definisi) dan ringkasan indeks.
+    pub fn refresh(&mut self, index: &SymbolIndex) {
+        if self.definition_of.is_none() {
+            self.results = index.search(&self.query);
+        }
+        self.root = index.root().to_path_buf();
+        self.indexing = index.is_indexing();
+        self.file_count = index.file_count();
+        self.symbol_count = index.symbol_count();
+        self.selected = self.selected.min(self.results.len().saturating_sub(1));
+    }
+
+    pub fn selected_symbol(&self) -> Option<&WorkspaceSymbol> {
+        self.results.get(self.selected)
+    }
+
+    pub fn move_up(&mut self, amount: usize) {
+        self.selected = self.selected.saturating_sub(amount);
+    }
+
+    pub fn move_down(&mut self, amount: usize) {
+        self.selected = (self.selected + amount).min(self.results.len().saturating_sub(1));
+    }
+
+    pub fn push_char(&mut self, ch: char, index: &SymbolIndex) {
+        self.query.push(ch);
+        self.query_changed(index);
+    }
+
+    pub fn pop_char(&mut self, index: &SymbolIndex) {
+        self.query.pop();
+        self.query_changed(index);
+    }
+
+    fn query_changed(&mut self, index: &SymbolIndex) {
+        self.definition_of = None;
+        self.selected = 0;
+        self.refresh(index);
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct TabHit {
     pub index: usize,
@@ -291,6 +1598,7 @@ pub enum ExplorerMenuAction {
     AddFile,
     RenameEntry,
     DeleteEntry,
+    CompareWith,
 }
 
 #[derive(Debug, Clone)]
@@ -309,6 +1617,7 @@ pub struct ExplorerContextMenuHit {
     pub add_file_rect: Option<Rect>,
     pub rename_entry_rect: Option<Rect>,
     pub delete_entry_rect: Option<Rect>,
+    pub compare_with_rect: Option<Rect>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -316,6 +1625,7 @@ pub enum ExplorerInputMode {
     AddFolder,
     AddFile,
     RenameEntry,
+    CompareWith,
 }
 
 #[derive(Debug, Clone)]
@@ -339,6 +1649,11 @@ impl ExplorerInputDialog {
                         .map(|name| name.to_string_lossy().to_string())
                 })
                 .unwrap_or_else(|| String::from("nama-baru")),
+            ExplorerInputMode::CompareWith => target_path
+                .as_ref()
+                .and_then(|path| path.strip_prefix(&base_dir).ok())
+                .map(|path| path.to_string_lossy().to_string())
+                .unwrap_or_default(),
         };
         Self {
             mode,
@@ -369,6 +1684,7 @@ impl ExplorerInputDialog {
             ExplorerInputMode::AddFolder => " Tambah Folder ",
             ExplorerInputMode::AddFile => " Tambah File ",
             ExplorerInputMode::RenameEntry => " Ubah Nama ",
+            ExplorerInputMode::CompareWith => " Bandingkan Dengan ",
         }
     }
 }
@@ -386,6 +1702,7 @@ pub struct UiState {
     pub tab_content_rect: Rect,
     pub tree_content_rect: Rect,
     pub editor_content_rect: Rect,
+    pub terminal_content_rect: Rect,
     pub save_dialog_hit: Option<SaveDialogHit>,
     pub context_menu_hit: Option<ContextMenuHit>,
     pub explorer_context_menu_hit: Option<ExplorerContextMenuHit>,
@@ -0,0 +1,72 @@
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use super::types::{UnsavedPromptChoice, UnsavedPromptDialog};
+
+pub fn render_unsaved_prompt(frame: &mut Frame, dialog: &UnsavedPromptDialog, title: &str) {
+    let area = centered_rect(frame.area(), 60, 7);
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Block::default()
+            .title(" Perubahan Belum Disimpan ")
+            .borders(Borders::ALL),
+        area,
+    );
+
+    let inner = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Length(1),
+    ])
+    .margin(1)
+    .split(area);
+
+    let message = if dialog.quitting {
+        format!(
+            "{} belum disimpan. Keluar dibatalkan sampai disimpan.",
+            title
+        )
+    } else {
+        format!("{} belum disimpan. Tab tidak bisa ditutup.", title)
+    };
+    frame.render_widget(Paragraph::new(message), inner[0]);
+
+    let button = |label: &'static str, choice: UnsavedPromptChoice, color: Color| {
+        if dialog.selected == choice {
+            Span::styled(label, Style::default().fg(Color::Black).bg(color))
+        } else {
+            Span::raw(label)
+        }
+    };
+    let buttons = Line::from(vec![
+        button("[ Simpan ]", UnsavedPromptChoice::Save, Color::Green),
+        Span::raw("  "),
+        button(
+            "[ Diff dengan disk ]",
+            UnsavedPromptChoice::DiffDisk,
+            Color::Cyan,
+        ),
+        Span::raw("  "),
+        button("[ Batal ]", UnsavedPromptChoice::Cancel, Color::Yellow),
+    ]);
+    frame.render_widget(Paragraph::new(buttons), inner[2]);
+    frame.render_widget(
+        Paragraph::new("Tab/Left/Right: pilih tombol | Enter: konfirmasi | Esc: batal"),
+        inner[3],
+    );
+}
+
+fn centered_rect(area: Rect, width_percent: u16, height: u16) -> Rect {
+    let max_width = area.width.saturating_sub(2).max(1);
+    let max_height = area.height.saturating_sub(2).max(1);
+    let width = area.width.saturating_mul(width_percent) / 100;
+    let width = width.max(50).min(max_width);
+    let height = height.max(5).min(max_height);
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    Rect::new(x, y, width, height)
+}
@@ -0,0 +1,452 @@
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+use anyhow::{Context, Result, anyhow};
+
+use crate::diff::{ChangeMarker, change_markers, diff_lines};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FileStatus {
+    Conflicted,
+    Modified,
+    Added,
+    Deleted,
+    Renamed,
+    Untracked,
+    Ignored,
+}
+
+impl FileStatus {
+    pub fn badge(self) -> &'static str {
+        match self {
+            Self::Conflicted => "C",
+            Self::Modified => "M",
+            Self::Added => "A",
+            Self::Deleted => "D",
+            Self::Renamed => "R",
+            Self::Untracked => "U",
+            Self::Ignored => "!",
+        }
+    }
+
+    fn from_porcelain(x: u8, y: u8) -> Option<Self> {
+        match (x, y) {
+            (b'?', b'?') => Some(Self::Untracked),
+            (b'!', b'!') => Some(Self::Ignored),
+            (b'U', _) | (_, b'U') | (b'A', b'A') | (b'D', b'D') => Some(Self::Conflicted),
+            (b'R', _) | (_, b'R') => Some(Self::Renamed),
+            (b'A', _) => Some(Self::Added),
+            (b'D', _) | (_, b'D') => Some(Self::Deleted),
+            (b'M', _) | (_, b'M') | (b'T', _) | (_, b'T') => Some(Self::Modified),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct GitRepository {
+    root: PathBuf,
+    branch: Option<String>,
+    head: Option<String>,
+    statuses: HashMap<PathBuf, FileStatus>,
+    dir_statuses: HashMap<PathBuf, FileStatus>,
+    head_cache: HashMap<PathBuf, Option<Vec<String>>>,
+    head_requests: HashSet<PathBuf>,
+    canonical_paths: HashMap<PathBuf, PathBuf>,
+    marker_cache: HashMap<PathBuf, (u64, Vec<Option<ChangeMarker>>)>,
+    pending: Option<Receiver<Result<GitSnapshot>>>,
+}
+
+/// Hasil satu kali membaca branch, HEAD, status working tree, dan isi HEAD file yang diminta.
+#[derive(Debug)]
+struct GitSnapshot {
+    branch: Option<String>,
+    head: Option<String>,
+    statuses: HashMap<PathBuf, FileStatus>,
+    dir_statuses: HashMap<PathBuf, FileStatus>,
+    head_lines: HashMap<PathBuf, Option<Vec<String>>>,
+}
+
+/// File yang isi HEAD-nya perlu dibaca bersama snapshot berikutnya.
+#[derive(Debug, Default)]
+struct HeadRequest {
+    known_head: Option<String>,
+    /// Sudah ada di cache; dibaca ulang hanya bila HEAD berpindah.
+    cached: Vec<PathBuf>,
+    requested: Vec<PathBuf>,
+}
+
+impl GitSnapshot {
+    fn read(root: &Path, request: HeadRequest) -> Result<Self> {
+        let branch = read_branch(root);
+        let head = run_git(root, &["rev-parse", "HEAD"])
+            .ok()
+            .map(|sha| sha.trim().to_string());
+
+        let mut head_lines = HashMap::new();
+        let mut paths = request.requested;
+        if head != request.known_head {
+            paths.extend(request.cached);
+        }
+        for path in paths {
+            let lines = read_head_lines(root, &path);
+            head_lines.insert(path, lines);
+        }
+
+        let output = run_git(root, &["status", "--porcelain=v1", "-z", "--ignored"])?;
+        let mut statuses = HashMap::new();
+        let mut records = output.split('\0');
+        while let Some(record) = records.next() {
+            let bytes = record.as_bytes();
+            if bytes.len() < 4 {
+                continue;
+            }
+            let (x, y) = (bytes[0], bytes[1]);
+            if x == b'R' || x == b'C' {
+                records.next();
+            }
+            let Some(status) = FileStatus::from_porcelain(x, y) else {
+                continue;
+            };
+            let rel = record[3..].trim_end_matches('/');
+            statuses.insert(root.join(rel), status);
+        }
+
+        let mut dir_statuses: HashMap<PathBuf, FileStatus> = HashMap::new();
+        for (path, status) in &statuses {
+            if *status == FileStatus::Ignored {
+                continue;
+            }
+            for ancestor in path.ancestors().skip(1) {
+                if !ancestor.starts_with(root) {
+                    break;
+                }
+                let entry = dir_statuses
+                    .entry(ancestor.to_path_buf())
+                    .or_insert(*status);
+                *entry = (*entry).min(*status);
+            }
+        }
+
+        Ok(Self {
+            branch,
+            head,
+            statuses,
+            dir_statuses,
+            head_lines,
+        })
+    }
+}
+
+impl GitRepository {
+    pub fn discover(start: &Path) -> Option<Self> {
+        let output = run_git(start, &["rev-parse", "--show-toplevel"]).ok()?;
+        let root = PathBuf::from(output.trim());
+        let mut repo = Self {
+            root,
+            branch: None,
+            head: None,
+            statuses: HashMap::new(),
+            dir_statuses: HashMap::new(),
+            head_cache: HashMap::new(),
+            head_requests: HashSet::new(),
+            canonical_paths: HashMap::new(),
+            marker_cache: HashMap::new(),
+            pending: None,
+        };
+        let _ = repo.refresh();
+        Some(repo)
+    }
+
+    pub fn root(&self) -> &Path {
+        self.root.as_path()
+    }
+
+    pub fn branch(&self) -> Option<&str> {
+        self.branch.as_deref()
+    }
+
+    pub fn refresh(&mut self) -> Result<()> {
+        // Hasil background yang masih jalan lebih lama dari pembacaan ini.
+        self.pending = None;
+        let snapshot = GitSnapshot::read(&self.root, self.head_request())?;
+        self.apply(snapshot);
+        Ok(())
+    }
+
+    /// Baca status git di thread terpisah; hasilnya dipasang oleh `poll`. Diabaikan bila
+    /// pembacaan sebelumnya belum selesai.
+    pub fn refresh_in_background(&mut self) {
+        if self.pending.is_some() {
+            return;
+        }
+        let (sender, receiver) = mpsc::channel();
+        let root = self.root.clone();
+        let request = self.head_request();
+        thread::spawn(move || {
+            let _ = sender.send(GitSnapshot::read(&root, request));
+        });
+        self.pending = Some(receiver);
+    }
+
+    fn head_request(&self) -> HeadRequest {
+        HeadRequest {
+            known_head: self.head.clone(),
+            cached: self.head_cache.keys().cloned().collect(),
+            requested: self.head_requests.iter().cloned().collect(),
+        }
+    }
+
+    /// Pasang hasil `refresh_in_background` bila sudah ada; `true` bila status berubah.
+    pub fn poll(&mut self) -> bool {
+        let Some(receiver) = self.pending.as_ref() else {
+            return false;
+        };
+        match receiver.try_recv() {
+            Ok(snapshot) => {
+                self.pending = None;
+                match snapshot {
+                    Ok(snapshot) => {
+                        self.apply(snapshot);
+                        true
+                    }
+                    Err(_) => false,
+                }
+            }
+            Err(TryRecvError::Empty) => false,
+            Err(TryRecvError::Disconnected) => {
+                self.pending = None;
+                false
+            }
+        }
+    }
+
+    fn apply(&mut self, snapshot: GitSnapshot) {
+        self.branch = snapshot.branch;
+        if snapshot.head != self.head {
+            self.head = snapshot.head;
+            self.head_cache.clear();
+        }
+        for (path, lines) in snapshot.head_lines {
+            self.head_requests.remove(&path);
+            self.head_cache.insert(path, lines);
+        }
+        self.marker_cache.clear();
+        self.statuses = snapshot.statuses;
+        self.dir_statuses = snapshot.dir_statuses;
+    }
+
+    pub fn status_for(&self, path: &Path) -> Option<FileStatus> {
+        let path = absolute_path(path);
+        if let Some(status) = self.statuses.get(&path) {
+            return Some(*status);
+        }
+        if let Some(status) = self.dir_statuses.get(&path) {
+            return Some(*status);
+        }
+        path.ancestors()
+            .skip(1)
+            .take_while(|ancestor| ancestor.starts_with(&self.root))
+            .find_map(|ancestor| match self.statuses.get(ancestor) {
+                Some(status @ (FileStatus::Untracked | FileStatus::Ignored)) => Some(*status),
+                _ => None,
+            })
+    }
+
+    pub fn relative_path(&self, path: &Path) -> Option<String> {
+        relative_to(&self.root, &absolute_path(path))
+    }
+
+    /// Path tab dikanonikalkan sekali lalu diingat; `line_markers` dipanggil tiap frame.
+    fn canonical_path(&mut self, path: &Path) -> PathBuf {
+        self.canonical_paths
+            .entry(path.to_path_buf())
+            .or_insert_with(|| path.canonicalize().unwrap_or_else(|_| absolute_path(path)))
+            .clone()
+    }
+
+    pub fn line_markers(
+        &mut self,
+        path: &Path,
+        revision: u64,
+        lines: &[String],
+    ) -> Vec<Option<ChangeMarker>> {
+        let key = self.canonical_path(path);
+        if let Some((cached_revision, markers)) = self.marker_cache.get(&key)
+            && *cached_revision == revision
+        {
+            return markers.clone();
+        }
+
+        let markers = match self.status_for(&key) {
+            Some(FileStatus::Ignored) => vec![None; lines.len()],
+            _ if self.relative_path(&key).is_none() => vec![None; lines.len()],
+            _ => match self.head_cache.get(&key) {
+                Some(Some(head)) => change_markers(&diff_lines(head, lines), lines.len()),
+                Some(None) => vec![Some(ChangeMarker::Added); lines.len()],
+                None => {
+                    // Isi HEAD dibaca oleh thread snapshot; marker muncul setelah `poll`. Bila
+                    // snapshot lain sedang jalan, permintaan ikut refresh berkala berikutnya.
+                    if self.head_requests.insert(key) {
+                        self.refresh_in_background();
+                    }
+                    return vec![None; lines.len()];
+                }
+            },
+        };
+        self.marker_cache.insert(key, (revision, markers.clone()));
+        markers
+    }
+}
+
+pub fn run_git(dir: &Path, args: &[&str]) -> Result<String> {
+    run_git_with_input(dir, args, None)
+}
+
+pub fn run_git_with_input(dir: &Path, args: &[&str], input: Option<&str>) -> Result<String> {
+    let mut child = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .stdin(if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("gagal menjalankan git")?;
+    // Input ditulis dari thread lain supaya git tidak macet menulis stdout yang belum dibaca
+    // sementara kita masih menunggu git membaca stdin.
+    let writer = match (input, child.stdin.take()) {
+        (Some(input), Some(mut stdin)) => {
+            let input = input.to_string();
+            Some(thread::spawn(move || stdin.write_all(input.as_bytes())))
+        }
+        _ => None,
+    };
+    let output = child.wait_with_output().context("gagal menunggu git")?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "git {} gagal: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    if let Some(writer) = writer {
+        writer
+            .join()
+            .map_err(|_| anyhow!("thread input git panik"))?
+            .context("gagal mengirim input ke git")?;
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn read_branch(root: &Path) -> Option<String> {
+    if let Ok(name) = run_git(root, &["symbolic-ref", "--short", "-q", "HEAD"]) {
+        let name = name.trim();
+        if !name.is_empty() {
+            return Some(name.to_string());
+        }
+    }
+    run_git(root, &["rev-parse", "--short", "HEAD"])
+        .ok()
+        .map(|sha| format!("({})", sha.trim()))
+}
+
+fn relative_to(root: &Path, path: &Path) -> Option<String> {
+    let rel = path.strip_prefix(root).ok()?;
+    Some(
+        rel.components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+    )
+}
+
+fn read_head_lines(root: &Path, path: &Path) -> Option<Vec<String>> {
+    let rel = relative_to(root, path)?;
+    run_git(root, &["show", &format!("HEAD:{}", rel)])
+        .ok()
+        .map(|text| text.split('\n').map(str::to_string).collect())
+}
+
+/// Path explorer sudah kanonik karena root `FileTree` dikanonikalkan saat dibuat, jadi di sini
+/// cukup melengkapi path relatif tanpa menyentuh filesystem.
+fn absolute_path(path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        return path.to_path_buf();
+    }
+    std::env::current_dir()
+        .map(|cwd| cwd.join(path))
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::{Duration, Instant};
+
+    /// Repo sementara berisi `a.txt` yang sudah di-commit; `name` membedakan direktori antar test.
+    pub(crate) fn temp_repo(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("action-codex-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        let root = root.canonicalize().unwrap();
+        fs::write(root.join("a.txt"), "satu\ndua\n").unwrap();
+        for args in [
+            &["init", "-q"][..],
+            &["add", "a.txt"],
+            &[
+                "-c",
+                "user.name=t",
+                "-c",
+                "user.email=t@t",
+                "commit",
+                "-qm",
+                "init",
+            ],
+        ] {
+            run_git(&root, args).unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn head_lines_are_loaded_off_the_draw_path() {
+        let root = temp_repo("git");
+        let mut repo = GitRepository::discover(&root).unwrap();
+        let path = root.join("a.txt");
+        let lines = vec![String::from("satu"), String::from("baru"), String::new()];
+
+        // Frame pertama tidak menunggu `git show`.
+        assert_eq!(repo.line_markers(&path, 1, &lines), vec![None; 3]);
+
+        let started = Instant::now();
+        while !repo.poll() {
+            assert!(started.elapsed() < Duration::from_secs(10));
+            thread::sleep(Duration::from_millis(10));
+        }
+        let markers = repo.line_markers(&path, 1, &lines);
+        assert_eq!(markers[1], Some(ChangeMarker::Modified));
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn large_input_and_output_do_not_block() {
+        let root = temp_repo("git-input");
+        // Output git jauh melebihi buffer pipe sebelum seluruh input terbaca.
+        let input = "HEAD:a.txt\n".repeat(20_000);
+        let output =
+            run_git_with_input(&root, &["cat-file", "--batch-check"], Some(&input)).unwrap();
+        assert_eq!(output.lines().count(), 20_000);
+        assert!(output.lines().all(|line| line.ends_with(" blob 9")));
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,387 @@
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result, anyhow};
+
+use super::git::{run_git, run_git_with_input};
+
+#[derive(Debug, Clone)]
+pub struct ChangedFile {
+    pub path: String,
+    pub index: char,
+    pub worktree: char,
+}
+
+impl ChangedFile {
+    pub fn is_untracked(&self) -> bool {
+        self.index == '?'
+    }
+
+    pub fn has_staged(&self) -> bool {
+        !matches!(self.index, ' ' | '?')
+    }
+
+    pub fn has_unstaged(&self) -> bool {
+        self.is_untracked() || self.worktree != ' '
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DiffHunk {
+    pub header: String,
+    pub lines: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FileDiff {
+    pub path: String,
+    pub staged: bool,
+    pub header: Vec<String>,
+    pub hunks: Vec<DiffHunk>,
+}
+
+impl FileDiff {
+    pub fn hunk_patch(&self, index: usize) -> Option<String> {
+        let hunk = self.hunks.get(index)?;
+        let mut patch = String::new();
+        for line in self.header.iter().chain(std::iter::once(&hunk.header)) {
+            patch.push_str(line);
+            patch.push('\n');
+        }
+        for line in &hunk.lines {
+            patch.push_str(line);
+            patch.push('\n');
+        }
+        Some(patch)
+    }
+
+    pub fn hunk_line_offset(&self, index: usize) -> usize {
+        self.hunks
+            .iter()
+            .take(index)
+            .map(|hunk| hunk.lines.len() + 1)
+            .sum()
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.hunks.iter().map(|hunk| hunk.lines.len() + 1).sum()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BlameLine {
+    pub commit: String,
+    pub author: String,
+    pub date: String,
+    pub summary: String,
+}
+
+impl BlameLine {
+    pub fn is_uncommitted(&self) -> bool {
+        self.commit.chars().all(|ch| ch == '0')
+    }
+
+    pub fn describe(&self) -> String {
+        if self.is_uncommitted() {
+            return String::from("Belum di-commit");
+        }
+        format!(
+            "{} {} {} - {}",
+            &self.commit[..self.commit.len().min(8)],
+            self.author,
+            self.date,
+            self.summary
+        )
+    }
+}
+
+pub fn changed_files(root: &Path) -> Result<Vec<ChangedFile>> {
+    let output = run_git(root, &["status", "--porcelain=v1", "-z", "-uall"])?;
+    let mut files = Vec::new();
+    let mut records = output.split('\0');
+    while let Some(record) = records.next() {
+        let mut chars = record.chars();
+        let (Some(index), Some(worktree)) = (chars.next(), chars.next()) else {
+            continue;
+        };
+        if record.len() < 4 {
+            continue;
+        }
+        if index == 'R' || index == 'C' {
+            records.next();
+        }
+        files.push(ChangedFile {
+            path: record[3..].to_string(),
+            index,
+            worktree,
+        });
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+pub fn file_diff(root: &Path, file: &ChangedFile, staged: bool) -> Result<FileDiff> {
+    let text = if !staged && file.is_untracked() {
+        let output = Command::new("git")
+            .args([
+                "diff",
+                "--no-color",
+                "--no-index",
+                "--",
+                "/dev/null",
+                &file.path,
+            ])
+            .current_dir(root)
+            .output()
+            .context("gagal menjalankan git diff")?;
+        if output.status.code() != Some(1) && !output.status.success() {
+            return Err(anyhow!(
+                "git diff gagal: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        String::from_utf8_lossy(&output.stdout).to_string()
+    } else {
+        let mut args = vec!["diff", "--no-color", "--no-ext-diff", "-U3"];
+        if staged {
+            args.push("--cached");
+        }
+        args.push("--");
+        args.push(&file.path);
+        run_git(root, &args)?
+    };
+    Ok(parse_diff(&file.path, staged, &text))
+}
+
+fn parse_diff(path: &str, staged: bool, text: &str) -> FileDiff {
+    let mut diff = FileDiff {
+        path: path.to_string(),
+        staged,
+        header: Vec::new(),
+        hunks: Vec::new(),
+    };
+    let text = text.strip_suffix('\n').unwrap_or(text);
+    for line in text.split('\n').filter(|_| !text.is_empty()) {
+        if line.starts_with("@@") {
+            diff.hunks.push(DiffHunk {
+                header: line.to_string(),
+                lines: Vec::new(),
+            });
+        } else if let Some(hunk) = diff.hunks.last_mut() {
+            hunk.lines.push(line.to_string());
+        } else {
+            diff.header.push(line.to_string());
+        }
+    }
+    diff
+}
+
+pub fn stage_hunk(root: &Path, diff: &FileDiff, index: usize) -> Result<()> {
+    let patch = diff
+        .hunk_patch(index)
+        .ok_or_else(|| anyhow!("hunk tidak ditemukan"))?;
+    run_git_with_input(
+        root,
+        &["apply", "--cached", "--whitespace=nowarn", "-"],
+        Some(&patch),
+    )?;
+    Ok(())
+}
+
+pub fn unstage_hunk(root: &Path, diff: &FileDiff, index: usize) -> Result<()> {
+    let patch = diff
+        .hunk_patch(index)
+        .ok_or_else(|| anyhow!("hunk tidak ditemukan"))?;
+    run_git_with_input(
+        root,
+        &["apply", "--cached", "--reverse", "--whitespace=nowarn", "-"],
+        Some(&patch),
+    )?;
+    Ok(())
+}
+
+pub fn stage_file(root: &Path, path: &str) -> Result<()> {
+    run_git(root, &["add", "--", path])?;
+    Ok(())
+}
+
+pub fn unstage_file(root: &Path, path: &str) -> Result<()> {
+    if run_git(root, &["rev-parse", "--verify", "-q", "HEAD"]).is_ok() {
+        run_git(root, &["reset", "-q", "HEAD", "--", path])?;
+    } else {
+        run_git(root, &["rm", "--cached", "-q", "--", path])?;
+    }
+    Ok(())
+}
+
+pub fn commit(root: &Path, message: &str) -> Result<String> {
+    if message.trim().is_empty() {
+        return Err(anyhow!("pesan commit tidak boleh kosong"));
+    }
+    let output = run_git_with_input(root, &["commit", "-F", "-"], Some(message))?;
+    Ok(output.trim().to_string())
+}
+
+pub fn blame_line(root: &Path, path: &str, line: usize, contents: &str) -> Result<BlameLine> {
+    let range = format!("{},{}", line, line);
+    let output = run_git_with_input(
+        root,
+        &[
+            "blame",
+            "--porcelain",
+            "-L",
+            &range,
+            "--contents",
+            "-",
+            "--",
+            path,
+        ],
+        Some(contents),
+    )?;
+    parse_blame(&output)
+}
+
+fn parse_blame(output: &str) -> Result<BlameLine> {
+    let mut lines = output.lines();
+    let commit = lines
+        .next()
+        .and_then(|first| first.split_whitespace().next())
+        .ok_or_else(|| anyhow!("output blame kosong"))?
+        .to_string();
+    let mut blame = BlameLine {
+        commit,
+        author: String::new(),
+        date: String::new(),
+        summary: String::new(),
+    };
+    for line in lines {
+        if let Some(author) = line.strip_prefix("author ") {
+            blame.author = author.to_string();
+        } else if let Some(time) = line.strip_prefix("author-time ") {
+            blame.date = time
+                .parse::<i64>()
+                .map(format_unix_date)
+                .unwrap_or_default();
+        } else if let Some(summary) = line.strip_prefix("summary ") {
+            blame.summary = summary.to_string();
+        }
+    }
+    Ok(blame)
+}
+
+fn format_unix_date(timestamp: i64) -> String {
+    let days = timestamp.div_euclid(86_400);
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vcs::git::tests::temp_repo;
+    use std::fs;
+
+    fn numbered(changed: &[usize]) -> String {
+        (1..=20)
+            .map(|n| {
+                if changed.contains(&n) {
+                    format!("ubah {}\n", n)
+                } else {
+                    format!("baris {}\n", n)
+                }
+            })
+            .collect()
+    }
+
+    fn diff_for(root: &Path, staged: bool) -> FileDiff {
+        let files = changed_files(root).unwrap();
+        let file = files.iter().find(|file| file.path == "a.txt").unwrap();
+        file_diff(root, file, staged).unwrap()
+    }
+
+    #[test]
+    fn single_hunk_is_staged_and_unstaged() {
+        let root = temp_repo("git-ops");
+        fs::write(root.join("a.txt"), numbered(&[])).unwrap();
+        run_git(&root, &["add", "a.txt"]).unwrap();
+        run_git(
+            &root,
+            &[
+                "-c",
+                "user.name=t",
+                "-c",
+                "user.email=t@t",
+                "commit",
+                "-qm",
+                "n",
+            ],
+        )
+        .unwrap();
+        fs::write(root.join("a.txt"), numbered(&[2, 18])).unwrap();
+
+        let unstaged = diff_for(&root, false);
+        assert_eq!(unstaged.header[0], "diff --git a/a.txt b/a.txt");
+        assert_eq!(unstaged.hunks.len(), 2);
+        assert_eq!(
+            unstaged.hunk_line_offset(1),
+            unstaged.hunks[0].lines.len() + 1
+        );
+        let patch = unstaged.hunk_patch(0).unwrap();
+        assert!(patch.contains("+ubah 2\n") && !patch.contains("ubah 18"));
+
+        stage_hunk(&root, &unstaged, 0).unwrap();
+        let staged = diff_for(&root, true);
+        assert_eq!(staged.hunks.len(), 1);
+        assert!(staged.hunks[0].lines.contains(&String::from("+ubah 2")));
+        let unstaged = diff_for(&root, false);
+        assert_eq!(unstaged.hunks.len(), 1);
+        assert!(unstaged.hunks[0].lines.contains(&String::from("+ubah 18")));
+
+        unstage_hunk(&root, &staged, 0).unwrap();
+        assert!(diff_for(&root, true).hunks.is_empty());
+        assert_eq!(diff_for(&root, false).hunks.len(), 2);
+        assert!(stage_hunk(&root, &staged, 5).is_err());
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn blame_porcelain_is_parsed() {
+        let output = "\
+3f2a9c1d5e7b8a9c0d1e2f3a4b5c6d7e8f9a0b1c 4 4 1
+author Sinta Dewi
+author-mail <sinta@example.com>
+author-time 1700000000
+author-tz +0700
+committer Sinta Dewi
+committer-time 1700000000
+summary Perbaiki parser
+filename src/main.rs
+\tfn main() {}
+";
+        let blame = parse_blame(output).unwrap();
+        assert_eq!(blame.commit, "3f2a9c1d5e7b8a9c0d1e2f3a4b5c6d7e8f9a0b1c");
+        assert_eq!(blame.author, "Sinta Dewi");
+        assert_eq!(blame.date, "2023-11-14");
+        assert_eq!(blame.summary, "Perbaiki parser");
+        assert_eq!(
+            blame.describe(),
+            "3f2a9c1d Sinta Dewi 2023-11-14 - Perbaiki parser"
+        );
+
+        let uncommitted = parse_blame(&format!(
+            "{} 1 1 1\nauthor Not Committed Yet\n",
+            "0".repeat(40)
+        ))
+        .unwrap();
+        assert!(uncommitted.is_uncommitted());
+        assert!(parse_blame("").is_err());
+    }
+}
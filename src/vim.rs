@@ -0,0 +1,1733 @@
+use std::collections::HashMap;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::editor::Editor;
+use crate::syntax::indent::INDENT;
+
+// Tombol non-karakter dikodekan sebagai karakter kontrol supaya urutan perintah (untuk `.`)
+// cukup disimpan sebagai `String`.
+const ESC: char = '\x1b';
+const REDO: char = '\x12';
+const BACKSPACE: char = '\x08';
+const DELETE: char = '\x7f';
+
+/// Posisi (baris, kolom byte).
+type Pos = (usize, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VimMode {
+    #[default]
+    Normal,
+    Insert,
+    Visual,
+    VisualLine,
+}
+
+impl VimMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Normal => "NORMAL",
+            Self::Insert => "INSERT",
+            Self::Visual => "VISUAL",
+            Self::VisualLine => "VISUAL LINE",
+        }
+    }
+
+    fn is_visual(self) -> bool {
+        matches!(self, Self::Visual | Self::VisualLine)
+    }
+}
+
+/// Perintah ex yang butuh state aplikasi (simpan file, tutup tab, keluar).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExCommand {
+    Write(Option<String>),
+    WriteQuit,
+    Quit { force: bool },
+    QuitAll { force: bool },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VimOutcome {
+    Done,
+    Status(String),
+    Ex(ExCommand),
+}
+
+#[derive(Debug, Clone, Default)]
+struct Register {
+    text: String,
+    linewise: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Delete,
+    Change,
+    Yank,
+    Indent,
+    Outdent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Motion {
+    Left,
+    Right,
+    Up,
+    Down,
+    LineStart,
+    FirstNonBlank,
+    LineEnd,
+    WordForward(bool),
+    WordBackward(bool),
+    WordEnd(bool),
+    Find { ch: char, forward: bool, till: bool },
+    RepeatFind { reverse: bool },
+    FileStart,
+    FileEnd,
+    ParagraphUp,
+    ParagraphDown,
+    MatchPair,
+    SearchNext { reverse: bool },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MotionKind {
+    Exclusive,
+    Inclusive,
+    Linewise,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target {
+    Line,
+    Motion(Motion),
+    Object { kind: char, inner: bool },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Move(Motion),
+    Operate(Operator, Target),
+    /// Operator pada seleksi visual.
+    Selection(Operator),
+    /// `i(`/`aw`/... di mode visual.
+    SelectObject {
+        kind: char,
+        inner: bool,
+    },
+    Replace(char),
+    Simple(char),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Command {
+    register: Option<char>,
+    count: Option<usize>,
+    action: Action,
+}
+
+enum Parse<T> {
+    Incomplete,
+    Invalid,
+    Done(T),
+}
+
+/// Rentang teks; `end` eksklusif, kecuali `linewise` yang mencakup baris `start.0..=end.0`.
+#[derive(Debug, Clone, Copy)]
+struct Range {
+    start: Pos,
+    end: Pos,
+    linewise: bool,
+}
+
+/// Emulasi Vim di atas primitif `Editor`: mode, operator + motion, text object, count,
+/// register, `.` repeat, pencarian `/` dan perintah ex.
+#[derive(Debug, Default)]
+pub struct VimState {
+    mode: VimMode,
+    pending: String,
+    visual_anchor: Pos,
+    registers: HashMap<char, Register>,
+    last_find: Option<(char, bool, bool)>,
+    last_search: Option<(String, bool)>,
+    last_change: String,
+    insert_record: Option<String>,
+    command_line: Option<String>,
+    replaying: bool,
+}
+
+impl VimState {
+    /// Teks untuk status bar: baris perintah yang sedang diketik, atau mode plus tombol tertunda.
+    pub fn status_label(&self) -> String {
+        if let Some(line) = self.command_line.as_ref() {
+            return line.clone();
+        }
+        if self.pending.is_empty() {
+            self.mode.label().to_string()
+        } else {
+            format!("{} {}", self.mode.label(), self.pending)
+        }
+    }
+
+    /// Kembali ke mode normal tanpa seleksi (dipakai saat Vim dimatikan).
+    pub fn reset(&mut self, editor: &mut Editor) {
+        self.mode = VimMode::Normal;
+        self.pending.clear();
+        self.command_line = None;
+        self.insert_record = None;
+        editor.clear_selection();
+    }
+
+    /// Terjemahkan tombol ke input Vim; `None` berarti tombol diproses seperti editor biasa.
+    pub fn key_char(&self, key: KeyEvent) -> Option<char> {
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+        if key.modifiers.contains(KeyModifiers::ALT) {
+            return None;
+        }
+        if self.command_line.is_some() {
+            return match key.code {
+                KeyCode::Esc => Some(ESC),
+                KeyCode::Enter => Some('\n'),
+                KeyCode::Backspace => Some(BACKSPACE),
+                KeyCode::Char(ch) if !control => Some(ch),
+                _ => None,
+            };
+        }
+        let ch = match (self.mode, key.code) {
+            (_, KeyCode::Esc) => ESC,
+            (_, KeyCode::Char('[')) if control => ESC,
+            (_, _) if control && self.mode == VimMode::Insert => return None,
+            (VimMode::Insert, KeyCode::Char(ch)) => ch,
+            (VimMode::Insert, KeyCode::Enter) => '\n',
+            (VimMode::Insert, KeyCode::Backspace) => BACKSPACE,
+            (VimMode::Insert, KeyCode::Delete) => DELETE,
+            (VimMode::Insert, KeyCode::Tab) => '\t',
+            (VimMode::Insert, _) => return None,
+            (_, KeyCode::Char('r')) if control => REDO,
+            (_, _) if control => return None,
+            (_, KeyCode::Char(ch)) => ch,
+            (_, KeyCode::Left | KeyCode::Backspace) => 'h',
+            (_, KeyCode::Right) => 'l',
+            (_, KeyCode::Up) => 'k',
+            (_, KeyCode::Down | KeyCode::Enter) => 'j',
+            (_, KeyCode::Home) => '0',
+            (_, KeyCode::End) => '$',
+            (_, KeyCode::Delete) => 'x',
+            // Tab di mode normal tidak punya arti; cukup membatalkan tombol tertunda.
+            (_, KeyCode::Tab | KeyCode::BackTab) => '\t',
+            _ => return None,
+        };
+        Some(ch)
+    }
+
+    pub fn feed(&mut self, ch: char, editor: &mut Editor) -> VimOutcome {
+        if let Some(line) = self.command_line.as_mut() {
+            match ch {
+                ESC => self.command_line = None,
+                '\n' => {
+                    let line = self.command_line.take().unwrap_or_default();
+                    return self.run_command_line(&line, editor);
+                }
+                BACKSPACE => {
+                    line.pop();
+                    if line.is_empty() {
+                        self.command_line = None;
+                    }
+                }
+                ch => line.push(ch),
+            }
+            return VimOutcome::Done;
+        }
+        if self.mode == VimMode::Insert {
+            self.insert_key(ch, editor);
+            return VimOutcome::Done;
+        }
+        if ch == ESC {
+            self.pending.clear();
+            if self.mode.is_visual() {
+                self.mode = VimMode::Normal;
+                editor.clear_selection();
+                self.clamp_cursor(editor);
+            }
+            return VimOutcome::Done;
+        }
+
+        self.pending.push(ch);
+        let keys = self.pending.chars().collect::<Vec<_>>();
+        let command = match parse(&keys, self.mode.is_visual()) {
+            Parse::Incomplete => return VimOutcome::Done,
+            Parse::Invalid => {
+                self.pending.clear();
+                return VimOutcome::Done;
+            }
+            Parse::Done(command) => command,
+        };
+        let keys = std::mem::take(&mut self.pending);
+
+        // Undo/redo tidak boleh berada di dalam grup undo.
+        let grouped = !self.replaying && !matches!(command.action, Action::Simple('u' | REDO));
+        if grouped {
+            editor.mark_undo();
+            editor.begin_undo_group();
+        }
+        let visual = self.mode.is_visual();
+        let outcome = self.execute(command, editor);
+        if grouped {
+            editor.end_undo_group();
+            // Tanda hanya dipakai bila perintah masuk insert; selain itu grupnya sudah satu langkah.
+            if self.mode != VimMode::Insert {
+                editor.merge_undo_since_mark();
+            }
+        }
+        if !self.replaying && !visual && is_change(command.action) {
+            if self.mode == VimMode::Insert {
+                self.insert_record = Some(keys);
+            } else {
+                self.last_change = keys;
+            }
+        }
+        match self.mode {
+            VimMode::Normal => self.clamp_cursor(editor),
+            VimMode::Visual | VimMode::VisualLine => self.sync_visual(editor),
+            VimMode::Insert => {}
+        }
+        outcome
+    }
+
+    fn insert_key(&mut self, ch: char, editor: &mut Editor) {
+        if let Some(record) = self.insert_record.as_mut() {
+            record.push(ch);
+        }
+        match ch {
+            ESC => {
+                self.mode = VimMode::Normal;
+                // Perintah yang masuk insert dan semua ketikan sampai Esc menjadi satu langkah undo.
+                if !self.replaying {
+                    editor.merge_undo_since_mark();
+                }
+                if let Some(record) = self.insert_record.take() {
+                    self.last_change = record;
+                }
+                let (row, col) = cursor(editor);
+                editor.set_cursor(row, prev_boundary(&editor.lines()[row], col));
+            }
+            '\n' => editor.insert_newline_smart(),
+            BACKSPACE => editor.backspace(),
+            DELETE => editor.delete_forward(),
+            '\t' => editor.insert_text(INDENT),
+            ch => editor.type_char_smart(ch),
+        }
+    }
+
+    fn execute(&mut self, command: Command, editor: &mut Editor) -> VimOutcome {
+        let count = command.count.unwrap_or(1);
+        match command.action {
+            Action::Move(motion) => {
+                let cursor = cursor(editor);
+                match self.motion_target(editor.lines(), cursor, motion, command.count) {
+                    Some((target, _)) => editor.set_cursor(target.0, target.1),
+                    None if matches!(motion, Motion::SearchNext { .. }) => {
+                        return self.search_failed();
+                    }
+                    None => {}
+                }
+            }
+            Action::Operate(operator, target) => {
+                if let Some(range) = self.target_range(editor, operator, target, command.count) {
+                    return self.apply(operator, range, command.register, editor);
+                }
+            }
+            Action::Selection(operator) => {
+                let range = self.visual_range(editor);
+                self.mode = VimMode::Normal;
+                editor.clear_selection();
+                return self.apply(operator, range, command.register, editor);
+            }
+            Action::SelectObject { kind, inner } => {
+                if let Some(range) = object_range(editor.lines(), cursor(editor), kind, inner)
+                    && range.end != range.start
+                {
+                    let lines = editor.lines();
+                    self.visual_anchor = range.start;
+                    let end = if range.linewise {
+                        (range.end.0, lines[range.end.0].len())
+                    } else {
+                        prev_pos(lines, range.end).unwrap_or(range.end)
+                    };
+                    editor.set_cursor(end.0, end.1);
+                }
+            }
+            Action::Replace(ch) => {
+                let (row, col) = cursor(editor);
+                let line = &editor.lines()[row];
+                let end = (0..count).try_fold(col, |col, _| {
+                    (col < line.len()).then(|| next_boundary(line, col))
+                });
+                if let Some(end) = end {
+                    editor.set_selection(row, col, row, end);
+                    editor.insert_text(&ch.to_string().repeat(count));
+                    let col = editor.cursor_x();
+                    editor.set_cursor(row, prev_boundary(&editor.lines()[row], col));
+                }
+            }
+            Action::Simple(key) => return self.simple(key, command, editor),
+        }
+        VimOutcome::Done
+    }
+
+    fn simple(&mut self, key: char, command: Command, editor: &mut Editor) -> VimOutcome {
+        let count = command.count.unwrap_or(1);
+        let (row, col) = cursor(editor);
+        let line = editor.lines()[row].clone();
+        if self.mode.is_visual() && "oJ~uU".contains(key) {
+            self.visual_key(key, editor);
+            return VimOutcome::Done;
+        }
+        match key {
+            'i' => self.mode = VimMode::Insert,
+            'a' => {
+                editor.set_cursor(row, next_boundary(&line, col));
+                self.mode = VimMode::Insert;
+            }
+            'I' => {
+                editor.set_cursor(row, first_non_blank(&line));
+                self.mode = VimMode::Insert;
+            }
+            'A' => {
+                editor.set_cursor(row, line.len());
+                self.mode = VimMode::Insert;
+            }
+            'o' => {
+                editor.set_cursor(row, line.len());
+                editor.insert_newline_smart();
+                self.mode = VimMode::Insert;
+            }
+            'O' => {
+                let indent = &line[..first_non_blank(&line)];
+                editor.set_cursor(row, 0);
+                editor.insert_text(&format!("{}\n", indent));
+                editor.set_cursor(row, indent.len());
+                self.mode = VimMode::Insert;
+            }
+            'p' | 'P' => return self.paste(key == 'p', command, editor),
+            'u' | REDO => {
+                let changed = (0..count)
+                    .take_while(|_| {
+                        if key == 'u' {
+                            editor.undo()
+                        } else {
+                            editor.redo()
+                        }
+                    })
+                    .count();
+                editor.clear_selection();
+                if changed == 0 {
+                    return VimOutcome::Status(String::from(if key == 'u' {
+                        "Tidak ada yang bisa di-undo"
+                    } else {
+                        "Tidak ada yang bisa di-redo"
+                    }));
+                }
+            }
+            'J' => {
+                let last = (row + count.max(2) - 1).min(editor.lines().len() - 1);
+                if last > row {
+                    editor.set_selection(row, 0, last, editor.lines()[last].len());
+                }
+                editor.join_lines();
+            }
+            '~' => {
+                let end = (0..count).fold(col, |col, _| next_boundary(&line, col));
+                if end > col {
+                    let toggled = line[col..end]
+                        .chars()
+                        .map(|ch| {
+                            if ch.is_uppercase() {
+                                ch.to_lowercase().collect::<String>()
+                            } else {
+                                ch.to_uppercase().collect::<String>()
+                            }
+                        })
+                        .collect::<String>();
+                    editor.set_selection(row, col, row, end);
+                    editor.insert_text(&toggled);
+                }
+            }
+            '.' => return self.repeat(command.count, editor),
+            'v' | 'V' => {
+                let mode = if key == 'v' {
+                    VimMode::Visual
+                } else {
+                    VimMode::VisualLine
+                };
+                if self.mode == mode {
+                    self.mode = VimMode::Normal;
+                    editor.clear_selection();
+                } else {
+                    if !self.mode.is_visual() {
+                        self.visual_anchor = (row, col);
+                    }
+                    self.mode = mode;
+                }
+            }
+            ':' | '/' | '?' => {
+                if self.mode.is_visual() {
+                    self.mode = VimMode::Normal;
+                    editor.clear_selection();
+                }
+                self.command_line = Some(key.to_string());
+            }
+            '*' | '#' => {
+                let Some(word) = word_under_cursor(&line, col) else {
+                    return VimOutcome::Status(String::from("Tidak ada kata di bawah cursor"));
+                };
+                self.last_search = Some((word, key == '*'));
+                let target = self.motion_target(
+                    editor.lines(),
+                    (row, col),
+                    Motion::SearchNext { reverse: false },
+                    None,
+                );
+                match target {
+                    Some((target, _)) => editor.set_cursor(target.0, target.1),
+                    None => return self.search_failed(),
+                }
+            }
+            'Z' => return VimOutcome::Ex(ExCommand::WriteQuit),
+            'Q' => return VimOutcome::Ex(ExCommand::Quit { force: true }),
+            _ => {}
+        }
+        VimOutcome::Done
+    }
+
+    fn visual_key(&mut self, key: char, editor: &mut Editor) {
+        match key {
+            'o' => {
+                let (row, col) = cursor(editor);
+                let anchor = std::mem::replace(&mut self.visual_anchor, (row, col));
+                editor.set_cursor(anchor.0, anchor.1);
+            }
+            'J' => {
+                let range = self.visual_range(editor);
+                let (start, end) = (range.start.0, range.end.0.max(range.start.0 + 1));
+                let end = end.min(editor.lines().len() - 1);
+                editor.set_selection(start, 0, end, editor.lines()[end].len());
+                editor.join_lines();
+                self.mode = VimMode::Normal;
+            }
+            '~' | 'u' | 'U' => {
+                let range = self.visual_range(editor);
+                let text = range_text(editor.lines(), range);
+                let changed = text
+                    .chars()
+                    .map(|ch| match key {
+                        'u' => ch.to_lowercase().collect::<String>(),
+                        'U' => ch.to_uppercase().collect::<String>(),
+                        _ if ch.is_uppercase() => ch.to_lowercase().collect(),
+                        _ => ch.to_uppercase().collect(),
+                    })
+                    .collect::<String>();
+                let (start, end) = selection_bounds(editor.lines(), range);
+                editor.set_selection(start.0, start.1, end.0, end.1);
+                editor.insert_text(&changed);
+                editor.set_cursor(
+                    range.start.0,
+                    if range.linewise { 0 } else { range.start.1 },
+                );
+                self.mode = VimMode::Normal;
+            }
+            _ => {}
+        }
+    }
+
+    fn paste(&mut self, after: bool, command: Command, editor: &mut Editor) -> VimOutcome {
+        let name = command.register.unwrap_or('"').to_ascii_lowercase();
+        let Some(register) = self.registers.get(&name).cloned() else {
+            return VimOutcome::Status(format!("Register \"{} kosong", name));
+        };
+        let count = command.count.unwrap_or(1);
+
+        if self.mode.is_visual() {
+            let range = self.visual_range(editor);
+            self.mode = VimMode::Normal;
+            let (start, end) = selection_bounds(editor.lines(), range);
+            editor.set_selection(start.0, start.1, end.0, end.1);
+            editor.insert_text(&register.text);
+            return VimOutcome::Done;
+        }
+
+        let (row, col) = cursor(editor);
+        if register.linewise {
+            let text = vec![register.text.as_str(); count].join("\n");
+            let target = if after {
+                editor.set_cursor(row, editor.lines()[row].len());
+                editor.insert_text(&format!("\n{}", text));
+                row + 1
+            } else {
+                editor.set_cursor(row, 0);
+                editor.insert_text(&format!("{}\n", text));
+                row
+            };
+            let indent = first_non_blank(&editor.lines()[target]);
+            editor.set_cursor(target, indent);
+        } else {
+            let line = &editor.lines()[row];
+            let at = if after && !line.is_empty() {
+                next_boundary(line, col)
+            } else {
+                col
+            };
+            editor.set_cursor(row, at);
+            editor.insert_text(&register.text.repeat(count));
+            let (row, col) = cursor(editor);
+            editor.set_cursor(row, prev_boundary(&editor.lines()[row], col));
+        }
+        VimOutcome::Done
+    }
+
+    fn repeat(&mut self, count: Option<usize>, editor: &mut Editor) -> VimOutcome {
+        if self.last_change.is_empty() {
+            return VimOutcome::Status(String::from("Belum ada perubahan untuk diulang"));
+        }
+        let mut keys = self.last_change.chars().collect::<Vec<_>>();
+        if let Some(count) = count {
+            let start = if keys.first() == Some(&'"') { 2 } else { 0 };
+            let digits = keys
+                .iter()
+                .skip(start)
+                .take_while(|ch| ch.is_ascii_digit())
+                .count();
+            keys.splice(start..start + digits, count.to_string().chars());
+        }
+        self.replaying = true;
+        for key in keys {
+            self.feed(key, editor);
+        }
+        self.replaying = false;
+        VimOutcome::Done
+    }
+
+    fn apply(
+        &mut self,
+        operator: Operator,
+        range: Range,
+        register: Option<char>,
+        editor: &mut Editor,
+    ) -> VimOutcome {
+        let lines = editor.lines();
+        let last_row = lines.len() - 1;
+        match operator {
+            Operator::Yank => {
+                let text = range_text(lines, range);
+                let line_count = range.end.0 - range.start.0 + 1;
+                self.store(register, text, range.linewise, true);
+                if !range.linewise {
+                    editor.set_cursor(range.start.0, range.start.1);
+                } else if editor.cursor_y() != range.start.0 {
+                    editor.set_cursor(range.start.0, editor.cursor_x());
+                }
+                if range.linewise && line_count > 2 {
+                    return VimOutcome::Status(format!("{} baris di-yank", line_count));
+                }
+            }
+            Operator::Delete => {
+                let text = range_text(lines, range);
+                self.store(register, text, range.linewise, false);
+                if range.linewise {
+                    let (first, last) = (range.start.0, range.end.0);
+                    if last < last_row {
+                        editor.set_selection(first, 0, last + 1, 0);
+                    } else if first > 0 {
+                        let len = lines[first - 1].len();
+                        editor.set_selection(first - 1, len, last, lines[last].len());
+                    } else {
+                        editor.set_selection(0, 0, last, lines[last].len());
+                    }
+                    editor.delete_forward();
+                    let row = first.min(editor.lines().len() - 1);
+                    let indent = first_non_blank(&editor.lines()[row]);
+                    editor.set_cursor(row, indent);
+                } else if range.start != range.end {
+                    editor.set_selection(range.start.0, range.start.1, range.end.0, range.end.1);
+                    editor.delete_forward();
+                }
+            }
+            Operator::Change => {
+                let text = range_text(lines, range);
+                self.store(register, text, range.linewise, false);
+                let (start, end) = if range.linewise {
+                    let (first, last) = (range.start.0, range.end.0);
+                    (
+                        (first, first_non_blank(&lines[first])),
+                        (last, lines[last].len()),
+                    )
+                } else {
+                    (range.start, range.end)
+                };
+                if start != end {
+                    editor.set_selection(start.0, start.1, end.0, end.1);
+                    editor.delete_forward();
+                } else {
+                    editor.set_cursor(start.0, start.1);
+                }
+                self.mode = VimMode::Insert;
+            }
+            Operator::Indent | Operator::Outdent => {
+                let (first, last) = (range.start.0, range.end.0);
+                if last > first {
+                    editor.set_selection(first, 0, last, lines[last].len());
+                } else {
+                    editor.set_cursor(first, 0);
+                }
+                if operator == Operator::Indent {
+                    editor.indent_lines();
+                } else {
+                    editor.outdent_lines();
+                }
+                editor.clear_selection();
+                let indent = first_non_blank(&editor.lines()[first]);
+                editor.set_cursor(first, indent);
+            }
+        }
+        VimOutcome::Done
+    }
+
+    fn store(&mut self, register: Option<char>, text: String, linewise: bool, yank: bool) {
+        let mut value = Register { text, linewise };
+        match register {
+            Some('_') => return,
+            Some(name) if name.is_ascii_uppercase() => {
+                let entry = self.registers.entry(name.to_ascii_lowercase()).or_default();
+                if entry.linewise || linewise {
+                    entry.text.push('\n');
+                }
+                entry.text.push_str(&value.text);
+                entry.linewise |= linewise;
+                value = entry.clone();
+            }
+            Some(name) if name != '"' => {
+                self.registers.insert(name, value.clone());
+            }
+            _ if yank => {
+                self.registers.insert('0', value.clone());
+            }
+            _ => {}
+        }
+        self.registers.insert('"', value);
+    }
+
+    fn target_range(
+        &mut self,
+        editor: &Editor,
+        operator: Operator,
+        target: Target,
+        count: Option<usize>,
+    ) -> Option<Range> {
+        let lines = editor.lines();
+        let cursor = cursor(editor);
+        let motion = match target {
+            Target::Line => {
+                let last = (cursor.0 + count.unwrap_or(1) - 1).min(lines.len() - 1);
+                return Some(Range {
+                    start: (cursor.0, 0),
+                    end: (last, 0),
+                    linewise: true,
+                });
+            }
+            Target::Object { kind, inner } => return object_range(lines, cursor, kind, inner),
+            Target::Motion(motion) => motion,
+        };
+
+        // `cw` berperilaku seperti `ce` bila cursor berada di atas kata.
+        if operator == Operator::Change
+            && let Motion::WordForward(big) = motion
+            && !char_at(lines, cursor).is_whitespace()
+        {
+            let class = |pos: Pos| class_of(char_at(lines, pos), big);
+            let mut end = cursor;
+            for step in 0..count.unwrap_or(1) {
+                let at_word_end = next_pos(lines, end).is_none_or(|next| class(next) != class(end));
+                if step > 0 || !at_word_end {
+                    end = word_end(lines, end, big);
+                }
+            }
+            return Some(Range {
+                start: cursor,
+                end: (end.0, next_boundary(&lines[end.0], end.1)),
+                linewise: false,
+            });
+        }
+
+        let (target, kind) = self.motion_target(lines, cursor, motion, count)?;
+        let (start, end) = if target < cursor {
+            (target, cursor)
+        } else {
+            (cursor, target)
+        };
+        let range = match kind {
+            MotionKind::Linewise => Range {
+                start: (start.0, 0),
+                end: (end.0, 0),
+                linewise: true,
+            },
+            MotionKind::Inclusive => Range {
+                start,
+                end: (end.0, next_boundary(&lines[end.0], end.1)),
+                linewise: false,
+            },
+            // Motion eksklusif yang berakhir sebelum teks pertama sebuah baris berhenti di
+            // akhir baris sebelumnya (`dw` di kata terakhir, `d}`).
+            MotionKind::Exclusive if end.0 > start.0 && lines[end.0][..end.1].trim().is_empty() => {
+                Range {
+                    start,
+                    end: (end.0 - 1, lines[end.0 - 1].len()),
+                    linewise: false,
+                }
+            }
+            MotionKind::Exclusive => Range {
+                start,
+                end,
+                linewise: false,
+            },
+        };
+        Some(range)
+    }
+
+    fn motion_target(
+        &mut self,
+        lines: &[String],
+        cursor: Pos,
+        motion: Motion,
+        count: Option<usize>,
+    ) -> Option<(Pos, MotionKind)> {
+        let times = count.unwrap_or(1);
+        let (row, col) = cursor;
+        let line = &lines[row];
+        let last_row = lines.len() - 1;
+        let repeat = |step: &dyn Fn(Pos) -> Pos| (0..times).fold(cursor, |pos, _| step(pos));
+        let target = match motion {
+            Motion::Left => (
+                (row, (0..times).fold(col, |col, _| prev_boundary(line, col))),
+                MotionKind::Exclusive,
+            ),
+            Motion::Right => (
+                (row, (0..times).fold(col, |col, _| next_boundary(line, col))),
+                MotionKind::Exclusive,
+            ),
+            Motion::Up => {
+                let row = row.saturating_sub(times);
+                ((row, col.min(lines[row].len())), MotionKind::Linewise)
+            }
+            Motion::Down => {
+                let row = (row + times).min(last_row);
+                ((row, col.min(lines[row].len())), MotionKind::Linewise)
+            }
+            Motion::LineStart => ((row, 0), MotionKind::Exclusive),
+            Motion::FirstNonBlank => ((row, first_non_blank(line)), MotionKind::Exclusive),
+            Motion::LineEnd => {
+                let row = (row + times - 1).min(last_row);
+                ((row, lines[row].len()), MotionKind::Exclusive)
+            }
+            Motion::WordForward(big) => (
+                repeat(&|pos| word_forward(lines, pos, big)),
+                MotionKind::Exclusive,
+            ),
+            Motion::WordBackward(big) => (
+                repeat(&|pos| word_backward(lines, pos, big)),
+                MotionKind::Exclusive,
+            ),
+            Motion::WordEnd(big) => (
+                repeat(&|pos| word_end(lines, pos, big)),
+                MotionKind::Inclusive,
+            ),
+            Motion::Find { ch, forward, till } => {
+                self.last_find = Some((ch, forward, till));
+                let (col, kind) = find_in_line(line, col, ch, forward, till, times)?;
+                ((row, col), kind)
+            }
+            Motion::RepeatFind { reverse } => {
+                let (ch, forward, till) = self.last_find?;
+                let (col, kind) = find_in_line(line, col, ch, forward != reverse, till, times)?;
+                ((row, col), kind)
+            }
+            Motion::FileStart | Motion::FileEnd => {
+                let row = match count {
+                    Some(count) => count.saturating_sub(1).min(last_row),
+                    None if motion == Motion::FileStart => 0,
+                    None => last_row,
+                };
+                ((row, first_non_blank(&lines[row])), MotionKind::Linewise)
+            }
+            Motion::ParagraphUp => {
+                let row = (0..times).fold(row, |row, _| paragraph(lines, row, false));
+                ((row, 0), MotionKind::Exclusive)
+            }
+            Motion::ParagraphDown => {
+                let row = (0..times).fold(row, |row, _| paragraph(lines, row, true));
+                let col = if lines[row].trim().is_empty() {
+                    0
+                } else {
+                    lines[row].len()
+                };
+                ((row, col), MotionKind::Exclusive)
+            }
+            Motion::MatchPair => (match_pair(lines, cursor)?, MotionKind::Inclusive),
+            Motion::SearchNext { reverse } => {
+                let (pattern, forward) = self.last_search.as_ref()?;
+                let forward = *forward != reverse;
+                let target =
+                    (0..times).try_fold(cursor, |pos, _| search(lines, pos, pattern, forward))?;
+                (target, MotionKind::Exclusive)
+            }
+        };
+        Some(target)
+    }
+
+    fn visual_range(&self, editor: &Editor) -> Range {
+        let lines = editor.lines();
+        let cursor = cursor(editor);
+        let (start, end) = if cursor < self.visual_anchor {
+            (cursor, self.visual_anchor)
+        } else {
+            (self.visual_anchor, cursor)
+        };
+        if self.mode == VimMode::VisualLine {
+            Range {
+                start: (start.0, 0),
+                end: (end.0, 0),
+                linewise: true,
+            }
+        } else {
+            Range {
+                start,
+                end: (end.0, next_boundary(&lines[end.0], end.1)),
+                linewise: false,
+            }
+        }
+    }
+
+    // Seleksi `Editor` eksklusif, sedangkan seleksi visual Vim mencakup karakter di bawah
+    // cursor; anchor digeser supaya sorotan sama dengan yang akan dioperasikan.
+    fn sync_visual(&self, editor: &mut Editor) {
+        let lines = editor.lines();
+        let cursor = cursor(editor);
+        let anchor = self.visual_anchor;
+        let anchor_row = anchor.0.min(lines.len() - 1);
+        let anchor = match self.mode {
+            VimMode::VisualLine if cursor.0 >= anchor_row => (anchor_row, 0),
+            VimMode::VisualLine => (anchor_row, lines[anchor_row].len()),
+            _ if cursor >= anchor => anchor,
+            _ => (anchor_row, next_boundary(&lines[anchor_row], anchor.1)),
+        };
+        editor.set_selection(anchor.0, anchor.1, cursor.0, cursor.1);
+    }
+
+    // Di mode normal cursor tidak boleh berada di belakang karakter terakhir.
+    fn clamp_cursor(&self, editor: &mut Editor) {
+        let (row, col) = cursor(editor);
+        let line = &editor.lines()[row];
+        if col >= line.len() && !line.is_empty() {
+            editor.set_cursor(row, prev_boundary(line, line.len()));
+        }
+    }
+
+    fn run_command_line(&mut self, line: &str, editor: &mut Editor) -> VimOutcome {
+        let mut chars = line.chars();
+        let prefix = chars.next();
+        let body = chars.as_str();
+        match prefix {
+            Some(prefix @ ('/' | '?')) => {
+                let forward = prefix == '/';
+                if !body.is_empty() {
+                    self.last_search = Some((body.to_string(), forward));
+                } else if let Some((_, direction)) = self.last_search.as_mut() {
+                    *direction = forward;
+                }
+                let target = self.motion_target(
+                    editor.lines(),
+                    cursor(editor),
+                    Motion::SearchNext { reverse: false },
+                    None,
+                );
+                match target {
+                    Some((target, _)) => {
+                        editor.set_cursor(target.0, target.1);
+                        VimOutcome::Done
+                    }
+                    None => self.search_failed(),
+                }
+            }
+            _ => self.ex(body.trim(), editor),
+        }
+    }
+
+    fn ex(&mut self, command: &str, editor: &mut Editor) -> VimOutcome {
+        match command {
+            "" => VimOutcome::Done,
+            "w" => VimOutcome::Ex(ExCommand::Write(None)),
+            "wq" | "x" => VimOutcome::Ex(ExCommand::WriteQuit),
+            "q" => VimOutcome::Ex(ExCommand::Quit { force: false }),
+            "q!" => VimOutcome::Ex(ExCommand::Quit { force: true }),
+            "qa" | "qall" => VimOutcome::Ex(ExCommand::QuitAll { force: false }),
+            "qa!" | "qall!" => VimOutcome::Ex(ExCommand::QuitAll { force: true }),
+            _ if command.starts_with("w ") => {
+                VimOutcome::Ex(ExCommand::Write(Some(command[2..].trim().to_string())))
+            }
+            _ => match command.parse::<usize>() {
+                Ok(line) => {
+                    let row = line.saturating_sub(1).min(editor.lines().len() - 1);
+                    let indent = first_non_blank(&editor.lines()[row]);
+                    editor.set_cursor(row, indent);
+                    VimOutcome::Done
+                }
+                Err(_) => VimOutcome::Status(format!("Perintah tidak dikenal: :{}", command)),
+            },
+        }
+    }
+
+    fn search_failed(&self) -> VimOutcome {
+        match self.last_search.as_ref() {
+            Some((pattern, _)) => VimOutcome::Status(format!("Pola tidak ditemukan: {}", pattern)),
+            None => VimOutcome::Status(String::from("Belum ada pola pencarian")),
+        }
+    }
+}
+
+fn is_change(action: Action) -> bool {
+    match action {
+        Action::Operate(operator, _) => operator != Operator::Yank,
+        Action::Replace(_) => true,
+        Action::Simple(key) => "iaIAoOpPJ~".contains(key),
+        _ => false,
+    }
+}
+
+fn parse(keys: &[char], visual: bool) -> Parse<Command> {
+    let mut idx = 0;
+    let mut register = None;
+    if keys.first() == Some(&'"') {
+        let Some(&name) = keys.get(1) else {
+            return Parse::Incomplete;
+        };
+        if !(name.is_ascii_alphanumeric() || name == '"' || name == '_') {
+            return Parse::Invalid;
+        }
+        register = Some(name);
+        idx = 2;
+    }
+    let (count, next) = parse_count(keys, idx);
+    let Some(&key) = keys.get(next) else {
+        return Parse::Incomplete;
+    };
+    let rest = &keys[next + 1..];
+    let done = |count: Option<usize>, action: Action| {
+        Parse::Done(Command {
+            register,
+            count,
+            action,
+        })
+    };
+
+    if visual {
+        let operator = match key {
+            'd' | 'x' => Some(Operator::Delete),
+            'c' | 's' => Some(Operator::Change),
+            'y' => Some(Operator::Yank),
+            '>' => Some(Operator::Indent),
+            '<' => Some(Operator::Outdent),
+            _ => None,
+        };
+        if let Some(operator) = operator {
+            return done(count, Action::Selection(operator));
+        }
+        if key == 'i' || key == 'a' {
+            return match rest.first() {
+                None => Parse::Incomplete,
+                Some(&kind) if is_object(kind) => done(
+                    count,
+                    Action::SelectObject {
+                        kind,
+                        inner: key == 'i',
+                    },
+                ),
+                Some(_) => Parse::Invalid,
+            };
+        }
+        if "oJ~uU".contains(key) {
+            return done(count, Action::Simple(key));
+        }
+    }
+
+    let operator = match key {
+        'd' => Some(Operator::Delete),
+        'c' => Some(Operator::Change),
+        'y' => Some(Operator::Yank),
+        '>' => Some(Operator::Indent),
+        '<' => Some(Operator::Outdent),
+        _ => None,
+    };
+    if let Some(operator) = operator {
+        if rest.first() == Some(&key) {
+            return done(count, Action::Operate(operator, Target::Line));
+        }
+        let (count2, next) = parse_count(rest, 0);
+        let count = match (count, count2) {
+            (None, None) => None,
+            (count, count2) => Some(count.unwrap_or(1) * count2.unwrap_or(1)),
+        };
+        let rest = &rest[next..];
+        return match rest.first() {
+            None => Parse::Incomplete,
+            Some('i' | 'a') => match rest.get(1) {
+                None => Parse::Incomplete,
+                Some(&kind) if is_object(kind) => done(
+                    count,
+                    Action::Operate(
+                        operator,
+                        Target::Object {
+                            kind,
+                            inner: rest[0] == 'i',
+                        },
+                    ),
+                ),
+                Some(_) => Parse::Invalid,
+            },
+            Some(_) => match parse_motion(rest) {
+                Parse::Done(motion) => {
+                    done(count, Action::Operate(operator, Target::Motion(motion)))
+                }
+                Parse::Incomplete => Parse::Incomplete,
+                Parse::Invalid => Parse::Invalid,
+            },
+        };
+    }
+
+    match parse_motion(&keys[next..]) {
+        Parse::Done(motion) => return done(count, Action::Move(motion)),
+        Parse::Incomplete => return Parse::Incomplete,
+        Parse::Invalid => {}
+    }
+
+    let shorthand =
+        |operator: Operator, target: Target| done(count, Action::Operate(operator, target));
+    match key {
+        'x' => shorthand(Operator::Delete, Target::Motion(Motion::Right)),
+        'X' => shorthand(Operator::Delete, Target::Motion(Motion::Left)),
+        'D' => shorthand(Operator::Delete, Target::Motion(Motion::LineEnd)),
+        'C' => shorthand(Operator::Change, Target::Motion(Motion::LineEnd)),
+        's' => shorthand(Operator::Change, Target::Motion(Motion::Right)),
+        'S' => shorthand(Operator::Change, Target::Line),
+        'Y' => shorthand(Operator::Yank, Target::Line),
+        'r' => match rest.first() {
+            None => Parse::Incomplete,
+            Some(&ch) if ch == ESC => Parse::Invalid,
+            Some(&ch) => done(count, Action::Replace(ch)),
+        },
+        'Z' => match rest.first() {
+            None => Parse::Incomplete,
+            Some('Z') => done(count, Action::Simple('Z')),
+            Some('Q') => done(count, Action::Simple('Q')),
+            Some(_) => Parse::Invalid,
+        },
+        'i' | 'a' | 'I' | 'A' | 'o' | 'O' | 'p' | 'P' | 'u' | REDO | 'J' | '~' | '.' | 'v'
+        | 'V' | ':' | '/' | '?' | '*' | '#' => done(count, Action::Simple(key)),
+        _ => Parse::Invalid,
+    }
+}
+
+// Count tidak boleh diawali `0` karena `0` sendiri adalah motion ke awal baris.
+fn parse_count(keys: &[char], start: usize) -> (Option<usize>, usize) {
+    let mut idx = start;
+    let mut count = None;
+    while let Some(digit) = keys.get(idx).and_then(|ch| ch.to_digit(10)) {
+        if digit == 0 && count.is_none() {
+            break;
+        }
+        count = Some(count.unwrap_or(0usize).saturating_mul(10) + digit as usize);
+        idx += 1;
+    }
+    (count, idx)
+}
+
+fn parse_motion(keys: &[char]) -> Parse<Motion> {
+    let Some(&key) = keys.first() else {
+        return Parse::Incomplete;
+    };
+    let motion = match key {
+        'h' => Motion::Left,
+        'l' | ' ' => Motion::Right,
+        'k' => Motion::Up,
+        'j' => Motion::Down,
+        '0' => Motion::LineStart,
+        '^' => Motion::FirstNonBlank,
+        '$' => Motion::LineEnd,
+        'w' | 'W' => Motion::WordForward(key == 'W'),
+        'b' | 'B' => Motion::WordBackward(key == 'B'),
+        'e' | 'E' => Motion::WordEnd(key == 'E'),
+        'f' | 'F' | 't' | 'T' => match keys.get(1) {
+            None => return Parse::Incomplete,
+            Some(&ESC) => return Parse::Invalid,
+            Some(&ch) => Motion::Find {
+                ch,
+                forward: key == 'f' || key == 't',
+                till: key == 't' || key == 'T',
+            },
+        },
+        ';' | ',' => Motion::RepeatFind {
+            reverse: key == ',',
+        },
+        'g' => match keys.get(1) {
+            None => return Parse::Incomplete,
+            Some('g') => Motion::FileStart,
+            Some(_) => return Parse::Invalid,
+        },
+        'G' => Motion::FileEnd,
+        '{' => Motion::ParagraphUp,
+        '}' => Motion::ParagraphDown,
+        '%' => Motion::MatchPair,
+        'n' | 'N' => Motion::SearchNext {
+            reverse: key == 'N',
+        },
+        _ => return Parse::Invalid,
+    };
+    Parse::Done(motion)
+}
+
+fn is_object(kind: char) -> bool {
+    "wW()b{}B[]<>\"'`".contains(kind)
+}
+
+fn cursor(editor: &Editor) -> Pos {
+    (editor.cursor_y(), editor.cursor_x())
+}
+
+fn next_boundary(line: &str, col: usize) -> usize {
+    line[col..]
+        .chars()
+        .next()
+        .map_or(col, |ch| col + ch.len_utf8())
+}
+
+fn prev_boundary(line: &str, col: usize) -> usize {
+    line[..col]
+        .chars()
+        .next_back()
+        .map_or(0, |ch| col - ch.len_utf8())
+}
+
+fn first_non_blank(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+// Akhir baris dianggap karakter `\n` supaya motion kata bisa melintasi baris.
+fn char_at(lines: &[String], (row, col): Pos) -> char {
+    lines[row][col..].chars().next().unwrap_or('\n')
+}
+
+fn next_pos(lines: &[String], (row, col): Pos) -> Option<Pos> {
+    let line = &lines[row];
+    if col < line.len() {
+        Some((row, next_boundary(line, col)))
+    } else if row + 1 < lines.len() {
+        Some((row + 1, 0))
+    } else {
+        None
+    }
+}
+
+fn prev_pos(lines: &[String], (row, col): Pos) -> Option<Pos> {
+    if col > 0 {
+        Some((row, prev_boundary(&lines[row], col)))
+    } else if row > 0 {
+        Some((row - 1, lines[row - 1].len()))
+    } else {
+        None
+    }
+}
+
+/// 0 = spasi, 1 = tanda baca, 2 = karakter kata; untuk WORD semua non-spasi bernilai 2.
+fn class_of(ch: char, big: bool) -> u8 {
+    if ch.is_whitespace() {
+        0
+    } else if big || ch.is_alphanumeric() || ch == '_' {
+        2
+    } else {
+        1
+    }
+}
+
+fn word_forward(lines: &[String], pos: Pos, big: bool) -> Pos {
+    let class = |pos: Pos| class_of(char_at(lines, pos), big);
+    let start = class(pos);
+    let mut current = pos;
+    if start != 0 {
+        while class(current) == start {
+            match next_pos(lines, current) {
+                Some(next) => current = next,
+                None => return current,
+            }
+        }
+    }
+    // Baris kosong dihitung sebagai satu kata.
+    while class(current) == 0 && !(lines[current.0].is_empty() && current != pos) {
+        match next_pos(lines, current) {
+            Some(next) => current = next,
+            None => break,
+        }
+    }
+    current
+}
+
+fn word_backward(lines: &[String], pos: Pos, big: bool) -> Pos {
+    let class = |pos: Pos| class_of(char_at(lines, pos), big);
+    let Some(mut current) = prev_pos(lines, pos) else {
+        return pos;
+    };
+    while class(current) == 0 {
+        if lines[current.0].is_empty() {
+            return current;
+        }
+        match prev_pos(lines, current) {
+            Some(prev) => current = prev,
+            None => return current,
+        }
+    }
+    let start = class(current);
+    while let Some(prev) = prev_pos(lines, current)
+        && class(prev) == start
+    {
+        current = prev;
+    }
+    current
+}
+
+fn word_end(lines: &[String], pos: Pos, big: bool) -> Pos {
+    let class = |pos: Pos| class_of(char_at(lines, pos), big);
+    let Some(mut current) = next_pos(lines, pos) else {
+        return pos;
+    };
+    while class(current) == 0 {
+        match next_pos(lines, current) {
+            Some(next) => current = next,
+            None => return current,
+        }
+    }
+    let start = class(current);
+    while let Some(next) = next_pos(lines, current)
+        && class(next) == start
+    {
+        current = next;
+    }
+    current
+}
+
+fn find_in_line(
+    line: &str,
+    col: usize,
+    ch: char,
+    forward: bool,
+    till: bool,
+    count: usize,
+) -> Option<(usize, MotionKind)> {
+    let mut found = col;
+    for _ in 0..count {
+        found = if forward {
+            let from = next_boundary(line, found);
+            from + line[from..].find(ch)?
+        } else {
+            line[..found].rfind(ch)?
+        };
+    }
+    let target = match (forward, till) {
+        (true, false) => (found, MotionKind::Inclusive),
+        (true, true) => (prev_boundary(line, found), MotionKind::Inclusive),
+        (false, false) => (found, MotionKind::Exclusive),
+        (false, true) => (next_boundary(line, found), MotionKind::Exclusive),
+    };
+    Some(target)
+}
+
+fn paragraph(lines: &[String], row: usize, forward: bool) -> usize {
+    let is_blank = |row: usize| lines[row].trim().is_empty();
+    let last = lines.len() - 1;
+    let mut row = row;
+    if forward {
+        while row < last && is_blank(row) {
+            row += 1;
+        }
+        while row < last && !is_blank(row) {
+            row += 1;
+        }
+    } else {
+        while row > 0 && is_blank(row) {
+            row -= 1;
+        }
+        while row > 0 && !is_blank(row) {
+            row -= 1;
+        }
+    }
+    row
+}
+
+fn pair_for(kind: char) -> Option<(char, char)> {
+    match kind {
+        '(' | ')' | 'b' => Some(('(', ')')),
+        '{' | '}' | 'B' => Some(('{', '}')),
+        '[' | ']' => Some(('[', ']')),
+        '<' | '>' => Some(('<', '>')),
+        _ => None,
+    }
+}
+
+fn match_pair(lines: &[String], cursor: Pos) -> Option<Pos> {
+    let line = &lines[cursor.0];
+    let (col, bracket) = line[cursor.1..]
+        .char_indices()
+        .map(|(idx, ch)| (cursor.1 + idx, ch))
+        .find(|(_, ch)| "(){}[]".contains(*ch))?;
+    let (open, close) = pair_for(bracket)?;
+    if bracket == open {
+        scan_forward(lines, (cursor.0, col), open, close)
+    } else {
+        scan_backward(lines, (cursor.0, col), open, close)
+    }
+}
+
+// Cari `close` pasangan dari `open` di posisi `from`.
+fn scan_forward(lines: &[String], from: Pos, open: char, close: char) -> Option<Pos> {
+    let mut depth = 0usize;
+    let mut pos = from;
+    loop {
+        let ch = char_at(lines, pos);
+        if ch == open {
+            depth += 1;
+        } else if ch == close {
+            depth -= 1;
+            if depth == 0 {
+                return Some(pos);
+            }
+        }
+        pos = next_pos(lines, pos)?;
+    }
+}
+
+// Cari `open` pembungkus sebelum `from`; bila `from` sendiri `close`, pasangannya.
+fn scan_backward(lines: &[String], from: Pos, open: char, close: char) -> Option<Pos> {
+    let mut depth = 0usize;
+    let mut pos = from;
+    loop {
+        let ch = char_at(lines, pos);
+        if ch == close && pos != from {
+            depth += 1;
+        } else if ch == open {
+            if depth == 0 {
+                return Some(pos);
+            }
+            depth -= 1;
+        }
+        pos = prev_pos(lines, pos)?;
+    }
+}
+
+fn object_range(lines: &[String], cursor: Pos, kind: char, inner: bool) -> Option<Range> {
+    if kind == 'w' || kind == 'W' {
+        return Some(word_object(lines, cursor, kind == 'W', inner));
+    }
+    if let Some((open, close)) = pair_for(kind) {
+        let start = scan_backward(lines, cursor, open, close)?;
+        let end = scan_forward(lines, start, open, close)?;
+        if !inner {
+            return Some(Range {
+                start,
+                end: (end.0, next_boundary(&lines[end.0], end.1)),
+                linewise: false,
+            });
+        }
+        let inner_start = (start.0, start.1 + open.len_utf8());
+        // Blok multi-baris `{\n ... \n}`: isi dianggap baris penuh.
+        if inner_start.1 == lines[start.0].len()
+            && end.0 > start.0 + 1
+            && lines[end.0][..end.1].trim().is_empty()
+        {
+            return Some(Range {
+                start: (start.0 + 1, 0),
+                end: (end.0 - 1, 0),
+                linewise: true,
+            });
+        }
+        return Some(Range {
+            start: inner_start,
+            end,
+            linewise: false,
+        });
+    }
+
+    // Tanda kutip hanya dicari di baris cursor.
+    let line = &lines[cursor.0];
+    let quotes = line
+        .char_indices()
+        .filter(|&(idx, ch)| ch == kind && !line[..idx].ends_with('\\'))
+        .map(|(idx, _)| idx)
+        .collect::<Vec<_>>();
+    // Pasangan yang memuat cursor, atau pasangan pertama sesudahnya.
+    let (open, close) = quotes
+        .chunks_exact(2)
+        .map(|pair| (pair[0], pair[1]))
+        .find(|&(_, close)| cursor.1 <= close)?;
+    let (start, end) = if inner {
+        (open + kind.len_utf8(), close)
+    } else {
+        (open, close + kind.len_utf8())
+    };
+    Some(Range {
+        start: (cursor.0, start),
+        end: (cursor.0, end),
+        linewise: false,
+    })
+}
+
+fn word_object(lines: &[String], cursor: Pos, big: bool, inner: bool) -> Range {
+    let line = &lines[cursor.0];
+    let row = cursor.0;
+    if line.is_empty() {
+        return Range {
+            start: (row, 0),
+            end: (row, 0),
+            linewise: false,
+        };
+    }
+    let col = cursor.1.min(prev_boundary(line, line.len()));
+    let class = |col: usize| class_of(line[col..].chars().next().unwrap_or(' '), big);
+    let target = class(col);
+    let mut start = col;
+    while start > 0 && class(prev_boundary(line, start)) == target {
+        start = prev_boundary(line, start);
+    }
+    let mut end = next_boundary(line, col);
+    while end < line.len() && class(end) == target {
+        end = next_boundary(line, end);
+    }
+    if !inner && target != 0 {
+        // `aw` ikut spasi sesudah kata, atau sebelumnya bila kata ada di akhir baris.
+        let trailing = end + line[end..].len() - line[end..].trim_start().len();
+        if trailing > end {
+            end = trailing;
+        } else {
+            start = line[..start].trim_end().len();
+        }
+    }
+    Range {
+        start: (row, start),
+        end: (row, end),
+        linewise: false,
+    }
+}
+
+fn word_under_cursor(line: &str, col: usize) -> Option<String> {
+    let is_word = |ch: char| ch.is_alphanumeric() || ch == '_';
+    let start_search = line
+        .get(col..)?
+        .char_indices()
+        .find(|&(_, ch)| is_word(ch))
+        .map(|(idx, _)| col + idx)?;
+    let start = line[..start_search]
+        .char_indices()
+        .rev()
+        .take_while(|&(_, ch)| is_word(ch))
+        .last()
+        .map_or(start_search, |(idx, _)| idx);
+    let end = line[start..]
+        .char_indices()
+        .find(|&(_, ch)| !is_word(ch))
+        .map_or(line.len(), |(idx, _)| start + idx);
+    Some(line[start..end].to_string())
+}
+
+// Cari `pattern` sesudah (atau sebelum) `from`, melingkar ke awal/akhir buffer.
+fn search(lines: &[String], from: Pos, pattern: &str, forward: bool) -> Option<Pos> {
+    let total = lines.len();
+    for step in 0..=total {
+        if forward {
+            let row = (from.0 + step) % total;
+            let line = &lines[row];
+            let start = if step == 0 {
+                next_boundary(line, from.1.min(line.len()))
+            } else {
+                0
+            };
+            if let Some((idx, _)) = line.match_indices(pattern).find(|&(idx, _)| idx >= start) {
+                return Some((row, idx));
+            }
+        } else {
+            let row = (from.0 + total - step % total) % total;
+            let line = &lines[row];
+            let end = if step == 0 { from.1 } else { line.len() + 1 };
+            if let Some((idx, _)) = line
+                .match_indices(pattern)
+                .filter(|&(idx, _)| idx < end)
+                .last()
+            {
+                return Some((row, idx));
+            }
+        }
+    }
+    None
+}
+
+fn range_text(lines: &[String], range: Range) -> String {
+    if range.linewise {
+        return lines[range.start.0..=range.end.0].join("\n");
+    }
+    let (start, end) = (range.start, range.end);
+    if start.0 == end.0 {
+        return lines[start.0][start.1..end.1].to_string();
+    }
+    let mut text = lines[start.0][start.1..].to_string();
+    for line in &lines[start.0 + 1..end.0] {
+        text.push('\n');
+        text.push_str(line);
+    }
+    text.push('\n');
+    text.push_str(&lines[end.0][..end.1]);
+    text
+}
+
+// Titik awal/akhir seleksi `Editor` untuk `range` (baris penuh tanpa newline terakhir).
+fn selection_bounds(lines: &[String], range: Range) -> (Pos, Pos) {
+    if range.linewise {
+        ((range.start.0, 0), (range.end.0, lines[range.end.0].len()))
+    } else {
+        (range.start, range.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(state: &mut VimState, editor: &mut Editor, keys: &str) {
+        for ch in keys.chars() {
+            state.feed(ch, editor);
+        }
+    }
+
+    fn edit(text: &str, keys: &str) -> Editor {
+        let mut editor = Editor::from_text(text);
+        run(&mut VimState::default(), &mut editor, keys);
+        editor
+    }
+
+    fn parsed(keys: &str) -> Option<Command> {
+        match parse(&keys.chars().collect::<Vec<_>>(), false) {
+            Parse::Done(command) => Some(command),
+            _ => None,
+        }
+    }
+
+    fn lines(text: &str) -> Vec<String> {
+        text.split('\n').map(str::to_string).collect()
+    }
+
+    #[test]
+    fn parse_counts_multiply() {
+        let command = parsed("2d3w").unwrap();
+        assert_eq!(command.count, Some(6));
+        assert_eq!(
+            command.action,
+            Action::Operate(Operator::Delete, Target::Motion(Motion::WordForward(false)))
+        );
+        assert_eq!(parsed("10j").unwrap().count, Some(10));
+        // `0` tanpa count adalah motion, bukan angka.
+        assert_eq!(parsed("0").unwrap().action, Action::Move(Motion::LineStart));
+    }
+
+    #[test]
+    fn parse_register_objects_and_incomplete() {
+        let command = parsed("\"ayy").unwrap();
+        assert_eq!(command.register, Some('a'));
+        assert_eq!(
+            command.action,
+            Action::Operate(Operator::Yank, Target::Line)
+        );
+        assert_eq!(
+            parsed("ci(").unwrap().action,
+            Action::Operate(
+                Operator::Change,
+                Target::Object {
+                    kind: '(',
+                    inner: true
+                }
+            )
+        );
+        let keys = |text: &str| text.chars().collect::<Vec<_>>();
+        assert!(matches!(parse(&keys("d"), false), Parse::Incomplete));
+        assert!(matches!(parse(&keys("di"), false), Parse::Incomplete));
+        assert!(matches!(parse(&keys("dz"), false), Parse::Invalid));
+        assert!(matches!(parse(&keys("iw"), true), Parse::Done(_)));
+    }
+
+    #[test]
+    fn bracket_objects() {
+        let lines = lines("call(a, (b), c)");
+        let inner = object_range(&lines, (0, 6), '(', true).unwrap();
+        assert_eq!((inner.start, inner.end), ((0, 5), (0, 14)));
+        let around = object_range(&lines, (0, 9), 'b', false).unwrap();
+        assert_eq!((around.start, around.end), ((0, 8), (0, 11)));
+        assert!(object_range(&lines, (0, 1), '(', true).is_none());
+    }
+
+    #[test]
+    fn multiline_block_object_is_linewise() {
+        let lines = lines("fn f() {\n    a;\n    b;\n}");
+        let range = object_range(&lines, (1, 4), '{', true).unwrap();
+        assert!(range.linewise);
+        assert_eq!((range.start.0, range.end.0), (1, 2));
+    }
+
+    #[test]
+    fn quote_and_word_objects() {
+        let quoted = lines("let s = \"a \\\" b\"; x");
+        let range = object_range(&quoted, (0, 0), '"', true).unwrap();
+        assert_eq!(range_text(&quoted, range), "a \\\" b");
+
+        let lines = lines("foo bar baz");
+        let word = object_range(&lines, (0, 5), 'w', false).unwrap();
+        assert_eq!((word.start, word.end), ((0, 4), (0, 8)));
+        // Kata terakhir mengambil spasi sebelumnya.
+        let last = object_range(&lines, (0, 9), 'w', false).unwrap();
+        assert_eq!((last.start, last.end), ((0, 7), (0, 11)));
+    }
+
+    #[test]
+    fn operators_with_motions_and_counts() {
+        assert_eq!(edit("one two three", "dw").text(), "two three");
+        assert_eq!(edit("one two three", "2dw").text(), "three");
+        assert_eq!(edit("abcdef", "3x").text(), "def");
+        assert_eq!(edit("a\nb\nc", "jddp").text(), "a\nc\nb");
+        assert_eq!(edit("f(x, y)", "fxci(z\x1b").text(), "f(z)");
+    }
+
+    #[test]
+    fn dot_repeats_last_change() {
+        assert_eq!(edit("one two three", "ciwX\x1bw.w.").text(), "X X X");
+        assert_eq!(edit("abcd", "x..").text(), "d");
+        assert_eq!(edit("a\nb", "A;\x1bj.").text(), "a;\nb;");
+    }
+
+    #[test]
+    fn insert_session_is_one_undo_step() {
+        let mut state = VimState::default();
+        let mut editor = Editor::from_text("one two");
+        run(&mut state, &mut editor, "cwab\ncd\x1b");
+        assert_eq!(editor.text(), "ab\ncd two");
+        run(&mut state, &mut editor, "u");
+        assert_eq!(editor.text(), "one two");
+
+        run(&mut state, &mut editor, "ofoo\x1bobar\x1b");
+        assert_eq!(editor.text(), "one two\nfoo\nbar");
+        run(&mut state, &mut editor, "u");
+        assert_eq!(editor.text(), "one two\nfoo");
+    }
+}